@@ -1,13 +1,21 @@
-use clap::{error::ErrorKind, CommandFactory, Parser};
-use drown_common::ext::{OptionExt, StrExt};
+use std::path::PathBuf;
+
+use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use drown_common::{
+    ext::{OptionExt, StrExt},
+    sql::format::KeywordCase,
+};
 use url::Url;
 
 const DEFAULT_HOST: &str = "localhost";
 const DEFAULT_PORT: u16 = 6472;
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
 struct CliArgs {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /* The user can supply either a uri, a host and/or port, or neither. */
     #[arg(value_parser = validate_drown_uri)]
     uri: Option<DrownUri>,
@@ -31,6 +39,41 @@ struct CliArgs {
     verbose: bool,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Format SQL files in place, or standard input to standard output if no files are given.
+    Fmt(FmtArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct FmtArgs {
+    pub files: Vec<PathBuf>,
+    /// The number of spaces to indent nested clauses by.
+    #[arg(long, default_value_t = 2)]
+    pub indent: usize,
+    /// How to capitalize keywords and type names.
+    #[arg(long, value_enum, default_value_t = KeywordCaseArg::Upper)]
+    pub keyword_case: KeywordCaseArg,
+    /// Only report which files are not formatted, exiting with an error if there are any.
+    #[arg(long)]
+    pub check: bool,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum KeywordCaseArg {
+    Upper,
+    Lower,
+}
+
+impl From<KeywordCaseArg> for KeywordCase {
+    fn from(value: KeywordCaseArg) -> Self {
+        match value {
+            KeywordCaseArg::Upper => KeywordCase::Upper,
+            KeywordCaseArg::Lower => KeywordCase::Lower,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DrownUri {
     pub host: String,
@@ -76,6 +119,7 @@ fn validate_drown_uri(s: &str) -> Result<DrownUri, String> {
 
 #[derive(Debug)]
 pub struct ClientOptions {
+    pub command: Option<Command>,
     pub connection: DrownUri,
     pub database: Option<String>,
     pub verbose: bool,
@@ -83,6 +127,7 @@ pub struct ClientOptions {
 
 pub fn get_client_options_from_args() -> ClientOptions {
     let CliArgs {
+        command,
         uri,
         host,
         port,
@@ -121,6 +166,7 @@ pub fn get_client_options_from_args() -> ClientOptions {
     };
 
     ClientOptions {
+        command,
         connection,
         database,
         verbose,
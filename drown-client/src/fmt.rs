@@ -0,0 +1,61 @@
+use std::{
+    error::Error,
+    io::{Read, Write},
+};
+
+use drown_common::sql::format::{format_source, FormatOptions};
+
+use crate::cli::FmtArgs;
+
+/// Formats every file given on the command line, or standard input if there are none.
+///
+/// Returns whether all input was already formatted, which is only relevant with `--check`.
+pub fn run(args: &FmtArgs) -> Result<bool, Box<dyn Error>> {
+    let options = FormatOptions {
+        indent_width: args.indent,
+        keyword_case: args.keyword_case.into(),
+    };
+
+    if args.files.is_empty() {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+
+        let formatted = format(&input, &options).map_err(|err| format!("<stdin>: {}", err))?;
+
+        if args.check {
+            return Ok(formatted == input);
+        }
+
+        std::io::stdout().write_all(formatted.as_bytes())?;
+
+        return Ok(true);
+    }
+
+    let mut formatted_already = true;
+
+    for path in &args.files {
+        let input =
+            std::fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        let formatted =
+            format(&input, &options).map_err(|err| format!("{}: {}", path.display(), err))?;
+
+        if formatted == input {
+            continue;
+        }
+
+        formatted_already = false;
+
+        if args.check {
+            println!("Would reformat {}", path.display());
+        } else {
+            std::fs::write(path, formatted)
+                .map_err(|err| format!("{}: {}", path.display(), err))?;
+        }
+    }
+
+    Ok(formatted_already)
+}
+
+fn format(input: &str, options: &FormatOptions) -> Result<String, Box<dyn Error>> {
+    Ok(format_source(input, options)?)
+}
@@ -13,19 +13,40 @@ use std::sync::atomic::Ordering;
 use tokio::net::TcpStream;
 use tokio_util::codec::{FramedRead, FramedWrite, LengthDelimitedCodec};
 
-use crate::cli::get_client_options_from_args;
+use crate::cli::{get_client_options_from_args, Command};
 
 mod cli;
+mod fmt;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
     let args = get_client_options_from_args();
+
+    // Formatted SQL is written to standard output, so nothing else may be
+    if let Some(Command::Fmt(fmt_args)) = &args.command {
+        match fmt::run(fmt_args) {
+            Ok(false) if fmt_args.check => std::process::exit(1),
+            Ok(_) => return Ok(()),
+            Err(err) => {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+        }
+    }
+
     println!("{:#?}", args);
 
     // Connect to a peer
     let mut stream =
         TcpStream::connect(format!("{}:{}", args.connection.host, args.connection.port)).await?;
 
+    if args.verbose {
+        println!(
+            "Connected to {}:{}",
+            args.connection.host, args.connection.port
+        );
+    }
+
     let (read, write) = stream.split();
 
     let mut delimited_reader = FramedRead::new(read, LengthDelimitedCodec::new());
@@ -33,6 +54,17 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     let message_id = AtomicU32::new(0);
 
+    // Enter the requested database before running anything else
+    if let Some(database) = &args.database {
+        let use_packet =
+            EncodedPacket::from_payload(C2SPacket::QueryRequest(C2SQueryRequestPacket {
+                query: format!("USE \"{}\";", database.replace('"', "\"\"")),
+            }))
+            .with_id(message_id.fetch_add(1, Ordering::SeqCst));
+
+        delimited_writer.send(use_packet.to_bytes()).await.unwrap();
+    }
+
     // Send query packet
     let query_packet =
         EncodedPacket::from_payload(C2SPacket::QueryRequest(C2SQueryRequestPacket {
@@ -59,11 +91,26 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 delimited_writer.send(res.to_bytes()).await.unwrap();
             }
             S2CPacket::QueryResponse(query_res) => match query_res {
-                Ok(S2CQuerySuccessResponsePacket { schema, data }) => {
-                    println!("Query succeeded: {:#?} {:#?}", schema, data);
+                Ok(S2CQuerySuccessResponsePacket {
+                    command: Some(command),
+                    ..
+                }) => {
+                    println!("{}", command);
+                }
+                Ok(S2CQuerySuccessResponsePacket {
+                    schema,
+                    types,
+                    data,
+                    command: None,
+                }) => {
+                    println!("Query succeeded: {:#?} {:#?} {:#?}", schema, types, data);
                 }
-                Err(S2CQueryErrorResponsePacket { error }) => {
+                Err(S2CQueryErrorResponsePacket { error, suggestion }) => {
                     println!("Query failed: {:#?}", error);
+
+                    if let Some(suggestion) = suggestion {
+                        println!("Did you mean `{}`?", suggestion);
+                    }
                 }
             },
         }
@@ -1,2 +1,3 @@
 pub mod ext;
-pub mod proto;
\ No newline at end of file
+pub mod proto;
+pub mod sql;
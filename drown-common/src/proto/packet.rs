@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
+use crate::sql::{data_type::DataType, value::Value};
+
 pub trait PacketPayload: Serialize + for<'a> Deserialize<'a> + Debug {}
 
 impl PacketPayload for C2SPacket {}
@@ -23,14 +25,22 @@ pub enum S2CPacket {
     QueryResponse(Result<S2CQuerySuccessResponsePacket, S2CQueryErrorResponsePacket>),
 }
 
-// TODO: Create a data structure for this
+/// A result set, where every row has one value per column in `schema`.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct S2CQuerySuccessResponsePacket {
-    pub schema: String,
-    pub data: Vec<String>,
+    /// The name of each column
+    pub schema: Vec<String>,
+    /// The type of each column, in the same order as `schema`
+    pub types: Vec<DataType>,
+    pub data: Vec<Vec<Value>>,
+    /// What a statement which does not return rows did, like `INSERT 0 3`, in which case there
+    /// are no columns
+    pub command: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct S2CQueryErrorResponsePacket {
     pub error: String,
+    /// The closest match to a misspelled keyword or object name, if there is one
+    pub suggestion: Option<String>,
 }
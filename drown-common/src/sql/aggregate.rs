@@ -0,0 +1,1031 @@
+use std::{cmp::Ordering, collections::HashSet, fmt::Debug, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::sql::{
+    collation::{CollatedValue, Collation},
+    data_type::DataType,
+    function::{check_fraction, FunctionError},
+    jsonb::Jsonb,
+    sketch::{HyperLogLog, TDigest},
+    value::{Decimal, RoundingMode, Value},
+};
+
+/// The state of an aggregate over the rows it has been updated with so far.
+///
+/// States are made of plain values so that partial states computed in parallel, or on other
+/// nodes, can be sent around and merged.
+pub type AggregateState = Vec<Value>;
+
+/// A function combining the values of its arguments across the rows of a group.
+///
+/// Rows can be split between any number of states, each updated with its own rows, which are
+/// then merged in any order before the result is finalized. Merging must give the same result
+/// as updating a single state with every row, up to rounding of floating point values.
+pub trait Aggregate: Debug + Send + Sync {
+    /// The state of an empty group.
+    fn init(&self) -> AggregateState;
+
+    /// Adds a row to the state, with its arguments coerced to the types of the parameters.
+    fn update(&self, state: &mut AggregateState, args: &[Value]) -> Result<(), FunctionError>;
+
+    /// Adds the rows of another state to the state, where the rows of `other` come after those
+    /// of `state`.
+    fn merge(&self, state: &mut AggregateState, other: AggregateState)
+        -> Result<(), FunctionError>;
+
+    /// Computes the result from the state of a group.
+    fn finalize(&self, state: AggregateState) -> Result<Value, FunctionError>;
+
+    /// Whether rows with a `NULL` argument are skipped rather than passed to `update`.
+    fn strict(&self) -> bool {
+        true
+    }
+
+    /// The same aggregate comparing its text arguments under `collations`, or `None` if it
+    /// does not compare them.
+    fn collated(&self, _collations: &[Collation]) -> Option<Arc<dyn Aggregate>> {
+        None
+    }
+}
+
+/// A call of an aggregate with the `DISTINCT` and `ORDER BY` it was written with.
+///
+/// `FILTER (WHERE ...)` is applied by only updating the call with the rows meeting the
+/// condition.
+#[derive(Debug, Clone)]
+pub struct AggregateCall {
+    pub aggregate: Arc<dyn Aggregate>,
+    /// Whether rows with the same arguments are only aggregated once
+    pub distinct: bool,
+    /// The keys the rows are sorted by before they are aggregated
+    pub order_by: Vec<SortKey>,
+    /// The collations the arguments are compared with, which decide which rows are distinct
+    pub collations: Vec<Collation>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SortKey {
+    pub descending: bool,
+    pub collation: Collation,
+}
+
+/// The state of an aggregate call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CallState {
+    /// The state of the aggregate, when rows can be aggregated as they come
+    Direct(AggregateState),
+    /// The arguments and sort keys of every row, when rows have to be deduplicated or sorted
+    /// before they are aggregated
+    Buffered(Vec<(Vec<Value>, Vec<Value>)>),
+}
+
+impl AggregateCall {
+    /// A call of `aggregate` whose arguments are compared with `collations`.
+    pub fn new(
+        aggregate: Arc<dyn Aggregate>,
+        distinct: bool,
+        order_by: Vec<SortKey>,
+        collations: Vec<Collation>,
+    ) -> Self {
+        AggregateCall {
+            aggregate: aggregate.collated(&collations).unwrap_or(aggregate),
+            distinct,
+            order_by,
+            collations,
+        }
+    }
+
+    pub fn init(&self) -> CallState {
+        match self.distinct || !self.order_by.is_empty() {
+            true => CallState::Buffered(Vec::new()),
+            false => CallState::Direct(self.aggregate.init()),
+        }
+    }
+
+    /// Adds a row to the state, with the values of its arguments and of its sort keys.
+    pub fn update(
+        &self,
+        state: &mut CallState,
+        args: &[Value],
+        keys: &[Value],
+    ) -> Result<(), FunctionError> {
+        if self.aggregate.strict() && args.iter().any(Value::is_null) {
+            return Ok(());
+        }
+
+        match state {
+            CallState::Direct(state) => self.aggregate.update(state, args),
+            CallState::Buffered(rows) => {
+                rows.push((args.to_vec(), keys.to_vec()));
+                Ok(())
+            }
+        }
+    }
+
+    pub fn merge(&self, state: &mut CallState, other: CallState) -> Result<(), FunctionError> {
+        match (state, other) {
+            (CallState::Direct(state), CallState::Direct(other)) => {
+                self.aggregate.merge(state, other)
+            }
+            (CallState::Buffered(rows), CallState::Buffered(other)) => {
+                rows.extend(other);
+                Ok(())
+            }
+            _ => unreachable!("States of the same aggregate call are either all buffered or none"),
+        }
+    }
+
+    pub fn finalize(&self, state: CallState) -> Result<Value, FunctionError> {
+        let mut rows = match state {
+            CallState::Direct(state) => return self.aggregate.finalize(state),
+            CallState::Buffered(rows) => rows,
+        };
+
+        // Sorting is stable, so rows with equal keys keep the order they came in
+        rows.sort_by(|(_, a), (_, b)| {
+            a.iter()
+                .zip(b)
+                .zip(&self.order_by)
+                .map(|((a, b), key)| match key.descending {
+                    true => key.collation.compare_values(b, a),
+                    false => key.collation.compare_values(a, b),
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let mut seen = HashSet::new();
+        let mut state = self.aggregate.init();
+
+        for (args, _) in rows {
+            // Text arguments are the same when they compare equal under their collations
+            let key = args
+                .iter()
+                .zip(&self.collations)
+                .map(|(value, collation)| CollatedValue {
+                    value: value.clone(),
+                    collation: *collation,
+                });
+
+            if !self.distinct || seen.insert(key.collect::<Vec<_>>()) {
+                self.aggregate.update(&mut state, &args)?;
+            }
+        }
+
+        self.aggregate.finalize(state)
+    }
+}
+
+/* BUILT-IN AGGREGATES */
+
+/// `count(*)` and `count(x)`, which counts the rows where `x` is not `NULL`.
+#[derive(Debug)]
+pub struct Count;
+
+impl Aggregate for Count {
+    fn init(&self) -> AggregateState {
+        vec![Value::BigInt(0)]
+    }
+
+    fn update(&self, state: &mut AggregateState, _: &[Value]) -> Result<(), FunctionError> {
+        state[0] = add("count", &state[0], &Value::BigInt(1))?;
+        Ok(())
+    }
+
+    fn merge(
+        &self,
+        state: &mut AggregateState,
+        other: AggregateState,
+    ) -> Result<(), FunctionError> {
+        state[0] = add("count", &state[0], &other[0])?;
+        Ok(())
+    }
+
+    fn finalize(&self, mut state: AggregateState) -> Result<Value, FunctionError> {
+        Ok(state.remove(0))
+    }
+}
+
+/// Adds up integers as `BIGINT`, `BIGINT` values as `DECIMAL`, and every other number as its
+/// own type, so that only the sum of `BIGINT` values can overflow.
+#[derive(Debug)]
+pub struct Sum;
+
+impl Aggregate for Sum {
+    fn init(&self) -> AggregateState {
+        vec![Value::Null]
+    }
+
+    fn update(&self, state: &mut AggregateState, args: &[Value]) -> Result<(), FunctionError> {
+        let value = match &args[0] {
+            Value::SmallInt(value) => Value::BigInt(*value as i64),
+            Value::Int(value) => Value::BigInt(*value as i64),
+            Value::BigInt(value) => Value::Decimal(Decimal::from(*value)),
+            value => value.clone(),
+        };
+
+        self.merge(state, vec![value])
+    }
+
+    fn merge(
+        &self,
+        state: &mut AggregateState,
+        other: AggregateState,
+    ) -> Result<(), FunctionError> {
+        state[0] = match (&state[0], &other[0]) {
+            (_, Value::Null) => return Ok(()),
+            (Value::Null, _) => other[0].clone(),
+            (sum, value) => add("sum", sum, value)?,
+        };
+
+        Ok(())
+    }
+
+    fn finalize(&self, mut state: AggregateState) -> Result<Value, FunctionError> {
+        Ok(state.remove(0))
+    }
+}
+
+/// The mean of exact numbers as `DECIMAL`, and of floating point numbers as `DOUBLE`.
+#[derive(Debug)]
+pub struct Avg;
+
+impl Avg {
+    /// The least number of digits after the decimal point of a mean, as in PostgreSQL.
+    const MIN_SCALE: u32 = 16;
+}
+
+impl Aggregate for Avg {
+    fn init(&self) -> AggregateState {
+        vec![Value::Null, Value::BigInt(0)]
+    }
+
+    fn update(&self, state: &mut AggregateState, args: &[Value]) -> Result<(), FunctionError> {
+        let value = match &args[0] {
+            Value::SmallInt(value) => Value::Decimal(Decimal::from(*value as i64)),
+            Value::Int(value) => Value::Decimal(Decimal::from(*value as i64)),
+            Value::BigInt(value) => Value::Decimal(Decimal::from(*value)),
+            Value::Real(value) => Value::Double(*value as f64),
+            value => value.clone(),
+        };
+
+        self.merge(state, vec![value, Value::BigInt(1)])
+    }
+
+    fn merge(
+        &self,
+        state: &mut AggregateState,
+        other: AggregateState,
+    ) -> Result<(), FunctionError> {
+        state[0] = match (&state[0], &other[0]) {
+            (_, Value::Null) => return Ok(()),
+            (Value::Null, _) => other[0].clone(),
+            (sum, value) => add("avg", sum, value)?,
+        };
+        state[1] = add("avg", &state[1], &other[1])?;
+
+        Ok(())
+    }
+
+    fn finalize(&self, state: AggregateState) -> Result<Value, FunctionError> {
+        let Value::BigInt(count) = state[1] else {
+            unreachable!("The count of `avg` is a BIGINT");
+        };
+
+        Ok(match &state[0] {
+            Value::Null => Value::Null,
+            Value::Double(sum) => Value::Double(sum / count as f64),
+            Value::Decimal(sum) => {
+                // As many digits after the decimal point as fit next to those before it
+                let integer_digits = sum.precision().saturating_sub(sum.scale());
+                let scale = sum
+                    .scale()
+                    .max(Self::MIN_SCALE)
+                    .min(Decimal::MAX_PRECISION - integer_digits);
+
+                Value::Decimal(sum.checked_div(
+                    &Decimal::from(count),
+                    scale,
+                    RoundingMode::HalfUp,
+                )?)
+            }
+            sum => unreachable!("`avg` cannot add up {sum:?}"),
+        })
+    }
+}
+
+/// `min(x)` and `max(x)`, in the order values are sorted in under the collation of `x`, which
+/// keep the first of values that compare equal.
+#[derive(Debug)]
+pub struct Extreme {
+    max: bool,
+    collation: Collation,
+}
+
+pub const MIN: Extreme = Extreme {
+    max: false,
+    collation: Collation::Binary,
+};
+pub const MAX: Extreme = Extreme {
+    max: true,
+    collation: Collation::Binary,
+};
+
+impl Aggregate for Extreme {
+    fn init(&self) -> AggregateState {
+        vec![Value::Null]
+    }
+
+    fn update(&self, state: &mut AggregateState, args: &[Value]) -> Result<(), FunctionError> {
+        self.merge(state, args.to_vec())
+    }
+
+    fn merge(
+        &self,
+        state: &mut AggregateState,
+        mut other: AggregateState,
+    ) -> Result<(), FunctionError> {
+        let replace = match (&state[0], &other[0]) {
+            (_, Value::Null) => false,
+            (Value::Null, _) => true,
+            (current, value) => match self.collation.compare_values(value, current) {
+                Ordering::Greater => self.max,
+                Ordering::Less => !self.max,
+                Ordering::Equal => false,
+            },
+        };
+
+        if replace {
+            state[0] = other.remove(0);
+        }
+
+        Ok(())
+    }
+
+    fn finalize(&self, mut state: AggregateState) -> Result<Value, FunctionError> {
+        Ok(state.remove(0))
+    }
+
+    fn collated(&self, collations: &[Collation]) -> Option<Arc<dyn Aggregate>> {
+        Some(Arc::new(Extreme {
+            max: self.max,
+            collation: collations[0],
+        }))
+    }
+}
+
+/// `string_agg(value, delimiter)`, which concatenates the values which are not `NULL` with the
+/// delimiter of every value but the first before it.
+///
+/// The state keeps the delimiter of the first value and its length, so that merged states are
+/// separated by the delimiter of the first value of the latter.
+#[derive(Debug)]
+pub struct StringAgg;
+
+impl Aggregate for StringAgg {
+    fn init(&self) -> AggregateState {
+        vec![Value::Null, Value::BigInt(0)]
+    }
+
+    fn update(&self, state: &mut AggregateState, args: &[Value]) -> Result<(), FunctionError> {
+        let Value::Text(value) = &args[0] else {
+            return Ok(());
+        };
+
+        // A `NULL` delimiter is an empty one
+        let delimiter = match &args[1] {
+            Value::Text(delimiter) => delimiter.as_str(),
+            _ => "",
+        };
+
+        self.merge(
+            state,
+            vec![
+                Value::Text(format!("{delimiter}{value}")),
+                Value::BigInt(delimiter.len() as i64),
+            ],
+        )
+    }
+
+    fn merge(
+        &self,
+        state: &mut AggregateState,
+        other: AggregateState,
+    ) -> Result<(), FunctionError> {
+        match (&mut state[0], &other[0]) {
+            (_, Value::Null) => {}
+            (Value::Text(text), Value::Text(other)) => text.push_str(other),
+            _ => *state = other,
+        }
+
+        Ok(())
+    }
+
+    fn finalize(&self, mut state: AggregateState) -> Result<Value, FunctionError> {
+        Ok(match (state.remove(0), &state[0]) {
+            (Value::Text(text), Value::BigInt(first)) => {
+                Value::Text(text[*first as usize..].into())
+            }
+            _ => Value::Null,
+        })
+    }
+
+    fn strict(&self) -> bool {
+        false
+    }
+}
+
+/// `array_agg(x)` and `jsonb_agg(x)`, which collect every value, `NULL` included, into a JSONB
+/// array, as there are no other arrays.
+#[derive(Debug)]
+pub struct ArrayAgg;
+
+impl Aggregate for ArrayAgg {
+    fn init(&self) -> AggregateState {
+        Vec::new()
+    }
+
+    fn update(&self, state: &mut AggregateState, args: &[Value]) -> Result<(), FunctionError> {
+        state.push(args[0].clone());
+        Ok(())
+    }
+
+    fn merge(
+        &self,
+        state: &mut AggregateState,
+        other: AggregateState,
+    ) -> Result<(), FunctionError> {
+        state.extend(other);
+        Ok(())
+    }
+
+    fn finalize(&self, state: AggregateState) -> Result<Value, FunctionError> {
+        Ok(match state.is_empty() {
+            true => Value::Null,
+            false => Value::Jsonb(Jsonb::agg(&state)),
+        })
+    }
+
+    fn strict(&self) -> bool {
+        false
+    }
+}
+
+/// `bool_and(x)`, which is true when every value is, and `bool_or(x)`, which is true when any
+/// value is.
+#[derive(Debug)]
+pub struct BoolAgg {
+    any: bool,
+}
+
+pub const BOOL_AND: BoolAgg = BoolAgg { any: false };
+pub const BOOL_OR: BoolAgg = BoolAgg { any: true };
+
+impl Aggregate for BoolAgg {
+    fn init(&self) -> AggregateState {
+        vec![Value::Null]
+    }
+
+    fn update(&self, state: &mut AggregateState, args: &[Value]) -> Result<(), FunctionError> {
+        self.merge(state, args.to_vec())
+    }
+
+    fn merge(
+        &self,
+        state: &mut AggregateState,
+        other: AggregateState,
+    ) -> Result<(), FunctionError> {
+        state[0] = match (&state[0], &other[0]) {
+            (_, Value::Null) => return Ok(()),
+            (Value::Boolean(a), Value::Boolean(b)) if self.any => Value::Boolean(*a || *b),
+            (Value::Boolean(a), Value::Boolean(b)) => Value::Boolean(*a && *b),
+            (_, value) => value.clone(),
+        };
+
+        Ok(())
+    }
+
+    fn finalize(&self, mut state: AggregateState) -> Result<Value, FunctionError> {
+        Ok(state.remove(0))
+    }
+}
+
+/// The variance and standard deviation of a sample or a whole population.
+///
+/// The state is the count, mean and sum of squared differences from the mean of the values,
+/// which are updated with Welford's algorithm and merged with Chan's, as adding up squares
+/// loses most of the precision when the values are large and close together.
+#[derive(Debug)]
+pub struct Variance {
+    sample: bool,
+    root: bool,
+}
+
+pub const VAR_SAMP: Variance = Variance {
+    sample: true,
+    root: false,
+};
+pub const VAR_POP: Variance = Variance {
+    sample: false,
+    root: false,
+};
+pub const STDDEV_SAMP: Variance = Variance {
+    sample: true,
+    root: true,
+};
+pub const STDDEV_POP: Variance = Variance {
+    sample: false,
+    root: true,
+};
+
+impl Variance {
+    fn moments(state: &AggregateState) -> (f64, f64, f64) {
+        match state[..] {
+            [Value::Double(count), Value::Double(mean), Value::Double(squares)] => {
+                (count, mean, squares)
+            }
+            _ => unreachable!("The state of a variance is three DOUBLE values"),
+        }
+    }
+}
+
+impl Aggregate for Variance {
+    fn init(&self) -> AggregateState {
+        vec![Value::Double(0.0); 3]
+    }
+
+    fn update(&self, state: &mut AggregateState, args: &[Value]) -> Result<(), FunctionError> {
+        let Value::Double(value) = args[0] else {
+            unreachable!("Argument {:?} was not coerced to DOUBLE", args[0]);
+        };
+
+        self.merge(
+            state,
+            vec![Value::Double(1.0), Value::Double(value), Value::Double(0.0)],
+        )
+    }
+
+    fn merge(
+        &self,
+        state: &mut AggregateState,
+        other: AggregateState,
+    ) -> Result<(), FunctionError> {
+        let (count_a, mean_a, squares_a) = Self::moments(state);
+        let (count_b, mean_b, squares_b) = Self::moments(&other);
+
+        if count_b == 0.0 {
+            return Ok(());
+        }
+
+        let count = count_a + count_b;
+        let delta = mean_b - mean_a;
+
+        *state = vec![
+            Value::Double(count),
+            Value::Double(mean_a + delta * count_b / count),
+            Value::Double(squares_a + squares_b + delta * delta * count_a * count_b / count),
+        ];
+
+        Ok(())
+    }
+
+    fn finalize(&self, state: AggregateState) -> Result<Value, FunctionError> {
+        let (count, _, squares) = Self::moments(&state);
+        let divisor = match self.sample {
+            true => count - 1.0,
+            false => count,
+        };
+
+        if divisor <= 0.0 {
+            return Ok(Value::Null);
+        }
+
+        let variance = squares / divisor;
+
+        Ok(Value::Double(match self.root {
+            true => variance.sqrt(),
+            false => variance,
+        }))
+    }
+}
+
+/* APPROXIMATE AGGREGATES */
+
+/// What an aggregate over a sketch returns.
+#[derive(Debug)]
+pub enum SketchOutput {
+    /// The estimate of the sketch of the values
+    Estimate,
+    /// The sketch of the values, which can be stored and merged with others later
+    Sketch,
+    /// The sketch merging the sketches which are the values
+    Union,
+}
+
+/// `approx_count_distinct(x)`, which estimates the number of distinct values with a
+/// HyperLogLog sketch, along with `hll_sketch(x)` and `hll_union(sketch)`.
+///
+/// Sketches of no rows are empty rather than `NULL`, so that they can still be merged.
+#[derive(Debug)]
+pub struct Hll {
+    output: SketchOutput,
+}
+
+pub const APPROX_COUNT_DISTINCT: Hll = Hll {
+    output: SketchOutput::Estimate,
+};
+pub const HLL_SKETCH: Hll = Hll {
+    output: SketchOutput::Sketch,
+};
+pub const HLL_UNION: Hll = Hll {
+    output: SketchOutput::Union,
+};
+
+impl Hll {
+    fn sketch(state: &mut AggregateState) -> HyperLogLog {
+        match &mut state[0] {
+            Value::Bytea(bytes) => HyperLogLog::from_state(std::mem::take(bytes)),
+            value => unreachable!("The state of a HyperLogLog aggregate is {value:?}"),
+        }
+    }
+}
+
+impl Aggregate for Hll {
+    fn init(&self) -> AggregateState {
+        vec![Value::Bytea(HyperLogLog::new().into_bytes())]
+    }
+
+    fn update(&self, state: &mut AggregateState, args: &[Value]) -> Result<(), FunctionError> {
+        let mut sketch = Self::sketch(state);
+
+        let result = match (&self.output, &args[0]) {
+            (SketchOutput::Union, Value::Bytea(other)) => HyperLogLog::from_bytes(other.clone())
+                .map(|other| sketch.merge(&other))
+                .map_err(FunctionError::from),
+            (_, value) => {
+                sketch.insert(value);
+                Ok(())
+            }
+        };
+
+        state[0] = Value::Bytea(sketch.into_bytes());
+        result
+    }
+
+    fn merge(
+        &self,
+        state: &mut AggregateState,
+        mut other: AggregateState,
+    ) -> Result<(), FunctionError> {
+        let mut sketch = Self::sketch(state);
+        sketch.merge(&Self::sketch(&mut other));
+        state[0] = Value::Bytea(sketch.into_bytes());
+
+        Ok(())
+    }
+
+    fn finalize(&self, mut state: AggregateState) -> Result<Value, FunctionError> {
+        let sketch = Self::sketch(&mut state);
+
+        Ok(match self.output {
+            SketchOutput::Estimate => Value::BigInt(sketch.estimate() as i64),
+            SketchOutput::Sketch | SketchOutput::Union => Value::Bytea(sketch.into_bytes()),
+        })
+    }
+}
+
+/// `approx_percentile(x, fraction)`, which estimates the percentile of the values with a
+/// t-digest, along with `tdigest_sketch(x)` and `tdigest_union(sketch)`.
+///
+/// The state is the digest and the fraction of the first row.
+#[derive(Debug)]
+pub struct Digest {
+    output: SketchOutput,
+}
+
+pub const APPROX_PERCENTILE: Digest = Digest {
+    output: SketchOutput::Estimate,
+};
+pub const TDIGEST_SKETCH: Digest = Digest {
+    output: SketchOutput::Sketch,
+};
+pub const TDIGEST_UNION: Digest = Digest {
+    output: SketchOutput::Union,
+};
+
+impl Digest {
+    fn digest(state: &mut AggregateState) -> TDigest {
+        match &mut state[0] {
+            Value::Bytea(bytes) => TDigest::from_state(std::mem::take(bytes)),
+            value => unreachable!("The state of a t-digest aggregate is {value:?}"),
+        }
+    }
+}
+
+impl Aggregate for Digest {
+    fn init(&self) -> AggregateState {
+        vec![Value::Bytea(TDigest::new().into_state()), Value::Null]
+    }
+
+    fn update(&self, state: &mut AggregateState, args: &[Value]) -> Result<(), FunctionError> {
+        let mut digest = Self::digest(state);
+
+        let result = match (&self.output, &args[0]) {
+            (SketchOutput::Union, Value::Bytea(other)) => TDigest::from_bytes(other.clone())
+                .map(|other| digest.merge(&other))
+                .map_err(FunctionError::from),
+            (_, Value::Double(value)) => {
+                digest.insert(*value);
+                Ok(())
+            }
+            (_, value) => unreachable!("Argument {value:?} was not coerced to its parameter type"),
+        };
+
+        state[0] = Value::Bytea(digest.into_state());
+
+        if let Some(Value::Double(fraction)) = args.get(1) {
+            state[1] = Value::Double(check_fraction("approx_percentile", *fraction)?);
+        }
+
+        result
+    }
+
+    fn merge(
+        &self,
+        state: &mut AggregateState,
+        mut other: AggregateState,
+    ) -> Result<(), FunctionError> {
+        let mut digest = Self::digest(state);
+        digest.merge(&Self::digest(&mut other));
+        state[0] = Value::Bytea(digest.into_state());
+
+        if state[1].is_null() {
+            state[1] = other.remove(1);
+        }
+
+        Ok(())
+    }
+
+    fn finalize(&self, mut state: AggregateState) -> Result<Value, FunctionError> {
+        let digest = Self::digest(&mut state);
+
+        Ok(match (&self.output, &state[1]) {
+            (SketchOutput::Estimate, Value::Double(fraction)) => digest
+                .quantile(*fraction)
+                .map_or(Value::Null, Value::Double),
+            (SketchOutput::Estimate, _) => Value::Null,
+            (SketchOutput::Sketch | SketchOutput::Union, _) => Value::Bytea(digest.into_bytes()),
+        })
+    }
+}
+
+/// Adds two numbers of the same type, failing when the sum is out of range for it.
+fn add(function: &'static str, a: &Value, b: &Value) -> Result<Value, FunctionError> {
+    Ok(match (a, b) {
+        (Value::BigInt(a), Value::BigInt(b)) => {
+            Value::BigInt(a.checked_add(*b).ok_or_else(|| FunctionError::OutOfRange {
+                function: function.to_string(),
+                data_type: DataType::BigInt,
+            })?)
+        }
+        (Value::Decimal(a), Value::Decimal(b)) => Value::Decimal(a.checked_add(b)?),
+        (Value::Real(a), Value::Real(b)) => Value::Real(a + b),
+        (Value::Double(a), Value::Double(b)) => Value::Double(a + b),
+        (a, b) => unreachable!("`{function}` cannot add {a:?} and {b:?}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(value: i32) -> Value {
+        Value::Int(value)
+    }
+
+    fn text(value: &str) -> Value {
+        Value::Text(value.to_string())
+    }
+
+    fn plain(aggregate: impl Aggregate + 'static, args: usize) -> AggregateCall {
+        AggregateCall::new(
+            Arc::new(aggregate),
+            false,
+            Vec::new(),
+            vec![Collation::Binary; args],
+        )
+    }
+
+    /// Runs the call over rows of arguments and sort keys, both in a single state and split
+    /// between two merged states, which must agree, and returns the result.
+    fn call_keyed(call: &AggregateCall, rows: &[(Vec<Value>, Vec<Value>)]) -> Value {
+        let run = |parts: &[&[(Vec<Value>, Vec<Value>)]]| {
+            let mut state = call.init();
+
+            for part in parts {
+                let mut part_state = call.init();
+
+                for (args, keys) in *part {
+                    call.update(&mut part_state, args, keys).unwrap();
+                }
+
+                call.merge(&mut state, part_state).unwrap();
+            }
+
+            call.finalize(state).unwrap()
+        };
+
+        let result = run(&[rows]);
+        let (first, second) = rows.split_at(rows.len() / 2);
+        assert_eq!(run(&[first, second]), result, "merged states disagree");
+
+        result
+    }
+
+    /// Runs the call over rows of arguments, and writes the result.
+    fn call(call: &AggregateCall, rows: &[Vec<Value>]) -> String {
+        let rows = rows
+            .iter()
+            .map(|args| (args.clone(), Vec::new()))
+            .collect::<Vec<_>>();
+
+        call_keyed(call, &rows).to_string()
+    }
+
+    #[test]
+    fn empty_and_null_input() {
+        let cases = [
+            (plain(Count, 1), "0", "0"),
+            (plain(Sum, 1), "NULL", "NULL"),
+            (plain(Avg, 1), "NULL", "NULL"),
+            (plain(MIN, 1), "NULL", "NULL"),
+            (plain(MAX, 1), "NULL", "NULL"),
+            (plain(StringAgg, 2), "NULL", "NULL"),
+            (plain(ArrayAgg, 1), "NULL", "[null, null]"),
+            (plain(BOOL_AND, 1), "NULL", "NULL"),
+            (plain(BOOL_OR, 1), "NULL", "NULL"),
+            (plain(VAR_SAMP, 1), "NULL", "NULL"),
+            (plain(VAR_POP, 1), "NULL", "NULL"),
+            (plain(STDDEV_SAMP, 1), "NULL", "NULL"),
+            (plain(STDDEV_POP, 1), "NULL", "NULL"),
+            (plain(APPROX_COUNT_DISTINCT, 1), "0", "0"),
+            (plain(APPROX_PERCENTILE, 2), "NULL", "NULL"),
+        ];
+
+        for (aggregate, empty, null) in cases {
+            let nulls = vec![vec![Value::Null; aggregate.collations.len()]; 2];
+
+            assert_eq!(call(&aggregate, &[]), empty, "{aggregate:?}");
+            assert_eq!(call(&aggregate, &nulls), null, "{aggregate:?}");
+        }
+
+        // Sketches of no rows are empty rather than `NULL`, so that they can still be merged
+        for sketch in [plain(HLL_SKETCH, 1), plain(TDIGEST_SKETCH, 1)] {
+            let empty = call(&sketch, &[]);
+
+            assert_ne!(empty, "NULL", "{sketch:?}");
+            assert_eq!(call(&sketch, &[vec![Value::Null]]), empty, "{sketch:?}");
+        }
+
+        // Variances of a sample need two values, and those of a population one
+        assert_eq!(
+            call(&plain(VAR_SAMP, 1), &[vec![Value::Double(1.0)]]),
+            "NULL"
+        );
+        assert_eq!(call(&plain(VAR_POP, 1), &[vec![Value::Double(1.0)]]), "0");
+    }
+
+    #[test]
+    fn results() {
+        let ints = [vec![int(1)], vec![Value::Null], vec![int(2)], vec![int(4)]];
+
+        assert_eq!(call(&plain(Count, 1), &ints), "3");
+        assert_eq!(call(&plain(Sum, 1), &ints), "7");
+        assert_eq!(call(&plain(Avg, 1), &ints), "2.3333333333333333");
+        assert_eq!(call(&plain(MIN, 1), &ints), "1");
+        assert_eq!(call(&plain(MAX, 1), &ints), "4");
+        assert_eq!(call(&plain(ArrayAgg, 1), &ints), "[1, null, 2, 4]");
+
+        // The sum of BIGINT values is a DECIMAL, which does not overflow
+        let big = [vec![Value::BigInt(i64::MAX)], vec![Value::BigInt(i64::MAX)]];
+        assert_eq!(call(&plain(Sum, 1), &big), "18446744073709551614");
+
+        let doubles =
+            [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0].map(|value| vec![Value::Double(value)]);
+        assert_eq!(call(&plain(VAR_POP, 1), &doubles), "4");
+        assert_eq!(call(&plain(STDDEV_POP, 1), &doubles), "2");
+
+        let bools = [
+            vec![Value::Boolean(true)],
+            vec![Value::Null],
+            vec![Value::Boolean(false)],
+        ];
+        assert_eq!(call(&plain(BOOL_AND, 1), &bools), "false");
+        assert_eq!(call(&plain(BOOL_OR, 1), &bools), "true");
+
+        // Values which are NULL are skipped along with their delimiters, and a NULL delimiter is
+        // an empty one
+        let strings = [
+            vec![text("a"), text(",")],
+            vec![Value::Null, text(";")],
+            vec![text("b"), text(";")],
+            vec![text("c"), Value::Null],
+        ];
+        assert_eq!(call(&plain(StringAgg, 2), &strings), "a;bc");
+    }
+
+    #[test]
+    fn distinct() {
+        let count =
+            |collation| AggregateCall::new(Arc::new(Count), true, Vec::new(), vec![collation]);
+        let codes = [
+            vec![text("a")],
+            vec![text("A")],
+            vec![Value::Null],
+            vec![text("b")],
+            vec![text("a")],
+        ];
+
+        assert_eq!(call(&count(Collation::Binary), &codes), "3");
+        assert_eq!(call(&count(Collation::NoCase), &codes), "2");
+
+        let sum = AggregateCall::new(Arc::new(Sum), true, Vec::new(), vec![Collation::Binary]);
+        let ints = [vec![int(1)], vec![int(2)], vec![int(1)], vec![int(1)]];
+        assert_eq!(call(&sum, &ints), "3");
+
+        // The first of the values which are the same is kept
+        let string_agg = AggregateCall::new(
+            Arc::new(StringAgg),
+            true,
+            Vec::new(),
+            vec![Collation::NoCase, Collation::Binary],
+        );
+        let codes = codes.map(|mut args| {
+            args.push(text(","));
+            args
+        });
+        assert_eq!(call(&string_agg, &codes), "a,b");
+    }
+
+    #[test]
+    fn order_by() {
+        let key = |descending, collation| SortKey {
+            descending,
+            collation,
+        };
+        let rows = |rows: &[(&str, Value)]| {
+            rows.iter()
+                .map(|(value, key)| (vec![text(value), text(",")], vec![key.clone()]))
+                .collect::<Vec<_>>()
+        };
+
+        // Rows with equal keys keep the order they came in, across merged states too
+        let string_agg = |order_by, distinct| {
+            AggregateCall::new(
+                Arc::new(StringAgg),
+                distinct,
+                order_by,
+                vec![Collation::Binary; 2],
+            )
+        };
+        let by_number = rows(&[("a", int(1)), ("b", int(3)), ("c", int(2)), ("d", int(3))]);
+
+        let ascending = string_agg(vec![key(false, Collation::Binary)], false);
+        assert_eq!(call_keyed(&ascending, &by_number), text("a,c,b,d"));
+
+        let descending = string_agg(vec![key(true, Collation::Binary)], false);
+        assert_eq!(call_keyed(&descending, &by_number), text("b,d,c,a"));
+
+        // Keys are compared under their collations
+        let by_text = rows(&[("1", text("b")), ("2", text("a")), ("3", text("B"))]);
+        assert_eq!(call_keyed(&ascending, &by_text), text("3,2,1"));
+
+        let nocase = string_agg(vec![key(false, Collation::NoCase)], false);
+        assert_eq!(call_keyed(&nocase, &by_text), text("2,1,3"));
+
+        // DISTINCT keeps the first of the values which are the same in the sorted order
+        let by_number = rows(&[("a", int(2)), ("b", int(1)), ("a", int(0)), ("b", int(3))]);
+        let distinct = string_agg(vec![key(true, Collation::Binary)], true);
+        assert_eq!(call_keyed(&distinct, &by_number), text("b,a"));
+    }
+
+    #[test]
+    fn collations() {
+        // Of the values which compare equal, `min` and `max` keep the first
+        let codes = [
+            vec![text("b")],
+            vec![text("A")],
+            vec![text("a")],
+            vec![text("B")],
+        ];
+        let extreme = |aggregate, collation| {
+            AggregateCall::new(Arc::new(aggregate), false, Vec::new(), vec![collation])
+        };
+
+        assert_eq!(call(&extreme(MIN, Collation::Binary), &codes), "A");
+        assert_eq!(call(&extreme(MAX, Collation::Binary), &codes), "b");
+        assert_eq!(call(&extreme(MIN, Collation::NoCase), &codes), "A");
+        assert_eq!(call(&extreme(MAX, Collation::NoCase), &codes), "b");
+    }
+}
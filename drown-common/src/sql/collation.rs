@@ -0,0 +1,264 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    hash::{Hash, Hasher},
+};
+
+use icu_collator::{Collator, CollatorOptions, Strength};
+use icu_normalizer::DecomposingNormalizer;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::sql::value::Value;
+
+/// How text is compared, sorted and hashed.
+///
+/// Two strings which a collation considers equal always hash the same under it, so that sorts,
+/// hash joins and indexes over the same values agree with one another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Collation {
+    /// Byte order, which is code point order as text is UTF-8
+    #[default]
+    Binary,
+    /// Code point order after lower-casing every character, so that `'ABC' = 'abc'`
+    NoCase,
+    /// The Unicode Collation Algorithm with the root collation order, which sorts letters with
+    /// accents next to those without and lower case before upper case. Strings are only equal
+    /// when they are canonically equivalent, like `'é'` written as one or two code points.
+    Unicode,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CollationError {
+    #[error("Collation `{0}` does not exist")]
+    UnknownCollation(String),
+}
+
+impl Collation {
+    const NAMES: &'static [(Collation, &'static str)] = &[
+        (Collation::Binary, "binary"),
+        (Collation::NoCase, "nocase"),
+        (Collation::Unicode, "unicode"),
+    ];
+
+    /// Looks up a collation by name in any case.
+    pub fn parse(name: &str) -> Result<Collation, CollationError> {
+        Self::NAMES
+            .iter()
+            .find(|(_, collation_name)| collation_name.eq_ignore_ascii_case(name))
+            .map(|(collation, _)| *collation)
+            .ok_or_else(|| CollationError::UnknownCollation(name.to_string()))
+    }
+
+    /// The names of every collation.
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        Self::NAMES.iter().map(|(_, name)| *name)
+    }
+
+    pub fn name(&self) -> &'static str {
+        Self::NAMES
+            .iter()
+            .find(|(collation, _)| collation == self)
+            .map(|(_, name)| *name)
+            .unwrap()
+    }
+
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        match self {
+            Collation::Binary => a.cmp(b),
+            Collation::NoCase => lowercase(a).cmp(lowercase(b)),
+            Collation::Unicode => UNICODE.with(|collator| collator.compare(a, b)),
+        }
+    }
+
+    /// Hashes text so that strings equal under the collation hash the same.
+    pub fn hash<H: Hasher>(&self, text: &str, state: &mut H) {
+        match self {
+            Collation::Binary => text.hash(state),
+            Collation::NoCase => lowercase(text).for_each(|c| c.hash(state)),
+            // Strings are equal at the identical strength exactly when their canonical
+            // decompositions are
+            Collation::Unicode => NFD.with(|normalizer| {
+                normalizer
+                    .normalize_iter(text.chars())
+                    .for_each(|c| c.hash(state))
+            }),
+        }
+    }
+
+    /// Compares two values, where text is compared under the collation and every other value
+    /// as usual.
+    pub fn compare_values(&self, a: &Value, b: &Value) -> Ordering {
+        match (a, b) {
+            (Value::Text(a), Value::Text(b)) => self.compare(a, b),
+            _ => a.cmp(b),
+        }
+    }
+
+    /// Hashes a value consistently with `compare_values`.
+    pub fn hash_value<H: Hasher>(&self, value: &Value, state: &mut H) {
+        match value {
+            Value::Text(text) => self.hash(text, state),
+            value => value.hash(state),
+        }
+    }
+}
+
+impl Display for Collation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A value together with the collation it is compared with, which can be used as a key of
+/// sorts, hash tables and indexes.
+#[derive(Debug, Clone)]
+pub struct CollatedValue {
+    pub value: Value,
+    pub collation: Collation,
+}
+
+impl Ord for CollatedValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.collation.compare_values(&self.value, &other.value)
+    }
+}
+
+impl PartialOrd for CollatedValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for CollatedValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for CollatedValue {}
+
+impl Hash for CollatedValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.collation.hash_value(&self.value, state)
+    }
+}
+
+thread_local! {
+    static UNICODE: Collator = {
+        let mut options = CollatorOptions::new();
+
+        // Ties between strings which only differ in ignorable characters are broken by their
+        // code points, so that only canonically equivalent strings are equal
+        options.strength = Some(Strength::Identical);
+
+        Collator::try_new(&Default::default(), options).expect("root collation data is compiled in")
+    };
+
+    static NFD: DecomposingNormalizer = const { DecomposingNormalizer::new_nfd() };
+}
+
+fn lowercase(text: &str) -> impl Iterator<Item = char> + '_ {
+    text.chars().flat_map(char::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{BuildHasher, RandomState};
+
+    use super::*;
+
+    /// Asserts that every pair of strings is equal under a collation exactly when it is expected
+    /// to be, and that equal strings hash the same.
+    fn assert_equality(collation: Collation, strings: &[&str], equal: &[(&str, &str)]) {
+        let state = RandomState::new();
+        let hash = |text: &str| {
+            state.hash_one(CollatedValue {
+                value: Value::Text(text.to_string()),
+                collation,
+            })
+        };
+
+        for a in strings {
+            for b in strings {
+                let expected = a == b || equal.contains(&(a, b)) || equal.contains(&(b, a));
+                assert_eq!(
+                    collation.compare(a, b).is_eq(),
+                    expected,
+                    "{collation}: {a} = {b}"
+                );
+                assert_eq!(collation.compare(a, b), collation.compare(b, a).reverse());
+
+                if expected {
+                    assert_eq!(hash(a), hash(b), "{collation}: {a} = {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn names() {
+        for name in Collation::names() {
+            assert_eq!(Collation::parse(name).unwrap().name(), name);
+        }
+
+        assert_eq!(Collation::parse("NoCase"), Ok(Collation::NoCase));
+        assert_eq!(
+            Collation::parse("C"),
+            Err(CollationError::UnknownCollation("C".to_string()))
+        );
+    }
+
+    #[test]
+    fn binary() {
+        assert_equality(Collation::Binary, &["abc", "ABC", "é", "e\u{301}"], &[]);
+        assert!(Collation::Binary.compare("B", "a").is_lt());
+    }
+
+    #[test]
+    fn nocase() {
+        let strings = ["abc", "ABC", "AbC", "abd", "ÉTÉ", "été", "ß", "ss"];
+        let equal = [
+            ("abc", "ABC"),
+            ("abc", "AbC"),
+            ("ABC", "AbC"),
+            ("ÉTÉ", "été"),
+        ];
+        assert_equality(Collation::NoCase, &strings, &equal);
+
+        assert!(Collation::NoCase.compare("a", "B").is_lt());
+        assert!(Collation::NoCase.compare("B", "a").is_gt());
+    }
+
+    #[test]
+    fn unicode() {
+        let strings = [
+            "abc", "ABC", "é", "e\u{301}", "e", "f", "Å", "A\u{30a}", "\u{212b}",
+        ];
+        let equal = [
+            ("é", "e\u{301}"),
+            ("Å", "A\u{30a}"),
+            ("Å", "\u{212b}"),
+            ("A\u{30a}", "\u{212b}"),
+        ];
+        assert_equality(Collation::Unicode, &strings, &equal);
+
+        // Accented letters sort next to unaccented ones, and lower case before upper case
+        assert!(Collation::Unicode.compare("é", "f").is_lt());
+        assert!(Collation::Unicode.compare("e", "é").is_lt());
+        assert!(Collation::Unicode.compare("abc", "ABC").is_lt());
+        assert!(Collation::Unicode.compare("ABC", "abd").is_lt());
+    }
+
+    #[test]
+    fn values() {
+        let a = Value::Text("A".to_string());
+        let b = Value::Text("a".to_string());
+
+        assert!(Collation::NoCase.compare_values(&a, &b).is_eq());
+        assert!(Collation::Binary.compare_values(&a, &b).is_lt());
+        assert!(Collation::NoCase
+            .compare_values(&Value::Int(1), &Value::Int(2))
+            .is_lt());
+    }
+}
@@ -0,0 +1,290 @@
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::sql::{statement::TypeName, value::Decimal};
+
+/// The type of a column, or of the values an expression produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DataType {
+    /// The type of an untyped `NULL`, which every other type can take the place of
+    Null,
+    Boolean,
+    SmallInt,
+    Int,
+    BigInt,
+    /// An exact number with the given precision and scale, or of any precision and scale if
+    /// there are no limits. Either way it has at most `Decimal::MAX_PRECISION` (38) digits, so
+    /// `DECIMAL(p)` and `NUMERIC(p)` with a larger precision are rejected, and results with more
+    /// digits are out of range rather than unbounded as in PostgreSQL.
+    Decimal(Option<(u32, u32)>),
+    Real,
+    Double,
+    Text,
+    /// Text of at most the given number of characters, or of any length if there is no limit
+    Varchar(Option<u32>),
+    Bytea,
+    Date,
+    Time,
+    Timestamp,
+    TimestampTz,
+    Interval,
+    Uuid,
+    /// A JSON document, stored parsed so that it can be queried without parsing it again
+    Jsonb,
+}
+
+impl DataType {
+    /// The names each type can be written as, with the canonical name first.
+    const NAMES: &'static [(DataType, &'static [&'static str])] = &[
+        (DataType::Boolean, &["BOOLEAN", "BOOL"]),
+        (DataType::SmallInt, &["SMALLINT", "INT2"]),
+        (DataType::Int, &["INT", "INTEGER", "INT4"]),
+        (DataType::BigInt, &["BIGINT", "INT8"]),
+        (DataType::Decimal(None), &["DECIMAL", "NUMERIC"]),
+        (DataType::Real, &["REAL", "FLOAT4"]),
+        (DataType::Double, &["DOUBLE", "FLOAT8", "FLOAT"]),
+        (DataType::Text, &["TEXT"]),
+        (DataType::Varchar(None), &["VARCHAR"]),
+        (DataType::Bytea, &["BYTEA"]),
+        (DataType::Date, &["DATE"]),
+        (DataType::Time, &["TIME"]),
+        (DataType::Timestamp, &["TIMESTAMP"]),
+        (DataType::TimestampTz, &["TIMESTAMPTZ"]),
+        (DataType::Interval, &["INTERVAL"]),
+        (DataType::Uuid, &["UUID"]),
+        (DataType::Jsonb, &["JSONB", "JSON"]),
+    ];
+
+    /// Looks up the type a column definition names, which only `VARCHAR(n)` and `DECIMAL(p, s)`
+    /// can give modifiers for.
+    pub fn from_type_name(type_name: &TypeName) -> Result<Self, TypeNameError> {
+        let (data_type, _) = Self::NAMES
+            .iter()
+            .find(|(_, names)| names.contains(&type_name.name.as_str()))
+            .ok_or_else(|| TypeNameError::UnknownType(type_name.name.clone()))?;
+
+        let invalid_modifiers = || TypeNameError::InvalidModifiers(type_name.name.clone());
+
+        match (data_type, &type_name.modifiers[..]) {
+            (_, []) => Ok(*data_type),
+            (DataType::Varchar(_), [length]) => Ok(DataType::Varchar(Some(
+                u32::try_from(*length).map_err(|_| invalid_modifiers())?,
+            ))),
+            (DataType::Decimal(_), [precision]) => Self::decimal(*precision, 0),
+            (DataType::Decimal(_), [precision, scale]) => Self::decimal(*precision, *scale),
+            _ => Err(invalid_modifiers()),
+        }
+    }
+
+    fn decimal(precision: u64, scale: u64) -> Result<Self, TypeNameError> {
+        if !(1..=Decimal::MAX_PRECISION as u64).contains(&precision) {
+            return Err(TypeNameError::InvalidPrecision(precision));
+        }
+
+        if scale > precision {
+            return Err(TypeNameError::InvalidScale { precision, scale });
+        }
+
+        Ok(DataType::Decimal(Some((precision as u32, scale as u32))))
+    }
+
+    /// Every name a type can be written as, starting with the canonical names.
+    pub fn type_names() -> impl Iterator<Item = &'static str> {
+        let canonical = Self::NAMES.iter().map(|(_, names)| names[0]);
+        let aliases = Self::NAMES.iter().flat_map(|(_, names)| &names[1..]);
+
+        canonical.chain(aliases.copied())
+    }
+
+    /// The canonical name of the type, without its modifiers.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DataType::Null => "NULL",
+            DataType::Varchar(_) => "VARCHAR",
+            DataType::Decimal(_) => "DECIMAL",
+            _ => Self::NAMES
+                .iter()
+                .find(|(data_type, _)| data_type == self)
+                .map(|(_, names)| names[0])
+                .unwrap(),
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        self.numeric_rank().is_some()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        matches!(self, DataType::SmallInt | DataType::Int | DataType::BigInt)
+    }
+
+    /// Whether values of the type are text, which is compared under a collation.
+    pub fn is_text(&self) -> bool {
+        matches!(self, DataType::Text | DataType::Varchar(_))
+    }
+
+    /// The position of a numeric type in the chain of implicit coercions
+    /// `SMALLINT → INT → BIGINT → DECIMAL → REAL → DOUBLE`, where every type can be coerced to
+    /// any type after it.
+    fn numeric_rank(&self) -> Option<usize> {
+        match self {
+            DataType::SmallInt => Some(0),
+            DataType::Int => Some(1),
+            DataType::BigInt => Some(2),
+            DataType::Decimal(_) => Some(3),
+            DataType::Real => Some(4),
+            DataType::Double => Some(5),
+            _ => None,
+        }
+    }
+
+    /// How many steps up the coercion lattice a value of this type has to be moved to become
+    /// `target`, or `None` if it cannot be implicitly coerced to it at all.
+    ///
+    /// Besides the numeric chain, `VARCHAR` can become `TEXT`, and `DATE` can become `TIMESTAMP`
+    /// and then `TIMESTAMPTZ`. Only a `DECIMAL` without limits can be coerced to, as values may
+    /// not fit within the precision and scale of a limited one.
+    pub fn coercion_cost(&self, target: DataType) -> Option<usize> {
+        if *self == target || *self == DataType::Null {
+            return Some(0);
+        }
+
+        match (self, target) {
+            (DataType::Decimal(_), DataType::Decimal(None)) => return Some(0),
+            (_, DataType::Decimal(Some(_))) => return None,
+            (DataType::Varchar(_), DataType::Text) => return Some(1),
+            (DataType::Date, DataType::Timestamp) => return Some(1),
+            (DataType::Date, DataType::TimestampTz) => return Some(2),
+            (DataType::Timestamp, DataType::TimestampTz) => return Some(1),
+            _ => {}
+        }
+
+        match (self.numeric_rank(), target.numeric_rank()) {
+            (Some(from), Some(to)) if from < to => Some(to - from),
+            _ => None,
+        }
+    }
+
+    /// Whether values of the type can be converted to `target` with `CAST`, which besides the
+    /// implicit coercions converts any type to and from text, numbers to any other numeric type,
+    /// integers to and from `BOOLEAN`, and dates and times to one another.
+    pub fn can_cast(&self, target: DataType) -> bool {
+        use DataType::*;
+
+        if self.coercion_cost(target).is_some() || self.unlimited() == target.unlimited() {
+            return true;
+        }
+
+        match (self, target) {
+            (_, Text | Varchar(_)) | (Text | Varchar(_), _) => true,
+            (a, b) if a.is_numeric() && b.is_numeric() => true,
+            (Boolean, b) if b.is_integer() => true,
+            (a, Boolean) if a.is_integer() => true,
+            (Date | Timestamp | TimestampTz, Date | Timestamp | TimestampTz) => true,
+            (Timestamp | TimestampTz, Time) | (Time, Interval) => true,
+            _ => false,
+        }
+    }
+
+    /// The type values of both types can be coerced to, which is whichever of the two is
+    /// further along the coercion lattice.
+    pub fn common_type(a: DataType, b: DataType) -> Option<DataType> {
+        if b.coercion_cost(a).is_some() {
+            Some(a)
+        } else if a.coercion_cost(b).is_some() {
+            Some(b)
+        } else {
+            match (a, b) {
+                // Differing limits are lifted, which turns `VARCHAR`s into text
+                (DataType::Varchar(_), DataType::Varchar(_)) => Some(DataType::Text),
+                (DataType::Decimal(Some(_)), _) | (_, DataType::Decimal(Some(_))) => {
+                    Self::common_type(a.unlimited(), b.unlimited())
+                }
+                _ => None,
+            }
+        }
+    }
+
+    /// The type without limits on its values, which is what arithmetic produces, as the result
+    /// can exceed the limits of its operands.
+    pub fn unlimited(self) -> DataType {
+        match self {
+            DataType::Varchar(_) => DataType::Text,
+            DataType::Decimal(_) => DataType::Decimal(None),
+            _ => self,
+        }
+    }
+}
+
+impl Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataType::Varchar(Some(length)) => write!(f, "VARCHAR({})", length),
+            DataType::Decimal(Some((precision, scale))) => {
+                write!(f, "DECIMAL({}, {})", precision, scale)
+            }
+            _ => f.write_str(self.name()),
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum TypeNameError {
+    #[error("Type `{0}` does not exist")]
+    UnknownType(String),
+    #[error("Invalid modifiers for type `{0}`")]
+    InvalidModifiers(String),
+    #[error(
+        "DECIMAL precision {0} must be between 1 and {max}",
+        max = Decimal::MAX_PRECISION
+    )]
+    InvalidPrecision(u64),
+    #[error("DECIMAL scale {scale} must not be greater than its precision {precision}")]
+    InvalidScale { precision: u64, scale: u64 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_name(name: &str, modifiers: &[u64]) -> Result<DataType, TypeNameError> {
+        DataType::from_type_name(&TypeName {
+            name: name.to_string(),
+            modifiers: modifiers.to_vec(),
+        })
+    }
+
+    #[test]
+    fn decimal_precision() {
+        for name in ["DECIMAL", "NUMERIC"] {
+            assert_eq!(type_name(name, &[]), Ok(DataType::Decimal(None)));
+            assert_eq!(type_name(name, &[38]), Ok(DataType::Decimal(Some((38, 0)))));
+            assert_eq!(
+                type_name(name, &[38, 38]),
+                Ok(DataType::Decimal(Some((38, 38))))
+            );
+
+            for precision in [0, 39, u64::MAX] {
+                assert_eq!(
+                    type_name(name, &[precision]),
+                    Err(TypeNameError::InvalidPrecision(precision))
+                );
+            }
+        }
+
+        assert_eq!(
+            type_name("NUMERIC", &[39, 2]).unwrap_err().to_string(),
+            "DECIMAL precision 39 must be between 1 and 38"
+        );
+        assert_eq!(
+            type_name("NUMERIC", &[10, 11]).unwrap_err().to_string(),
+            "DECIMAL scale 11 must not be greater than its precision 10"
+        );
+        assert_eq!(
+            type_name("NUMERIC", &[10, 2, 1]),
+            Err(TypeNameError::InvalidModifiers("NUMERIC".to_string()))
+        );
+    }
+}
@@ -0,0 +1,911 @@
+use std::fmt::{self, Display, Write};
+
+use chrono::{DateTime, LocalResult, NaiveDateTime, Offset, TimeZone as _};
+use chrono_tz::{OffsetName, Tz, TZ_VARIANTS};
+use thiserror::Error;
+
+use crate::sql::value::Interval;
+
+pub(crate) const MICROS_PER_SECOND: i64 = 1_000_000;
+pub(crate) const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+pub(crate) const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+pub(crate) const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+
+/// The time zone `TIMESTAMPTZ` values are shown in, and timestamps without an offset are read in.
+///
+/// Zones are looked up in the IANA time zone database, which is compiled into the server, so
+/// that every client sees the same rules regardless of the machine it runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeZone {
+    Named(Tz),
+    /// A fixed offset east of UTC in seconds, written like `+05:30`
+    Fixed(i32),
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum DateTimeError {
+    #[error("Unknown date field `{0}`")]
+    UnknownField(String),
+    #[error("Date field `{field}` is not supported by {function}")]
+    UnsupportedField { field: DateField, function: String },
+    #[error("Time zone `{0}` does not exist")]
+    UnknownTimeZone(String),
+    #[error("Date or time is out of range")]
+    OutOfRange,
+    #[error("Interval `{0}` cannot be used to bin timestamps, as it is not positive or mixes months with days")]
+    InvalidStride(Interval),
+    #[error("Division by zero")]
+    DivisionByZero,
+}
+
+impl TimeZone {
+    pub const UTC: TimeZone = TimeZone::Named(Tz::UTC);
+
+    /// Parses the name of a zone in the time zone database in any case, such as
+    /// `Europe/Berlin`, or an offset from UTC such as `+02`, `-05:30` or `+0530`.
+    pub fn parse(text: &str) -> Result<TimeZone, DateTimeError> {
+        let unknown = || DateTimeError::UnknownTimeZone(text.to_string());
+
+        let (negative, offset) = match text.as_bytes().first() {
+            Some(b'+') => (false, &text[1..]),
+            Some(b'-') => (true, &text[1..]),
+            _ => {
+                return TZ_VARIANTS
+                    .iter()
+                    .find(|tz| tz.name().eq_ignore_ascii_case(text))
+                    .map(|tz| TimeZone::Named(*tz))
+                    .ok_or_else(unknown)
+            }
+        };
+
+        let (hours, minutes) = match offset.split_once(':') {
+            Some((hours, minutes)) => (hours, minutes),
+            None if offset.len() == 4 => offset.split_at(2),
+            None => (offset, "0"),
+        };
+
+        let digits = |part: &str| {
+            (1..=2).contains(&part.len()) && part.bytes().all(|byte| byte.is_ascii_digit())
+        };
+
+        if !digits(hours) || !digits(minutes) {
+            return Err(unknown());
+        }
+
+        let (hours, minutes) = (
+            hours.parse::<i32>().unwrap(),
+            minutes.parse::<i32>().unwrap(),
+        );
+
+        if hours > 15 || minutes > 59 {
+            return Err(unknown());
+        }
+
+        let seconds = hours * 3600 + minutes * 60;
+        Ok(TimeZone::Fixed(if negative { -seconds } else { seconds }))
+    }
+
+    /// The names of every zone in the time zone database.
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        TZ_VARIANTS.iter().map(|tz| tz.name())
+    }
+
+    /// The offset from UTC in microseconds at the instant `utc`, in microseconds since
+    /// 1970-01-01 00:00:00 UTC.
+    pub fn offset_at(&self, utc: i64) -> i64 {
+        let seconds = match self {
+            TimeZone::Named(tz) => naive(utc).map_or(0, |naive| {
+                tz.offset_from_utc_datetime(&naive).fix().local_minus_utc()
+            }),
+            TimeZone::Fixed(seconds) => *seconds,
+        };
+
+        seconds as i64 * MICROS_PER_SECOND
+    }
+
+    /// Converts a local time in microseconds since 1970-01-01 00:00:00 into UTC.
+    ///
+    /// Local times which occur twice as clocks are turned back are taken to be the later one,
+    /// and those skipped as clocks are turned forward are read with the offset before the
+    /// transition, like `02:30` becoming `03:30` daylight saving time.
+    pub fn to_utc(&self, local: i64) -> i64 {
+        let TimeZone::Named(tz) = self else {
+            return local - self.offset_at(local);
+        };
+
+        let Some(naive) = naive(local) else {
+            return local;
+        };
+
+        let offset = |offset: <Tz as chrono::TimeZone>::Offset| {
+            offset.fix().local_minus_utc() as i64 * MICROS_PER_SECOND
+        };
+
+        match tz.offset_from_local_datetime(&naive) {
+            LocalResult::Single(single) => local - offset(single),
+            LocalResult::Ambiguous(earlier, later) => local - offset(earlier).min(offset(later)),
+            LocalResult::None => local - self.offset_at(local - MICROS_PER_DAY),
+        }
+    }
+
+    /// The abbreviation of the zone at the instant `utc`, such as `CEST`, or its offset if it
+    /// has none.
+    pub fn abbreviation(&self, utc: i64) -> String {
+        let abbreviation = match self {
+            TimeZone::Named(tz) => naive(utc).and_then(|naive| {
+                tz.offset_from_utc_datetime(&naive)
+                    .abbreviation()
+                    .map(ToString::to_string)
+            }),
+            TimeZone::Fixed(_) => None,
+        };
+
+        abbreviation.unwrap_or_else(|| {
+            let mut offset = String::new();
+            write_offset(&mut offset, self.offset_at(utc)).unwrap();
+            offset
+        })
+    }
+}
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        TimeZone::UTC
+    }
+}
+
+impl Display for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeZone::Named(tz) => f.write_str(tz.name()),
+            TimeZone::Fixed(seconds) => {
+                let (sign, seconds) = if *seconds < 0 {
+                    ('-', -seconds)
+                } else {
+                    ('+', *seconds)
+                };
+                write!(
+                    f,
+                    "{}{:02}:{:02}",
+                    sign,
+                    seconds / 3600,
+                    seconds % 3600 / 60
+                )
+            }
+        }
+    }
+}
+
+/* FIELDS */
+
+/// A part of a date, time or interval, which `date_part` extracts and `date_trunc` truncates to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateField {
+    Microseconds,
+    Milliseconds,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    /// The ISO 8601 week, which starts on a Monday
+    Week,
+    Month,
+    Quarter,
+    Year,
+    Decade,
+    Century,
+    Millennium,
+    /// The day of the week, from Sunday as 0 to Saturday as 6
+    DayOfWeek,
+    /// The day of the week, from Monday as 1 to Sunday as 7
+    IsoDayOfWeek,
+    DayOfYear,
+    /// The year the ISO 8601 week falls in
+    IsoYear,
+    /// Seconds since 1970-01-01 00:00:00 UTC, or the length of an interval in seconds
+    Epoch,
+    /// The offset from UTC in seconds
+    Timezone,
+    TimezoneHour,
+    TimezoneMinute,
+}
+
+impl DateField {
+    /// Looks up a field by name in any case, accepting plurals and common abbreviations.
+    pub fn parse(name: &str) -> Result<DateField, DateTimeError> {
+        let field = match name.to_ascii_lowercase().as_str() {
+            "microsecond" | "microseconds" | "us" => DateField::Microseconds,
+            "millisecond" | "milliseconds" | "ms" => DateField::Milliseconds,
+            "second" | "seconds" | "sec" | "secs" => DateField::Second,
+            "minute" | "minutes" | "min" | "mins" => DateField::Minute,
+            "hour" | "hours" => DateField::Hour,
+            "day" | "days" => DateField::Day,
+            "week" | "weeks" => DateField::Week,
+            "month" | "months" | "mon" | "mons" => DateField::Month,
+            "quarter" | "quarters" => DateField::Quarter,
+            "year" | "years" => DateField::Year,
+            "decade" | "decades" => DateField::Decade,
+            "century" | "centuries" => DateField::Century,
+            "millennium" | "millennia" => DateField::Millennium,
+            "dow" => DateField::DayOfWeek,
+            "isodow" => DateField::IsoDayOfWeek,
+            "doy" => DateField::DayOfYear,
+            "isoyear" => DateField::IsoYear,
+            "epoch" => DateField::Epoch,
+            "timezone" => DateField::Timezone,
+            "timezone_hour" => DateField::TimezoneHour,
+            "timezone_minute" => DateField::TimezoneMinute,
+            _ => return Err(DateTimeError::UnknownField(name.to_string())),
+        };
+
+        Ok(field)
+    }
+
+    /// Whether timestamps can be truncated to the field, which are the fields that are units of
+    /// time.
+    pub fn is_unit(&self) -> bool {
+        matches!(
+            self,
+            DateField::Microseconds
+                | DateField::Milliseconds
+                | DateField::Second
+                | DateField::Minute
+                | DateField::Hour
+                | DateField::Day
+                | DateField::Week
+                | DateField::Month
+                | DateField::Quarter
+                | DateField::Year
+                | DateField::Decade
+                | DateField::Century
+                | DateField::Millennium
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            DateField::Microseconds => "microseconds",
+            DateField::Milliseconds => "milliseconds",
+            DateField::Second => "second",
+            DateField::Minute => "minute",
+            DateField::Hour => "hour",
+            DateField::Day => "day",
+            DateField::Week => "week",
+            DateField::Month => "month",
+            DateField::Quarter => "quarter",
+            DateField::Year => "year",
+            DateField::Decade => "decade",
+            DateField::Century => "century",
+            DateField::Millennium => "millennium",
+            DateField::DayOfWeek => "dow",
+            DateField::IsoDayOfWeek => "isodow",
+            DateField::DayOfYear => "doy",
+            DateField::IsoYear => "isoyear",
+            DateField::Epoch => "epoch",
+            DateField::Timezone => "timezone",
+            DateField::TimezoneHour => "timezone_hour",
+            DateField::TimezoneMinute => "timezone_minute",
+        }
+    }
+}
+
+impl Display for DateField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/* INTERVAL ARITHMETIC */
+
+impl Interval {
+    pub fn checked_add(&self, other: &Interval) -> Result<Interval, DateTimeError> {
+        Ok(Interval {
+            months: self
+                .months
+                .checked_add(other.months)
+                .ok_or(DateTimeError::OutOfRange)?,
+            days: self
+                .days
+                .checked_add(other.days)
+                .ok_or(DateTimeError::OutOfRange)?,
+            micros: self
+                .micros
+                .checked_add(other.micros)
+                .ok_or(DateTimeError::OutOfRange)?,
+        })
+    }
+
+    pub fn checked_sub(&self, other: &Interval) -> Result<Interval, DateTimeError> {
+        self.checked_add(&other.checked_neg()?)
+    }
+
+    pub fn checked_neg(&self) -> Result<Interval, DateTimeError> {
+        Ok(Interval {
+            months: self.months.checked_neg().ok_or(DateTimeError::OutOfRange)?,
+            days: self.days.checked_neg().ok_or(DateTimeError::OutOfRange)?,
+            micros: self.micros.checked_neg().ok_or(DateTimeError::OutOfRange)?,
+        })
+    }
+
+    /// Scales the interval, where fractions of a month are carried into days counting a month as
+    /// 30 days, and fractions of a day into its time.
+    pub fn checked_mul(&self, factor: f64) -> Result<Interval, DateTimeError> {
+        let months = self.months as f64 * factor;
+        let days = self.days as f64 * factor + months.fract() * 30.0;
+        let micros = self.micros as f64 * factor + days.fract() * MICROS_PER_DAY as f64;
+
+        let whole = |value: f64, max: f64| match value.is_finite() && value.abs() <= max {
+            true => Ok(value),
+            false => Err(DateTimeError::OutOfRange),
+        };
+
+        Ok(Interval {
+            months: whole(months.trunc(), i32::MAX as f64)? as i32,
+            days: whole(days.trunc(), i32::MAX as f64)? as i32,
+            micros: whole(micros.round(), i64::MAX as f64)? as i64,
+        })
+    }
+
+    pub fn checked_div(&self, divisor: f64) -> Result<Interval, DateTimeError> {
+        match divisor == 0.0 {
+            true => Err(DateTimeError::DivisionByZero),
+            false => self.checked_mul(1.0 / divisor),
+        }
+    }
+}
+
+/* TIMESTAMP ARITHMETIC */
+
+/// Adds an interval to a timestamp, moving it by whole months first, keeping the day of the
+/// month unless the month is shorter, then by days and then by the rest.
+pub fn add_interval(timestamp: i64, interval: &Interval) -> Result<i64, DateTimeError> {
+    let (days, time) = split(timestamp);
+    let days = add_months(days, interval.months)? + interval.days as i64;
+
+    days.checked_mul(MICROS_PER_DAY)
+        .and_then(|micros| micros.checked_add(time))
+        .and_then(|micros| micros.checked_add(interval.micros))
+        .ok_or(DateTimeError::OutOfRange)
+}
+
+/// Adds an interval to a `TIMESTAMPTZ`, whose months and days are added in local time, so that
+/// adding a day across a daylight saving time transition keeps the time of day.
+pub fn add_interval_in(
+    timestamp: i64,
+    interval: &Interval,
+    time_zone: &TimeZone,
+) -> Result<i64, DateTimeError> {
+    if interval.months == 0 && interval.days == 0 {
+        return timestamp
+            .checked_add(interval.micros)
+            .ok_or(DateTimeError::OutOfRange);
+    }
+
+    let local = timestamp + time_zone.offset_at(timestamp);
+    let calendar = Interval {
+        micros: 0,
+        ..*interval
+    };
+
+    time_zone
+        .to_utc(add_interval(local, &calendar)?)
+        .checked_add(interval.micros)
+        .ok_or(DateTimeError::OutOfRange)
+}
+
+/// The interval between two timestamps, in days and the time left over.
+pub fn subtract_timestamps(left: i64, right: i64) -> Result<Interval, DateTimeError> {
+    let difference = left.checked_sub(right).ok_or(DateTimeError::OutOfRange)?;
+
+    Ok(Interval {
+        months: 0,
+        days: i32::try_from(difference / MICROS_PER_DAY).map_err(|_| DateTimeError::OutOfRange)?,
+        micros: difference % MICROS_PER_DAY,
+    })
+}
+
+/// Adds the time of an interval to a time of day, wrapping around midnight.
+pub fn add_interval_to_time(time: i64, interval: &Interval) -> i64 {
+    (time + interval.micros % MICROS_PER_DAY).rem_euclid(MICROS_PER_DAY)
+}
+
+/* DATE_TRUNC */
+
+/// Truncates a timestamp to the start of the unit `field` it falls in.
+pub fn date_trunc(field: DateField, timestamp: i64) -> Result<i64, DateTimeError> {
+    let (days, _) = split(timestamp);
+    let (year, month, _) = civil_from_days(days);
+    let start_of_month = |year: i64, month: u32| days_from_civil(year, month, 1) * MICROS_PER_DAY;
+    let truncated = |unit: i64| timestamp - timestamp.rem_euclid(unit);
+
+    let truncated = match field {
+        DateField::Microseconds => timestamp,
+        DateField::Milliseconds => truncated(1000),
+        DateField::Second => truncated(MICROS_PER_SECOND),
+        DateField::Minute => truncated(MICROS_PER_MINUTE),
+        DateField::Hour => truncated(MICROS_PER_HOUR),
+        DateField::Day => truncated(MICROS_PER_DAY),
+        DateField::Week => (days - (days + 3).rem_euclid(7)) * MICROS_PER_DAY,
+        DateField::Month => start_of_month(year, month),
+        DateField::Quarter => start_of_month(year, (month - 1) / 3 * 3 + 1),
+        DateField::Year => start_of_month(year, 1),
+        DateField::Decade => start_of_month(year.div_euclid(10) * 10, 1),
+        // Centuries and millennia start with their year 1, so the 21st century starts in 2001
+        DateField::Century => start_of_month((year - 1).div_euclid(100) * 100 + 1, 1),
+        DateField::Millennium => start_of_month((year - 1).div_euclid(1000) * 1000 + 1, 1),
+        _ => {
+            return Err(DateTimeError::UnsupportedField {
+                field,
+                function: "date_trunc".to_string(),
+            })
+        }
+    };
+
+    Ok(truncated)
+}
+
+/// Truncates a `TIMESTAMPTZ` to the start of the unit `field` it falls in, in local time.
+pub fn date_trunc_in(
+    field: DateField,
+    timestamp: i64,
+    time_zone: &TimeZone,
+) -> Result<i64, DateTimeError> {
+    let local = timestamp + time_zone.offset_at(timestamp);
+    Ok(time_zone.to_utc(date_trunc(field, local)?))
+}
+
+/// Truncates an interval to the unit `field`, dropping every smaller unit.
+pub fn date_trunc_interval(
+    field: DateField,
+    interval: &Interval,
+) -> Result<Interval, DateTimeError> {
+    let months = |unit: i32| Interval {
+        months: interval.months - interval.months % unit,
+        days: 0,
+        micros: 0,
+    };
+
+    let micros = |unit: i64| Interval {
+        micros: interval.micros - interval.micros % unit,
+        ..*interval
+    };
+
+    let truncated = match field {
+        DateField::Microseconds => *interval,
+        DateField::Milliseconds => micros(1000),
+        DateField::Second => micros(MICROS_PER_SECOND),
+        DateField::Minute => micros(MICROS_PER_MINUTE),
+        DateField::Hour => micros(MICROS_PER_HOUR),
+        DateField::Day => micros(i64::MAX),
+        DateField::Month => months(1),
+        DateField::Quarter => months(3),
+        DateField::Year => months(12),
+        DateField::Decade => months(120),
+        DateField::Century => months(1200),
+        DateField::Millennium => months(12000),
+        _ => {
+            return Err(DateTimeError::UnsupportedField {
+                field,
+                function: "date_trunc".to_string(),
+            })
+        }
+    };
+
+    Ok(truncated)
+}
+
+/* DATE_PART */
+
+/// Extracts the field `field` from a timestamp.
+pub fn date_part(field: DateField, timestamp: i64) -> Result<f64, DateTimeError> {
+    let (days, time) = split(timestamp);
+    let (year, month, day) = civil_from_days(days);
+
+    let part = match field {
+        DateField::Microseconds
+        | DateField::Milliseconds
+        | DateField::Second
+        | DateField::Minute
+        | DateField::Hour => time_part(field, time)?,
+        DateField::Day => day as f64,
+        DateField::Week => iso_week(days).1 as f64,
+        DateField::Month => month as f64,
+        DateField::Quarter => ((month - 1) / 3 + 1) as f64,
+        DateField::Year => year as f64,
+        DateField::Decade => year.div_euclid(10) as f64,
+        DateField::Century => (year - 1).div_euclid(100) as f64 + 1.0,
+        DateField::Millennium => (year - 1).div_euclid(1000) as f64 + 1.0,
+        DateField::DayOfWeek => (days + 4).rem_euclid(7) as f64,
+        DateField::IsoDayOfWeek => ((days + 3).rem_euclid(7) + 1) as f64,
+        DateField::DayOfYear => (days - days_from_civil(year, 1, 1) + 1) as f64,
+        DateField::IsoYear => iso_week(days).0 as f64,
+        DateField::Epoch => timestamp as f64 / MICROS_PER_SECOND as f64,
+        DateField::Timezone | DateField::TimezoneHour | DateField::TimezoneMinute => {
+            return Err(DateTimeError::UnsupportedField {
+                field,
+                function: "date_part on TIMESTAMP".to_string(),
+            })
+        }
+    };
+
+    Ok(part)
+}
+
+/// Extracts the field `field` from a `TIMESTAMPTZ` in local time.
+pub fn date_part_in(
+    field: DateField,
+    timestamp: i64,
+    time_zone: &TimeZone,
+) -> Result<f64, DateTimeError> {
+    let offset = time_zone.offset_at(timestamp) / MICROS_PER_SECOND;
+
+    match field {
+        DateField::Epoch => date_part(field, timestamp),
+        DateField::Timezone => Ok(offset as f64),
+        DateField::TimezoneHour => Ok((offset / 3600) as f64),
+        DateField::TimezoneMinute => Ok((offset / 60 % 60) as f64),
+        _ => date_part(field, timestamp + offset * MICROS_PER_SECOND),
+    }
+}
+
+/// Extracts the field `field` from a time of day.
+pub fn date_part_time(field: DateField, time: i64) -> Result<f64, DateTimeError> {
+    match field {
+        DateField::Epoch => Ok(time as f64 / MICROS_PER_SECOND as f64),
+        _ => time_part(field, time),
+    }
+}
+
+/// Extracts the field `field` from an interval, where the epoch counts a year as 365.25 days
+/// and a month as 30 days.
+pub fn date_part_interval(field: DateField, interval: &Interval) -> Result<f64, DateTimeError> {
+    let months = interval.months as f64;
+
+    let part = match field {
+        DateField::Microseconds | DateField::Milliseconds | DateField::Second => {
+            time_part(field, interval.micros % MICROS_PER_MINUTE)?
+        }
+        DateField::Minute => (interval.micros / MICROS_PER_MINUTE % 60) as f64,
+        DateField::Hour => (interval.micros / MICROS_PER_HOUR) as f64,
+        DateField::Day => interval.days as f64,
+        DateField::Month => (interval.months % 12) as f64,
+        DateField::Quarter => (interval.months % 12 / 3 + 1) as f64,
+        DateField::Year => (interval.months / 12) as f64,
+        DateField::Decade => (interval.months / 120) as f64,
+        DateField::Century => (interval.months / 1200) as f64,
+        DateField::Millennium => (interval.months / 12000) as f64,
+        DateField::Epoch => {
+            let days = (months / 12.0).trunc() * 365.25 + (months % 12.0) * 30.0;
+            (days + interval.days as f64) * 86400.0
+                + interval.micros as f64 / MICROS_PER_SECOND as f64
+        }
+        _ => {
+            return Err(DateTimeError::UnsupportedField {
+                field,
+                function: "date_part on INTERVAL".to_string(),
+            })
+        }
+    };
+
+    Ok(part)
+}
+
+/// Extracts a field of the time of day, where seconds include their fraction.
+fn time_part(field: DateField, time: i64) -> Result<f64, DateTimeError> {
+    let within_minute = time % MICROS_PER_MINUTE;
+
+    let part = match field {
+        DateField::Microseconds => within_minute as f64,
+        DateField::Milliseconds => within_minute as f64 / 1000.0,
+        DateField::Second => within_minute as f64 / MICROS_PER_SECOND as f64,
+        DateField::Minute => (time / MICROS_PER_MINUTE % 60) as f64,
+        DateField::Hour => (time / MICROS_PER_HOUR) as f64,
+        _ => {
+            return Err(DateTimeError::UnsupportedField {
+                field,
+                function: "date_part on TIME".to_string(),
+            })
+        }
+    };
+
+    Ok(part)
+}
+
+/* BINNING */
+
+/// Aligns timestamps with the start of the stride they fall in, counting strides from `origin`.
+pub fn date_bin(stride: &Interval, timestamp: i64, origin: i64) -> Result<i64, DateTimeError> {
+    let stride_micros = (stride.days as i64)
+        .checked_mul(MICROS_PER_DAY)
+        .and_then(|micros| micros.checked_add(stride.micros))
+        .filter(|micros| *micros > 0 && stride.months == 0)
+        .ok_or(DateTimeError::InvalidStride(*stride))?;
+
+    let strides = timestamp
+        .checked_sub(origin)
+        .ok_or(DateTimeError::OutOfRange)?
+        .div_euclid(stride_micros);
+
+    strides
+        .checked_mul(stride_micros)
+        .and_then(|micros| micros.checked_add(origin))
+        .ok_or(DateTimeError::OutOfRange)
+}
+
+/// Aligns timestamps with the start of the bucket of `width` they fall in, where buckets of
+/// months are counted from 2000-01-01, and all others from Monday 2000-01-03 so that weeks
+/// start on Mondays.
+pub fn time_bucket(width: &Interval, timestamp: i64) -> Result<i64, DateTimeError> {
+    if width.months == 0 {
+        return date_bin(
+            width,
+            timestamp,
+            days_from_civil(2000, 1, 3) * MICROS_PER_DAY,
+        );
+    }
+
+    if width.months < 0 || width.days != 0 || width.micros != 0 {
+        return Err(DateTimeError::InvalidStride(*width));
+    }
+
+    let (year, month, _) = civil_from_days(split(timestamp).0);
+    let months = (year - 2000) * 12 + month as i64 - 1;
+    let bucket = months.div_euclid(width.months as i64) * width.months as i64;
+    let (year, month) = (
+        2000 + bucket.div_euclid(12),
+        bucket.rem_euclid(12) as u32 + 1,
+    );
+
+    Ok(days_from_civil(year, month, 1) * MICROS_PER_DAY)
+}
+
+/* TO_CHAR */
+
+/// The patterns `to_char` replaces, longest first so that `DDD` is not read as `DD` and `D`.
+const PATTERNS: &[&str] = &[
+    "MONTH", "Month", "month", "HH24", "HH12", "YYYY", "IYYY", "DAY", "Day", "day", "DDD", "MON",
+    "Mon", "mon", "HH", "MI", "SS", "MS", "US", "AM", "PM", "am", "pm", "YY", "MM", "DD", "DY",
+    "Dy", "dy", "ID", "IW", "TZ", "tz", "OF", "D", "Q",
+];
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const DAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// Formats a timestamp with the patterns of `format`, such as `YYYY-MM-DD HH24:MI:SS`, copying
+/// anything else and text in double quotes as it is.
+///
+/// Names are padded to the length of the longest one and numbers with zeros unless the
+/// pattern is prefixed with `FM`. A `TIMESTAMPTZ` is formatted in the local time of
+/// `time_zone`, which `TZ` and `OF` show.
+pub fn to_char(timestamp: i64, time_zone: Option<&TimeZone>, format: &str) -> String {
+    let offset = time_zone.map_or(0, |time_zone| time_zone.offset_at(timestamp));
+    let local = timestamp + offset;
+
+    let (days, time) = split(local);
+    let (year, month, day) = civil_from_days(days);
+    let (iso_year, iso_week) = iso_week(days);
+    let day_of_week = (days + 4).rem_euclid(7) as usize;
+    let hour = time / MICROS_PER_HOUR;
+
+    let mut out = String::new();
+    let mut rest = format;
+
+    while !rest.is_empty() {
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted.find('"').unwrap_or(quoted.len());
+            out.push_str(&quoted[..end]);
+            rest = quoted.get(end + 1..).unwrap_or("");
+            continue;
+        }
+
+        let fill_mode = rest.starts_with("FM");
+        let pattern_start = if fill_mode { &rest[2..] } else { rest };
+
+        let Some(pattern) = PATTERNS
+            .iter()
+            .find(|pattern| pattern_start.starts_with(**pattern))
+        else {
+            let c = rest.chars().next().unwrap();
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        };
+
+        rest = &pattern_start[pattern.len()..];
+
+        let number = |value: i64, width: usize| match fill_mode {
+            true => value.to_string(),
+            false => format!("{:0width$}", value, width = width),
+        };
+
+        let name = |name: &str, width: usize| {
+            let name = match pattern.chars().nth(1) {
+                Some(c) if c.is_ascii_uppercase() => name.to_ascii_uppercase(),
+                _ if pattern.starts_with(|c: char| c.is_ascii_lowercase()) => {
+                    name.to_ascii_lowercase()
+                }
+                _ => name.to_string(),
+            };
+
+            match fill_mode {
+                true => name,
+                false => format!("{:width$}", name, width = width),
+            }
+        };
+
+        let hour12 = match hour % 12 {
+            0 => 12,
+            hour => hour,
+        };
+
+        let replacement = match *pattern {
+            "YYYY" => number(year, 4),
+            "YY" => number(year.rem_euclid(100), 2),
+            "IYYY" => number(iso_year, 4),
+            "MONTH" | "Month" | "month" => name(MONTHS[month as usize - 1], 9),
+            "MON" | "Mon" | "mon" => name(&MONTHS[month as usize - 1][..3], 3),
+            "MM" => number(month as i64, 2),
+            "DAY" | "Day" | "day" => name(DAYS[day_of_week], 9),
+            "DY" | "Dy" | "dy" => name(&DAYS[day_of_week][..3], 3),
+            "DDD" => number(days - days_from_civil(year, 1, 1) + 1, 3),
+            "DD" => number(day as i64, 2),
+            "D" => number(day_of_week as i64 + 1, 1),
+            "ID" => number((days + 3).rem_euclid(7) + 1, 1),
+            "IW" => number(iso_week as i64, 2),
+            "Q" => number((month as i64 - 1) / 3 + 1, 1),
+            "HH24" => number(hour, 2),
+            "HH12" | "HH" => number(hour12, 2),
+            "MI" => number(time / MICROS_PER_MINUTE % 60, 2),
+            "SS" => number(time / MICROS_PER_SECOND % 60, 2),
+            "MS" => number(time / 1000 % 1000, 3),
+            "US" => number(time % MICROS_PER_SECOND, 6),
+            "AM" | "PM" => (if hour < 12 { "AM" } else { "PM" }).to_string(),
+            "am" | "pm" => (if hour < 12 { "am" } else { "pm" }).to_string(),
+            "TZ" | "tz" => match time_zone {
+                Some(time_zone) if *pattern == "TZ" => time_zone.abbreviation(timestamp),
+                Some(time_zone) => time_zone.abbreviation(timestamp).to_ascii_lowercase(),
+                None => String::new(),
+            },
+            "OF" => match time_zone {
+                Some(_) => {
+                    let mut written = String::new();
+                    write_offset(&mut written, offset).unwrap();
+                    written
+                }
+                None => String::new(),
+            },
+            _ => unreachable!("Pattern `{}` is not handled", pattern),
+        };
+
+        out.push_str(&replacement);
+    }
+
+    out
+}
+
+/* FORMATTING */
+
+/// Writes an offset from UTC as `+HH`, or `+HH:MM` if it is not a whole number of hours.
+pub(crate) fn write_offset(f: &mut impl Write, offset: i64) -> fmt::Result {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let minutes = offset.abs() / MICROS_PER_MINUTE;
+
+    write!(f, "{}{:02}", sign, minutes / 60)?;
+
+    if minutes % 60 != 0 {
+        write!(f, ":{:02}", minutes % 60)?;
+    }
+
+    Ok(())
+}
+
+/* CALENDAR */
+
+/// Splits a timestamp into days since 1970-01-01 and microseconds since midnight.
+pub(crate) fn split(timestamp: i64) -> (i64, i64) {
+    (
+        timestamp.div_euclid(MICROS_PER_DAY),
+        timestamp.rem_euclid(MICROS_PER_DAY),
+    )
+}
+
+fn naive(timestamp: i64) -> Option<NaiveDateTime> {
+    DateTime::from_timestamp_micros(timestamp).map(|date_time| date_time.naive_utc())
+}
+
+/// Moves a date by whole months, keeping its day of the month unless the month is shorter.
+fn add_months(days: i64, months: i32) -> Result<i64, DateTimeError> {
+    if months == 0 {
+        return Ok(days);
+    }
+
+    let (year, month, day) = civil_from_days(days);
+    let total = (year * 12 + month as i64 - 1)
+        .checked_add(months as i64)
+        .ok_or(DateTimeError::OutOfRange)?;
+    let (year, month) = (total.div_euclid(12), total.rem_euclid(12) as u32 + 1);
+
+    Ok(days_from_civil(
+        year,
+        month,
+        day.min(days_in_month(year, month)),
+    ))
+}
+
+/// The ISO 8601 year and week a day falls in, where weeks start on Mondays and the first week
+/// of a year is the one with its first Thursday.
+fn iso_week(days: i64) -> (i64, u32) {
+    let thursday = days - (days + 3).rem_euclid(7) + 3;
+    let (year, _, _) = civil_from_days(thursday);
+
+    (
+        year,
+        ((thursday - days_from_civil(year, 1, 1)) / 7 + 1) as u32,
+    )
+}
+
+pub(crate) fn days_in_month(year: i64, month: u32) -> u32 {
+    let leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if leap_year => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+/// Converts a date of the proleptic Gregorian calendar into days since 1970-01-01.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+/// Converts days since 1970-01-01 into a date of the proleptic Gregorian calendar.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
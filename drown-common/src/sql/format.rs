@@ -0,0 +1,1661 @@
+use std::fmt::{self, Display, Write};
+
+use crate::sql::{
+    keyword::Keyword,
+    parser::{CommentedStatements, StatementParseError, StatementParser},
+    statement::{
+        AliasedExpr, AliasedFromTable, Assignment, BinaryOperator, CheckOption, ColumnDefinition,
+        ColumnName, CreateDatabaseStatement, CreateFunctionStatement,
+        CreateMaterializedViewStatement, CreateSchemaStatement, CreateTableStatement,
+        CreateViewStatement, DeleteStatement, DropFunctionStatement, DropSchemaStatement,
+        DropViewStatement, ExplainFormat, ExplainStatement, Expr, FromClause, FromTable,
+        FunctionArgs, FunctionParam, GroupByClause, HavingClause, InsertSource, InsertStatement,
+        JoinCondition, JoinType, LimitClause, Literal, ObjectName, OffsetClause, OrderByClause,
+        OrderByExpr, OrderByItem, Ordering, RefreshMaterializedViewStatement, ResultColumns,
+        SchemaName, SelectStatement, SetOperation, SetOperator, SetSearchPathStatement,
+        SetTimeZoneStatement, ShowColumnsStatement, ShowCreateTableStatement, Statement, TypeName,
+        UnaryOperator, UpdateStatement, UseStatement, Volatility, WhereClause,
+    },
+};
+
+/// How keywords and type names are capitalized. Identifiers are always written as they are.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordCase {
+    #[default]
+    Upper,
+    Lower,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Number of spaces nested parts of a statement are indented by
+    pub indent_width: usize,
+    pub keyword_case: KeywordCase,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent_width: 2,
+            keyword_case: KeywordCase::Upper,
+        }
+    }
+}
+
+/// Pretty-prints a statement over multiple lines, without a terminating semicolon.
+///
+/// The output parses back into the same statement, as does the single line form produced by
+/// `Display`.
+pub fn format_statement(statement: &Statement, options: &FormatOptions) -> String {
+    let mut output = String::new();
+
+    statement
+        .write_sql(&mut SqlWriter::pretty(&mut output, options))
+        .expect("Writing to a String cannot fail");
+
+    output
+}
+
+/// Pretty-prints each statement followed by a semicolon, with a blank line between statements.
+pub fn format_statements(statements: &[Statement], options: &FormatOptions) -> String {
+    statements
+        .iter()
+        .map(|statement| format!("{};\n", format_statement(statement, options)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Pretty-prints every statement in SQL source like `format_statements`, keeping its comments.
+///
+/// Comments on the same line as the end of a statement are written after its semicolon, and
+/// every other comment on its own line in front of the statement it is before or inside of, as
+/// statements are laid out anew.
+pub fn format_source(input: &str, options: &FormatOptions) -> Result<String, StatementParseError> {
+    let CommentedStatements {
+        statements,
+        comments,
+    } = StatementParser::parse_with_comments(input)?;
+
+    // The comments in front of each statement, and those after the last one
+    let mut leading = vec![Vec::new(); statements.len() + 1];
+    let mut trailing = vec![Vec::new(); statements.len()];
+
+    for comment in &comments {
+        let start = comment.span.start();
+        let next = statements.partition_point(|(_, span)| span.end() <= start);
+
+        let before_next = statements
+            .get(next)
+            .is_none_or(|(_, span)| start < span.start());
+
+        let same_line = next.checked_sub(1).filter(|&previous| {
+            before_next && !input[statements[previous].1.end()..start].contains('\n')
+        });
+
+        match same_line {
+            Some(previous) => trailing[previous].push(comment.text.as_str()),
+            None => leading[next].push(comment.text.as_str()),
+        }
+    }
+
+    let mut parts = statements
+        .iter()
+        .zip(leading.iter().zip(&trailing))
+        .map(|((statement, _), (leading, trailing))| {
+            let mut part = String::new();
+
+            for comment in leading {
+                part.push_str(comment);
+                part.push('\n');
+            }
+
+            part.push_str(&format_statement(statement, options));
+            part.push(';');
+
+            for comment in trailing {
+                part.push(' ');
+                part.push_str(comment);
+            }
+
+            part.push('\n');
+            part
+        })
+        .collect::<Vec<_>>();
+
+    let last = leading.last().unwrap();
+
+    if !last.is_empty() {
+        parts.push(format!("{}\n", last.join("\n")));
+    }
+
+    Ok(parts.join("\n"))
+}
+
+/// Writes SQL either on a single line or laid out over indented lines.
+struct SqlWriter<'a> {
+    out: &'a mut dyn Write,
+    keyword_case: KeywordCase,
+    /// Spaces per level of indentation, or `None` when writing everything on a single line
+    indent_width: Option<usize>,
+    depth: usize,
+}
+
+impl<'a> SqlWriter<'a> {
+    fn compact(out: &'a mut dyn Write) -> Self {
+        Self {
+            out,
+            keyword_case: KeywordCase::Upper,
+            indent_width: None,
+            depth: 0,
+        }
+    }
+
+    fn pretty(out: &'a mut dyn Write, options: &FormatOptions) -> Self {
+        Self {
+            out,
+            keyword_case: options.keyword_case,
+            indent_width: Some(options.indent_width),
+            depth: 0,
+        }
+    }
+
+    fn text(&mut self, text: &str) -> fmt::Result {
+        self.out.write_str(text)
+    }
+
+    /// Writes text which is capitalized like a keyword, such as a type name.
+    fn keyword_text(&mut self, text: &str) -> fmt::Result {
+        match self.keyword_case {
+            KeywordCase::Upper => self.text(&text.to_ascii_uppercase()),
+            KeywordCase::Lower => self.text(&text.to_ascii_lowercase()),
+        }
+    }
+
+    fn keyword(&mut self, keyword: Keyword) -> fmt::Result {
+        self.keyword_text(keyword.as_str())
+    }
+
+    /// Writes a space separated sequence of keywords, like `GROUP BY`.
+    fn keywords(&mut self, keywords: &[Keyword]) -> fmt::Result {
+        for (i, keyword) in keywords.iter().enumerate() {
+            if i > 0 {
+                self.text(" ")?;
+            }
+
+            self.keyword(*keyword)?;
+        }
+
+        Ok(())
+    }
+
+    fn identifier(&mut self, name: &str) -> fmt::Result {
+        match is_plain_identifier(name) && Keyword::lookup(name).is_none() {
+            true => self.text(name),
+            false => write!(self.out, "\"{}\"", name.replace('"', "\"\"")),
+        }
+    }
+
+    /// Writes the name of a called function, which only has to be quoted if it is a reserved
+    /// keyword, as a function name is always followed by an opening parenthesis.
+    fn function_name(&mut self, name: &str) -> fmt::Result {
+        let reserved = Keyword::lookup(name).is_some_and(|keyword| keyword.is_reserved());
+
+        match is_plain_identifier(name) && !reserved {
+            true => self.text(name),
+            false => write!(self.out, "\"{}\"", name.replace('"', "\"\"")),
+        }
+    }
+
+    /// Writes the name of a collation, which never has to be quoted for being a keyword, as it
+    /// always follows `COLLATE`.
+    fn collation_name(&mut self, name: &str) -> fmt::Result {
+        match is_plain_identifier(name) {
+            true => self.text(name),
+            false => write!(self.out, "\"{}\"", name.replace('"', "\"\"")),
+        }
+    }
+
+    fn string(&mut self, text: &str) -> fmt::Result {
+        write!(self.out, "'{}'", text.replace('\'', "''"))
+    }
+
+    fn identifiers(&mut self, names: &[String]) -> fmt::Result {
+        self.comma_separated(names, |w, name| w.identifier(name))
+    }
+
+    fn comma_separated<T>(
+        &mut self,
+        items: &[T],
+        mut write: impl FnMut(&mut Self, &T) -> fmt::Result,
+    ) -> fmt::Result {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.text(", ")?;
+            }
+
+            write(self, item)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a comma separated list with one item per line when pretty-printing.
+    fn comma_lines<T: ToSql>(&mut self, items: &[T]) -> fmt::Result {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.text(",")?;
+                self.line()?;
+            }
+
+            item.write_sql(self)?;
+        }
+
+        Ok(())
+    }
+
+    /// Starts a new line when pretty-printing, or writes a space otherwise.
+    fn line(&mut self) -> fmt::Result {
+        match self.indent_width {
+            Some(width) => write!(self.out, "\n{}", " ".repeat(width * self.depth)),
+            None => self.text(" "),
+        }
+    }
+
+    /// Starts a new line when pretty-printing, or writes nothing otherwise.
+    fn soft_line(&mut self) -> fmt::Result {
+        match self.indent_width {
+            Some(_) => self.line(),
+            None => Ok(()),
+        }
+    }
+
+    fn indented(&mut self, write: impl FnOnce(&mut Self) -> fmt::Result) -> fmt::Result {
+        self.depth += 1;
+        let result = write(self);
+        self.depth -= 1;
+
+        result
+    }
+
+    /// Writes `(items)`, with the items on their own indented lines when pretty-printing.
+    fn parenthesized_lines<T: ToSql>(&mut self, items: &[T]) -> fmt::Result {
+        self.text("(")?;
+        self.indented(|w| {
+            w.soft_line()?;
+            w.comma_lines(items)
+        })?;
+        self.soft_line()?;
+        self.text(")")
+    }
+}
+
+/// Whether `name` can be written without quotes, ignoring that it might be read as a keyword.
+fn is_plain_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// A syntax tree node which can be written as SQL.
+trait ToSql {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result;
+}
+
+/// Implements `Display` as the single line SQL form of each node.
+macro_rules! impl_display {
+    ($($node:ty),* $(,)?) => {
+        $(
+            impl Display for $node {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    self.write_sql(&mut SqlWriter::compact(f))
+                }
+            }
+        )*
+    };
+}
+
+impl_display!(
+    Statement,
+    SelectStatement,
+    InsertStatement,
+    UpdateStatement,
+    Assignment,
+    DeleteStatement,
+    ResultColumns,
+    AliasedExpr,
+    ColumnName,
+    ObjectName,
+    FromClause,
+    AliasedFromTable,
+    FromTable,
+    JoinType,
+    JoinCondition,
+    Expr,
+    Literal,
+    FunctionArgs,
+    UnaryOperator,
+    BinaryOperator,
+    WhereClause,
+    GroupByClause,
+    HavingClause,
+    OrderByClause,
+    OrderByItem,
+    OrderByExpr,
+    Ordering,
+    OffsetClause,
+    LimitClause,
+    ExplainStatement,
+    ExplainFormat,
+    CreateViewStatement,
+    CheckOption,
+    CreateMaterializedViewStatement,
+    RefreshMaterializedViewStatement,
+    DropViewStatement,
+    CreateDatabaseStatement,
+    CreateTableStatement,
+    ColumnDefinition,
+    TypeName,
+    CreateFunctionStatement,
+    FunctionParam,
+    Volatility,
+    DropFunctionStatement,
+    SchemaName,
+    CreateSchemaStatement,
+    DropSchemaStatement,
+    UseStatement,
+    SetSearchPathStatement,
+    SetTimeZoneStatement,
+    ShowColumnsStatement,
+    ShowCreateTableStatement,
+);
+
+impl ToSql for Statement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            Statement::Select(select) => select.write_sql(w),
+            Statement::Insert(insert) => insert.write_sql(w),
+            Statement::Update(update) => update.write_sql(w),
+            Statement::Delete(delete) => delete.write_sql(w),
+            Statement::Explain(explain) => explain.write_sql(w),
+            Statement::CreateView(create_view) => create_view.write_sql(w),
+            Statement::DropView(drop_view) => write_drop_view(w, drop_view, false),
+            Statement::CreateMaterializedView(create_view) => create_view.write_sql(w),
+            Statement::RefreshMaterializedView(refresh) => refresh.write_sql(w),
+            Statement::DropMaterializedView(drop_view) => write_drop_view(w, drop_view, true),
+            Statement::CreateDatabase(create_database) => create_database.write_sql(w),
+            Statement::CreateSchema(create_schema) => create_schema.write_sql(w),
+            Statement::DropSchema(drop_schema) => drop_schema.write_sql(w),
+            Statement::CreateTable(create_table) => create_table.write_sql(w),
+            Statement::CreateFunction(create_function) => create_function.write_sql(w),
+            Statement::DropFunction(drop_function) => drop_function.write_sql(w),
+            Statement::Use(use_statement) => use_statement.write_sql(w),
+            Statement::SetSearchPath(set_search_path) => set_search_path.write_sql(w),
+            Statement::SetTimeZone(set_time_zone) => set_time_zone.write_sql(w),
+            Statement::ShowDatabases => w.keywords(&[Keyword::Show, Keyword::Databases]),
+            Statement::ShowSchemas => w.keywords(&[Keyword::Show, Keyword::Schemas]),
+            Statement::ShowTables => w.keywords(&[Keyword::Show, Keyword::Tables]),
+            Statement::ShowColumns(show_columns) => show_columns.write_sql(w),
+            Statement::ShowCreateTable(show_create_table) => show_create_table.write_sql(w),
+            Statement::ShowSearchPath => {
+                w.keyword(Keyword::Show)?;
+                w.text(" search_path")
+            }
+            Statement::ShowTimeZone => w.keywords(&[Keyword::Show, Keyword::Time, Keyword::Zone]),
+        }
+    }
+}
+
+/* SELECT */
+
+impl ToSql for SelectStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keyword(Keyword::Select)?;
+
+        if self.distinct {
+            w.text(" ")?;
+            w.keyword(Keyword::Distinct)?;
+        }
+
+        match &self.columns {
+            ResultColumns::All => w.text(" *")?,
+            columns => w.indented(|w| {
+                w.line()?;
+                columns.write_sql(w)
+            })?,
+        }
+
+        w.line()?;
+        w.keyword(Keyword::From)?;
+        w.text(" ")?;
+        self.from_clause.write_sql(w)?;
+
+        if let Some(where_clause) = &self.where_clause {
+            w.line()?;
+            where_clause.write_sql(w)?;
+        }
+
+        if let Some(group_by_clause) = &self.group_by_clause {
+            w.line()?;
+            group_by_clause.write_sql(w)?;
+        }
+
+        if let Some(having_clause) = &self.having_clause {
+            w.line()?;
+            having_clause.write_sql(w)?;
+        }
+
+        for set_operation in &self.set_operations {
+            w.line()?;
+            set_operation.write_sql(w)?;
+        }
+
+        if let Some(order_by_clause) = &self.order_by_clause {
+            w.line()?;
+            order_by_clause.write_sql(w)?;
+        }
+
+        if let Some(limit) = &self.limit {
+            w.line()?;
+            limit.write_sql(w)?;
+        }
+
+        if let Some(offset) = &self.offset {
+            w.line()?;
+            offset.write_sql(w)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToSql for SetOperation {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keyword(match self.operator {
+            SetOperator::Union => Keyword::Union,
+            SetOperator::Intersect => Keyword::Intersect,
+            SetOperator::Except => Keyword::Except,
+        })?;
+
+        if self.all {
+            w.text(" ")?;
+            w.keyword(Keyword::All)?;
+        }
+
+        w.line()?;
+
+        let query = &self.query;
+        if query.set_operations.is_empty()
+            && query.order_by_clause.is_none()
+            && query.offset.is_none()
+            && query.limit.is_none()
+        {
+            return query.write_sql(w);
+        }
+
+        w.text("(")?;
+        w.indented(|w| {
+            w.soft_line()?;
+            query.write_sql(w)
+        })?;
+        w.soft_line()?;
+        w.text(")")
+    }
+}
+
+impl ToSql for ResultColumns {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            ResultColumns::All => w.text("*"),
+            ResultColumns::Specific(columns) => w.comma_lines(columns),
+        }
+    }
+}
+
+impl ToSql for AliasedExpr {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        self.expr.write_sql(w)?;
+        write_alias(w, &self.alias)
+    }
+}
+
+impl ToSql for ColumnName {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            ColumnName::Direct { name, .. } => w.identifier(name),
+            ColumnName::Qualified {
+                table_name,
+                column_name,
+                ..
+            } => {
+                table_name.write_sql(w)?;
+                w.text(".")?;
+                w.identifier(column_name)
+            }
+        }
+    }
+}
+
+impl ToSql for ObjectName {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        for (i, part) in self.parts.iter().enumerate() {
+            if i > 0 {
+                w.text(".")?;
+            }
+
+            w.identifier(part)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes ` AS alias`, as aliases are always written with `AS` so that they can be keywords.
+fn write_alias(w: &mut SqlWriter<'_>, alias: &Option<String>) -> fmt::Result {
+    match alias {
+        Some(alias) => {
+            w.text(" ")?;
+            w.keyword(Keyword::As)?;
+            w.text(" ")?;
+            w.identifier(alias)
+        }
+        None => Ok(()),
+    }
+}
+
+/* FROM */
+
+impl ToSql for FromClause {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            FromClause::Direct { table } => table.write_sql(w),
+            FromClause::Join {
+                left,
+                right,
+                join_type,
+                join_condition,
+            } => {
+                left.write_sql(w)?;
+                w.line()?;
+                join_type.write_sql(w)?;
+                w.text(" ")?;
+                right.write_sql(w)?;
+
+                match join_condition {
+                    Some(join_condition) => {
+                        w.text(" ")?;
+                        join_condition.write_sql(w)
+                    }
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl ToSql for AliasedFromTable {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        self.table.write_sql(w)?;
+        write_alias(w, &self.alias)
+    }
+}
+
+impl ToSql for FromTable {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            FromTable::TableName { name } => name.write_sql(w),
+            FromTable::Values { rows } => {
+                w.text("(")?;
+                w.keyword(Keyword::Values)?;
+                w.text(" ")?;
+                w.comma_separated(rows, |w, row| {
+                    w.text("(")?;
+                    w.comma_separated(row, |w, expr| expr.write_sql(w))?;
+                    w.text(")")
+                })?;
+                w.text(")")
+            }
+            FromTable::DerivedTable { query, lateral } => {
+                if *lateral {
+                    w.keyword(Keyword::Lateral)?;
+                    w.text(" ")?;
+                }
+
+                w.text("(")?;
+                w.indented(|w| {
+                    w.soft_line()?;
+                    query.write_sql(w)
+                })?;
+                w.soft_line()?;
+                w.text(")")
+            }
+            FromTable::Function {
+                name,
+                args,
+                lateral,
+            } => {
+                if *lateral {
+                    w.keyword(Keyword::Lateral)?;
+                    w.text(" ")?;
+                }
+
+                w.function_name(name)?;
+                w.text("(")?;
+                w.comma_separated(args, |w, arg| arg.write_sql(w))?;
+                w.text(")")
+            }
+        }
+    }
+}
+
+impl ToSql for JoinType {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            JoinType::Inner => w.keyword(Keyword::Join),
+            JoinType::Cross => w.keywords(&[Keyword::Cross, Keyword::Join]),
+            JoinType::LeftOuter => w.keywords(&[Keyword::Left, Keyword::Join]),
+            JoinType::RightOuter => w.keywords(&[Keyword::Right, Keyword::Join]),
+            JoinType::FullOuter => w.keywords(&[Keyword::Full, Keyword::Join]),
+        }
+    }
+}
+
+impl ToSql for JoinCondition {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            JoinCondition::On(condition) => {
+                w.keyword(Keyword::On)?;
+                w.text(" ")?;
+                condition.write_sql(w)
+            }
+            JoinCondition::Using(columns) => {
+                w.keyword(Keyword::Using)?;
+                w.text(" (")?;
+                w.identifiers(columns)?;
+                w.text(")")
+            }
+        }
+    }
+}
+
+/* EXPRESSIONS */
+
+impl ToSql for Expr {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            Expr::Literal(literal) => literal.write_sql(w),
+            Expr::Column(column_name) => column_name.write_sql(w),
+            Expr::Unary { operator, operand } => {
+                operator.write_sql(w)?;
+
+                // `NOT` is a word, and `- -a` must not be written as `--a`
+                let spaced = *operator == UnaryOperator::Not
+                    || matches!(
+                        operand.as_ref(),
+                        Expr::Unary {
+                            operator: UnaryOperator::Plus | UnaryOperator::Minus,
+                            ..
+                        }
+                    );
+
+                if spaced {
+                    w.text(" ")?;
+                }
+
+                // Prefix operators can always be applied directly to one another
+                let parenthesize = operand.precedence() < operator.precedence()
+                    && !matches!(operand.as_ref(), Expr::Unary { .. });
+
+                write_operand(w, operand, parenthesize)
+            }
+            Expr::Binary {
+                left,
+                operator,
+                right,
+            } => {
+                // Binary operators are left associative, so an operand of equal precedence only
+                // has to be parenthesized on the right
+                write_operand(w, left, left.precedence() < operator.precedence())?;
+                w.text(" ")?;
+                operator.write_sql(w)?;
+                w.text(" ")?;
+                write_operand(w, right, right.precedence() <= operator.precedence())
+            }
+            Expr::IsNull { operand, negated } => {
+                write_operand(w, operand, operand.precedence() < self.precedence())?;
+                w.text(" ")?;
+
+                match negated {
+                    true => w.keywords(&[Keyword::Is, Keyword::Not, Keyword::Null]),
+                    false => w.keywords(&[Keyword::Is, Keyword::Null]),
+                }
+            }
+            // Strings are cast with the shorter `DATE '2024-01-01'`, which allows no modifiers
+            Expr::Cast { operand, data_type } => match operand.as_ref() {
+                Expr::Literal(Literal::String(text)) if data_type.modifiers.is_empty() => {
+                    data_type.write_sql(w)?;
+                    w.text(" ")?;
+                    w.string(text)
+                }
+                _ => {
+                    w.keyword(Keyword::Cast)?;
+                    w.text("(")?;
+                    operand.write_sql(w)?;
+                    w.text(" ")?;
+                    w.keyword(Keyword::As)?;
+                    w.text(" ")?;
+                    data_type.write_sql(w)?;
+                    w.text(")")
+                }
+            },
+            Expr::Collate { operand, collation } => {
+                write_operand(w, operand, operand.precedence() < self.precedence())?;
+                w.text(" ")?;
+                w.keyword(Keyword::Collate)?;
+                w.text(" ")?;
+                w.collation_name(collation)
+            }
+            Expr::Function {
+                name,
+                args,
+                distinct,
+                order_by,
+                filter,
+            } => {
+                w.function_name(name)?;
+                w.text("(")?;
+
+                if *distinct {
+                    w.keyword(Keyword::Distinct)?;
+                    w.text(" ")?;
+                }
+
+                args.write_sql(w)?;
+
+                if !order_by.is_empty() {
+                    w.text(" ")?;
+                    w.keywords(&[Keyword::Order, Keyword::By])?;
+                    w.text(" ")?;
+                    w.comma_separated(order_by, |w, item| item.write_sql(w))?;
+                }
+
+                w.text(")")?;
+
+                if let Some(filter) = filter {
+                    w.text(" ")?;
+                    w.keyword(Keyword::Filter)?;
+                    w.text(" (")?;
+                    w.keyword(Keyword::Where)?;
+                    w.text(" ")?;
+                    filter.write_sql(w)?;
+                    w.text(")")?;
+                }
+
+                Ok(())
+            }
+            Expr::Nested(expr) => {
+                w.text("(")?;
+                expr.write_sql(w)?;
+                w.text(")")
+            }
+        }
+    }
+}
+
+fn write_operand(w: &mut SqlWriter<'_>, operand: &Expr, parenthesize: bool) -> fmt::Result {
+    if parenthesize {
+        w.text("(")?;
+        operand.write_sql(w)?;
+        w.text(")")
+    } else {
+        operand.write_sql(w)
+    }
+}
+
+impl ToSql for Literal {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            Literal::Number(number) => w.text(number),
+            Literal::String(string) => w.string(string),
+            Literal::Boolean(true) => w.keyword(Keyword::True),
+            Literal::Boolean(false) => w.keyword(Keyword::False),
+            Literal::Null => w.keyword(Keyword::Null),
+        }
+    }
+}
+
+impl ToSql for FunctionArgs {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            FunctionArgs::Wildcard => w.text("*"),
+            FunctionArgs::List(args) => w.comma_separated(args, |w, arg| arg.write_sql(w)),
+        }
+    }
+}
+
+impl ToSql for UnaryOperator {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            UnaryOperator::Plus => w.text("+"),
+            UnaryOperator::Minus => w.text("-"),
+            UnaryOperator::Not => w.keyword(Keyword::Not),
+        }
+    }
+}
+
+impl ToSql for BinaryOperator {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            BinaryOperator::Or => w.keyword(Keyword::Or),
+            BinaryOperator::And => w.keyword(Keyword::And),
+            BinaryOperator::Equals => w.text("="),
+            BinaryOperator::NotEquals => w.text("<>"),
+            BinaryOperator::LessThan => w.text("<"),
+            BinaryOperator::LessThanOrEqual => w.text("<="),
+            BinaryOperator::GreaterThan => w.text(">"),
+            BinaryOperator::GreaterThanOrEqual => w.text(">="),
+            BinaryOperator::Plus => w.text("+"),
+            BinaryOperator::Minus => w.text("-"),
+            BinaryOperator::Multiply => w.text("*"),
+            BinaryOperator::Divide => w.text("/"),
+            BinaryOperator::Modulo => w.text("%"),
+            BinaryOperator::JsonGet => w.text("->"),
+            BinaryOperator::JsonGetText => w.text("->>"),
+            BinaryOperator::JsonGetPath => w.text("#>"),
+            BinaryOperator::JsonGetPathText => w.text("#>>"),
+            BinaryOperator::Contains => w.text("@>"),
+            BinaryOperator::ContainedBy => w.text("<@"),
+            BinaryOperator::HasKey => w.text("?"),
+            BinaryOperator::HasAnyKey => w.text("?|"),
+            BinaryOperator::HasAllKeys => w.text("?&"),
+        }
+    }
+}
+
+/* WHERE / GROUP BY / HAVING */
+
+impl ToSql for WhereClause {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keyword(Keyword::Where)?;
+        w.text(" ")?;
+        self.condition.write_sql(w)
+    }
+}
+
+impl ToSql for GroupByClause {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keywords(&[Keyword::Group, Keyword::By])?;
+        w.text(" ")?;
+        w.comma_separated(&self.groupings, |w, grouping| grouping.write_sql(w))
+    }
+}
+
+impl ToSql for HavingClause {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keyword(Keyword::Having)?;
+        w.text(" ")?;
+        self.condition.write_sql(w)
+    }
+}
+
+/* ORDER BY / OFFSET / LIMIT */
+
+impl ToSql for OrderByClause {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keywords(&[Keyword::Order, Keyword::By])?;
+        w.text(" ")?;
+        w.comma_separated(&self.orderings, |w, ordering| ordering.write_sql(w))
+    }
+}
+
+impl ToSql for OrderByItem {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        self.column_name.write_sql(w)?;
+
+        if let Some(collation) = &self.collation {
+            w.text(" ")?;
+            w.keyword(Keyword::Collate)?;
+            w.text(" ")?;
+            w.collation_name(collation)?;
+        }
+
+        if let Some(ordering) = &self.ordering {
+            w.text(" ")?;
+            ordering.write_sql(w)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToSql for OrderByExpr {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        self.expr.write_sql(w)?;
+
+        if let Some(ordering) = &self.ordering {
+            w.text(" ")?;
+            ordering.write_sql(w)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToSql for Ordering {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            Ordering::Ascending => w.keyword(Keyword::Asc),
+            Ordering::Descending => w.keyword(Keyword::Desc),
+        }
+    }
+}
+
+impl ToSql for OffsetClause {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keyword(Keyword::Offset)?;
+        write!(w.out, " {}", self.offset)
+    }
+}
+
+impl ToSql for LimitClause {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keyword(Keyword::Limit)?;
+        write!(w.out, " {}", self.limit)
+    }
+}
+
+/* INSERT */
+
+impl ToSql for InsertStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keywords(&[Keyword::Insert, Keyword::Into])?;
+        w.text(" ")?;
+        self.table.write_sql(w)?;
+
+        if !self.columns.is_empty() {
+            w.text(" (")?;
+            w.identifiers(&self.columns)?;
+            w.text(")")?;
+        }
+
+        w.line()?;
+
+        match &self.source {
+            InsertSource::Values { rows } => {
+                w.keyword(Keyword::Values)?;
+                w.indented(|w| {
+                    for (i, row) in rows.iter().enumerate() {
+                        if i > 0 {
+                            w.text(",")?;
+                        }
+
+                        w.line()?;
+                        w.text("(")?;
+                        w.comma_separated(row, |w, expr| expr.write_sql(w))?;
+                        w.text(")")?;
+                    }
+
+                    Ok(())
+                })
+            }
+            InsertSource::Select(query) => query.write_sql(w),
+        }
+    }
+}
+
+/* UPDATE */
+
+impl ToSql for UpdateStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keyword(Keyword::Update)?;
+        w.text(" ")?;
+        self.table.write_sql(w)?;
+        w.line()?;
+        w.keyword(Keyword::Set)?;
+        w.indented(|w| {
+            w.line()?;
+            w.comma_lines(&self.assignments)
+        })?;
+
+        if let Some(where_clause) = &self.where_clause {
+            w.line()?;
+            where_clause.write_sql(w)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToSql for Assignment {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.identifier(&self.column)?;
+        w.text(" = ")?;
+        self.value.write_sql(w)
+    }
+}
+
+/* DELETE */
+
+impl ToSql for DeleteStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keywords(&[Keyword::Delete, Keyword::From])?;
+        w.text(" ")?;
+        self.table.write_sql(w)?;
+
+        if let Some(where_clause) = &self.where_clause {
+            w.line()?;
+            where_clause.write_sql(w)?;
+        }
+
+        Ok(())
+    }
+}
+
+/* EXPLAIN */
+
+impl ToSql for ExplainStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keyword(Keyword::Explain)?;
+
+        if self.analyze {
+            w.text(" ")?;
+            w.keyword(Keyword::Analyze)?;
+        }
+
+        if self.verbose {
+            w.text(" ")?;
+            w.keyword(Keyword::Verbose)?;
+        }
+
+        if self.format != ExplainFormat::default() {
+            w.text(" ")?;
+            w.keyword(Keyword::Format)?;
+            w.text(" ")?;
+            self.format.write_sql(w)?;
+        }
+
+        w.line()?;
+        self.statement.write_sql(w)
+    }
+}
+
+impl ToSql for ExplainFormat {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            ExplainFormat::Text => w.keyword(Keyword::Text),
+            ExplainFormat::Json => w.keyword(Keyword::Json),
+        }
+    }
+}
+
+/* VIEWS */
+
+impl ToSql for CreateViewStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self.or_replace {
+            true => w.keywords(&[
+                Keyword::Create,
+                Keyword::Or,
+                Keyword::Replace,
+                Keyword::View,
+            ])?,
+            false => w.keywords(&[Keyword::Create, Keyword::View])?,
+        }
+
+        w.text(" ")?;
+        write_view_name(w, &self.name, &self.columns)?;
+        w.text(" ")?;
+        w.keyword(Keyword::As)?;
+        w.line()?;
+        self.query.write_sql(w)?;
+
+        if let Some(check_option) = &self.check_option {
+            w.line()?;
+            w.keyword(Keyword::With)?;
+            w.text(" ")?;
+            check_option.write_sql(w)?;
+            w.text(" ")?;
+            w.keywords(&[Keyword::Check, Keyword::Option])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToSql for CheckOption {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            CheckOption::Local => w.keyword(Keyword::Local),
+            CheckOption::Cascaded => w.keyword(Keyword::Cascaded),
+        }
+    }
+}
+
+impl ToSql for CreateMaterializedViewStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keywords(&[Keyword::Create, Keyword::Materialized, Keyword::View])?;
+        w.text(" ")?;
+        write_view_name(w, &self.name, &self.columns)?;
+
+        if self.incremental {
+            w.text(" ")?;
+            w.keyword(Keyword::With)?;
+            w.text(" (incremental)")?;
+        }
+
+        w.text(" ")?;
+        w.keyword(Keyword::As)?;
+        w.line()?;
+        self.query.write_sql(w)?;
+
+        if !self.with_data {
+            w.line()?;
+            w.keywords(&[Keyword::With, Keyword::No, Keyword::Data])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToSql for RefreshMaterializedViewStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keywords(&[Keyword::Refresh, Keyword::Materialized, Keyword::View])?;
+
+        if self.concurrently {
+            w.text(" ")?;
+            w.keyword(Keyword::Concurrently)?;
+        }
+
+        w.text(" ")?;
+        self.name.write_sql(w)?;
+
+        if !self.with_data {
+            w.text(" ")?;
+            w.keywords(&[Keyword::With, Keyword::No, Keyword::Data])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToSql for DropViewStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        write_drop_view(w, self, false)
+    }
+}
+
+/// Writes `name [(a, b, ...)]`.
+fn write_view_name(w: &mut SqlWriter<'_>, name: &ObjectName, columns: &[String]) -> fmt::Result {
+    name.write_sql(w)?;
+
+    if !columns.is_empty() {
+        w.text(" (")?;
+        w.identifiers(columns)?;
+        w.text(")")?;
+    }
+
+    Ok(())
+}
+
+fn write_drop_view(
+    w: &mut SqlWriter<'_>,
+    statement: &DropViewStatement,
+    materialized: bool,
+) -> fmt::Result {
+    match materialized {
+        true => w.keywords(&[Keyword::Drop, Keyword::Materialized, Keyword::View])?,
+        false => w.keywords(&[Keyword::Drop, Keyword::View])?,
+    }
+
+    if statement.if_exists {
+        w.text(" ")?;
+        w.keywords(&[Keyword::If, Keyword::Exists])?;
+    }
+
+    w.text(" ")?;
+    w.comma_separated(&statement.names, |w, name| name.write_sql(w))?;
+
+    if statement.cascade {
+        w.text(" ")?;
+        w.keyword(Keyword::Cascade)?;
+    }
+
+    Ok(())
+}
+
+/* DATABASES AND TABLES */
+
+impl ToSql for CreateDatabaseStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keywords(&[Keyword::Create, Keyword::Database])?;
+
+        if self.if_not_exists {
+            w.text(" ")?;
+            w.keywords(&[Keyword::If, Keyword::Not, Keyword::Exists])?;
+        }
+
+        w.text(" ")?;
+        w.identifier(&self.name)
+    }
+}
+
+impl ToSql for SchemaName {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        if let Some(database) = &self.database {
+            w.identifier(database)?;
+            w.text(".")?;
+        }
+
+        w.identifier(&self.name)
+    }
+}
+
+impl ToSql for CreateSchemaStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keywords(&[Keyword::Create, Keyword::Schema])?;
+
+        if self.if_not_exists {
+            w.text(" ")?;
+            w.keywords(&[Keyword::If, Keyword::Not, Keyword::Exists])?;
+        }
+
+        w.text(" ")?;
+        self.name.write_sql(w)
+    }
+}
+
+impl ToSql for DropSchemaStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keywords(&[Keyword::Drop, Keyword::Schema])?;
+
+        if self.if_exists {
+            w.text(" ")?;
+            w.keywords(&[Keyword::If, Keyword::Exists])?;
+        }
+
+        w.text(" ")?;
+        w.comma_separated(&self.names, |w, name| name.write_sql(w))?;
+
+        if self.cascade {
+            w.text(" ")?;
+            w.keyword(Keyword::Cascade)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToSql for CreateTableStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keywords(&[Keyword::Create, Keyword::Table])?;
+
+        if self.if_not_exists {
+            w.text(" ")?;
+            w.keywords(&[Keyword::If, Keyword::Not, Keyword::Exists])?;
+        }
+
+        w.text(" ")?;
+        self.name.write_sql(w)?;
+        w.text(" ")?;
+        w.parenthesized_lines(&self.columns)
+    }
+}
+
+impl ToSql for ColumnDefinition {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.identifier(&self.name)?;
+        w.text(" ")?;
+        self.data_type.write_sql(w)?;
+
+        if let Some(collation) = &self.collation {
+            w.text(" ")?;
+            w.keyword(Keyword::Collate)?;
+            w.text(" ")?;
+            w.collation_name(collation)?;
+        }
+
+        if !self.nullable {
+            w.text(" ")?;
+            w.keywords(&[Keyword::Not, Keyword::Null])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToSql for TypeName {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keyword_text(&self.name)?;
+
+        if !self.modifiers.is_empty() {
+            w.text("(")?;
+            w.comma_separated(&self.modifiers, |w, modifier| write!(w.out, "{}", modifier))?;
+            w.text(")")?;
+        }
+
+        Ok(())
+    }
+}
+
+/* FUNCTIONS */
+
+impl ToSql for CreateFunctionStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self.or_replace {
+            true => w.keywords(&[
+                Keyword::Create,
+                Keyword::Or,
+                Keyword::Replace,
+                Keyword::Function,
+            ])?,
+            false => w.keywords(&[Keyword::Create, Keyword::Function])?,
+        }
+
+        w.text(" ")?;
+        w.function_name(&self.name)?;
+        w.text("(")?;
+        w.comma_separated(&self.params, |w, param| param.write_sql(w))?;
+        w.text(")")?;
+
+        w.line()?;
+        w.keyword(Keyword::Returns)?;
+        w.text(" ")?;
+        self.returns.write_sql(w)?;
+        w.text(" ")?;
+        w.keyword(Keyword::Language)?;
+        w.text(" ")?;
+        w.identifier(&self.language)?;
+        w.text(" ")?;
+        self.volatility.write_sql(w)?;
+
+        w.line()?;
+        w.keyword(Keyword::As)?;
+        w.text(" ")?;
+        w.string(&self.definition)?;
+
+        if let Some(symbol) = &self.symbol {
+            w.text(", ")?;
+            w.string(symbol)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToSql for FunctionParam {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        if let Some(name) = &self.name {
+            w.identifier(name)?;
+            w.text(" ")?;
+        }
+
+        self.data_type.write_sql(w)
+    }
+}
+
+impl ToSql for Volatility {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        match self {
+            Volatility::Immutable => w.keyword(Keyword::Immutable),
+            Volatility::Stable => w.keyword(Keyword::Stable),
+            Volatility::Volatile => w.keyword(Keyword::Volatile),
+        }
+    }
+}
+
+impl ToSql for DropFunctionStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keywords(&[Keyword::Drop, Keyword::Function])?;
+
+        if self.if_exists {
+            w.text(" ")?;
+            w.keywords(&[Keyword::If, Keyword::Exists])?;
+        }
+
+        w.text(" ")?;
+        w.function_name(&self.name)?;
+        w.text("(")?;
+        w.comma_separated(&self.params, |w, param| param.write_sql(w))?;
+        w.text(")")?;
+
+        if self.cascade {
+            w.text(" ")?;
+            w.keyword(Keyword::Cascade)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToSql for UseStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keyword(Keyword::Use)?;
+        w.text(" ")?;
+        w.identifier(&self.database)
+    }
+}
+
+impl ToSql for SetSearchPathStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keyword(Keyword::Set)?;
+        w.text(" search_path ")?;
+        w.keyword(Keyword::To)?;
+        w.text(" ")?;
+
+        match &self.schemas {
+            Some(schemas) => w.identifiers(schemas),
+            None => w.keyword(Keyword::Default),
+        }
+    }
+}
+
+impl ToSql for SetTimeZoneStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keywords(&[Keyword::Set, Keyword::Time, Keyword::Zone])?;
+        w.text(" ")?;
+
+        match &self.time_zone {
+            Some(time_zone) => write!(w.out, "'{}'", time_zone.replace('\'', "''")),
+            None => w.keyword(Keyword::Default),
+        }
+    }
+}
+
+impl ToSql for ShowColumnsStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keywords(&[Keyword::Show, Keyword::Columns, Keyword::From])?;
+        w.text(" ")?;
+        self.table.write_sql(w)
+    }
+}
+
+impl ToSql for ShowCreateTableStatement {
+    fn write_sql(&self, w: &mut SqlWriter<'_>) -> fmt::Result {
+        w.keywords(&[Keyword::Show, Keyword::Create, Keyword::Table])?;
+        w.text(" ")?;
+        self.table.write_sql(w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql::parser::Comment;
+
+    const STATEMENTS: &[&str] = &[
+        // Queries
+        "SELECT 1 FROM t",
+        "SELECT * FROM t",
+        "SELECT DISTINCT a, t.b AS c FROM s.t AS t WHERE a = 1 AND NOT b OR c IS NOT NULL",
+        "SELECT -a, (a + b) * c, a - (b - c), a / b % c, 'it''s', TRUE, FALSE, NULL, 1.50 FROM t",
+        "SELECT a <> b, a < b, a <= b, a > b, a >= b, a IS NULL FROM t",
+        "SELECT CAST(a AS DECIMAL(10, 2)), a::TEXT, DATE '2024-01-01', a COLLATE nocase FROM t",
+        "SELECT count(*), count(DISTINCT a), string_agg(a, ',' ORDER BY b DESC) FILTER (WHERE c > 0) FROM t",
+        "SELECT a -> 'k', a ->> 0, a #> '{k,0}', a #>> '{k}', a @> '{}', a <@ b, a ? 'k', a ?| b, a ?& b FROM t",
+        "SELECT a, sum(b) FROM t GROUP BY a HAVING sum(b) > 1 ORDER BY a COLLATE nocase DESC, b ASC LIMIT 10 OFFSET 5",
+        "SELECT time_bucket('1 hour', ts), count(*) FROM t GROUP BY time_bucket('1 hour', ts), a + 1",
+        "SELECT a FROM t UNION SELECT a FROM u UNION ALL SELECT b FROM v EXCEPT SELECT c FROM w ORDER BY a LIMIT 1",
+        "SELECT a FROM t INTERSECT SELECT a FROM u",
+        "SELECT * FROM t JOIN u ON t.a = u.a LEFT JOIN v USING (a, b) CROSS JOIN w",
+        "SELECT * FROM t, u AS v, (SELECT a FROM w WHERE a > 1) AS x",
+        "SELECT * FROM t RIGHT JOIN u ON TRUE FULL JOIN v ON FALSE",
+        // Data manipulation
+        "INSERT INTO t VALUES (1, 'a'), (2, NULL)",
+        "INSERT INTO s.t (a, b) SELECT a, b FROM u WHERE a > 0",
+        "UPDATE t SET a = a + 1, b = 'x' WHERE c IS NULL",
+        "UPDATE t SET a = 1",
+        "DELETE FROM s.t WHERE a = 1",
+        "DELETE FROM t",
+        // Definitions
+        "CREATE DATABASE d",
+        "CREATE SCHEMA s",
+        "DROP SCHEMA s",
+        "CREATE TABLE s.t (a INT NOT NULL, b TEXT COLLATE nocase, c DECIMAL(10, 2), d JSONB)",
+        "CREATE VIEW v AS SELECT a FROM t",
+        "CREATE OR REPLACE VIEW s.v (x, y) AS SELECT a, b FROM t WHERE a > 0 WITH CHECK OPTION",
+        "CREATE VIEW v AS SELECT a FROM t WHERE a > 0 WITH LOCAL CHECK OPTION",
+        "CREATE VIEW v AS SELECT a FROM t WHERE a > 0 WITH CASCADED CHECK OPTION",
+        "DROP VIEW v",
+        "CREATE MATERIALIZED VIEW m AS SELECT a, count(*) FROM t GROUP BY a",
+        "CREATE MATERIALIZED VIEW s.m (a, n) WITH (incremental) AS SELECT a, count(*) FROM t GROUP BY a WITH NO DATA",
+        "REFRESH MATERIALIZED VIEW m",
+        "DROP MATERIALIZED VIEW m",
+        "CREATE FUNCTION f(a INT, b TEXT) RETURNS TEXT LANGUAGE wasm IMMUTABLE AS 'module.wasm', 'f'",
+        "CREATE OR REPLACE FUNCTION g() RETURNS INT LANGUAGE wasm AS 'module.wasm'",
+        "DROP FUNCTION f(INT, TEXT)",
+        "DROP FUNCTION IF EXISTS f() CASCADE",
+        // Sessions
+        "USE d",
+        "SET search_path TO s, public",
+        "SET TIME ZONE 'Europe/Amsterdam'",
+        "SET TIME ZONE DEFAULT",
+        "SHOW DATABASES",
+        "SHOW SCHEMAS",
+        "SHOW TABLES",
+        "SHOW COLUMNS FROM s.t",
+        "SHOW CREATE TABLE t",
+        "SHOW search_path",
+        "SHOW TIME ZONE",
+        "EXPLAIN ANALYZE VERBOSE FORMAT JSON SELECT a FROM t",
+        "EXPLAIN SELECT a FROM t",
+        "EXPLAIN (FORMAT JSON, ANALYZE) DELETE FROM t WHERE a = 1",
+        "EXPLAIN (ANALYZE FALSE, VERBOSE TRUE) INSERT INTO t VALUES (1)",
+        "EXPLAIN UPDATE t SET a = a + 1",
+    ];
+
+    fn parse(sql: &str) -> Vec<Statement> {
+        let mut statements =
+            StatementParser::parse(sql).unwrap_or_else(|err| panic!("{sql}: {err}"));
+
+        // The text of a view's query is kept as it was written, which formatting changes
+        for statement in &mut statements {
+            match statement {
+                Statement::CreateView(create_view) => create_view.query_text.clear(),
+                Statement::CreateMaterializedView(create_view) => create_view.query_text.clear(),
+                _ => {}
+            }
+        }
+
+        statements
+    }
+
+    #[test]
+    fn round_trip() {
+        let lower = FormatOptions {
+            indent_width: 4,
+            keyword_case: KeywordCase::Lower,
+        };
+
+        for sql in STATEMENTS {
+            let [statement] = &parse(sql)[..] else {
+                panic!("{sql} is not a single statement");
+            };
+
+            let compact = statement.to_string();
+            assert_eq!(
+                parse(&compact),
+                std::slice::from_ref(statement),
+                "{compact}"
+            );
+
+            for options in [FormatOptions::default(), lower] {
+                let pretty = format_statement(statement, &options);
+                assert_eq!(parse(&pretty), std::slice::from_ref(statement), "{pretty}");
+            }
+        }
+    }
+
+    #[test]
+    fn several_statements() {
+        let statements = STATEMENTS
+            .iter()
+            .flat_map(|sql| parse(sql))
+            .collect::<Vec<_>>();
+        let formatted = format_statements(&statements, &FormatOptions::default());
+
+        assert_eq!(parse(&formatted), statements);
+    }
+
+    #[test]
+    fn comments() {
+        let input = "-- Every user\n\
+            SELECT a, /* the name */ b FROM users; -- trailing\n\
+            /* Only\n   recent */ DELETE FROM t WHERE a > 1 -- old rows\n\
+            ;select 1 from t; /* same line */\n\
+            -- The end";
+
+        let formatted = format_source(input, &FormatOptions::default()).unwrap();
+        assert_eq!(
+            formatted,
+            "-- Every user\n\
+            /* the name */\n\
+            SELECT\n  a,\n  b\nFROM users; -- trailing\n\
+            \n\
+            /* Only\n   recent */\n\
+            DELETE FROM t\nWHERE a > 1; -- old rows\n\
+            \n\
+            SELECT\n  1\nFROM t; /* same line */\n\
+            \n\
+            -- The end\n"
+        );
+
+        // Formatting keeps every comment and the statements, and changes nothing the second time
+        let parsed = StatementParser::parse_with_comments(input).unwrap();
+        let reparsed = StatementParser::parse_with_comments(&formatted).unwrap();
+
+        let statements = |parsed: &CommentedStatements| {
+            let statements = parsed.statements.iter();
+            statements
+                .map(|(statement, _)| statement.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(statements(&parsed), statements(&reparsed));
+
+        let texts = |comments: Vec<Comment>| {
+            let mut texts = comments
+                .into_iter()
+                .map(|comment| comment.text)
+                .collect::<Vec<_>>();
+            texts.sort();
+            texts
+        };
+        assert_eq!(texts(parsed.comments), texts(reparsed.comments));
+
+        assert_eq!(
+            format_source(&formatted, &FormatOptions::default()).unwrap(),
+            formatted
+        );
+    }
+
+    #[test]
+    fn only_comments() {
+        let options = FormatOptions::default();
+
+        assert_eq!(format_source("", &options).unwrap(), "");
+        assert_eq!(
+            format_source("/* a */ -- b", &options).unwrap(),
+            "/* a */\n-- b\n"
+        );
+        assert!(format_source("/* a", &options).is_err());
+    }
+}
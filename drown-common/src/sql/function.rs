@@ -0,0 +1,1676 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{Debug, Write},
+    sync::Arc,
+};
+
+use md5::{Digest, Md5};
+use regex::{Regex, RegexBuilder};
+use sha2::Sha256;
+use thiserror::Error;
+
+use crate::sql::{
+    aggregate::{
+        Aggregate, ArrayAgg, Avg, Count, StringAgg, Sum, APPROX_COUNT_DISTINCT, APPROX_PERCENTILE,
+        BOOL_AND, BOOL_OR, HLL_SKETCH, HLL_UNION, MAX, MIN, STDDEV_POP, STDDEV_SAMP,
+        TDIGEST_SKETCH, TDIGEST_UNION, VAR_POP, VAR_SAMP,
+    },
+    data_type::DataType,
+    datetime::{self, DateField, DateTimeError, TimeZone, MICROS_PER_DAY},
+    jsonb::{Jsonb, JsonbError, JsonbKind},
+    sketch::{HyperLogLog, SketchError, TDigest},
+    value::{Decimal, DecimalError, RoundingMode, Value},
+};
+
+/// A function which can be called by name, which can be overloaded by defining several
+/// functions with the same name and different parameters.
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub signature: Signature,
+    pub kind: FunctionKind,
+}
+
+#[derive(Debug, Clone)]
+pub struct Signature {
+    pub name: Cow<'static, str>,
+    /// `None` for `f(*)`
+    pub params: Option<Cow<'static, [Param]>>,
+    /// How many of the last parameters can be repeated any number of times, written
+    /// `f(a, [b, c])`
+    pub variadic: usize,
+    pub returns: Returns,
+}
+
+/// A parameter of a function.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Param {
+    Type(DataType),
+    /// A value of any type
+    Any,
+    /// The name of a date field, like `'year'`
+    Field,
+    /// The name of a date field which is a unit of time, like `'hour'`
+    Unit,
+    /// The name of a time zone or an offset from UTC
+    Zone,
+    /// A regular expression
+    Pattern,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Returns {
+    Type(DataType),
+    /// The type of the first argument
+    FirstArg,
+    /// The common type of all arguments, which they must have
+    CommonType,
+    /// Rows of these columns, for functions called in FROM
+    Table(&'static [TableColumn]),
+}
+
+/// A column of the rows returned by a table function.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableColumn {
+    pub name: &'static str,
+    pub data_type: DataType,
+}
+
+#[derive(Clone)]
+pub enum FunctionKind {
+    /// A function computing one value from the values of its arguments in a single row
+    Scalar {
+        call: ScalarFn,
+        /// Whether the function returns `NULL` whenever an argument is `NULL` without being
+        /// called
+        strict: bool,
+        /// Whether the function always returns the same value for the same arguments, whatever
+        /// its context, so that calls with constant arguments can be evaluated while planning
+        deterministic: bool,
+    },
+    /// A function combining the values of its arguments across the rows of a group
+    Aggregate(Arc<dyn Aggregate>),
+    /// A function returning any number of rows, which is called in FROM
+    Table(Arc<dyn TableFunction>),
+}
+
+impl Debug for FunctionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FunctionKind::Scalar {
+                strict,
+                deterministic,
+                ..
+            } => f
+                .debug_struct("Scalar")
+                .field("strict", strict)
+                .field("deterministic", deterministic)
+                .finish_non_exhaustive(),
+            FunctionKind::Aggregate(aggregate) => {
+                f.debug_tuple("Aggregate").field(aggregate).finish()
+            }
+            FunctionKind::Table(function) => f.debug_tuple("Table").field(function).finish(),
+        }
+    }
+}
+
+/// Computes the value of a scalar function from its arguments, which have already been coerced
+/// to the types of its parameters.
+pub type ScalarFn =
+    Arc<dyn Fn(&FunctionContext, &[Value]) -> Result<Value, FunctionError> + Send + Sync>;
+
+/// A function returning rows of the columns given by `Returns::Table` in its signature.
+pub trait TableFunction: Debug + Send + Sync {
+    /// Computes the rows from the arguments, which have already been coerced to the types of
+    /// the parameters.
+    fn call(
+        &self,
+        context: &FunctionContext,
+        args: &[Value],
+    ) -> Result<Vec<Vec<Value>>, FunctionError>;
+}
+
+/// How a scalar function registered with `FunctionRegistry::register_scalar` behaves.
+#[derive(Debug, Clone, Copy)]
+pub struct ScalarOptions {
+    /// Whether the function returns `NULL` whenever an argument is `NULL` without being called,
+    /// which it does by default
+    pub strict: bool,
+    /// Whether the function always returns the same value for the same arguments, whatever its
+    /// context, which it is not assumed to by default
+    pub deterministic: bool,
+}
+
+impl Default for ScalarOptions {
+    fn default() -> Self {
+        Self {
+            strict: true,
+            deterministic: false,
+        }
+    }
+}
+
+/// What the value of a function can depend on other than its arguments.
+#[derive(Debug, Clone, Copy)]
+pub struct FunctionContext {
+    /// The time zone of the session, which `TIMESTAMPTZ` values are shown in
+    pub time_zone: TimeZone,
+    /// When the statement started, in microseconds since 1970-01-01 00:00:00 UTC
+    pub now: i64,
+}
+
+#[derive(Debug, Error)]
+pub enum FunctionError {
+    #[error("Invalid argument to `{function}`: {reason}")]
+    InvalidArgument {
+        function: &'static str,
+        reason: &'static str,
+    },
+    #[error("Result of `{function}` is out of range for {data_type}")]
+    OutOfRange {
+        function: String,
+        data_type: DataType,
+    },
+    #[error("Invalid regular expression: {0}")]
+    InvalidPattern(#[from] regex::Error),
+    #[error("Aggregate function `{0}` can only be called on groups of rows")]
+    NotScalar(String),
+    #[error("Table function `{0}` can only be called in FROM")]
+    NotExpression(String),
+    /// An error reported by a registered function
+    #[error("Function `{function}` failed: {reason}")]
+    Failed { function: String, reason: String },
+    #[error("{0}")]
+    Decimal(#[from] DecimalError),
+    #[error("{0}")]
+    DateTime(#[from] DateTimeError),
+    #[error("{0}")]
+    Jsonb(#[from] JsonbError),
+    #[error("{0}")]
+    Sketch(#[from] SketchError),
+}
+
+impl Function {
+    /// Calls a scalar function on arguments coerced to the types of its parameters.
+    pub fn call(&self, context: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+        let name = || self.signature.name.to_string();
+
+        match &self.kind {
+            FunctionKind::Scalar { strict: true, .. } if args.iter().any(Value::is_null) => {
+                Ok(Value::Null)
+            }
+            FunctionKind::Scalar { call, .. } => call(context, args),
+            FunctionKind::Aggregate(_) => Err(FunctionError::NotScalar(name())),
+            FunctionKind::Table(_) => Err(FunctionError::NotExpression(name())),
+        }
+    }
+}
+
+impl Signature {
+    /// The parameter the argument at `index` is passed to, if any.
+    pub fn param(&self, index: usize) -> Option<Param> {
+        let params = self.params.as_deref()?;
+        let fixed = params.len() - self.variadic;
+
+        match index.checked_sub(fixed) {
+            None => Some(params[index]),
+            Some(_) if self.variadic == 0 => None,
+            Some(repeated) => Some(params[fixed + repeated % self.variadic]),
+        }
+    }
+
+    /// Whether the function can be called with `count` arguments, which for variadic functions
+    /// must be a whole number of repetitions of their last parameters.
+    pub fn accepts(&self, count: usize) -> bool {
+        let Some(params) = self.params.as_deref() else {
+            return false;
+        };
+
+        let fixed = params.len() - self.variadic;
+
+        match self.variadic {
+            0 => count == fixed,
+            variadic => count >= fixed && (count - fixed).is_multiple_of(variadic),
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum RegistryError {
+    #[error("Function names are looked up in lower case, so `{0}` could never be called")]
+    NotLowerCase(String),
+    #[error("Function `{0}` has more repeated parameters than parameters")]
+    InvalidVariadic(String),
+    #[error("Function `{0}` already has an overload with the same parameters")]
+    DuplicateOverload(String),
+    #[error("Function `{0}` must return a table, as it is a table function")]
+    NotTable(String),
+    #[error("Function `{0}` cannot return a table, as it is not a table function")]
+    UnexpectedTable(String),
+    #[error("Function `{0}` cannot have both table and other overloads")]
+    MixedOverloads(String),
+    #[error("Overloads of table function `{0}` must return columns of the same names")]
+    ColumnMismatch(String),
+}
+
+/// Every function which can be called by name, which are the built-in functions along with
+/// those registered by the application embedding the server.
+#[derive(Debug)]
+pub struct FunctionRegistry {
+    functions: HashMap<Cow<'static, str>, Vec<Function>>,
+}
+
+impl FunctionRegistry {
+    /// Registers a scalar function computing its value with `call`.
+    pub fn register_scalar(
+        &mut self,
+        signature: Signature,
+        options: ScalarOptions,
+        call: impl Fn(&FunctionContext, &[Value]) -> Result<Value, FunctionError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Result<(), RegistryError> {
+        self.register(Function {
+            signature,
+            kind: FunctionKind::Scalar {
+                call: Arc::new(call),
+                strict: options.strict,
+                deterministic: options.deterministic,
+            },
+        })
+    }
+
+    /// Registers an aggregate function, which combines its arguments across the rows of a group.
+    pub fn register_aggregate(
+        &mut self,
+        signature: Signature,
+        aggregate: impl Aggregate + 'static,
+    ) -> Result<(), RegistryError> {
+        self.register(Function {
+            signature,
+            kind: FunctionKind::Aggregate(Arc::new(aggregate)),
+        })
+    }
+
+    /// Registers a table function, whose signature must return `Returns::Table`.
+    pub fn register_table(
+        &mut self,
+        signature: Signature,
+        function: impl TableFunction + 'static,
+    ) -> Result<(), RegistryError> {
+        self.register(Function {
+            signature,
+            kind: FunctionKind::Table(Arc::new(function)),
+        })
+    }
+
+    fn register(&mut self, function: Function) -> Result<(), RegistryError> {
+        let signature = &function.signature;
+        let name = &signature.name;
+        let error_name = || name.to_string();
+
+        if name.bytes().any(|byte| byte.is_ascii_uppercase()) {
+            return Err(RegistryError::NotLowerCase(error_name()));
+        }
+
+        if signature.variadic > signature.params.as_deref().map_or(0, <[_]>::len) {
+            return Err(RegistryError::InvalidVariadic(error_name()));
+        }
+
+        match (&function.kind, signature.returns) {
+            (FunctionKind::Table(_), Returns::Table(_)) => {}
+            (FunctionKind::Table(_), _) => return Err(RegistryError::NotTable(error_name())),
+            (_, Returns::Table(_)) => return Err(RegistryError::UnexpectedTable(error_name())),
+            _ => {}
+        }
+
+        // Columns in FROM are named before the overload of their function is resolved
+        for overload in self.overloads(name) {
+            match (overload.signature.returns, signature.returns) {
+                (Returns::Table(existing), Returns::Table(columns)) => {
+                    let names = |columns: &[TableColumn]| {
+                        columns.iter().map(|column| column.name).collect::<Vec<_>>()
+                    };
+
+                    if names(existing) != names(columns) {
+                        return Err(RegistryError::ColumnMismatch(error_name()));
+                    }
+                }
+                (Returns::Table(_), _) | (_, Returns::Table(_)) => {
+                    return Err(RegistryError::MixedOverloads(error_name()))
+                }
+                _ => {}
+            }
+        }
+
+        let duplicate = self.overloads(name).iter().any(|overload| {
+            overload.signature.params == signature.params
+                && overload.signature.variadic == signature.variadic
+        });
+
+        if duplicate {
+            return Err(RegistryError::DuplicateOverload(error_name()));
+        }
+
+        self.functions
+            .entry(name.clone())
+            .or_default()
+            .push(function);
+
+        Ok(())
+    }
+
+    /// Removes the overload of `name` taking exactly `params`, returning it if there was one.
+    pub fn unregister(&mut self, name: &str, params: &[Param]) -> Option<Function> {
+        let overloads = self.functions.get_mut(name)?;
+
+        let position = overloads.iter().position(|overload| {
+            overload.signature.params.as_deref() == Some(params) && overload.signature.variadic == 0
+        })?;
+
+        let function = overloads.remove(position);
+
+        if overloads.is_empty() {
+            self.functions.remove(name);
+        }
+
+        Some(function)
+    }
+
+    /// Every overload of the function named `name`, which is in lower case.
+    pub fn overloads(&self, name: &str) -> &[Function] {
+        self.functions.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// The columns returned by every overload of the table function named `name`, whose names
+    /// are the same for all of them, or `None` if there is no such table function.
+    pub fn table_columns(&self, name: &str) -> Option<&'static [TableColumn]> {
+        match self.overloads(name).first()?.signature.returns {
+            Returns::Table(columns) => Some(columns),
+            _ => None,
+        }
+    }
+
+    /// The names of every function.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.functions.keys().map(|name| name.as_ref())
+    }
+}
+
+impl Default for FunctionRegistry {
+    /// The registry of the built-in functions.
+    fn default() -> Self {
+        let mut functions = HashMap::<_, Vec<_>>::new();
+
+        for function in builtin_functions() {
+            functions
+                .entry(function.signature.name.clone())
+                .or_default()
+                .push(function);
+        }
+
+        FunctionRegistry { functions }
+    }
+}
+
+macro_rules! functions {
+    ($($name:literal ($($param:tt)*) -> $returns:ident $(($columns:ident))? : $kind:ident $(($call:ident))? $($nondeterministic:ident)?),* $(,)?) => {
+        vec![$(Function {
+            signature: Signature {
+                name: Cow::Borrowed($name),
+                params: functions!(@params $($param)*),
+                variadic: functions!(@variadic $($param)*),
+                returns: functions!(@returns $returns $($columns)?),
+            },
+            kind: functions!(@kind $kind $($call)? $($nondeterministic)?),
+        }),*]
+    };
+    (@params *) => { None };
+    (@params $($param:ident),*) => { Some(Cow::Borrowed(&[$(functions!(@param $param)),*])) };
+    (@params $($param:ident,)* [$($repeated:ident),+]) => {
+        Some(Cow::Borrowed(&[$(functions!(@param $param),)* $(functions!(@param $repeated)),+]))
+    };
+    (@variadic $($param:ident,)* [$($repeated:ident),+]) => { [$(functions!(@param $repeated)),+].len() };
+    (@variadic $($param:tt)*) => { 0 };
+    (@param Any) => { Param::Any };
+    (@param Field) => { Param::Field };
+    (@param Unit) => { Param::Unit };
+    (@param Zone) => { Param::Zone };
+    (@param Pattern) => { Param::Pattern };
+    (@param $type:ident) => { Param::Type(functions!(@type $type)) };
+    (@returns FirstArg) => { Returns::FirstArg };
+    (@returns CommonType) => { Returns::CommonType };
+    (@returns Table $columns:ident) => { Returns::Table($columns) };
+    (@returns $type:ident) => { Returns::Type(functions!(@type $type)) };
+    // Functions take and return decimals of any precision and scale
+    (@type Decimal) => { DataType::Decimal(None) };
+    (@type $type:ident) => { DataType::$type };
+    (@kind aggregate $aggregate:ident) => { FunctionKind::Aggregate(Arc::new($aggregate)) };
+    (@kind table $function:ident) => { FunctionKind::Table(Arc::new($function)) };
+    (@kind strict $call:ident) => {
+        FunctionKind::Scalar { call: Arc::new($call), strict: true, deterministic: true }
+    };
+    // Functions called on `NULL` arguments decide themselves what they return for them
+    (@kind called_on_null $call:ident) => {
+        FunctionKind::Scalar { call: Arc::new($call), strict: false, deterministic: true }
+    };
+    // Functions which are random, or depend on the time or time zone of the session
+    (@kind strict $call:ident nondeterministic) => {
+        FunctionKind::Scalar { call: Arc::new($call), strict: true, deterministic: false }
+    };
+    (@kind called_on_null $call:ident nondeterministic) => {
+        FunctionKind::Scalar { call: Arc::new($call), strict: false, deterministic: false }
+    };
+}
+
+/// Every overload of the built-in functions.
+fn builtin_functions() -> Vec<Function> {
+    functions![
+        /* AGGREGATE */
+        "count"(*) -> BigInt: aggregate(Count),
+        "count"(Any) -> BigInt: aggregate(Count),
+        "sum"(SmallInt) -> BigInt: aggregate(Sum),
+        "sum"(Int) -> BigInt: aggregate(Sum),
+        "sum"(BigInt) -> Decimal: aggregate(Sum),
+        "sum"(Decimal) -> Decimal: aggregate(Sum),
+        "sum"(Real) -> Real: aggregate(Sum),
+        "sum"(Double) -> Double: aggregate(Sum),
+        "avg"(SmallInt) -> Decimal: aggregate(Avg),
+        "avg"(Int) -> Decimal: aggregate(Avg),
+        "avg"(BigInt) -> Decimal: aggregate(Avg),
+        "avg"(Decimal) -> Decimal: aggregate(Avg),
+        "avg"(Real) -> Double: aggregate(Avg),
+        "avg"(Double) -> Double: aggregate(Avg),
+        "min"(Any) -> FirstArg: aggregate(MIN),
+        "max"(Any) -> FirstArg: aggregate(MAX),
+        "string_agg"(Text, Text) -> Text: aggregate(StringAgg),
+        "array_agg"(Any) -> Jsonb: aggregate(ArrayAgg),
+        "jsonb_agg"(Any) -> Jsonb: aggregate(ArrayAgg),
+        "bool_and"(Boolean) -> Boolean: aggregate(BOOL_AND),
+        "bool_or"(Boolean) -> Boolean: aggregate(BOOL_OR),
+        "variance"(Double) -> Double: aggregate(VAR_SAMP),
+        "var_samp"(Double) -> Double: aggregate(VAR_SAMP),
+        "var_pop"(Double) -> Double: aggregate(VAR_POP),
+        "stddev"(Double) -> Double: aggregate(STDDEV_SAMP),
+        "stddev_samp"(Double) -> Double: aggregate(STDDEV_SAMP),
+        "stddev_pop"(Double) -> Double: aggregate(STDDEV_POP),
+        "approx_count_distinct"(Any) -> BigInt: aggregate(APPROX_COUNT_DISTINCT),
+        "approx_percentile"(Double, Double) -> Double: aggregate(APPROX_PERCENTILE),
+        "hll_sketch"(Any) -> Bytea: aggregate(HLL_SKETCH),
+        "hll_union"(Bytea) -> Bytea: aggregate(HLL_UNION),
+        "tdigest_sketch"(Double) -> Bytea: aggregate(TDIGEST_SKETCH),
+        "tdigest_union"(Bytea) -> Bytea: aggregate(TDIGEST_UNION),
+        /* MATH */
+        "abs"(SmallInt) -> SmallInt: strict(abs),
+        "abs"(Int) -> Int: strict(abs),
+        "abs"(BigInt) -> BigInt: strict(abs),
+        "abs"(Decimal) -> Decimal: strict(abs),
+        "abs"(Real) -> Real: strict(abs),
+        "abs"(Double) -> Double: strict(abs),
+        "round"(Decimal) -> Decimal: strict(round),
+        "round"(Decimal, Int) -> Decimal: strict(round),
+        "round"(Double) -> Double: strict(round),
+        "trunc"(Decimal) -> Decimal: strict(trunc),
+        "trunc"(Decimal, Int) -> Decimal: strict(trunc),
+        "trunc"(Double) -> Double: strict(trunc),
+        "floor"(Decimal) -> Decimal: strict(floor),
+        "floor"(Double) -> Double: strict(floor),
+        "ceil"(Decimal) -> Decimal: strict(ceil),
+        "ceil"(Double) -> Double: strict(ceil),
+        "power"(Double, Double) -> Double: strict(power),
+        "ln"(Double) -> Double: strict(ln),
+        "random"() -> Double: strict(random) nondeterministic,
+        /* STRING */
+        "upper"(Text) -> Text: strict(upper),
+        "lower"(Text) -> Text: strict(lower),
+        "length"(Text) -> Int: strict(length),
+        "substring"(Text, Int) -> Text: strict(substring),
+        "substring"(Text, Int, Int) -> Text: strict(substring),
+        "trim"(Text) -> Text: strict(trim),
+        "trim"(Text, Text) -> Text: strict(trim),
+        "ltrim"(Text) -> Text: strict(ltrim),
+        "ltrim"(Text, Text) -> Text: strict(ltrim),
+        "rtrim"(Text) -> Text: strict(rtrim),
+        "rtrim"(Text, Text) -> Text: strict(rtrim),
+        "replace"(Text, Text, Text) -> Text: strict(replace),
+        "split_part"(Text, Text, Int) -> Text: strict(split_part),
+        "regexp_match"(Text, Pattern) -> Jsonb: strict(regexp_match),
+        "regexp_match"(Text, Pattern, Text) -> Jsonb: strict(regexp_match),
+        "regexp_replace"(Text, Pattern, Text) -> Text: strict(regexp_replace),
+        "regexp_replace"(Text, Pattern, Text, Text) -> Text: strict(regexp_replace),
+        "concat_ws"(Text, [Any]) -> Text: called_on_null(concat_ws) nondeterministic,
+        "lpad"(Text, Int) -> Text: strict(lpad),
+        "lpad"(Text, Int, Text) -> Text: strict(lpad),
+        "rpad"(Text, Int) -> Text: strict(rpad),
+        "rpad"(Text, Int, Text) -> Text: strict(rpad),
+        /* CONDITIONAL */
+        "coalesce"(Any, [Any]) -> CommonType: called_on_null(coalesce),
+        "nullif"(Any, Any) -> CommonType: called_on_null(nullif),
+        "greatest"(Any, [Any]) -> CommonType: called_on_null(greatest),
+        "least"(Any, [Any]) -> CommonType: called_on_null(least),
+        /* HASHING */
+        "md5"(Text) -> Text: strict(md5),
+        "md5"(Bytea) -> Text: strict(md5),
+        "sha256"(Text) -> Bytea: strict(sha256),
+        "sha256"(Bytea) -> Bytea: strict(sha256),
+        /* DATE AND TIME */
+        "now"() -> TimestampTz: strict(now) nondeterministic,
+        "date_trunc"(Unit, Timestamp) -> Timestamp: strict(date_trunc),
+        "date_trunc"(Unit, TimestampTz) -> TimestampTz: strict(date_trunc) nondeterministic,
+        "date_trunc"(Unit, TimestampTz, Zone) -> TimestampTz: strict(date_trunc),
+        "date_trunc"(Unit, Interval) -> Interval: strict(date_trunc),
+        "date_part"(Field, Timestamp) -> Double: strict(date_part),
+        "date_part"(Field, TimestampTz) -> Double: strict(date_part) nondeterministic,
+        "date_part"(Field, Time) -> Double: strict(date_part),
+        "date_part"(Field, Interval) -> Double: strict(date_part),
+        "date_bin"(Interval, Timestamp, Timestamp) -> Timestamp: strict(date_bin),
+        "date_bin"(Interval, TimestampTz, TimestampTz) -> TimestampTz: strict(date_bin),
+        "time_bucket"(Interval, Timestamp) -> Timestamp: strict(time_bucket),
+        "time_bucket"(Interval, TimestampTz) -> TimestampTz: strict(time_bucket),
+        "time_bucket"(Interval, Date) -> Date: strict(time_bucket),
+        "timezone"(Zone, Timestamp) -> TimestampTz: strict(timezone),
+        "timezone"(Zone, TimestampTz) -> Timestamp: strict(timezone),
+        "to_char"(Timestamp, Text) -> Text: strict(to_char),
+        "to_char"(TimestampTz, Text) -> Text: strict(to_char) nondeterministic,
+        /* SKETCHES */
+        "hll_estimate"(Bytea) -> BigInt: strict(hll_estimate),
+        "tdigest_percentile"(Bytea, Double) -> Double: strict(tdigest_percentile),
+        /* JSONB */
+        "to_jsonb"(Any) -> Jsonb: strict(to_jsonb),
+        "jsonb_build_object"([Any, Any]) -> Jsonb: called_on_null(jsonb_build_object),
+        /* TABLE */
+        "generate_series"(Int, Int) -> Table(INT_SERIES): table(GenerateSeries),
+        "generate_series"(Int, Int, Int) -> Table(INT_SERIES): table(GenerateSeries),
+        "generate_series"(BigInt, BigInt) -> Table(BIGINT_SERIES): table(GenerateSeries),
+        "generate_series"(BigInt, BigInt, BigInt) -> Table(BIGINT_SERIES): table(GenerateSeries),
+        "unnest"(Jsonb) -> Table(UNNEST_COLUMNS): table(UNNEST),
+        "jsonb_array_elements"(Jsonb) -> Table(VALUE_COLUMNS): table(JSONB_ARRAY_ELEMENTS),
+        "jsonb_array_elements_text"(Jsonb) -> Table(TEXT_VALUE_COLUMNS): table(JSONB_ARRAY_ELEMENTS_TEXT),
+        "jsonb_each"(Jsonb) -> Table(KEY_VALUE_COLUMNS): table(JSONB_EACH),
+        "jsonb_each_text"(Jsonb) -> Table(KEY_TEXT_VALUE_COLUMNS): table(JSONB_EACH_TEXT),
+        "jsonb_object_keys"(Jsonb) -> Table(OBJECT_KEYS_COLUMNS): table(JsonbObjectKeys),
+    ]
+}
+
+/// The largest text functions build, in bytes, which is the largest value PostgreSQL can store.
+const MAX_TEXT_BYTES: usize = 1 << 30;
+
+/// Takes the value out of an argument, which has been coerced to the type of its parameter.
+macro_rules! arg {
+    ($value:expr, $variant:ident) => {
+        match &$value {
+            Value::$variant(value) => value,
+            value => unreachable!("Argument {value:?} was not coerced to its parameter type"),
+        }
+    };
+}
+
+/* MATH */
+
+fn abs(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let out_of_range = |data_type| FunctionError::OutOfRange {
+        function: "abs".to_string(),
+        data_type,
+    };
+
+    Ok(match &args[0] {
+        Value::SmallInt(value) => Value::SmallInt(
+            value
+                .checked_abs()
+                .ok_or_else(|| out_of_range(DataType::SmallInt))?,
+        ),
+        Value::Int(value) => Value::Int(
+            value
+                .checked_abs()
+                .ok_or_else(|| out_of_range(DataType::Int))?,
+        ),
+        Value::BigInt(value) => Value::BigInt(
+            value
+                .checked_abs()
+                .ok_or_else(|| out_of_range(DataType::BigInt))?,
+        ),
+        Value::Decimal(value) => {
+            Value::Decimal(Decimal::new(value.mantissa().abs(), value.scale())?)
+        }
+        Value::Real(value) => Value::Real(value.abs()),
+        Value::Double(value) => Value::Double(value.abs()),
+        value => unreachable!("Argument {value:?} was not coerced to its parameter type"),
+    })
+}
+
+fn round(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    match &args[0] {
+        // Like C's `rint`, which rounds halfway cases to even
+        Value::Double(value) => Ok(Value::Double(value.round_ties_even())),
+        _ => round_decimal(args, RoundingMode::HalfUp),
+    }
+}
+
+fn trunc(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    match &args[0] {
+        Value::Double(value) => Ok(Value::Double(value.trunc())),
+        _ => round_decimal(args, RoundingMode::Down),
+    }
+}
+
+fn floor(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    match &args[0] {
+        Value::Double(value) => Ok(Value::Double(value.floor())),
+        _ => round_decimal(args, RoundingMode::Floor),
+    }
+}
+
+fn ceil(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    match &args[0] {
+        Value::Double(value) => Ok(Value::Double(value.ceil())),
+        _ => round_decimal(args, RoundingMode::Ceiling),
+    }
+}
+
+/// Rounds a decimal to the number of digits after the decimal point given by the second
+/// argument, or none, where negative numbers of digits round to tens, hundreds and so on.
+fn round_decimal(args: &[Value], mode: RoundingMode) -> Result<Value, FunctionError> {
+    let value = arg!(args[0], Decimal);
+    let digits = args.get(1).map_or(0, |digits| *arg!(digits, Int));
+
+    if let Ok(digits) = u32::try_from(digits) {
+        return Ok(Value::Decimal(
+            value.round(digits.min(Decimal::MAX_PRECISION), mode)?,
+        ));
+    }
+
+    // Every number is rounded to zero when rounding to a power of ten larger than any decimal
+    let unit = 10i128
+        .checked_pow(digits.unsigned_abs())
+        .and_then(|unit| Decimal::new(unit, 0).ok());
+
+    let Some(unit) = unit else {
+        return Ok(Value::Decimal(Decimal::from(0)));
+    };
+
+    let units = value.checked_div(&unit, 0, mode)?;
+
+    Ok(Value::Decimal(units.checked_mul(&unit)?))
+}
+
+fn power(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let (base, exponent) = (*arg!(args[0], Double), *arg!(args[1], Double));
+
+    let invalid = |reason| FunctionError::InvalidArgument {
+        function: "power",
+        reason,
+    };
+
+    if base == 0.0 && exponent < 0.0 {
+        return Err(invalid("zero raised to a negative power is undefined"));
+    }
+
+    if base < 0.0 && exponent.fract() != 0.0 {
+        return Err(invalid(
+            "a negative number raised to a non-integer power yields a complex result",
+        ));
+    }
+
+    let result = base.powf(exponent);
+
+    match result.is_infinite() && base.is_finite() && exponent.is_finite() {
+        true => Err(FunctionError::OutOfRange {
+            function: "power".to_string(),
+            data_type: DataType::Double,
+        }),
+        false => Ok(Value::Double(result)),
+    }
+}
+
+fn ln(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let value = *arg!(args[0], Double);
+
+    let invalid = |reason| FunctionError::InvalidArgument {
+        function: "ln",
+        reason,
+    };
+
+    if value == 0.0 {
+        Err(invalid("cannot take the logarithm of zero"))
+    } else if value < 0.0 {
+        Err(invalid("cannot take the logarithm of a negative number"))
+    } else {
+        Ok(Value::Double(value.ln()))
+    }
+}
+
+/// A number in `[0, 1)`, which is different every time the function is called.
+fn random(_: &FunctionContext, _: &[Value]) -> Result<Value, FunctionError> {
+    Ok(Value::Double(fastrand::f64()))
+}
+
+/* STRING */
+
+fn upper(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    Ok(Value::Text(arg!(args[0], Text).to_uppercase()))
+}
+
+fn lower(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    Ok(Value::Text(arg!(args[0], Text).to_lowercase()))
+}
+
+/// The number of characters, rather than bytes, in the text.
+fn length(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let length = arg!(args[0], Text).chars().count();
+
+    Ok(Value::Int(length.try_into().map_err(|_| {
+        FunctionError::OutOfRange {
+            function: "length".to_string(),
+            data_type: DataType::Int,
+        }
+    })?))
+}
+
+/// The characters from the position given by the second argument, counted from 1, up to the
+/// end or as many as the third argument gives. Positions before the first character count
+/// towards the number of characters taken, so `substring('abc', 0, 2)` is `'a'`.
+fn substring(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let text = arg!(args[0], Text);
+    let start = *arg!(args[1], Int) as i64;
+
+    let end = match args.get(2) {
+        Some(count) => match *arg!(count, Int) {
+            count if count < 0 => {
+                return Err(FunctionError::InvalidArgument {
+                    function: "substring",
+                    reason: "negative substring length not allowed",
+                })
+            }
+            count => start + count as i64,
+        },
+        None => i64::MAX,
+    };
+
+    let start = start.max(1);
+    let count = (end - start).max(0) as usize;
+
+    Ok(Value::Text(
+        text.chars().skip(start as usize - 1).take(count).collect(),
+    ))
+}
+
+fn trim(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let (text, characters) = trimmed(args);
+    Ok(Value::Text(text.trim_matches(characters).to_string()))
+}
+
+fn ltrim(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let (text, characters) = trimmed(args);
+    Ok(Value::Text(text.trim_start_matches(characters).to_string()))
+}
+
+fn rtrim(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let (text, characters) = trimmed(args);
+    Ok(Value::Text(text.trim_end_matches(characters).to_string()))
+}
+
+/// The text to trim and the characters to trim from it, which are spaces unless the second
+/// argument gives them.
+fn trimmed(args: &[Value]) -> (&str, impl Fn(char) -> bool + '_) {
+    let characters = args.get(1).map_or(" ", |characters| arg!(characters, Text));
+    (arg!(args[0], Text), move |c| characters.contains(c))
+}
+
+fn replace(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let (text, from, to) = (
+        arg!(args[0], Text),
+        arg!(args[1], Text),
+        arg!(args[2], Text),
+    );
+
+    if from.is_empty() {
+        return Ok(Value::Text(text.clone()));
+    }
+
+    // Nested replacements can grow text exponentially
+    if to.len() > from.len() {
+        let matches = text.matches(from.as_str()).count();
+        check_length("replace", text.len() + matches * (to.len() - from.len()))?;
+    }
+
+    Ok(Value::Text(text.replace(from.as_str(), to)))
+}
+
+/// The field at a position counted from 1 after splitting the text at a delimiter, where
+/// negative positions count from the last field.
+fn split_part(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let (text, delimiter, position) = (
+        arg!(args[0], Text),
+        arg!(args[1], Text),
+        *arg!(args[2], Int),
+    );
+
+    let fields = match delimiter.is_empty() {
+        true => vec![text.as_str()],
+        false => text.split(delimiter.as_str()).collect(),
+    };
+
+    let index = match position {
+        0 => {
+            return Err(FunctionError::InvalidArgument {
+                function: "split_part",
+                reason: "field position must not be zero",
+            })
+        }
+        position if position > 0 => Some(position as usize - 1),
+        position => fields.len().checked_sub(position.unsigned_abs() as usize),
+    };
+
+    let field = index.and_then(|index| fields.get(index)).unwrap_or(&"");
+
+    Ok(Value::Text(field.to_string()))
+}
+
+/// The groups of the first match of a pattern as a JSONB array, or the whole match if the
+/// pattern has no groups, or `NULL` if it does not match.
+fn regexp_match(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let text = arg!(args[0], Text);
+    let (regex, global) = pattern("regexp_match", &args[1], args.get(2))?;
+
+    if global {
+        return Err(FunctionError::InvalidArgument {
+            function: "regexp_match",
+            reason: "the `g` flag is not supported",
+        });
+    }
+
+    let Some(captures) = regex.captures(text) else {
+        return Ok(Value::Null);
+    };
+
+    let text = |group: Option<regex::Match<'_>>| match group {
+        Some(group) => Value::Text(group.as_str().to_string()),
+        None => Value::Null,
+    };
+
+    let groups = match captures.len() {
+        1 => vec![text(captures.get(0))],
+        _ => captures.iter().skip(1).map(text).collect(),
+    };
+
+    Ok(Value::Jsonb(Jsonb::agg(&groups)))
+}
+
+/// Replaces the first match of a pattern, or every match with the `g` flag, where `\1` to `\9`
+/// in the replacement stand for the groups of the match and `\&` for the whole match.
+fn regexp_replace(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let (text, replacement) = (arg!(args[0], Text), arg!(args[2], Text));
+    let (regex, global) = pattern("regexp_replace", &args[1], args.get(3))?;
+
+    // Translated to the syntax of the `regex` crate, where `$` starts a group reference
+    let mut translated = String::new();
+    let mut chars = replacement.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some(digit @ '0'..='9')) => {
+                write!(translated, "${{{digit}}}").unwrap();
+                chars.next();
+            }
+            ('\\', Some('&')) => {
+                translated.push_str("${0}");
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                translated.push('\\');
+                chars.next();
+            }
+            ('$', _) => translated.push_str("$$"),
+            (c, _) => translated.push(c),
+        }
+    }
+
+    let limit = match global {
+        true => 0,
+        false => 1,
+    };
+
+    Ok(Value::Text(
+        regex
+            .replacen(text, limit, translated.as_str())
+            .into_owned(),
+    ))
+}
+
+/// Compiles a pattern with flags, of which `i` makes it case insensitive, `c` case sensitive
+/// again, and `g` is returned for the caller to handle.
+fn pattern(
+    function: &'static str,
+    pattern: &Value,
+    flags: Option<&Value>,
+) -> Result<(Regex, bool), FunctionError> {
+    let mut builder = RegexBuilder::new(arg!(pattern, Text));
+    let mut global = false;
+
+    for flag in flags.map_or("", |flags| arg!(flags, Text)).chars() {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'c' => builder.case_insensitive(false),
+            'g' => {
+                global = true;
+                continue;
+            }
+            _ => {
+                return Err(FunctionError::InvalidArgument {
+                    function,
+                    reason: "unknown flag, only `i`, `c` and `g` are supported",
+                })
+            }
+        };
+    }
+
+    Ok((builder.build()?, global))
+}
+
+/// Checks that a pattern is a valid regular expression.
+pub fn check_pattern(pattern: &str) -> Result<(), FunctionError> {
+    Regex::new(pattern)?;
+    Ok(())
+}
+
+/// Joins every argument after the first which is not `NULL` with the first as the separator.
+fn concat_ws(context: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let Value::Text(separator) = &args[0] else {
+        return Ok(Value::Null);
+    };
+
+    let values = args[1..]
+        .iter()
+        .filter(|value| !value.is_null())
+        .map(|value| value.to_string_in(&context.time_zone))
+        .collect::<Vec<_>>();
+
+    Ok(Value::Text(values.join(separator)))
+}
+
+fn lpad(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    Ok(Value::Text(pad("lpad", args, true)?))
+}
+
+fn rpad(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    Ok(Value::Text(pad("rpad", args, false)?))
+}
+
+/// Fills the text up to a number of characters with the characters of the third argument, or
+/// spaces, repeated as often as needed, or cuts it off at that many characters if it is longer.
+fn pad(function: &'static str, args: &[Value], left: bool) -> Result<String, FunctionError> {
+    let text = arg!(args[0], Text);
+    let length = (*arg!(args[1], Int)).max(0) as usize;
+    let fill = args.get(2).map_or(" ", |fill| arg!(fill, Text));
+
+    // Every character can take up to 4 bytes
+    check_length(function, length.saturating_mul(4))?;
+
+    let chars = text.chars().count();
+
+    if chars >= length || fill.is_empty() {
+        return Ok(text.chars().take(length).collect());
+    }
+
+    let padding = fill.chars().cycle().take(length - chars);
+
+    Ok(match left {
+        true => padding.chain(text.chars()).collect(),
+        false => text.chars().chain(padding).collect(),
+    })
+}
+
+/// Checks that the text a function is about to build, of `bytes` bytes, is not too large
+/// before it is allocated.
+fn check_length(function: &'static str, bytes: usize) -> Result<(), FunctionError> {
+    match bytes > MAX_TEXT_BYTES {
+        true => Err(FunctionError::InvalidArgument {
+            function,
+            reason: "requested length too large",
+        }),
+        false => Ok(()),
+    }
+}
+
+/* CONDITIONAL */
+
+/// The first argument which is not `NULL`.
+fn coalesce(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let value = args.iter().find(|value| !value.is_null());
+    Ok(value.cloned().unwrap_or(Value::Null))
+}
+
+/// `NULL` if both arguments are equal, or else the first.
+fn nullif(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    match !args[0].is_null() && args[0] == args[1] {
+        true => Ok(Value::Null),
+        false => Ok(args[0].clone()),
+    }
+}
+
+/// The largest argument, ignoring `NULL` unless every argument is `NULL`.
+fn greatest(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let value = args.iter().filter(|value| !value.is_null()).max();
+    Ok(value.cloned().unwrap_or(Value::Null))
+}
+
+/// The smallest argument, ignoring `NULL` unless every argument is `NULL`.
+fn least(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let value = args.iter().filter(|value| !value.is_null()).min();
+    Ok(value.cloned().unwrap_or(Value::Null))
+}
+
+/* HASHING */
+
+/// The MD5 digest of the text or bytes, in hexadecimal.
+fn md5(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let digest = Md5::digest(bytes(&args[0]));
+
+    let mut hex = String::with_capacity(digest.len() * 2);
+
+    for byte in digest {
+        write!(hex, "{byte:02x}").unwrap();
+    }
+
+    Ok(Value::Text(hex))
+}
+
+fn sha256(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    Ok(Value::Bytea(Sha256::digest(bytes(&args[0])).to_vec()))
+}
+
+/// The bytes of a `BYTEA` value, or the UTF-8 encoding of text.
+fn bytes(value: &Value) -> &[u8] {
+    match value {
+        Value::Text(text) => text.as_bytes(),
+        _ => arg!(value, Bytea),
+    }
+}
+
+/* DATE AND TIME */
+
+/// When the statement started, which is the same however often it is called in one statement.
+fn now(context: &FunctionContext, _: &[Value]) -> Result<Value, FunctionError> {
+    Ok(Value::TimestampTz(context.now))
+}
+
+fn date_trunc(context: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let field = DateField::parse(arg!(args[0], Text))?;
+
+    Ok(match &args[1] {
+        Value::Timestamp(timestamp) => Value::Timestamp(datetime::date_trunc(field, *timestamp)?),
+        Value::TimestampTz(timestamp) => {
+            let time_zone = match args.get(2) {
+                Some(zone) => TimeZone::parse(arg!(zone, Text))?,
+                None => context.time_zone,
+            };
+
+            Value::TimestampTz(datetime::date_trunc_in(field, *timestamp, &time_zone)?)
+        }
+        value => Value::Interval(datetime::date_trunc_interval(field, arg!(value, Interval))?),
+    })
+}
+
+fn date_part(context: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let field = DateField::parse(arg!(args[0], Text))?;
+
+    Ok(Value::Double(match &args[1] {
+        Value::Timestamp(timestamp) => datetime::date_part(field, *timestamp)?,
+        Value::TimestampTz(timestamp) => {
+            datetime::date_part_in(field, *timestamp, &context.time_zone)?
+        }
+        Value::Time(time) => datetime::date_part_time(field, *time)?,
+        value => datetime::date_part_interval(field, arg!(value, Interval))?,
+    }))
+}
+
+fn date_bin(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let stride = arg!(args[0], Interval);
+
+    Ok(match (&args[1], &args[2]) {
+        (Value::Timestamp(timestamp), Value::Timestamp(origin)) => {
+            Value::Timestamp(datetime::date_bin(stride, *timestamp, *origin)?)
+        }
+        (timestamp, origin) => Value::TimestampTz(datetime::date_bin(
+            stride,
+            *arg!(timestamp, TimestampTz),
+            *arg!(origin, TimestampTz),
+        )?),
+    })
+}
+
+fn time_bucket(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let width = arg!(args[0], Interval);
+
+    Ok(match &args[1] {
+        Value::Timestamp(timestamp) => Value::Timestamp(datetime::time_bucket(width, *timestamp)?),
+        Value::TimestampTz(timestamp) => {
+            Value::TimestampTz(datetime::time_bucket(width, *timestamp)?)
+        }
+        value => {
+            let timestamp = *arg!(value, Date) as i64 * MICROS_PER_DAY;
+            let bucket = datetime::time_bucket(width, timestamp)?.div_euclid(MICROS_PER_DAY);
+
+            Value::Date(bucket.try_into().map_err(|_| DateTimeError::OutOfRange)?)
+        }
+    })
+}
+
+/// Converts a `TIMESTAMP` in the local time of a zone into a `TIMESTAMPTZ`, or a `TIMESTAMPTZ`
+/// into the local time of a zone.
+fn timezone(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let time_zone = TimeZone::parse(arg!(args[0], Text))?;
+
+    Ok(match &args[1] {
+        Value::Timestamp(local) => Value::TimestampTz(time_zone.to_utc(*local)),
+        value => {
+            let utc = *arg!(value, TimestampTz);
+            Value::Timestamp(utc + time_zone.offset_at(utc))
+        }
+    })
+}
+
+fn to_char(context: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let format = arg!(args[1], Text);
+
+    Ok(Value::Text(match &args[0] {
+        Value::Timestamp(timestamp) => datetime::to_char(*timestamp, None, format),
+        value => datetime::to_char(*arg!(value, TimestampTz), Some(&context.time_zone), format),
+    }))
+}
+
+/* SKETCHES */
+
+fn hll_estimate(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let sketch = HyperLogLog::from_bytes(arg!(args[0], Bytea).clone())?;
+
+    Ok(Value::BigInt(sketch.estimate() as i64))
+}
+
+fn tdigest_percentile(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    let digest = TDigest::from_bytes(arg!(args[0], Bytea).clone())?;
+    let fraction = check_fraction("tdigest_percentile", *arg!(args[1], Double))?;
+
+    Ok(digest.quantile(fraction).map_or(Value::Null, Value::Double))
+}
+
+/// Checks that the fraction of a percentile is between 0 and 1.
+pub fn check_fraction(function: &'static str, fraction: f64) -> Result<f64, FunctionError> {
+    match (0.0..=1.0).contains(&fraction) {
+        true => Ok(fraction),
+        false => Err(FunctionError::InvalidArgument {
+            function,
+            reason: "percentile must be between 0 and 1",
+        }),
+    }
+}
+
+/* JSONB */
+
+fn to_jsonb(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    Ok(Value::Jsonb(Jsonb::from_value(&args[0])))
+}
+
+fn jsonb_build_object(_: &FunctionContext, args: &[Value]) -> Result<Value, FunctionError> {
+    Ok(Value::Jsonb(Jsonb::build_object(args)?))
+}
+
+/* TABLE */
+
+// Most functions returning a single column name it after themselves
+const INT_SERIES: &[TableColumn] = &[TableColumn {
+    name: "generate_series",
+    data_type: DataType::Int,
+}];
+const BIGINT_SERIES: &[TableColumn] = &[TableColumn {
+    name: "generate_series",
+    data_type: DataType::BigInt,
+}];
+const UNNEST_COLUMNS: &[TableColumn] = &[TableColumn {
+    name: "unnest",
+    data_type: DataType::Jsonb,
+}];
+const VALUE_COLUMNS: &[TableColumn] = &[TableColumn {
+    name: "value",
+    data_type: DataType::Jsonb,
+}];
+const TEXT_VALUE_COLUMNS: &[TableColumn] = &[TableColumn {
+    name: "value",
+    data_type: DataType::Text,
+}];
+const KEY_VALUE_COLUMNS: &[TableColumn] = &[
+    TableColumn {
+        name: "key",
+        data_type: DataType::Text,
+    },
+    TableColumn {
+        name: "value",
+        data_type: DataType::Jsonb,
+    },
+];
+const KEY_TEXT_VALUE_COLUMNS: &[TableColumn] = &[
+    TableColumn {
+        name: "key",
+        data_type: DataType::Text,
+    },
+    TableColumn {
+        name: "value",
+        data_type: DataType::Text,
+    },
+];
+const OBJECT_KEYS_COLUMNS: &[TableColumn] = &[TableColumn {
+    name: "jsonb_object_keys",
+    data_type: DataType::Text,
+}];
+
+/// The numbers from a start to a stop, both included, counting by a step which is 1 by default.
+#[derive(Debug)]
+pub struct GenerateSeries;
+
+impl TableFunction for GenerateSeries {
+    fn call(&self, _: &FunctionContext, args: &[Value]) -> Result<Vec<Vec<Value>>, FunctionError> {
+        if args.iter().any(Value::is_null) {
+            return Ok(Vec::new());
+        }
+
+        let int = |value: &Value| match value {
+            Value::Int(value) => i64::from(*value),
+            Value::BigInt(value) => *value,
+            value => unreachable!("Argument {value:?} was not coerced to its parameter type"),
+        };
+
+        let (start, stop) = (int(&args[0]), int(&args[1]));
+        let step = args.get(2).map_or(1, int);
+
+        if step == 0 {
+            return Err(FunctionError::InvalidArgument {
+                function: "generate_series",
+                reason: "step size cannot be 0",
+            });
+        }
+
+        let mut rows = Vec::new();
+        let mut current = Some(start);
+
+        // Stops before overflowing rather than after, as the stop may be close to the limit
+        while let Some(value) = current.filter(|value| match step > 0 {
+            true => *value <= stop,
+            false => *value >= stop,
+        }) {
+            rows.push(vec![match &args[0] {
+                Value::Int(_) => Value::Int(value as i32),
+                _ => Value::BigInt(value),
+            }]);
+
+            current = value.checked_add(step);
+        }
+
+        Ok(rows)
+    }
+}
+
+/// One row for each element of a JSON array, which is how arrays are represented.
+#[derive(Debug)]
+pub struct JsonbArrayElements {
+    function: &'static str,
+    /// Whether the elements are returned as text, as `->>` returns them
+    text: bool,
+}
+
+pub const UNNEST: JsonbArrayElements = JsonbArrayElements {
+    function: "unnest",
+    text: false,
+};
+pub const JSONB_ARRAY_ELEMENTS: JsonbArrayElements = JsonbArrayElements {
+    function: "jsonb_array_elements",
+    text: false,
+};
+pub const JSONB_ARRAY_ELEMENTS_TEXT: JsonbArrayElements = JsonbArrayElements {
+    function: "jsonb_array_elements_text",
+    text: true,
+};
+
+impl TableFunction for JsonbArrayElements {
+    fn call(&self, _: &FunctionContext, args: &[Value]) -> Result<Vec<Vec<Value>>, FunctionError> {
+        let array = match &args[0] {
+            Value::Null => return Ok(Vec::new()),
+            value => arg!(value, Jsonb).root(),
+        };
+
+        if array.kind() != JsonbKind::Array {
+            return Err(FunctionError::InvalidArgument {
+                function: self.function,
+                reason: "not an array",
+            });
+        }
+
+        Ok(array
+            .elements()
+            .map(|element| match self.text {
+                true => vec![element.to_text().map_or(Value::Null, Value::Text)],
+                false => vec![Value::Jsonb(element.to_jsonb())],
+            })
+            .collect())
+    }
+}
+
+/// One row for each key and value of a JSON object, ordered by key.
+#[derive(Debug)]
+pub struct JsonbEach {
+    function: &'static str,
+    /// Whether the values are returned as text, as `->>` returns them
+    text: bool,
+}
+
+pub const JSONB_EACH: JsonbEach = JsonbEach {
+    function: "jsonb_each",
+    text: false,
+};
+pub const JSONB_EACH_TEXT: JsonbEach = JsonbEach {
+    function: "jsonb_each_text",
+    text: true,
+};
+
+impl TableFunction for JsonbEach {
+    fn call(&self, _: &FunctionContext, args: &[Value]) -> Result<Vec<Vec<Value>>, FunctionError> {
+        let object = match &args[0] {
+            Value::Null => return Ok(Vec::new()),
+            value => arg!(value, Jsonb).root(),
+        };
+
+        if object.kind() != JsonbKind::Object {
+            return Err(FunctionError::InvalidArgument {
+                function: self.function,
+                reason: "not an object",
+            });
+        }
+
+        Ok(object
+            .entries()
+            .map(|(key, value)| {
+                let value = match self.text {
+                    true => value.to_text().map_or(Value::Null, Value::Text),
+                    false => Value::Jsonb(value.to_jsonb()),
+                };
+
+                vec![Value::Text(key.to_string()), value]
+            })
+            .collect())
+    }
+}
+
+/// One row for each key of a JSON object, ordered by key.
+#[derive(Debug)]
+pub struct JsonbObjectKeys;
+
+impl TableFunction for JsonbObjectKeys {
+    fn call(&self, _: &FunctionContext, args: &[Value]) -> Result<Vec<Vec<Value>>, FunctionError> {
+        let object = match &args[0] {
+            Value::Null => return Ok(Vec::new()),
+            value => arg!(value, Jsonb).root(),
+        };
+
+        if object.kind() != JsonbKind::Object {
+            return Err(FunctionError::InvalidArgument {
+                function: "jsonb_object_keys",
+                reason: "not an object",
+            });
+        }
+
+        Ok(object
+            .entries()
+            .map(|(key, _)| vec![Value::Text(key.to_string())])
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Call = fn(&FunctionContext, &[Value]) -> Result<Value, FunctionError>;
+
+    fn call(function: Call, args: &[Value]) -> Result<Value, FunctionError> {
+        let context = FunctionContext {
+            time_zone: TimeZone::UTC,
+            now: 0,
+        };
+
+        function(&context, args)
+    }
+
+    fn text(text: &str) -> Value {
+        Value::Text(text.to_string())
+    }
+
+    fn too_large(result: Result<Value, FunctionError>) -> bool {
+        matches!(
+            result,
+            Err(FunctionError::InvalidArgument {
+                reason: "requested length too large",
+                ..
+            })
+        )
+    }
+
+    #[test]
+    fn padding() {
+        assert_eq!(
+            call(lpad, &[text("5"), Value::Int(3), text("0")]).unwrap(),
+            text("005")
+        );
+        assert_eq!(
+            call(rpad, &[text("ab"), Value::Int(5), text("xy")]).unwrap(),
+            text("abxyx")
+        );
+        assert_eq!(
+            call(lpad, &[text("é"), Value::Int(3)]).unwrap(),
+            text("  é")
+        );
+        assert_eq!(
+            call(lpad, &[text("abcd"), Value::Int(2)]).unwrap(),
+            text("ab")
+        );
+        assert_eq!(call(rpad, &[text("ab"), Value::Int(-1)]).unwrap(), text(""));
+        assert_eq!(
+            call(rpad, &[text("ab"), Value::Int(4), text("")]).unwrap(),
+            text("ab")
+        );
+
+        for function in [lpad, rpad] {
+            assert!(too_large(call(
+                function,
+                &[text("x"), Value::Int(2_000_000_000), text("ab")]
+            )));
+        }
+    }
+
+    #[test]
+    fn replacing() {
+        assert_eq!(
+            call(replace, &[text("a-b-c"), text("-"), text("+=")]).unwrap(),
+            text("a+=b+=c")
+        );
+        assert_eq!(
+            call(replace, &[text("abc"), text(""), text("x")]).unwrap(),
+            text("abc")
+        );
+
+        let large = text(&"a".repeat(1 << 20));
+        assert!(too_large(call(
+            replace,
+            &[large, text("a"), text(&"b".repeat(1 << 11))]
+        )));
+    }
+
+    #[test]
+    fn null_handling() {
+        let registry = FunctionRegistry::default();
+        let strict = |name: &str| {
+            registry
+                .overloads(name)
+                .iter()
+                .all(|function| matches!(function.kind, FunctionKind::Scalar { strict: true, .. }))
+        };
+
+        // Strict functions are never called with `NULL`, as the result is `NULL` without calling
+        // them
+        for name in [
+            "upper",
+            "substring",
+            "split_part",
+            "regexp_replace",
+            "lpad",
+            "md5",
+        ] {
+            assert!(strict(name), "{name}");
+        }
+
+        for name in ["coalesce", "nullif", "greatest", "least", "concat_ws"] {
+            assert!(!strict(name), "{name}");
+        }
+
+        let ints = |values: &[Option<i32>]| {
+            values
+                .iter()
+                .map(|value| value.map_or(Value::Null, Value::Int))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            call(coalesce, &ints(&[None, Some(2), Some(1)])).unwrap(),
+            Value::Int(2)
+        );
+        assert_eq!(call(coalesce, &ints(&[None, None])).unwrap(), Value::Null);
+        assert_eq!(
+            call(nullif, &ints(&[Some(1), None])).unwrap(),
+            Value::Int(1)
+        );
+        assert_eq!(
+            call(nullif, &ints(&[Some(1), Some(1)])).unwrap(),
+            Value::Null
+        );
+        assert_eq!(call(nullif, &ints(&[None, None])).unwrap(), Value::Null);
+        assert_eq!(
+            call(greatest, &ints(&[Some(1), None, Some(3)])).unwrap(),
+            Value::Int(3)
+        );
+        assert_eq!(
+            call(least, &ints(&[None, Some(3), Some(1)])).unwrap(),
+            Value::Int(1)
+        );
+        assert_eq!(call(greatest, &ints(&[None, None])).unwrap(), Value::Null);
+
+        assert_eq!(
+            call(
+                concat_ws,
+                &[text(","), text("a"), Value::Null, Value::Int(1)]
+            )
+            .unwrap(),
+            text("a,1")
+        );
+        assert_eq!(
+            call(concat_ws, &[Value::Null, text("a")]).unwrap(),
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn negative_lengths() {
+        let substring_of = |args: &[i32]| {
+            let mut all = vec![text("abcdef")];
+            all.extend(args.iter().map(|arg| Value::Int(*arg)));
+            call(substring, &all)
+        };
+
+        // Positions before the first character count towards the length
+        assert_eq!(substring_of(&[-1, 4]).unwrap(), text("ab"));
+        assert_eq!(substring_of(&[-5, 2]).unwrap(), text(""));
+        assert_eq!(substring_of(&[-5]).unwrap(), text("abcdef"));
+        assert_eq!(substring_of(&[3, 0]).unwrap(), text(""));
+        assert!(matches!(
+            substring_of(&[1, -1]),
+            Err(FunctionError::InvalidArgument {
+                reason: "negative substring length not allowed",
+                ..
+            })
+        ));
+
+        // Negative positions count from the last field
+        let split = |position| {
+            call(
+                split_part,
+                &[text("a,b,c"), text(","), Value::Int(position)],
+            )
+        };
+        assert_eq!(split(-1).unwrap(), text("c"));
+        assert_eq!(split(-3).unwrap(), text("a"));
+        assert_eq!(split(-4).unwrap(), text(""));
+        assert!(split(0).is_err());
+
+        for function in [lpad, rpad] {
+            assert_eq!(
+                call(function, &[text("ab"), Value::Int(i32::MIN), text("x")]).unwrap(),
+                text("")
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_regexes() {
+        assert!(check_pattern("a(b|c)*").is_ok());
+
+        for pattern in ["(", "a[", "*", "a{2,1}"] {
+            assert!(
+                matches!(
+                    check_pattern(pattern),
+                    Err(FunctionError::InvalidPattern(_))
+                ),
+                "{pattern}"
+            );
+            assert!(
+                matches!(
+                    call(regexp_match, &[text("abc"), text(pattern)]),
+                    Err(FunctionError::InvalidPattern(_))
+                ),
+                "{pattern}"
+            );
+            assert!(
+                matches!(
+                    call(regexp_replace, &[text("abc"), text(pattern), text("x")]),
+                    Err(FunctionError::InvalidPattern(_))
+                ),
+                "{pattern}"
+            );
+        }
+
+        assert!(matches!(
+            call(
+                regexp_replace,
+                &[text("abc"), text("b"), text("x"), text("q")]
+            ),
+            Err(FunctionError::InvalidArgument {
+                reason: "unknown flag, only `i`, `c` and `g` are supported",
+                ..
+            })
+        ));
+        assert!(matches!(
+            call(regexp_match, &[text("abc"), text("b"), text("g")]),
+            Err(FunctionError::InvalidArgument {
+                reason: "the `g` flag is not supported",
+                ..
+            })
+        ));
+
+        // Flags which are valid still apply
+        assert_eq!(
+            call(
+                regexp_replace,
+                &[text("aBAb"), text("b"), text("x"), text("gi")]
+            )
+            .unwrap(),
+            text("axAx")
+        );
+    }
+}
@@ -0,0 +1,1165 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeSet, HashMap},
+    fmt::{self, Display, Write},
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::sql::value::{Decimal, Value};
+
+/// How deeply arrays and objects can be nested, which keeps parsing and printing documents from
+/// overflowing the stack.
+const MAX_DEPTH: usize = 256;
+
+const NULL: u8 = 0;
+const FALSE: u8 = 1;
+const TRUE: u8 = 2;
+const NUMBER: u8 = 3;
+const STRING: u8 = 4;
+const ARRAY: u8 = 5;
+const OBJECT: u8 = 6;
+
+/// A JSON document in the binary form `JSONB` values are stored in.
+///
+/// Every value starts with a tag byte. Numbers and strings are followed by their text, up to the
+/// end of the value. Arrays and objects are followed by their number of elements or pairs and
+/// then by the end of each child, as little-endian `u32` offsets from where the children start,
+/// so that any child can be found without reading those before it. The children of an object
+/// are its keys followed by its values, and its keys are sorted by length and then bytewise so
+/// that they can be binary searched.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Jsonb {
+    bytes: Vec<u8>,
+}
+
+/// A value within a `Jsonb` document, which is read in place.
+#[derive(Clone, Copy)]
+pub struct JsonbRef<'a> {
+    bytes: &'a [u8],
+}
+
+/// The kinds of JSON values, in the order values of different kinds sort in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum JsonbKind {
+    Null,
+    String,
+    Number,
+    Boolean,
+    Array,
+    Object,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum JsonbError {
+    #[error("Invalid JSON at position {position}: {reason}")]
+    InvalidJson {
+        position: usize,
+        reason: &'static str,
+    },
+    #[error("JSON is nested more than {MAX_DEPTH} levels deep")]
+    TooDeep,
+    #[error("Invalid path `{0}`, which has to be written like `{{a,b,0}}`")]
+    InvalidPath(String),
+    #[error("Object keys must not be NULL")]
+    NullKey,
+    #[error("Object keys and values have to be given in pairs")]
+    UnpairedKey,
+}
+
+impl Jsonb {
+    /// Parses a JSON document, where later duplicates of a key in an object replace earlier ones.
+    pub fn parse(text: &str) -> Result<Jsonb, JsonbError> {
+        let mut parser = Parser { text, position: 0 };
+
+        parser.skip_whitespace();
+        let node = parser.parse_value(0)?;
+        parser.skip_whitespace();
+
+        if parser.position < text.len() {
+            return Err(parser.error("unexpected text after the document"));
+        }
+
+        Ok(node.into_jsonb())
+    }
+
+    /// The number of bytes the encoded document takes up.
+    pub fn size(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    /// The value the document consists of.
+    pub fn root(&self) -> JsonbRef<'_> {
+        JsonbRef { bytes: &self.bytes }
+    }
+
+    /// Converts a value into JSON, where numbers and booleans are kept, `NULL` becomes `null`
+    /// and every other value becomes a string of its text.
+    pub fn from_value(value: &Value) -> Jsonb {
+        Node::from_value(value).into_jsonb()
+    }
+
+    /// Builds an object from alternating keys and values, as `jsonb_build_object` does.
+    pub fn build_object(args: &[Value]) -> Result<Jsonb, JsonbError> {
+        let pairs = args.chunks_exact(2);
+
+        if !pairs.remainder().is_empty() {
+            return Err(JsonbError::UnpairedKey);
+        }
+
+        let entries = pairs
+            .map(|pair| match &pair[0] {
+                Value::Null => Err(JsonbError::NullKey),
+                key => Ok((key.to_string(), Node::from_value(&pair[1]))),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Node::Object(entries).into_jsonb())
+    }
+
+    /// Collects values into an array, as `jsonb_agg` does.
+    pub fn agg<'v>(values: impl IntoIterator<Item = &'v Value>) -> Jsonb {
+        Node::Array(values.into_iter().map(Node::from_value).collect()).into_jsonb()
+    }
+}
+
+impl<'a> JsonbRef<'a> {
+    pub fn kind(&self) -> JsonbKind {
+        match self.bytes[0] {
+            NULL => JsonbKind::Null,
+            FALSE | TRUE => JsonbKind::Boolean,
+            NUMBER => JsonbKind::Number,
+            STRING => JsonbKind::String,
+            ARRAY => JsonbKind::Array,
+            OBJECT => JsonbKind::Object,
+            tag => unreachable!("Unknown JSONB tag {}", tag),
+        }
+    }
+
+    /// The number of elements of an array or pairs of an object, which is 0 for scalars.
+    pub fn len(&self) -> usize {
+        match self.kind() {
+            JsonbKind::Array | JsonbKind::Object => self.read_u32(1),
+            _ => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The string a JSON string holds.
+    pub fn as_str(&self) -> Option<&'a str> {
+        match self.kind() {
+            JsonbKind::String => Some(self.text()),
+            _ => None,
+        }
+    }
+
+    /// The value as text, as `->>` returns it: strings without quotes, `null` as `None` and
+    /// everything else as JSON.
+    pub fn to_text(&self) -> Option<String> {
+        match self.kind() {
+            JsonbKind::Null => None,
+            JsonbKind::String => Some(self.text().to_string()),
+            _ => Some(self.to_string()),
+        }
+    }
+
+    /// The value of a key of an object.
+    pub fn get(&self, key: &str) -> Option<JsonbRef<'a>> {
+        if self.kind() != JsonbKind::Object {
+            return None;
+        }
+
+        let len = self.len();
+        let (mut low, mut high) = (0, len);
+
+        while low < high {
+            let middle = (low + high) / 2;
+
+            match compare_keys(self.child(middle), key.as_bytes()) {
+                Ordering::Less => low = middle + 1,
+                Ordering::Greater => high = middle,
+                Ordering::Equal => return Some(self.child_value(len + middle)),
+            }
+        }
+
+        None
+    }
+
+    /// An element of an array, where negative indexes count from its end.
+    pub fn index(&self, index: i64) -> Option<JsonbRef<'a>> {
+        if self.kind() != JsonbKind::Array {
+            return None;
+        }
+
+        let len = self.len() as i64;
+        let index = if index < 0 { len + index } else { index };
+
+        match (0..len).contains(&index) {
+            true => Some(self.child_value(index as usize)),
+            false => None,
+        }
+    }
+
+    /// Follows a path of keys and array indexes, as `#>` does.
+    pub fn path(&self, path: &[impl AsRef<str>]) -> Option<JsonbRef<'a>> {
+        path.iter()
+            .try_fold(*self, |value, step| match value.kind() {
+                JsonbKind::Object => value.get(step.as_ref()),
+                JsonbKind::Array => value.index(step.as_ref().parse().ok()?),
+                _ => None,
+            })
+    }
+
+    /// The elements of an array, or nothing for other values.
+    pub fn elements(&self) -> impl Iterator<Item = JsonbRef<'a>> + '_ {
+        let len = match self.kind() {
+            JsonbKind::Array => self.len(),
+            _ => 0,
+        };
+
+        (0..len).map(|i| self.child_value(i))
+    }
+
+    /// The keys and values of an object ordered by key, or nothing for other values, which is
+    /// what `jsonb_each` produces.
+    pub fn entries(&self) -> impl Iterator<Item = (&'a str, JsonbRef<'a>)> + '_ {
+        let len = match self.kind() {
+            JsonbKind::Object => self.len(),
+            _ => 0,
+        };
+
+        (0..len).map(move |i| (utf8(self.child(i)), self.child_value(len + i)))
+    }
+
+    /// Whether the value contains `other`, as `@>` decides: objects contain objects whose pairs
+    /// they contain, arrays contain arrays whose elements they contain in any order, and scalars
+    /// contain equal scalars. An array also contains the scalars which are its elements.
+    pub fn contains(&self, other: &JsonbRef<'_>) -> bool {
+        match (self.kind(), other.kind()) {
+            (JsonbKind::Array, JsonbKind::Array | JsonbKind::Object) => self.contains_nested(other),
+            (JsonbKind::Array, _) => self.elements().any(|element| element == *other),
+            _ => self.contains_nested(other),
+        }
+    }
+
+    /// Containment below the top level, where arrays no longer contain bare scalars.
+    fn contains_nested(&self, other: &JsonbRef<'_>) -> bool {
+        match (self.kind(), other.kind()) {
+            (JsonbKind::Object, JsonbKind::Object) => other.entries().all(|(key, wanted)| {
+                self.get(key)
+                    .is_some_and(|value| value.contains_nested(&wanted))
+            }),
+            (JsonbKind::Array, JsonbKind::Array) => other.elements().all(|wanted| {
+                self.elements()
+                    .any(|element| element.contains_nested(&wanted))
+            }),
+            (JsonbKind::Array | JsonbKind::Object, _) => false,
+            _ => self == other,
+        }
+    }
+
+    /// Whether the value has a key, as `?` decides, which for arrays is whether they have the key
+    /// as a string element and for strings whether they are equal to it.
+    pub fn has_key(&self, key: &str) -> bool {
+        match self.kind() {
+            JsonbKind::Object => self.get(key).is_some(),
+            JsonbKind::Array => self.elements().any(|element| element.as_str() == Some(key)),
+            JsonbKind::String => self.text() == key,
+            _ => false,
+        }
+    }
+
+    /// Whether the value has any of the keys, as `?|` decides.
+    pub fn has_any_key(&self, keys: &[impl AsRef<str>]) -> bool {
+        keys.iter().any(|key| self.has_key(key.as_ref()))
+    }
+
+    /// Whether the value has all of the keys, as `?&` decides.
+    pub fn has_all_keys(&self, keys: &[impl AsRef<str>]) -> bool {
+        keys.iter().all(|key| self.has_key(key.as_ref()))
+    }
+
+    /// Copies the value out into a document of its own.
+    pub fn to_jsonb(&self) -> Jsonb {
+        Jsonb {
+            bytes: self.bytes.to_vec(),
+        }
+    }
+
+    /// The text of a number or string.
+    fn text(&self) -> &'a str {
+        utf8(&self.bytes[1..])
+    }
+
+    fn read_u32(&self, position: usize) -> usize {
+        u32::from_le_bytes(self.bytes[position..position + 4].try_into().unwrap()) as usize
+    }
+
+    /// The bytes of a child, which for an object with `n` pairs are its keys for `0..n` and its
+    /// values for `n..2n`.
+    fn child(&self, i: usize) -> &'a [u8] {
+        let children = match self.kind() {
+            JsonbKind::Object => 2 * self.len(),
+            _ => self.len(),
+        };
+
+        let start = 5 + 4 * children;
+        let end = |i: usize| self.read_u32(5 + 4 * i);
+        let begin = if i == 0 { 0 } else { end(i - 1) };
+
+        &self.bytes[start + begin..start + end(i)]
+    }
+
+    fn child_value(&self, i: usize) -> JsonbRef<'a> {
+        JsonbRef {
+            bytes: self.child(i),
+        }
+    }
+
+    fn write_json(&self, f: &mut impl Write, depth: usize) -> fmt::Result {
+        if depth > MAX_DEPTH {
+            return Err(fmt::Error);
+        }
+
+        match self.kind() {
+            JsonbKind::Null => f.write_str("null"),
+            JsonbKind::Boolean => f.write_str(if self.bytes[0] == TRUE {
+                "true"
+            } else {
+                "false"
+            }),
+            JsonbKind::Number => f.write_str(self.text()),
+            JsonbKind::String => write_string(f, self.text()),
+            JsonbKind::Array => {
+                f.write_char('[')?;
+
+                for (i, element) in self.elements().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    element.write_json(f, depth + 1)?;
+                }
+
+                f.write_char(']')
+            }
+            JsonbKind::Object => {
+                f.write_char('{')?;
+
+                for (i, (key, value)) in self.entries().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    write_string(f, key)?;
+                    f.write_str(": ")?;
+                    value.write_json(f, depth + 1)?;
+                }
+
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+/// Values are ordered like PostgreSQL orders them: by kind first, then arrays and objects by
+/// their number of elements or pairs before their contents, and numbers by their value.
+impl Ord for JsonbRef<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.kind()
+            .cmp(&other.kind())
+            .then_with(|| match self.kind() {
+                JsonbKind::Null => Ordering::Equal,
+                JsonbKind::Boolean => self.bytes[0].cmp(&other.bytes[0]),
+                JsonbKind::Number => compare_numbers(self.text(), other.text()),
+                JsonbKind::String => self.text().cmp(other.text()),
+                JsonbKind::Array => self
+                    .len()
+                    .cmp(&other.len())
+                    .then_with(|| self.elements().cmp(other.elements())),
+                JsonbKind::Object => self
+                    .len()
+                    .cmp(&other.len())
+                    .then_with(|| self.entries().cmp(other.entries())),
+            })
+    }
+}
+
+impl PartialOrd for JsonbRef<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for JsonbRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for JsonbRef<'_> {}
+
+impl Hash for JsonbRef<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind().hash(state);
+
+        match self.kind() {
+            JsonbKind::Null => {}
+            JsonbKind::Boolean => self.bytes[0].hash(state),
+            JsonbKind::Number => match Decimal::parse(self.text()) {
+                Ok(decimal) => decimal.hash(state),
+                Err(_) => float(self.text()).to_bits().hash(state),
+            },
+            JsonbKind::String => self.text().hash(state),
+            JsonbKind::Array => self.elements().for_each(|element| element.hash(state)),
+            JsonbKind::Object => self.entries().for_each(|entry| entry.hash(state)),
+        }
+    }
+}
+
+impl Display for JsonbRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_json(f, 0)
+    }
+}
+
+impl fmt::Debug for JsonbRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "JsonbRef({})", self)
+    }
+}
+
+impl Ord for Jsonb {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.root().cmp(&other.root())
+    }
+}
+
+impl PartialOrd for Jsonb {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Jsonb {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Jsonb {}
+
+impl Hash for Jsonb {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.root().hash(state)
+    }
+}
+
+impl Display for Jsonb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.root().fmt(f)
+    }
+}
+
+impl fmt::Debug for Jsonb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Jsonb({})", self)
+    }
+}
+
+/// Parses a path or list of keys written as a text array, like `{a,b,0}` or `{"a b",c}`.
+pub fn parse_path(text: &str) -> Result<Vec<String>, JsonbError> {
+    let invalid = || JsonbError::InvalidPath(text.to_string());
+
+    let inner = text
+        .trim()
+        .strip_prefix('{')
+        .and_then(|text| text.strip_suffix('}'))
+        .ok_or_else(invalid)?;
+
+    if inner.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut steps = Vec::new();
+    let mut chars = inner.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let mut step = String::new();
+
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next().ok_or_else(invalid)? {
+                    '"' => break,
+                    '\\' => step.push(chars.next().ok_or_else(invalid)?),
+                    c => step.push(c),
+                }
+            }
+
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        } else {
+            while let Some(c) = chars.next_if(|c| !matches!(c, ',' | '"' | '{' | '}')) {
+                step.push(c);
+            }
+
+            step.truncate(step.trim_end().len());
+
+            if step.is_empty() {
+                return Err(invalid());
+            }
+        }
+
+        steps.push(step);
+
+        match chars.next() {
+            Some(',') => continue,
+            None => return Ok(steps),
+            Some(_) => return Err(invalid()),
+        }
+    }
+}
+
+/* CONTAINMENT INDEX */
+
+/// An inverted index over the documents of a `JSONB` column which finds the rows that may
+/// contain a document, like a GIN index with `jsonb_path_ops`.
+///
+/// Every scalar in a document is indexed by a hash of the keys leading to it together with its
+/// value, ignoring positions in arrays. A document can only contain another if it has every
+/// entry the other one has, so a query only has to look at rows in the posting lists of all of
+/// its entries. Hashes can collide and array positions are not indexed, so candidates still have
+/// to be checked with `JsonbRef::contains`.
+#[derive(Debug, Default)]
+pub struct ContainmentIndex {
+    postings: HashMap<u64, BTreeSet<u64>>,
+    rows: BTreeSet<u64>,
+}
+
+impl ContainmentIndex {
+    pub fn insert(&mut self, row: u64, document: JsonbRef<'_>) {
+        for entry in index_entries(document) {
+            self.postings.entry(entry).or_default().insert(row);
+        }
+
+        self.rows.insert(row);
+    }
+
+    /// Removes a row, which has to be given the document it was inserted with.
+    pub fn remove(&mut self, row: u64, document: JsonbRef<'_>) {
+        for entry in index_entries(document) {
+            if let Some(rows) = self.postings.get_mut(&entry) {
+                rows.remove(&row);
+
+                if rows.is_empty() {
+                    self.postings.remove(&entry);
+                }
+            }
+        }
+
+        self.rows.remove(&row);
+    }
+
+    /// The rows whose documents may contain `query`, which are every row when the query has no
+    /// scalars to look up, like `{}`.
+    pub fn candidates(&self, query: JsonbRef<'_>) -> BTreeSet<u64> {
+        let mut lists = Vec::new();
+
+        for entry in index_entries(query) {
+            match self.postings.get(&entry) {
+                Some(rows) => lists.push(rows),
+                None => return BTreeSet::new(),
+            }
+        }
+
+        // Intersecting starts from the shortest list, as no result can be longer
+        lists.sort_by_key(|rows| rows.len());
+
+        let Some((shortest, rest)) = lists.split_first() else {
+            return self.rows.clone();
+        };
+
+        shortest
+            .iter()
+            .filter(|row| rest.iter().all(|rows| rows.contains(row)))
+            .copied()
+            .collect()
+    }
+}
+
+/// The hashes of the path to every scalar in a document together with the scalar.
+fn index_entries(document: JsonbRef<'_>) -> BTreeSet<u64> {
+    fn walk(value: JsonbRef<'_>, path: u64, entries: &mut BTreeSet<u64>) {
+        match value.kind() {
+            JsonbKind::Object => {
+                for (key, value) in value.entries() {
+                    let mut hasher = DefaultHasher::new();
+                    (path, key).hash(&mut hasher);
+                    walk(value, hasher.finish(), entries);
+                }
+            }
+            JsonbKind::Array => value
+                .elements()
+                .for_each(|element| walk(element, path, entries)),
+            _ => {
+                let mut hasher = DefaultHasher::new();
+                (path, value).hash(&mut hasher);
+                entries.insert(hasher.finish());
+            }
+        }
+    }
+
+    let mut entries = BTreeSet::new();
+    walk(document, 0, &mut entries);
+    entries
+}
+
+/* BUILDING */
+
+/// A JSON value which is being built, before it is encoded.
+enum Node<'a> {
+    Null,
+    Boolean(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Node<'a>>),
+    Object(Vec<(String, Node<'a>)>),
+    /// A value of an existing document, which is copied as it is
+    Encoded(JsonbRef<'a>),
+}
+
+impl<'a> Node<'a> {
+    fn from_value(value: &'a Value) -> Node<'a> {
+        match value {
+            Value::Null => Node::Null,
+            Value::Boolean(value) => Node::Boolean(*value),
+            Value::SmallInt(_) | Value::Int(_) | Value::BigInt(_) | Value::Decimal(_) => {
+                Node::Number(number(&value.to_string()))
+            }
+            // JSON has no infinite numbers or NaN, so those are kept as strings
+            Value::Real(float) if float.is_finite() => Node::Number(number(&value.to_string())),
+            Value::Double(float) if float.is_finite() => Node::Number(number(&value.to_string())),
+            Value::Jsonb(jsonb) => Node::Encoded(jsonb.root()),
+            value => Node::String(value.to_string()),
+        }
+    }
+
+    fn into_jsonb(self) -> Jsonb {
+        let mut bytes = Vec::new();
+        self.encode(&mut bytes);
+        Jsonb { bytes }
+    }
+
+    fn encode(self, out: &mut Vec<u8>) {
+        match self {
+            Node::Null => out.push(NULL),
+            Node::Boolean(false) => out.push(FALSE),
+            Node::Boolean(true) => out.push(TRUE),
+            Node::Number(text) => {
+                out.push(NUMBER);
+                out.extend_from_slice(text.as_bytes());
+            }
+            Node::String(text) => {
+                out.push(STRING);
+                out.extend_from_slice(text.as_bytes());
+            }
+            Node::Array(elements) => {
+                out.push(ARRAY);
+
+                let len = elements.len();
+                let mut elements = elements.into_iter();
+                encode_children(out, len, len, |out, _| elements.next().unwrap().encode(out));
+            }
+            Node::Object(mut entries) => {
+                out.push(OBJECT);
+
+                // Sorting the reversed entries stably puts the last duplicate of a key first,
+                // which is the one that is kept
+                entries.reverse();
+                entries.sort_by(|(a, _), (b, _)| compare_keys(a.as_bytes(), b.as_bytes()));
+                entries.dedup_by(|(a, _), (b, _)| a == b);
+
+                let len = entries.len();
+                let (keys, values): (Vec<_>, Vec<_>) = entries.into_iter().unzip();
+                let mut values = values.into_iter();
+
+                encode_children(out, len, 2 * len, |out, i| match keys.get(i) {
+                    Some(key) => out.extend_from_slice(key.as_bytes()),
+                    None => values.next().unwrap().encode(out),
+                });
+            }
+            Node::Encoded(value) => out.extend_from_slice(value.bytes),
+        }
+    }
+}
+
+/// Writes the length of an array or object and the offsets of the ends of its children, which
+/// are filled in as `write_child` writes each child after them.
+fn encode_children(
+    out: &mut Vec<u8>,
+    len: usize,
+    children: usize,
+    mut write_child: impl FnMut(&mut Vec<u8>, usize),
+) {
+    out.extend_from_slice(&(len as u32).to_le_bytes());
+
+    let offsets = out.len();
+    out.resize(offsets + 4 * children, 0);
+    let start = out.len();
+
+    for i in 0..children {
+        write_child(out, i);
+
+        let end = ((out.len() - start) as u32).to_le_bytes();
+        out[offsets + 4 * i..offsets + 4 * i + 4].copy_from_slice(&end);
+    }
+}
+
+/* PARSING */
+
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, reason: &'static str) -> JsonbError {
+        JsonbError::InvalidJson {
+            position: self.position,
+            reason,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8, reason: &'static str) -> Result<(), JsonbError> {
+        match self.peek() == Some(byte) {
+            true => {
+                self.position += 1;
+                Ok(())
+            }
+            false => Err(self.error(reason)),
+        }
+    }
+
+    fn parse_value(&mut self, depth: usize) -> Result<Node<'static>, JsonbError> {
+        if depth > MAX_DEPTH {
+            return Err(JsonbError::TooDeep);
+        }
+
+        match self.peek() {
+            Some(b'{') => self.parse_object(depth),
+            Some(b'[') => self.parse_array(depth),
+            Some(b'"') => Ok(Node::String(self.parse_string()?)),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(b'a'..=b'z') => {
+                let start = self.position;
+
+                while matches!(self.peek(), Some(b'a'..=b'z')) {
+                    self.position += 1;
+                }
+
+                match &self.text[start..self.position] {
+                    "null" => Ok(Node::Null),
+                    "true" => Ok(Node::Boolean(true)),
+                    "false" => Ok(Node::Boolean(false)),
+                    _ => {
+                        self.position = start;
+                        Err(self.error("expected a value"))
+                    }
+                }
+            }
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn parse_array(&mut self, depth: usize) -> Result<Node<'static>, JsonbError> {
+        self.position += 1;
+        self.skip_whitespace();
+
+        let mut elements = Vec::new();
+
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(Node::Array(elements));
+        }
+
+        loop {
+            elements.push(self.parse_value(depth + 1)?);
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(b',') => {
+                    self.position += 1;
+                    self.skip_whitespace();
+                }
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(Node::Array(elements));
+                }
+                _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn parse_object(&mut self, depth: usize) -> Result<Node<'static>, JsonbError> {
+        self.position += 1;
+        self.skip_whitespace();
+
+        let mut entries = Vec::new();
+
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(Node::Object(entries));
+        }
+
+        loop {
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected a string key"));
+            }
+
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(b':', "expected `:`")?;
+            self.skip_whitespace();
+
+            entries.push((key, self.parse_value(depth + 1)?));
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(b',') => {
+                    self.position += 1;
+                    self.skip_whitespace();
+                }
+                Some(b'}') => {
+                    self.position += 1;
+                    return Ok(Node::Object(entries));
+                }
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonbError> {
+        self.position += 1;
+
+        let mut string = String::new();
+
+        loop {
+            let rest = &self.text[self.position..];
+            let Some(c) = rest.chars().next() else {
+                return Err(self.error("unterminated string"));
+            };
+
+            match c {
+                '"' => {
+                    self.position += 1;
+                    return Ok(string);
+                }
+                '\\' => {
+                    self.position += 1;
+                    string.push(self.parse_escape()?);
+                }
+                c if c.is_control() && c.is_ascii() => {
+                    return Err(self.error("control characters in strings have to be escaped"))
+                }
+                c => {
+                    self.position += c.len_utf8();
+                    string.push(c);
+                }
+            }
+        }
+    }
+
+    fn parse_escape(&mut self) -> Result<char, JsonbError> {
+        let escaped = match self.peek() {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                self.position += 1;
+                let high = self.parse_hex()?;
+
+                // Characters outside the basic plane are escaped as a surrogate pair
+                let code = if (0xd800..0xdc00).contains(&high) {
+                    if !self.text[self.position..].starts_with("\\u") {
+                        return Err(self.error("unpaired surrogate"));
+                    }
+
+                    self.position += 2;
+                    let low = self.parse_hex()?;
+
+                    if !(0xdc00..0xe000).contains(&low) {
+                        return Err(self.error("unpaired surrogate"));
+                    }
+
+                    0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                } else {
+                    high
+                };
+
+                return match char::from_u32(code) {
+                    Some('\0') => Err(self.error("`\\u0000` is not allowed")),
+                    Some(c) => Ok(c),
+                    None => Err(self.error("unpaired surrogate")),
+                };
+            }
+            _ => return Err(self.error("invalid escape")),
+        };
+
+        self.position += 1;
+        Ok(escaped)
+    }
+
+    fn parse_hex(&mut self) -> Result<u32, JsonbError> {
+        let digits = self
+            .text
+            .get(self.position..self.position + 4)
+            .filter(|digits| digits.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("expected 4 hexadecimal digits"))?;
+
+        self.position += 4;
+        Ok(u32::from_str_radix(digits, 16).unwrap())
+    }
+
+    /// Parses `-?(0|[1-9][0-9]*)(.[0-9]+)?([eE][+-]?[0-9]+)?`.
+    fn parse_number(&mut self) -> Result<Node<'static>, JsonbError> {
+        let start = self.position;
+
+        let digits = |parser: &mut Self| {
+            let start = parser.position;
+
+            while matches!(parser.peek(), Some(b'0'..=b'9')) {
+                parser.position += 1;
+            }
+
+            match parser.position > start {
+                true => Ok(&parser.text[start..parser.position]),
+                false => Err(parser.error("expected a digit")),
+            }
+        };
+
+        if self.peek() == Some(b'-') {
+            self.position += 1;
+        }
+
+        let integer = digits(self)?;
+
+        if integer.len() > 1 && integer.starts_with('0') {
+            return Err(self.error("numbers must not have leading zeros"));
+        }
+
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            digits(self)?;
+        }
+
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.position += 1;
+
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.position += 1;
+            }
+
+            digits(self)?;
+        }
+
+        Ok(Node::Number(number(&self.text[start..self.position])))
+    }
+}
+
+/* HELPERS */
+
+/// The text a number is stored as, which is that of the exact decimal it is where it fits into
+/// one, so that `1e3` is stored as `1000`.
+fn number(text: &str) -> String {
+    match Decimal::parse(text) {
+        Ok(decimal) => decimal.to_string(),
+        Err(_) => text.to_string(),
+    }
+}
+
+fn compare_numbers(a: &str, b: &str) -> Ordering {
+    match (Decimal::parse(a), Decimal::parse(b)) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => float(a).total_cmp(&float(b)),
+    }
+}
+
+fn float(text: &str) -> f64 {
+    text.parse().unwrap_or(f64::NAN)
+}
+
+/// The order keys of an object are stored in, shortest first, which is how PostgreSQL orders
+/// them.
+fn compare_keys(a: &[u8], b: &[u8]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+fn utf8(bytes: &[u8]) -> &str {
+    std::str::from_utf8(bytes).expect("JSONB strings are valid UTF-8")
+}
+
+fn write_string(f: &mut impl Write, string: &str) -> fmt::Result {
+    f.write_char('"')?;
+
+    for c in string.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\u{8}' => f.write_str("\\b")?,
+            '\u{c}' => f.write_str("\\f")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() && c.is_ascii() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+
+    f.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jsonb(text: &str) -> Jsonb {
+        Jsonb::parse(text).unwrap()
+    }
+
+    fn contains(document: &str, query: &str) -> bool {
+        jsonb(document).root().contains(&jsonb(query).root())
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(
+            jsonb(r#" {"b": [1, 2.50, "x"], "a": null, "b": true} "#).to_string(),
+            r#"{"a": null, "b": true}"#
+        );
+        assert_eq!(jsonb(r#""é\n""#).root().as_str(), Some("é\n"));
+
+        assert!(matches!(
+            Jsonb::parse("[1,]"),
+            Err(JsonbError::InvalidJson { .. })
+        ));
+        assert!(matches!(
+            Jsonb::parse("{} {}"),
+            Err(JsonbError::InvalidJson { .. })
+        ));
+        assert_eq!(
+            Jsonb::parse(&"[".repeat(MAX_DEPTH + 1)),
+            Err(JsonbError::TooDeep)
+        );
+    }
+
+    #[test]
+    fn containment() {
+        assert!(contains(
+            r#"{"a": 1, "b": {"c": [1, 2]}}"#,
+            r#"{"b": {"c": [2]}}"#
+        ));
+        assert!(contains(r#"{"a": 1}"#, "{}"));
+        assert!(contains("[1, [2, 3]]", "[[3]]"));
+        assert!(contains("[1, 1, 2]", "[2, 1, 2]"));
+        assert!(contains(r#"["a", "b"]"#, r#""a""#));
+        assert!(contains("1", "1"));
+
+        assert!(!contains(r#"{"a": 1}"#, r#"{"a": 2}"#));
+        assert!(!contains(r#"{"a": {"b": 1}}"#, r#"{"b": 1}"#));
+        assert!(!contains("[1, [2, 3]]", "[3]"));
+        // Only arrays at the top level contain bare scalars
+        assert!(!contains(r#"{"a": [1]}"#, r#"{"a": 1}"#));
+        assert!(!contains(r#""a""#, r#"["a"]"#));
+        // Numbers are compared by value, not by how they are written
+        assert!(contains("[1.50]", "1.5"));
+    }
+
+    #[test]
+    fn keys() {
+        let object = jsonb(r#"{"a": 1, "bb": 2}"#);
+        assert!(object.root().has_key("bb"));
+        assert!(!object.root().has_key("b"));
+        assert!(object.root().has_any_key(&["x", "a"]));
+        assert!(!object.root().has_all_keys(&["x", "a"]));
+
+        assert!(jsonb(r#"["a", 1]"#).root().has_key("a"));
+        assert!(!jsonb(r#"["a", 1]"#).root().has_key("1"));
+    }
+
+    #[test]
+    fn containment_index() {
+        let documents = [
+            r#"{"tags": ["x", "y"], "n": 1}"#,
+            r#"{"tags": ["y"], "n": 2}"#,
+            r#"{"n": 1, "other": {"n": 2}}"#,
+            r#"[1, 2, {"n": 1}]"#,
+            "{}",
+        ]
+        .map(jsonb);
+
+        let mut index = ContainmentIndex::default();
+
+        for (row, document) in documents.iter().enumerate() {
+            index.insert(row as u64, document.root());
+        }
+
+        let queries = [
+            r#"{"tags": ["y"]}"#,
+            r#"{"n": 1}"#,
+            r#"{"n": 2}"#,
+            r#"{"other": {"n": 2}}"#,
+            "[1]",
+            "2",
+            "{}",
+            r#"{"missing": true}"#,
+        ];
+
+        // Every row containing a query is a candidate for it
+        for query in queries.map(jsonb) {
+            let candidates = index.candidates(query.root());
+
+            for (row, document) in documents.iter().enumerate() {
+                if document.root().contains(&query.root()) {
+                    assert!(candidates.contains(&(row as u64)), "{query} in {document}");
+                }
+            }
+        }
+
+        assert_eq!(
+            index.candidates(jsonb(r#"{"tags": ["y"]}"#).root()),
+            BTreeSet::from([0, 1])
+        );
+        assert_eq!(index.candidates(jsonb("{}").root()).len(), documents.len());
+
+        index.remove(0, documents[0].root());
+        assert_eq!(
+            index.candidates(jsonb(r#"{"tags": ["y"]}"#).root()),
+            BTreeSet::from([1])
+        );
+        assert_eq!(
+            index.candidates(jsonb("{}").root()).len(),
+            documents.len() - 1
+        );
+    }
+}
@@ -0,0 +1,636 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+/// Whether a keyword can also be used as the name of a column, table or other object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordClass {
+    /// Can only ever be used as a keyword, unless quoted
+    Reserved,
+    /// Can be used as an identifier wherever the grammar is not ambiguous
+    NonReserved,
+}
+
+/// Defines the `Keyword` enum along with a table of every keyword's text and class.
+///
+/// The entries must be sorted by their text, which is checked at compile time, so that they can
+/// be binary searched and so that the enum discriminant of a keyword is its index in the table.
+macro_rules! keywords {
+    ($($variant:ident => $text:literal, $class:ident;)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Keyword {
+            $($variant,)*
+        }
+
+        const KEYWORDS: &[(&str, Keyword, KeywordClass)] = &[
+            $(($text, Keyword::$variant, KeywordClass::$class),)*
+        ];
+    };
+}
+
+keywords! {
+    Abort => "ABORT", NonReserved;
+    Absent => "ABSENT", NonReserved;
+    Absolute => "ABSOLUTE", NonReserved;
+    Access => "ACCESS", NonReserved;
+    Action => "ACTION", NonReserved;
+    Add => "ADD", NonReserved;
+    Admin => "ADMIN", NonReserved;
+    After => "AFTER", NonReserved;
+    Aggregate => "AGGREGATE", NonReserved;
+    All => "ALL", Reserved;
+    Also => "ALSO", NonReserved;
+    Alter => "ALTER", NonReserved;
+    Always => "ALWAYS", NonReserved;
+    Analyse => "ANALYSE", Reserved;
+    Analyze => "ANALYZE", Reserved;
+    And => "AND", Reserved;
+    Any => "ANY", Reserved;
+    Array => "ARRAY", Reserved;
+    As => "AS", Reserved;
+    Asc => "ASC", Reserved;
+    Asensitive => "ASENSITIVE", NonReserved;
+    Assertion => "ASSERTION", NonReserved;
+    Assignment => "ASSIGNMENT", NonReserved;
+    Asymmetric => "ASYMMETRIC", Reserved;
+    At => "AT", NonReserved;
+    Atomic => "ATOMIC", NonReserved;
+    Attach => "ATTACH", NonReserved;
+    Attribute => "ATTRIBUTE", NonReserved;
+    Authorization => "AUTHORIZATION", Reserved;
+    Backward => "BACKWARD", NonReserved;
+    Before => "BEFORE", NonReserved;
+    Begin => "BEGIN", NonReserved;
+    Between => "BETWEEN", NonReserved;
+    Bigint => "BIGINT", NonReserved;
+    Binary => "BINARY", Reserved;
+    Bit => "BIT", NonReserved;
+    Boolean => "BOOLEAN", NonReserved;
+    Both => "BOTH", Reserved;
+    Breadth => "BREADTH", NonReserved;
+    By => "BY", NonReserved;
+    Cache => "CACHE", NonReserved;
+    Call => "CALL", NonReserved;
+    Called => "CALLED", NonReserved;
+    Cascade => "CASCADE", NonReserved;
+    Cascaded => "CASCADED", NonReserved;
+    Case => "CASE", Reserved;
+    Cast => "CAST", Reserved;
+    Catalog => "CATALOG", NonReserved;
+    Chain => "CHAIN", NonReserved;
+    Char => "CHAR", NonReserved;
+    Character => "CHARACTER", NonReserved;
+    Characteristics => "CHARACTERISTICS", NonReserved;
+    Check => "CHECK", Reserved;
+    Checkpoint => "CHECKPOINT", NonReserved;
+    Class => "CLASS", NonReserved;
+    Close => "CLOSE", NonReserved;
+    Cluster => "CLUSTER", NonReserved;
+    Coalesce => "COALESCE", NonReserved;
+    Collate => "COLLATE", Reserved;
+    Collation => "COLLATION", Reserved;
+    Column => "COLUMN", Reserved;
+    Columns => "COLUMNS", NonReserved;
+    Comment => "COMMENT", NonReserved;
+    Comments => "COMMENTS", NonReserved;
+    Commit => "COMMIT", NonReserved;
+    Committed => "COMMITTED", NonReserved;
+    Compression => "COMPRESSION", NonReserved;
+    Concurrently => "CONCURRENTLY", Reserved;
+    Configuration => "CONFIGURATION", NonReserved;
+    Conflict => "CONFLICT", NonReserved;
+    Connection => "CONNECTION", NonReserved;
+    Constraint => "CONSTRAINT", Reserved;
+    Constraints => "CONSTRAINTS", NonReserved;
+    Content => "CONTENT", NonReserved;
+    Continue => "CONTINUE", NonReserved;
+    Conversion => "CONVERSION", NonReserved;
+    Copy => "COPY", NonReserved;
+    Cost => "COST", NonReserved;
+    Create => "CREATE", Reserved;
+    Cross => "CROSS", Reserved;
+    Csv => "CSV", NonReserved;
+    Cube => "CUBE", NonReserved;
+    Current => "CURRENT", NonReserved;
+    CurrentCatalog => "CURRENT_CATALOG", Reserved;
+    CurrentDate => "CURRENT_DATE", Reserved;
+    CurrentRole => "CURRENT_ROLE", Reserved;
+    CurrentSchema => "CURRENT_SCHEMA", Reserved;
+    CurrentTime => "CURRENT_TIME", Reserved;
+    CurrentTimestamp => "CURRENT_TIMESTAMP", Reserved;
+    CurrentUser => "CURRENT_USER", Reserved;
+    Cursor => "CURSOR", NonReserved;
+    Cycle => "CYCLE", NonReserved;
+    Data => "DATA", NonReserved;
+    Database => "DATABASE", NonReserved;
+    Databases => "DATABASES", NonReserved;
+    Day => "DAY", NonReserved;
+    Deallocate => "DEALLOCATE", NonReserved;
+    Dec => "DEC", NonReserved;
+    Decimal => "DECIMAL", NonReserved;
+    Declare => "DECLARE", NonReserved;
+    Default => "DEFAULT", Reserved;
+    Defaults => "DEFAULTS", NonReserved;
+    Deferrable => "DEFERRABLE", Reserved;
+    Deferred => "DEFERRED", NonReserved;
+    Definer => "DEFINER", NonReserved;
+    Delete => "DELETE", NonReserved;
+    Delimiter => "DELIMITER", NonReserved;
+    Delimiters => "DELIMITERS", NonReserved;
+    Depends => "DEPENDS", NonReserved;
+    Depth => "DEPTH", NonReserved;
+    Desc => "DESC", Reserved;
+    Describe => "DESCRIBE", NonReserved;
+    Detach => "DETACH", NonReserved;
+    Dictionary => "DICTIONARY", NonReserved;
+    Disable => "DISABLE", NonReserved;
+    Discard => "DISCARD", NonReserved;
+    Distinct => "DISTINCT", Reserved;
+    Do => "DO", Reserved;
+    Document => "DOCUMENT", NonReserved;
+    Domain => "DOMAIN", NonReserved;
+    Double => "DOUBLE", NonReserved;
+    Drop => "DROP", NonReserved;
+    Each => "EACH", NonReserved;
+    Else => "ELSE", Reserved;
+    Enable => "ENABLE", NonReserved;
+    Encoding => "ENCODING", NonReserved;
+    Encrypted => "ENCRYPTED", NonReserved;
+    End => "END", Reserved;
+    Enum => "ENUM", NonReserved;
+    Escape => "ESCAPE", NonReserved;
+    Event => "EVENT", NonReserved;
+    Except => "EXCEPT", Reserved;
+    Exclude => "EXCLUDE", NonReserved;
+    Excluding => "EXCLUDING", NonReserved;
+    Exclusive => "EXCLUSIVE", NonReserved;
+    Execute => "EXECUTE", NonReserved;
+    Exists => "EXISTS", NonReserved;
+    Explain => "EXPLAIN", NonReserved;
+    Expression => "EXPRESSION", NonReserved;
+    Extension => "EXTENSION", NonReserved;
+    External => "EXTERNAL", NonReserved;
+    Extract => "EXTRACT", NonReserved;
+    False => "FALSE", Reserved;
+    Family => "FAMILY", NonReserved;
+    Fetch => "FETCH", Reserved;
+    Filter => "FILTER", NonReserved;
+    Finalize => "FINALIZE", NonReserved;
+    First => "FIRST", NonReserved;
+    Float => "FLOAT", NonReserved;
+    Following => "FOLLOWING", NonReserved;
+    For => "FOR", Reserved;
+    Force => "FORCE", NonReserved;
+    Foreign => "FOREIGN", Reserved;
+    Format => "FORMAT", NonReserved;
+    Forward => "FORWARD", NonReserved;
+    Freeze => "FREEZE", Reserved;
+    From => "FROM", Reserved;
+    Full => "FULL", Reserved;
+    Function => "FUNCTION", NonReserved;
+    Functions => "FUNCTIONS", NonReserved;
+    Generated => "GENERATED", NonReserved;
+    Global => "GLOBAL", NonReserved;
+    Grant => "GRANT", Reserved;
+    Granted => "GRANTED", NonReserved;
+    Greatest => "GREATEST", NonReserved;
+    Group => "GROUP", Reserved;
+    Grouping => "GROUPING", NonReserved;
+    Groups => "GROUPS", NonReserved;
+    Handler => "HANDLER", NonReserved;
+    Having => "HAVING", Reserved;
+    Header => "HEADER", NonReserved;
+    Hold => "HOLD", NonReserved;
+    Hour => "HOUR", NonReserved;
+    Identity => "IDENTITY", NonReserved;
+    If => "IF", NonReserved;
+    Ilike => "ILIKE", Reserved;
+    Immediate => "IMMEDIATE", NonReserved;
+    Immutable => "IMMUTABLE", NonReserved;
+    Implicit => "IMPLICIT", NonReserved;
+    Import => "IMPORT", NonReserved;
+    In => "IN", Reserved;
+    Include => "INCLUDE", NonReserved;
+    Including => "INCLUDING", NonReserved;
+    Increment => "INCREMENT", NonReserved;
+    Indent => "INDENT", NonReserved;
+    Index => "INDEX", NonReserved;
+    Indexes => "INDEXES", NonReserved;
+    Inherit => "INHERIT", NonReserved;
+    Inherits => "INHERITS", NonReserved;
+    Initially => "INITIALLY", Reserved;
+    Inline => "INLINE", NonReserved;
+    Inner => "INNER", Reserved;
+    Inout => "INOUT", NonReserved;
+    Input => "INPUT", NonReserved;
+    Insensitive => "INSENSITIVE", NonReserved;
+    Insert => "INSERT", NonReserved;
+    Instead => "INSTEAD", NonReserved;
+    Int => "INT", NonReserved;
+    Integer => "INTEGER", NonReserved;
+    Intersect => "INTERSECT", Reserved;
+    Interval => "INTERVAL", NonReserved;
+    Into => "INTO", Reserved;
+    Invoker => "INVOKER", NonReserved;
+    Is => "IS", Reserved;
+    Isnull => "ISNULL", Reserved;
+    Isolation => "ISOLATION", NonReserved;
+    Join => "JOIN", Reserved;
+    Json => "JSON", NonReserved;
+    JsonArray => "JSON_ARRAY", NonReserved;
+    JsonArrayagg => "JSON_ARRAYAGG", NonReserved;
+    JsonObject => "JSON_OBJECT", NonReserved;
+    JsonObjectagg => "JSON_OBJECTAGG", NonReserved;
+    Key => "KEY", NonReserved;
+    Keys => "KEYS", NonReserved;
+    Label => "LABEL", NonReserved;
+    Language => "LANGUAGE", NonReserved;
+    Large => "LARGE", NonReserved;
+    Last => "LAST", NonReserved;
+    Lateral => "LATERAL", Reserved;
+    Leading => "LEADING", Reserved;
+    Leakproof => "LEAKPROOF", NonReserved;
+    Least => "LEAST", NonReserved;
+    Left => "LEFT", Reserved;
+    Level => "LEVEL", NonReserved;
+    Like => "LIKE", Reserved;
+    Limit => "LIMIT", NonReserved;
+    Listen => "LISTEN", NonReserved;
+    Load => "LOAD", NonReserved;
+    Local => "LOCAL", NonReserved;
+    Localtime => "LOCALTIME", Reserved;
+    Localtimestamp => "LOCALTIMESTAMP", Reserved;
+    Location => "LOCATION", NonReserved;
+    Lock => "LOCK", NonReserved;
+    Locked => "LOCKED", NonReserved;
+    Logged => "LOGGED", NonReserved;
+    Mapping => "MAPPING", NonReserved;
+    Match => "MATCH", NonReserved;
+    Matched => "MATCHED", NonReserved;
+    Materialized => "MATERIALIZED", NonReserved;
+    Maxvalue => "MAXVALUE", NonReserved;
+    Merge => "MERGE", NonReserved;
+    Method => "METHOD", NonReserved;
+    Minute => "MINUTE", NonReserved;
+    Minvalue => "MINVALUE", NonReserved;
+    Mode => "MODE", NonReserved;
+    Month => "MONTH", NonReserved;
+    Move => "MOVE", NonReserved;
+    Name => "NAME", NonReserved;
+    Names => "NAMES", NonReserved;
+    National => "NATIONAL", NonReserved;
+    Natural => "NATURAL", Reserved;
+    Nchar => "NCHAR", NonReserved;
+    New => "NEW", NonReserved;
+    Next => "NEXT", NonReserved;
+    Nfc => "NFC", NonReserved;
+    Nfd => "NFD", NonReserved;
+    Nfkc => "NFKC", NonReserved;
+    Nfkd => "NFKD", NonReserved;
+    No => "NO", NonReserved;
+    None => "NONE", NonReserved;
+    Normalize => "NORMALIZE", NonReserved;
+    Normalized => "NORMALIZED", NonReserved;
+    Not => "NOT", Reserved;
+    Nothing => "NOTHING", NonReserved;
+    Notify => "NOTIFY", NonReserved;
+    Notnull => "NOTNULL", Reserved;
+    Nowait => "NOWAIT", NonReserved;
+    Null => "NULL", Reserved;
+    Nullif => "NULLIF", NonReserved;
+    Nulls => "NULLS", NonReserved;
+    Numeric => "NUMERIC", NonReserved;
+    Object => "OBJECT", NonReserved;
+    Of => "OF", NonReserved;
+    Off => "OFF", NonReserved;
+    Offset => "OFFSET", NonReserved;
+    Oids => "OIDS", NonReserved;
+    Old => "OLD", NonReserved;
+    On => "ON", Reserved;
+    Only => "ONLY", Reserved;
+    Operator => "OPERATOR", NonReserved;
+    Option => "OPTION", NonReserved;
+    Options => "OPTIONS", NonReserved;
+    Or => "OR", Reserved;
+    Order => "ORDER", Reserved;
+    Ordinality => "ORDINALITY", NonReserved;
+    Others => "OTHERS", NonReserved;
+    Out => "OUT", NonReserved;
+    Outer => "OUTER", Reserved;
+    Over => "OVER", NonReserved;
+    Overlaps => "OVERLAPS", Reserved;
+    Overlay => "OVERLAY", NonReserved;
+    Overriding => "OVERRIDING", NonReserved;
+    Owned => "OWNED", NonReserved;
+    Owner => "OWNER", NonReserved;
+    Parallel => "PARALLEL", NonReserved;
+    Parameter => "PARAMETER", NonReserved;
+    Parser => "PARSER", NonReserved;
+    Partial => "PARTIAL", NonReserved;
+    Partition => "PARTITION", NonReserved;
+    Passing => "PASSING", NonReserved;
+    Password => "PASSWORD", NonReserved;
+    Placing => "PLACING", Reserved;
+    Plans => "PLANS", NonReserved;
+    Policy => "POLICY", NonReserved;
+    Position => "POSITION", NonReserved;
+    Preceding => "PRECEDING", NonReserved;
+    Precision => "PRECISION", NonReserved;
+    Prepare => "PREPARE", NonReserved;
+    Prepared => "PREPARED", NonReserved;
+    Preserve => "PRESERVE", NonReserved;
+    Primary => "PRIMARY", Reserved;
+    Prior => "PRIOR", NonReserved;
+    Privileges => "PRIVILEGES", NonReserved;
+    Procedural => "PROCEDURAL", NonReserved;
+    Procedure => "PROCEDURE", NonReserved;
+    Procedures => "PROCEDURES", NonReserved;
+    Program => "PROGRAM", NonReserved;
+    Publication => "PUBLICATION", NonReserved;
+    Quote => "QUOTE", NonReserved;
+    Range => "RANGE", NonReserved;
+    Read => "READ", NonReserved;
+    Real => "REAL", NonReserved;
+    Reassign => "REASSIGN", NonReserved;
+    Recheck => "RECHECK", NonReserved;
+    Recursive => "RECURSIVE", NonReserved;
+    Ref => "REF", NonReserved;
+    References => "REFERENCES", Reserved;
+    Referencing => "REFERENCING", NonReserved;
+    Refresh => "REFRESH", NonReserved;
+    Reindex => "REINDEX", NonReserved;
+    Relative => "RELATIVE", NonReserved;
+    Release => "RELEASE", NonReserved;
+    Rename => "RENAME", NonReserved;
+    Repeatable => "REPEATABLE", NonReserved;
+    Replace => "REPLACE", NonReserved;
+    Replica => "REPLICA", NonReserved;
+    Reset => "RESET", NonReserved;
+    Restart => "RESTART", NonReserved;
+    Restrict => "RESTRICT", NonReserved;
+    Return => "RETURN", NonReserved;
+    Returning => "RETURNING", Reserved;
+    Returns => "RETURNS", NonReserved;
+    Revoke => "REVOKE", NonReserved;
+    Right => "RIGHT", Reserved;
+    Role => "ROLE", NonReserved;
+    Rollback => "ROLLBACK", NonReserved;
+    Rollup => "ROLLUP", NonReserved;
+    Routine => "ROUTINE", NonReserved;
+    Routines => "ROUTINES", NonReserved;
+    Row => "ROW", NonReserved;
+    Rows => "ROWS", NonReserved;
+    Rule => "RULE", NonReserved;
+    Savepoint => "SAVEPOINT", NonReserved;
+    Scalar => "SCALAR", NonReserved;
+    Schema => "SCHEMA", NonReserved;
+    Schemas => "SCHEMAS", NonReserved;
+    Scroll => "SCROLL", NonReserved;
+    Search => "SEARCH", NonReserved;
+    Second => "SECOND", NonReserved;
+    Security => "SECURITY", NonReserved;
+    Select => "SELECT", Reserved;
+    Sequence => "SEQUENCE", NonReserved;
+    Sequences => "SEQUENCES", NonReserved;
+    Serializable => "SERIALIZABLE", NonReserved;
+    Server => "SERVER", NonReserved;
+    Session => "SESSION", NonReserved;
+    SessionUser => "SESSION_USER", Reserved;
+    Set => "SET", NonReserved;
+    Setof => "SETOF", NonReserved;
+    Sets => "SETS", NonReserved;
+    Share => "SHARE", NonReserved;
+    Show => "SHOW", NonReserved;
+    Similar => "SIMILAR", Reserved;
+    Simple => "SIMPLE", NonReserved;
+    Skip => "SKIP", NonReserved;
+    Smallint => "SMALLINT", NonReserved;
+    Snapshot => "SNAPSHOT", NonReserved;
+    Some => "SOME", Reserved;
+    Sql => "SQL", NonReserved;
+    Stable => "STABLE", NonReserved;
+    Standalone => "STANDALONE", NonReserved;
+    Start => "START", NonReserved;
+    Statement => "STATEMENT", NonReserved;
+    Statistics => "STATISTICS", NonReserved;
+    Stdin => "STDIN", NonReserved;
+    Stdout => "STDOUT", NonReserved;
+    Storage => "STORAGE", NonReserved;
+    Stored => "STORED", NonReserved;
+    Strict => "STRICT", NonReserved;
+    Strip => "STRIP", NonReserved;
+    Subscription => "SUBSCRIPTION", NonReserved;
+    Substring => "SUBSTRING", NonReserved;
+    Support => "SUPPORT", NonReserved;
+    Symmetric => "SYMMETRIC", Reserved;
+    Sysid => "SYSID", NonReserved;
+    System => "SYSTEM", NonReserved;
+    SystemUser => "SYSTEM_USER", Reserved;
+    Table => "TABLE", Reserved;
+    Tables => "TABLES", NonReserved;
+    Tablesample => "TABLESAMPLE", Reserved;
+    Tablespace => "TABLESPACE", NonReserved;
+    Temp => "TEMP", NonReserved;
+    Template => "TEMPLATE", NonReserved;
+    Temporary => "TEMPORARY", NonReserved;
+    Text => "TEXT", NonReserved;
+    Then => "THEN", Reserved;
+    Ties => "TIES", NonReserved;
+    Time => "TIME", NonReserved;
+    Timestamp => "TIMESTAMP", NonReserved;
+    To => "TO", Reserved;
+    Trailing => "TRAILING", Reserved;
+    Transaction => "TRANSACTION", NonReserved;
+    Transform => "TRANSFORM", NonReserved;
+    Treat => "TREAT", NonReserved;
+    Trigger => "TRIGGER", NonReserved;
+    Trim => "TRIM", NonReserved;
+    True => "TRUE", Reserved;
+    Truncate => "TRUNCATE", NonReserved;
+    Trusted => "TRUSTED", NonReserved;
+    Type => "TYPE", NonReserved;
+    Types => "TYPES", NonReserved;
+    Uescape => "UESCAPE", NonReserved;
+    Unbounded => "UNBOUNDED", NonReserved;
+    Uncommitted => "UNCOMMITTED", NonReserved;
+    Unencrypted => "UNENCRYPTED", NonReserved;
+    Union => "UNION", Reserved;
+    Unique => "UNIQUE", Reserved;
+    Unknown => "UNKNOWN", NonReserved;
+    Unlisten => "UNLISTEN", NonReserved;
+    Unlogged => "UNLOGGED", NonReserved;
+    Until => "UNTIL", NonReserved;
+    Update => "UPDATE", NonReserved;
+    Use => "USE", NonReserved;
+    User => "USER", Reserved;
+    Using => "USING", Reserved;
+    Vacuum => "VACUUM", NonReserved;
+    Valid => "VALID", NonReserved;
+    Validate => "VALIDATE", NonReserved;
+    Validator => "VALIDATOR", NonReserved;
+    Value => "VALUE", NonReserved;
+    Values => "VALUES", NonReserved;
+    Varchar => "VARCHAR", NonReserved;
+    Variadic => "VARIADIC", Reserved;
+    Varying => "VARYING", NonReserved;
+    Verbose => "VERBOSE", Reserved;
+    Version => "VERSION", NonReserved;
+    View => "VIEW", NonReserved;
+    Views => "VIEWS", NonReserved;
+    Volatile => "VOLATILE", NonReserved;
+    When => "WHEN", Reserved;
+    Where => "WHERE", Reserved;
+    Whitespace => "WHITESPACE", NonReserved;
+    Window => "WINDOW", Reserved;
+    With => "WITH", Reserved;
+    Within => "WITHIN", NonReserved;
+    Without => "WITHOUT", NonReserved;
+    Work => "WORK", NonReserved;
+    Wrapper => "WRAPPER", NonReserved;
+    Write => "WRITE", NonReserved;
+    Xml => "XML", NonReserved;
+    Xmlattributes => "XMLATTRIBUTES", NonReserved;
+    Xmlconcat => "XMLCONCAT", NonReserved;
+    Xmlelement => "XMLELEMENT", NonReserved;
+    Xmlexists => "XMLEXISTS", NonReserved;
+    Xmlforest => "XMLFOREST", NonReserved;
+    Xmlnamespaces => "XMLNAMESPACES", NonReserved;
+    Xmlparse => "XMLPARSE", NonReserved;
+    Xmlpi => "XMLPI", NonReserved;
+    Xmlroot => "XMLROOT", NonReserved;
+    Xmlserialize => "XMLSERIALIZE", NonReserved;
+    Xmltable => "XMLTABLE", NonReserved;
+    Year => "YEAR", NonReserved;
+    Yes => "YES", NonReserved;
+    Zone => "ZONE", NonReserved;
+}
+
+const _: () = assert!(is_sorted(KEYWORDS), "The keyword table must be sorted");
+
+pub struct IllegalEnumValueError;
+
+impl Keyword {
+    /// Finds the keyword spelled by `text`, ignoring ASCII case, without allocating.
+    pub fn lookup(text: &str) -> Option<Self> {
+        KEYWORDS
+            .binary_search_by(|(keyword, _, _)| cmp_ignore_ascii_case(keyword, text))
+            .ok()
+            .map(|index| KEYWORDS[index].1)
+    }
+
+    /// Every keyword, in alphabetical order.
+    pub fn all() -> impl Iterator<Item = Self> {
+        KEYWORDS.iter().map(|(_, keyword, _)| *keyword)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        KEYWORDS[*self as usize].0
+    }
+
+    pub fn class(&self) -> KeywordClass {
+        KEYWORDS[*self as usize].2
+    }
+
+    pub fn is_reserved(&self) -> bool {
+        self.class() == KeywordClass::Reserved
+    }
+}
+
+impl Display for Keyword {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Keyword {
+    type Err = IllegalEnumValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::lookup(s).ok_or(IllegalEnumValueError)
+    }
+}
+
+/// Compares an uppercase keyword to arbitrary text as if the text was uppercased too.
+fn cmp_ignore_ascii_case(keyword: &str, text: &str) -> Ordering {
+    keyword
+        .bytes()
+        .cmp(text.bytes().map(|byte| byte.to_ascii_uppercase()))
+}
+
+const fn is_sorted(keywords: &[(&str, Keyword, KeywordClass)]) -> bool {
+    let mut i = 1;
+
+    while i < keywords.len() {
+        if !is_less(keywords[i - 1].0.as_bytes(), keywords[i].0.as_bytes()) {
+            return false;
+        }
+
+        i += 1;
+    }
+
+    true
+}
+
+const fn is_less(a: &[u8], b: &[u8]) -> bool {
+    let mut i = 0;
+
+    while i < a.len() && i < b.len() {
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+
+        i += 1;
+    }
+
+    a.len() < b.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup() {
+        assert_eq!(Keyword::lookup("SELECT"), Some(Keyword::Select));
+        assert_eq!(Keyword::lookup("select"), Some(Keyword::Select));
+        assert_eq!(Keyword::lookup("sElEcT"), Some(Keyword::Select));
+        assert_eq!(Keyword::lookup("current_user"), Some(Keyword::CurrentUser));
+        assert_eq!(Keyword::lookup("abort"), Some(Keyword::Abort));
+        assert_eq!(Keyword::lookup("zone"), Some(Keyword::Zone));
+
+        for text in [
+            "",
+            "selec",
+            "selects",
+            "users",
+            "current user",
+            "ſelect",
+            "AAA",
+            "ZZZ",
+        ] {
+            assert_eq!(Keyword::lookup(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn every_keyword() {
+        let keywords = Keyword::all().collect::<Vec<_>>();
+        assert_eq!(keywords.len(), KEYWORDS.len());
+
+        for (index, keyword) in keywords.into_iter().enumerate() {
+            assert_eq!(keyword as usize, index);
+            assert_eq!(Keyword::lookup(keyword.as_str()), Some(keyword));
+            assert_eq!(
+                Keyword::lookup(&keyword.as_str().to_lowercase()),
+                Some(keyword)
+            );
+            assert_eq!(keyword.as_str().parse::<Keyword>().ok(), Some(keyword));
+        }
+    }
+
+    #[test]
+    fn classes() {
+        assert!(Keyword::Select.is_reserved());
+        assert!(Keyword::User.is_reserved());
+        assert!(!Keyword::Abort.is_reserved());
+        assert_eq!(Keyword::Year.class(), KeywordClass::NonReserved);
+    }
+}
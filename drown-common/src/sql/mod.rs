@@ -0,0 +1,17 @@
+#[cfg(feature = "functions")]
+pub mod aggregate;
+pub mod collation;
+pub mod data_type;
+pub mod datetime;
+pub mod format;
+#[cfg(feature = "functions")]
+pub mod function;
+pub mod jsonb;
+pub mod keyword;
+pub mod parser;
+#[cfg(feature = "functions")]
+pub mod sketch;
+pub mod statement;
+pub mod suggest;
+pub mod value;
+pub mod visitor;
@@ -0,0 +1,411 @@
+use thiserror::Error;
+
+use super::token::{Span, Token, TokenKind};
+
+#[derive(Debug)]
+pub struct Lexer<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum LexerError {
+    #[error("Encountered Non-ASCII Character: `{0}` ({})", .0.escape_unicode())]
+    NonAsciiCharacter(char),
+    #[error("Encountered Unexpected Control Character: `{0}` ({})", .0.escape_debug())]
+    ControlCharacter(char),
+    #[error("Encountered Unexpected Character: `{0}`")]
+    UnexpectedCharacter(char),
+    #[error("Unterminated quoted literal starting at position {0}")]
+    UnterminatedLiteral(usize),
+    #[error("Unterminated comment starting at position {0}")]
+    UnterminatedComment(usize),
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    /// Consumes the next character. Positions are byte offsets into the input.
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.peek_char()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.peek_nth_char(0)
+    }
+
+    /// The character `n` characters after the current one.
+    fn peek_nth_char(&self, n: usize) -> Option<char> {
+        self.input[self.position..].chars().nth(n)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek_char() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.next_char();
+        }
+    }
+
+    fn parse_single_char_token(&mut self, kind: TokenKind) -> Token<'a> {
+        let token = Token::new(
+            kind,
+            &self.input[self.position..self.position + 1],
+            Span::single(self.position),
+        );
+
+        self.position += 1;
+
+        token
+    }
+
+    fn parse_keyword_or_identifier(&mut self) -> Token<'a> {
+        let starting_position = self.position;
+
+        while let Some(c) = self.peek_char() {
+            if !c.is_ascii_alphanumeric() && c != '_' {
+                break;
+            }
+            self.next_char().unwrap();
+        }
+
+        let literal = &self.input[starting_position..self.position];
+
+        Token::new(
+            TokenKind::from_keyword_or_identifier(literal),
+            literal,
+            Span::new(starting_position, self.position),
+        )
+    }
+
+    fn parse_number(&mut self) -> Token<'a> {
+        let starting_position = self.position;
+        let mut token_kind = TokenKind::IntegerLiteral;
+
+        // Integer or first portion of float
+        while let Some(c) = self.peek_char() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+
+            self.next_char().unwrap();
+        }
+
+        // Parse Float Literal if applicable
+        if let Some('.') = self.peek_char() {
+            self.next_char().unwrap();
+            token_kind = TokenKind::FloatLiteral;
+
+            while let Some(c) = self.peek_char() {
+                if !c.is_ascii_digit() {
+                    break;
+                }
+
+                self.next_char().unwrap();
+            }
+        }
+
+        let literal = &self.input[starting_position..self.position];
+
+        Token::new(
+            token_kind,
+            literal,
+            Span::new(starting_position, self.position),
+        )
+    }
+
+    fn parse_two_char_token(&mut self, kind: TokenKind) -> Token<'a> {
+        let token = Token::new(
+            kind,
+            &self.input[self.position..self.position + 2],
+            Span::new(self.position, self.position + 2),
+        );
+
+        self.position += 2;
+
+        token
+    }
+
+    /// Parses a token that is either one or two characters long, depending on whether the
+    /// character after the current one is in `continuations`.
+    fn parse_one_or_two_char_token(
+        &mut self,
+        single: TokenKind,
+        continuations: &[(char, TokenKind)],
+    ) -> Token<'a> {
+        let next = self.peek_nth_char(1);
+
+        match continuations.iter().find(|(c, _)| Some(*c) == next) {
+            Some((_, kind)) => self.parse_two_char_token(*kind),
+            None => self.parse_single_char_token(single),
+        }
+    }
+
+    /// Parses an arrow which is either two or three characters long, like `->` and `->>`,
+    /// depending on whether it is followed by another `>`.
+    fn parse_arrow(&mut self, short: TokenKind, long: TokenKind) -> Token<'a> {
+        if self.peek_nth_char(2) != Some('>') {
+            return self.parse_two_char_token(short);
+        }
+
+        let token = Token::new(
+            long,
+            &self.input[self.position..self.position + 3],
+            Span::new(self.position, self.position + 3),
+        );
+
+        self.position += 3;
+
+        token
+    }
+
+    /// Parses a literal delimited by `quote`, where a doubled quote character is an escaped quote.
+    ///
+    /// The literal of the resulting token includes the surrounding quotes.
+    fn parse_quoted(&mut self, kind: TokenKind) -> Result<Token<'a>, LexerError> {
+        let starting_position = self.position;
+        let quote = self.next_char().unwrap();
+
+        loop {
+            match self.next_char() {
+                Some(c) if c == quote => {
+                    if self.peek_char() == Some(quote) {
+                        self.next_char().unwrap();
+                    } else {
+                        break;
+                    }
+                }
+                Some(c) if c.is_control() && !c.is_whitespace() => {
+                    return Err(LexerError::ControlCharacter(c))
+                }
+                Some(_) => {}
+                None => return Err(LexerError::UnterminatedLiteral(starting_position)),
+            }
+        }
+
+        let literal = &self.input[starting_position..self.position];
+
+        Ok(Token::new(
+            kind,
+            literal,
+            Span::new(starting_position, self.position),
+        ))
+    }
+
+    /// Parses a comment from `--` up to the end of the line, which is not part of the comment.
+    fn parse_line_comment(&mut self) -> Token<'a> {
+        let starting_position = self.position;
+
+        while self.peek_char().is_some_and(|c| c != '\n') {
+            self.next_char();
+        }
+
+        let literal = self.input[starting_position..self.position].trim_end();
+
+        Token::new(
+            TokenKind::Comment,
+            literal,
+            Span::new(starting_position, starting_position + literal.len()),
+        )
+    }
+
+    /// Parses a comment from `/*` up to the matching `*/`, where comments can be nested.
+    fn parse_block_comment(&mut self) -> Result<Token<'a>, LexerError> {
+        let starting_position = self.position;
+        let mut depth = 0;
+
+        loop {
+            match (self.next_char(), self.peek_char()) {
+                (Some('/'), Some('*')) => depth += 1,
+                (Some('*'), Some('/')) => depth -= 1,
+                (Some(_), _) => continue,
+                (None, _) => return Err(LexerError::UnterminatedComment(starting_position)),
+            }
+
+            self.next_char();
+
+            if depth == 0 {
+                break;
+            }
+        }
+
+        Ok(Token::new(
+            TokenKind::Comment,
+            &self.input[starting_position..self.position],
+            Span::new(starting_position, self.position),
+        ))
+    }
+
+    pub fn next_token(&mut self) -> Result<Token<'a>, LexerError> {
+        self.skip_whitespace();
+
+        let Some(c) = self.peek_char() else {
+            return Ok(Token::eof(self.position));
+        };
+
+        let token = match c {
+            'a'..='z' | 'A'..='Z' => self.parse_keyword_or_identifier(),
+            '0'..='9' => self.parse_number(),
+            '\'' => self.parse_quoted(TokenKind::StringLiteral)?,
+            '"' | '`' => self.parse_quoted(TokenKind::QuotedIdentifier)?,
+            ';' => self.parse_single_char_token(TokenKind::Semicolon),
+            '+' => self.parse_single_char_token(TokenKind::Plus),
+            '-' => match self.peek_nth_char(1) {
+                Some('-') => self.parse_line_comment(),
+                Some('>') => self.parse_arrow(TokenKind::Arrow, TokenKind::LongArrow),
+                _ => self.parse_single_char_token(TokenKind::Minus),
+            },
+            '#' => match self.peek_nth_char(1) {
+                Some('>') => self.parse_arrow(TokenKind::HashArrow, TokenKind::HashLongArrow),
+                _ => return Err(LexerError::UnexpectedCharacter('#')),
+            },
+            '@' => match self.peek_nth_char(1) {
+                Some('>') => self.parse_two_char_token(TokenKind::AtArrow),
+                _ => return Err(LexerError::UnexpectedCharacter('@')),
+            },
+            ':' => match self.peek_nth_char(1) {
+                Some(':') => self.parse_two_char_token(TokenKind::DoubleColon),
+                _ => return Err(LexerError::UnexpectedCharacter(':')),
+            },
+            '?' => self.parse_one_or_two_char_token(
+                TokenKind::Question,
+                &[
+                    ('|', TokenKind::QuestionPipe),
+                    ('&', TokenKind::QuestionAmpersand),
+                ],
+            ),
+            '*' => self.parse_single_char_token(TokenKind::Asterisk),
+            '/' => match self.peek_nth_char(1) {
+                Some('*') => self.parse_block_comment()?,
+                _ => self.parse_single_char_token(TokenKind::ForwardSlash),
+            },
+            '%' => self.parse_single_char_token(TokenKind::Percent),
+            '=' => self.parse_single_char_token(TokenKind::Equals),
+            ',' => self.parse_single_char_token(TokenKind::Comma),
+            '.' => self.parse_single_char_token(TokenKind::Period),
+            '(' => self.parse_single_char_token(TokenKind::OpeningParen),
+            ')' => self.parse_single_char_token(TokenKind::ClosingParen),
+            '[' => self.parse_single_char_token(TokenKind::OpeningBracket),
+            ']' => self.parse_single_char_token(TokenKind::ClosingBracket),
+            '{' => self.parse_single_char_token(TokenKind::OpeningBrace),
+            '}' => self.parse_single_char_token(TokenKind::ClosingBrace),
+            '<' => self.parse_one_or_two_char_token(
+                TokenKind::LessThan,
+                &[
+                    ('=', TokenKind::LessThanOrEqual),
+                    ('>', TokenKind::NotEquals),
+                    ('@', TokenKind::ArrowAt),
+                ],
+            ),
+            '>' => self.parse_one_or_two_char_token(
+                TokenKind::GreaterThan,
+                &[('=', TokenKind::GreaterThanOrEqual)],
+            ),
+            '!' => match self.peek_nth_char(1) {
+                Some('=') => self.parse_two_char_token(TokenKind::NotEquals),
+                _ => return Err(LexerError::UnexpectedCharacter('!')),
+            },
+            x if !x.is_ascii() => {
+                return Err(LexerError::NonAsciiCharacter(x));
+            }
+            x if x.is_ascii_control() => {
+                return Err(LexerError::ControlCharacter(x));
+            }
+            x => {
+                return Err(LexerError::UnexpectedCharacter(x));
+            }
+        };
+
+        Ok(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql::keyword::Keyword;
+
+    fn tokens(input: &str) -> Result<Vec<(TokenKind, &str, Span)>, LexerError> {
+        let mut lexer = Lexer::new(input);
+        let mut tokens = Vec::new();
+
+        loop {
+            let token = lexer.next_token()?;
+
+            if token.kind() == TokenKind::Eof {
+                return Ok(tokens);
+            }
+
+            tokens.push((token.kind(), token.literal(), token.span()));
+        }
+    }
+
+    #[test]
+    fn non_ascii_text() {
+        assert_eq!(
+            tokens("SELECT 'é', \"Å\"->'日本', 'it''s ß'").unwrap(),
+            [
+                (
+                    TokenKind::Keyword(Keyword::Select),
+                    "SELECT",
+                    Span::new(0, 6)
+                ),
+                (TokenKind::StringLiteral, "'é'", Span::new(7, 11)),
+                (TokenKind::Comma, ",", Span::new(11, 12)),
+                (TokenKind::QuotedIdentifier, "\"Å\"", Span::new(13, 17)),
+                (TokenKind::Arrow, "->", Span::new(17, 19)),
+                (TokenKind::StringLiteral, "'日本'", Span::new(19, 27)),
+                (TokenKind::Comma, ",", Span::new(27, 28)),
+                (TokenKind::StringLiteral, "'it''s ß'", Span::new(29, 39)),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_characters() {
+        assert!(tokens("'a\nb\tc'").is_ok());
+
+        assert!(matches!(
+            tokens("'a\u{7}'"),
+            Err(LexerError::ControlCharacter('\u{7}'))
+        ));
+        assert!(matches!(
+            tokens("a \u{1}"),
+            Err(LexerError::ControlCharacter('\u{1}'))
+        ));
+        assert!(matches!(
+            tokens("é"),
+            Err(LexerError::NonAsciiCharacter('é'))
+        ));
+        assert!(matches!(
+            tokens("'é"),
+            Err(LexerError::UnterminatedLiteral(0))
+        ));
+    }
+
+    #[test]
+    fn comments() {
+        assert_eq!(
+            tokens("a -- b\r\n'--' /* c /* d */ */-/**/1").unwrap(),
+            [
+                (TokenKind::Identifier, "a", Span::new(0, 1)),
+                (TokenKind::Comment, "-- b", Span::new(2, 6)),
+                (TokenKind::StringLiteral, "'--'", Span::new(8, 12)),
+                (TokenKind::Comment, "/* c /* d */ */", Span::new(13, 28)),
+                (TokenKind::Minus, "-", Span::new(28, 29)),
+                (TokenKind::Comment, "/**/", Span::new(29, 33)),
+                (TokenKind::IntegerLiteral, "1", Span::new(33, 34)),
+            ]
+        );
+
+        assert!(matches!(
+            tokens("a /* b /* c */"),
+            Err(LexerError::UnterminatedComment(2))
+        ));
+    }
+}
@@ -0,0 +1,99 @@
+use thiserror::Error;
+
+use crate::sql::{
+    parser::{
+        lexer::Lexer,
+        parser::Parser,
+        token::{Token, TokenKind},
+    },
+    statement::Statement,
+};
+
+mod lexer;
+#[allow(clippy::module_inception)]
+mod parser;
+mod token;
+
+pub use token::Span;
+
+pub struct StatementParser;
+
+#[derive(Debug, Error)]
+pub enum StatementParseError {
+    #[error("Encountered Lexer Error: {0}")]
+    LexerError(#[from] lexer::LexerError),
+    #[error("Encountered Parser Error: {0}")]
+    ParserError(#[from] parser::ParserError),
+}
+
+impl StatementParseError {
+    /// The keyword the input most likely meant to use at the point where parsing failed.
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            StatementParseError::LexerError(_) => None,
+            StatementParseError::ParserError(err) => err.suggestion(),
+        }
+    }
+}
+
+/// A comment in the input, including the `--` or `/* */` around it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub text: String,
+    pub span: Span,
+}
+
+/// Statements along with the span each covers and the comments between and inside them.
+#[derive(Debug, Clone)]
+pub struct CommentedStatements {
+    pub statements: Vec<(Statement, Span)>,
+    pub comments: Vec<Comment>,
+}
+
+impl StatementParser {
+    pub fn parse(statement: &str) -> Result<Vec<Statement>, StatementParseError> {
+        let (tokens, _) = Self::lex(statement)?;
+
+        Ok(Parser::new(statement, tokens).parse_statements()?)
+    }
+
+    /// Parses every statement like `parse`, keeping the comments for tools which have to write
+    /// them back out.
+    pub fn parse_with_comments(input: &str) -> Result<CommentedStatements, StatementParseError> {
+        let (tokens, comments) = Self::lex(input)?;
+        let statements = Parser::new(input, tokens).parse_statement_spans()?;
+
+        Ok(CommentedStatements {
+            statements,
+            comments,
+        })
+    }
+
+    /// Splits the input into the tokens the parser reads and the comments it skips.
+    fn lex(input: &str) -> Result<(Vec<Token<'_>>, Vec<Comment>), StatementParseError> {
+        let mut lexer = Lexer::new(input);
+        let mut tokens = Vec::new();
+        let mut comments = Vec::new();
+
+        loop {
+            let token = lexer.next_token()?;
+            let kind = token.kind();
+
+            if kind == TokenKind::Comment {
+                comments.push(Comment {
+                    text: token.literal().to_string(),
+                    span: token.span(),
+                });
+                continue;
+            }
+
+            tokens.push(token);
+
+            if kind == TokenKind::Eof {
+                break;
+            }
+        }
+
+        Ok((tokens, comments))
+    }
+}
@@ -0,0 +1,1854 @@
+use thiserror::Error;
+
+use crate::sql::{
+    keyword::Keyword,
+    statement::{
+        AliasedExpr, AliasedFromTable, Assignment, BinaryOperator, CheckOption, ColumnDefinition,
+        ColumnName, CreateDatabaseStatement, CreateFunctionStatement,
+        CreateMaterializedViewStatement, CreateSchemaStatement, CreateTableStatement,
+        CreateViewStatement, DeleteStatement, DropFunctionStatement, DropSchemaStatement,
+        DropViewStatement, ExplainFormat, ExplainStatement, Expr, FromClause, FromTable,
+        FunctionArgs, FunctionParam, GroupByClause, HavingClause, InsertSource, InsertStatement,
+        JoinCondition, JoinType, LimitClause, Literal, ObjectName, OffsetClause, OrderByClause,
+        OrderByExpr, OrderByItem, Ordering, Precedence, RefreshMaterializedViewStatement,
+        ResultColumns, SchemaName, SelectStatement, SetOperation, SetOperator,
+        SetSearchPathStatement, SetTimeZoneStatement, ShowColumnsStatement,
+        ShowCreateTableStatement, Statement, TypeName, UnaryOperator, UpdateStatement,
+        UseStatement, Volatility, WhereClause,
+    },
+    suggest,
+};
+
+use super::token::{Span, Token, TokenKind};
+
+/// Recursive descent parser over the tokens produced by the lexer.
+#[derive(Debug)]
+pub struct Parser<'a> {
+    input: &'a str,
+    tokens: Vec<Token<'a>>,
+    position: usize,
+    /// The keywords the parser looked for at each token, which an identifier found in their
+    /// place is most likely a misspelling of
+    expected_keywords: Vec<Vec<Keyword>>,
+    /// The position of the last alias given without `AS`, which may be a misspelled keyword
+    implicit_alias: Option<usize>,
+}
+
+#[derive(Debug, Error)]
+pub enum ParserError {
+    #[error("Expected {expected} but found `{found}` at {span}")]
+    UnexpectedToken {
+        expected: String,
+        found: String,
+        span: Span,
+        /// The keyword `found` is most likely a misspelling of
+        suggestion: Option<String>,
+    },
+    #[error("Expected {expected} but reached the end of the input")]
+    UnexpectedEof { expected: String },
+    #[error("Invalid integer literal `{literal}` at {span}")]
+    InvalidInteger { literal: String, span: Span },
+    #[error("Qualified name has more than {max} parts at {span}")]
+    TooManyNameParts { max: usize, span: Span },
+}
+
+impl<'a> Parser<'a> {
+    /// Creates a parser from the token stream lexed from `input`, which must be terminated by an
+    /// EOF token.
+    pub fn new(input: &'a str, tokens: Vec<Token<'a>>) -> Self {
+        debug_assert!(matches!(
+            tokens.last().map(Token::kind),
+            Some(TokenKind::Eof)
+        ));
+
+        Self {
+            input,
+            expected_keywords: vec![Vec::new(); tokens.len()],
+            tokens,
+            position: 0,
+            implicit_alias: None,
+        }
+    }
+
+    /* Token helpers */
+
+    fn peek(&self) -> &Token<'a> {
+        &self.tokens[self.position]
+    }
+
+    fn peek_kind(&self) -> TokenKind {
+        self.peek().kind()
+    }
+
+    fn peek_nth_kind(&self, n: usize) -> TokenKind {
+        self.tokens
+            .get(self.position + n)
+            .map_or(TokenKind::Eof, Token::kind)
+    }
+
+    fn next_token(&mut self) -> Token<'a> {
+        let token = self.tokens[self.position].clone();
+
+        // Never advance past the EOF token
+        if token.kind() != TokenKind::Eof {
+            self.position += 1;
+        }
+
+        token
+    }
+
+    /// Records that `keywords` could have come next, for suggesting one of them when an
+    /// identifier comes instead.
+    fn expect_keywords(&mut self, keywords: &[Keyword]) {
+        self.expected_keywords[self.position].extend_from_slice(keywords);
+    }
+
+    fn consume_if(&mut self, kind: TokenKind) -> bool {
+        if let TokenKind::Keyword(keyword) = kind {
+            self.expect_keywords(&[keyword]);
+        }
+
+        if self.peek_kind() == kind {
+            self.next_token();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Whether the next token can be parsed as an identifier, which includes non-reserved
+    /// keywords.
+    fn peek_is_identifier(&self) -> bool {
+        match self.peek_kind() {
+            TokenKind::Identifier | TokenKind::QuotedIdentifier => true,
+            TokenKind::Keyword(keyword) => !keyword.is_reserved(),
+            _ => false,
+        }
+    }
+
+    fn consume_keyword(&mut self, keyword: Keyword) -> bool {
+        self.consume_if(TokenKind::Keyword(keyword))
+    }
+
+    /// Consumes an identifier spelled `word` in any case, for words that have a special meaning
+    /// in one place but are not keywords.
+    fn consume_word(&mut self, word: &str) -> bool {
+        let matches = self.peek_kind() == TokenKind::Identifier
+            && self.peek().literal().eq_ignore_ascii_case(word);
+
+        if matches {
+            self.next_token();
+        }
+
+        matches
+    }
+
+    fn unexpected<T>(&self, expected: impl Into<String>) -> Result<T, ParserError> {
+        let token = self.peek();
+        let expected = expected.into();
+
+        if let Some(suggestion) = self.misspelled_keyword(self.position, &[]) {
+            return Err(ParserError::UnexpectedToken {
+                expected,
+                found: token.literal().to_string(),
+                span: token.span(),
+                suggestion: Some(suggestion),
+            });
+        }
+
+        // A misspelled keyword right before is taken as an alias, so it is only found not to be
+        // one by the token after it, which the keyword it is a misspelling of was expected at
+        if let Some(position) = self
+            .implicit_alias
+            .filter(|alias| alias + 1 == self.position)
+        {
+            let next = &self.expected_keywords[self.position];
+
+            if let Some(suggestion) = self.misspelled_keyword(position, next) {
+                let alias = &self.tokens[position];
+
+                return Err(ParserError::UnexpectedToken {
+                    expected,
+                    found: alias.literal().to_string(),
+                    span: alias.span(),
+                    suggestion: Some(suggestion),
+                });
+            }
+        }
+
+        Err(match token.kind() {
+            TokenKind::Eof => ParserError::UnexpectedEof { expected },
+            _ => ParserError::UnexpectedToken {
+                expected,
+                found: token.literal().to_string(),
+                span: token.span(),
+                suggestion: None,
+            },
+        })
+    }
+
+    /// Like [`Self::unexpected`], when one of `keywords` was expected, which were peeked for
+    /// rather than consumed.
+    fn unexpected_keyword<T>(
+        &mut self,
+        expected: &str,
+        keywords: &[Keyword],
+    ) -> Result<T, ParserError> {
+        self.expect_keywords(keywords);
+        self.unexpected(expected)
+    }
+
+    /// The keyword the identifier at `position` is most likely a misspelling of, out of `first`
+    /// and those expected at it.
+    fn misspelled_keyword(&self, position: usize, first: &[Keyword]) -> Option<String> {
+        let token = &self.tokens[position];
+
+        if token.kind() != TokenKind::Identifier {
+            return None;
+        }
+
+        let candidates = first.iter().chain(&self.expected_keywords[position]);
+
+        suggest::closest(token.literal(), candidates.map(Keyword::as_str)).map(ToString::to_string)
+    }
+
+    fn expect(&mut self, kind: TokenKind, expected: &str) -> Result<Token<'a>, ParserError> {
+        if let TokenKind::Keyword(keyword) = kind {
+            self.expect_keywords(&[keyword]);
+        }
+
+        if self.peek_kind() == kind {
+            Ok(self.next_token())
+        } else {
+            self.unexpected(expected)
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: Keyword) -> Result<Token<'a>, ParserError> {
+        self.expect(TokenKind::Keyword(keyword), &format!("`{}`", keyword))
+    }
+
+    /// Returns the input text spanned by the tokens consumed since `start_position`.
+    fn text_since(&self, start_position: usize) -> &'a str {
+        let span = self.span_since(start_position);
+
+        &self.input[span.start()..span.end()]
+    }
+
+    /// Returns the span covering the tokens consumed since `start_position`.
+    fn span_since(&self, start_position: usize) -> Span {
+        Span::new(
+            self.tokens[start_position].span().start(),
+            self.tokens[self.position - 1].span().end(),
+        )
+    }
+
+    /* Statements */
+
+    /// Parses every statement in the input, separated by semicolons.
+    pub fn parse_statements(&mut self) -> Result<Vec<Statement>, ParserError> {
+        let statements = self.parse_statement_spans()?;
+
+        Ok(statements
+            .into_iter()
+            .map(|(statement, _)| statement)
+            .collect())
+    }
+
+    /// Parses every statement like `parse_statements`, along with the span of each statement,
+    /// which does not include the semicolon after it.
+    pub fn parse_statement_spans(&mut self) -> Result<Vec<(Statement, Span)>, ParserError> {
+        let mut statements = Vec::new();
+
+        loop {
+            while self.consume_if(TokenKind::Semicolon) {}
+
+            if self.peek_kind() == TokenKind::Eof {
+                break;
+            }
+
+            let start = self.position;
+            let statement = self.parse_statement()?;
+            statements.push((statement, self.span_since(start)));
+
+            if self.peek_kind() != TokenKind::Eof {
+                self.expect(TokenKind::Semicolon, "`;`")?;
+            }
+        }
+
+        Ok(statements)
+    }
+
+    fn parse_statement(&mut self) -> Result<Statement, ParserError> {
+        match self.peek_kind() {
+            TokenKind::Keyword(Keyword::Select) => Ok(Statement::Select(self.parse_select()?)),
+            TokenKind::Keyword(Keyword::Insert) => Ok(Statement::Insert(self.parse_insert()?)),
+            TokenKind::Keyword(Keyword::Update) => Ok(Statement::Update(self.parse_update()?)),
+            TokenKind::Keyword(Keyword::Delete) => Ok(Statement::Delete(self.parse_delete()?)),
+            TokenKind::Keyword(Keyword::Explain) => Ok(Statement::Explain(self.parse_explain()?)),
+            TokenKind::Keyword(Keyword::Create) => self.parse_create(),
+            TokenKind::Keyword(Keyword::Refresh) => Ok(Statement::RefreshMaterializedView(
+                self.parse_refresh_materialized_view()?,
+            )),
+            TokenKind::Keyword(Keyword::Drop) => self.parse_drop(),
+            TokenKind::Keyword(Keyword::Set) => self.parse_set(),
+            TokenKind::Keyword(Keyword::Use) => {
+                self.next_token();
+
+                Ok(Statement::Use(UseStatement {
+                    database: self.parse_identifier()?,
+                }))
+            }
+            TokenKind::Keyword(Keyword::Show) => self.parse_show(),
+            TokenKind::Keyword(Keyword::Describe) => {
+                self.next_token();
+
+                Ok(Statement::ShowColumns(ShowColumnsStatement {
+                    table: self.parse_object_name()?,
+                }))
+            }
+            _ => self.unexpected_keyword(
+                "a statement",
+                &[
+                    Keyword::Select,
+                    Keyword::Insert,
+                    Keyword::Update,
+                    Keyword::Delete,
+                    Keyword::Explain,
+                    Keyword::Create,
+                    Keyword::Refresh,
+                    Keyword::Drop,
+                    Keyword::Set,
+                    Keyword::Use,
+                    Keyword::Show,
+                    Keyword::Describe,
+                ],
+            ),
+        }
+    }
+
+    fn parse_insert(&mut self) -> Result<InsertStatement, ParserError> {
+        self.expect_keyword(Keyword::Insert)?;
+        self.expect_keyword(Keyword::Into)?;
+
+        let table = self.parse_object_name()?;
+        let columns = self.parse_optional_column_list()?;
+
+        let source = match self.peek_kind() {
+            TokenKind::Keyword(Keyword::Values) => {
+                self.next_token();
+
+                InsertSource::Values {
+                    rows: self.parse_values_rows()?,
+                }
+            }
+            TokenKind::Keyword(Keyword::Select) => {
+                InsertSource::Select(Box::new(self.parse_select()?))
+            }
+            _ => {
+                return self.unexpected_keyword(
+                    "`VALUES` or `SELECT`",
+                    &[Keyword::Values, Keyword::Select],
+                )
+            }
+        };
+
+        Ok(InsertStatement {
+            table,
+            columns,
+            source,
+        })
+    }
+
+    fn parse_update(&mut self) -> Result<UpdateStatement, ParserError> {
+        self.expect_keyword(Keyword::Update)?;
+
+        let table = self.parse_object_name()?;
+
+        self.expect_keyword(Keyword::Set)?;
+
+        let assignments = self.parse_comma_separated(|parser| {
+            let column = parser.parse_identifier()?;
+            parser.expect(TokenKind::Equals, "`=`")?;
+
+            Ok(Assignment {
+                column,
+                value: parser.parse_expr()?,
+            })
+        })?;
+
+        Ok(UpdateStatement {
+            table,
+            assignments,
+            where_clause: self.parse_where_clause()?,
+        })
+    }
+
+    fn parse_delete(&mut self) -> Result<DeleteStatement, ParserError> {
+        self.expect_keyword(Keyword::Delete)?;
+        self.expect_keyword(Keyword::From)?;
+
+        Ok(DeleteStatement {
+            table: self.parse_object_name()?,
+            where_clause: self.parse_where_clause()?,
+        })
+    }
+
+    fn parse_create(&mut self) -> Result<Statement, ParserError> {
+        self.expect_keyword(Keyword::Create)?;
+
+        let or_replace = if self.consume_keyword(Keyword::Or) {
+            self.expect_keyword(Keyword::Replace)?;
+            true
+        } else {
+            false
+        };
+
+        match self.peek_kind() {
+            TokenKind::Keyword(Keyword::View) => {
+                Ok(Statement::CreateView(self.parse_create_view(or_replace)?))
+            }
+            TokenKind::Keyword(Keyword::Function) => Ok(Statement::CreateFunction(
+                self.parse_create_function(or_replace)?,
+            )),
+            _ if or_replace => {
+                self.unexpected_keyword("`VIEW` or `FUNCTION`", &[Keyword::View, Keyword::Function])
+            }
+            TokenKind::Keyword(Keyword::Materialized) => Ok(Statement::CreateMaterializedView(
+                self.parse_create_materialized_view()?,
+            )),
+            TokenKind::Keyword(Keyword::Database) => {
+                self.next_token();
+
+                Ok(Statement::CreateDatabase(CreateDatabaseStatement {
+                    if_not_exists: self.parse_if_not_exists()?,
+                    name: self.parse_identifier()?,
+                }))
+            }
+            TokenKind::Keyword(Keyword::Schema) => {
+                self.next_token();
+
+                Ok(Statement::CreateSchema(CreateSchemaStatement {
+                    if_not_exists: self.parse_if_not_exists()?,
+                    name: self.parse_schema_name()?,
+                }))
+            }
+            TokenKind::Keyword(Keyword::Table) => {
+                Ok(Statement::CreateTable(self.parse_create_table()?))
+            }
+            _ => self.unexpected_keyword(
+                "`DATABASE`, `SCHEMA`, `TABLE`, `VIEW`, `MATERIALIZED VIEW` or `FUNCTION`",
+                &[
+                    Keyword::Database,
+                    Keyword::Schema,
+                    Keyword::Table,
+                    Keyword::View,
+                    Keyword::Materialized,
+                    Keyword::Function,
+                ],
+            ),
+        }
+    }
+
+    fn parse_create_table(&mut self) -> Result<CreateTableStatement, ParserError> {
+        self.expect_keyword(Keyword::Table)?;
+
+        let if_not_exists = self.parse_if_not_exists()?;
+        let name = self.parse_object_name()?;
+
+        self.expect(TokenKind::OpeningParen, "`(`")?;
+        let columns = self.parse_comma_separated(Self::parse_column_definition)?;
+        self.expect(TokenKind::ClosingParen, "`)`")?;
+
+        Ok(CreateTableStatement {
+            if_not_exists,
+            name,
+            columns,
+        })
+    }
+
+    fn parse_column_definition(&mut self) -> Result<ColumnDefinition, ParserError> {
+        let name = self.parse_identifier()?;
+        let data_type = self.parse_type_name()?;
+
+        let collation = match self.consume_keyword(Keyword::Collate) {
+            true => Some(self.parse_collation_name()?),
+            false => None,
+        };
+
+        let nullable = if self.consume_keyword(Keyword::Not) {
+            self.expect_keyword(Keyword::Null)?;
+            false
+        } else {
+            self.consume_keyword(Keyword::Null);
+            true
+        };
+
+        Ok(ColumnDefinition {
+            name,
+            data_type,
+            collation,
+            nullable,
+        })
+    }
+
+    fn parse_type_name(&mut self) -> Result<TypeName, ParserError> {
+        // Built-in type names such as `INT` and `TEXT` are non-reserved keywords
+        let name = match self.peek_kind() {
+            TokenKind::Identifier => self.next_token().literal().to_ascii_uppercase(),
+            TokenKind::Keyword(keyword) if !keyword.is_reserved() => {
+                self.next_token().literal().to_ascii_uppercase()
+            }
+            _ => return self.unexpected("a type name"),
+        };
+
+        let modifiers = if self.consume_if(TokenKind::OpeningParen) {
+            let modifiers = self.parse_comma_separated(Self::parse_unsigned_integer)?;
+            self.expect(TokenKind::ClosingParen, "`)`")?;
+            modifiers
+        } else {
+            Vec::new()
+        };
+
+        Ok(TypeName { name, modifiers })
+    }
+
+    fn parse_create_function(
+        &mut self,
+        or_replace: bool,
+    ) -> Result<CreateFunctionStatement, ParserError> {
+        self.expect_keyword(Keyword::Function)?;
+
+        let name = self.parse_identifier()?;
+        let params = self.parse_function_params(Self::parse_function_param)?;
+
+        self.expect_keyword(Keyword::Returns)?;
+        let returns = self.parse_type_name()?;
+
+        let mut language = None;
+        let mut volatility = None;
+        let mut body = None;
+
+        // The clauses after RETURNS can be written in any order, but only once each
+        loop {
+            match self.peek_kind() {
+                TokenKind::Keyword(Keyword::Language) if language.is_none() => {
+                    self.next_token();
+
+                    language = Some(match self.peek_kind() {
+                        TokenKind::StringLiteral => unquote(self.next_token().literal()),
+                        _ => self.parse_identifier()?,
+                    });
+                }
+                TokenKind::Keyword(
+                    keyword @ (Keyword::Immutable | Keyword::Stable | Keyword::Volatile),
+                ) if volatility.is_none() => {
+                    self.next_token();
+
+                    volatility = Some(match keyword {
+                        Keyword::Immutable => Volatility::Immutable,
+                        Keyword::Stable => Volatility::Stable,
+                        _ => Volatility::Volatile,
+                    });
+                }
+                TokenKind::Keyword(Keyword::As) if body.is_none() => {
+                    self.next_token();
+
+                    let definition = self.parse_string()?;
+
+                    let symbol = match self.consume_if(TokenKind::Comma) {
+                        true => Some(self.parse_string()?),
+                        false => None,
+                    };
+
+                    body = Some((definition, symbol));
+                }
+                _ => break,
+            }
+        }
+
+        let Some(language) = language else {
+            return self.unexpected_keyword("`LANGUAGE`", &[Keyword::Language]);
+        };
+
+        let Some((definition, symbol)) = body else {
+            return self.unexpected_keyword("`AS`", &[Keyword::As]);
+        };
+
+        Ok(CreateFunctionStatement {
+            or_replace,
+            name,
+            params,
+            returns,
+            language: language.to_ascii_lowercase(),
+            volatility: volatility.unwrap_or(Volatility::Volatile),
+            definition,
+            symbol,
+        })
+    }
+
+    /// Parses `(a, b, ...)`, which may be empty, with `parse_param` parsing each parameter.
+    fn parse_function_params<T>(
+        &mut self,
+        parse_param: impl FnMut(&mut Self) -> Result<T, ParserError>,
+    ) -> Result<Vec<T>, ParserError> {
+        self.expect(TokenKind::OpeningParen, "`(`")?;
+
+        if self.consume_if(TokenKind::ClosingParen) {
+            return Ok(Vec::new());
+        }
+
+        let params = self.parse_comma_separated(parse_param)?;
+        self.expect(TokenKind::ClosingParen, "`)`")?;
+
+        Ok(params)
+    }
+
+    /// Parses `[a] INT`, where the parameter is only named when two words follow one another, as
+    /// type names are not reserved.
+    fn parse_function_param(&mut self) -> Result<FunctionParam, ParserError> {
+        let named = match self.peek_nth_kind(1) {
+            TokenKind::Identifier | TokenKind::QuotedIdentifier => true,
+            TokenKind::Keyword(keyword) => !keyword.is_reserved(),
+            _ => false,
+        };
+
+        let name = match named {
+            true => Some(self.parse_identifier()?),
+            false => None,
+        };
+
+        Ok(FunctionParam {
+            name,
+            data_type: self.parse_type_name()?,
+        })
+    }
+
+    fn parse_string(&mut self) -> Result<String, ParserError> {
+        match self.peek_kind() {
+            TokenKind::StringLiteral => Ok(unquote(self.next_token().literal())),
+            _ => self.unexpected("a string"),
+        }
+    }
+
+    fn parse_show(&mut self) -> Result<Statement, ParserError> {
+        self.expect_keyword(Keyword::Show)?;
+
+        match self.peek_kind() {
+            TokenKind::Keyword(Keyword::Databases) => {
+                self.next_token();
+                Ok(Statement::ShowDatabases)
+            }
+            TokenKind::Keyword(Keyword::Schemas) => {
+                self.next_token();
+                Ok(Statement::ShowSchemas)
+            }
+            TokenKind::Keyword(Keyword::Tables) => {
+                self.next_token();
+                Ok(Statement::ShowTables)
+            }
+            TokenKind::Keyword(Keyword::Columns) => {
+                self.next_token();
+                self.expect_keyword(Keyword::From)?;
+
+                Ok(Statement::ShowColumns(ShowColumnsStatement {
+                    table: self.parse_object_name()?,
+                }))
+            }
+            TokenKind::Keyword(Keyword::Create) => {
+                self.next_token();
+                self.expect_keyword(Keyword::Table)?;
+
+                Ok(Statement::ShowCreateTable(ShowCreateTableStatement {
+                    table: self.parse_object_name()?,
+                }))
+            }
+            TokenKind::Keyword(Keyword::Time) => {
+                self.next_token();
+                self.expect_keyword(Keyword::Zone)?;
+
+                Ok(Statement::ShowTimeZone)
+            }
+            _ if self.consume_word("search_path") => Ok(Statement::ShowSearchPath),
+            _ if self.consume_word("timezone") => Ok(Statement::ShowTimeZone),
+            _ => self.unexpected_keyword(
+                "`DATABASES`, `SCHEMAS`, `TABLES`, `COLUMNS`, `CREATE TABLE`, `TIME ZONE` or \
+                 `search_path`",
+                &[
+                    Keyword::Databases,
+                    Keyword::Schemas,
+                    Keyword::Tables,
+                    Keyword::Columns,
+                    Keyword::Create,
+                    Keyword::Time,
+                ],
+            ),
+        }
+    }
+
+    /// Parses `(a, b, ...)` if present, returning an empty list otherwise.
+    fn parse_optional_column_list(&mut self) -> Result<Vec<String>, ParserError> {
+        if !self.consume_if(TokenKind::OpeningParen) {
+            return Ok(Vec::new());
+        }
+
+        let columns = self.parse_comma_separated(Self::parse_identifier)?;
+        self.expect(TokenKind::ClosingParen, "`)`")?;
+
+        Ok(columns)
+    }
+
+    /// Parses an optional `WITH DATA` or `WITH NO DATA`, which defaults to `WITH DATA`.
+    fn parse_with_data(&mut self) -> Result<bool, ParserError> {
+        if !self.consume_keyword(Keyword::With) {
+            return Ok(true);
+        }
+
+        let with_data = !self.consume_keyword(Keyword::No);
+        self.expect_keyword(Keyword::Data)?;
+
+        Ok(with_data)
+    }
+
+    fn parse_create_materialized_view(
+        &mut self,
+    ) -> Result<CreateMaterializedViewStatement, ParserError> {
+        self.expect_keyword(Keyword::Materialized)?;
+        self.expect_keyword(Keyword::View)?;
+
+        let name = self.parse_object_name()?;
+        let columns = self.parse_optional_column_list()?;
+
+        let incremental = if self.consume_keyword(Keyword::With) {
+            self.expect(TokenKind::OpeningParen, "`(`")?;
+
+            if !self.consume_word("incremental") {
+                return self.unexpected("`incremental`");
+            }
+
+            self.expect(TokenKind::ClosingParen, "`)`")?;
+            true
+        } else {
+            false
+        };
+
+        self.expect_keyword(Keyword::As)?;
+
+        let query_start = self.position;
+        let query = Box::new(self.parse_select()?);
+        let query_text = self.text_since(query_start).to_string();
+
+        let with_data = self.parse_with_data()?;
+
+        Ok(CreateMaterializedViewStatement {
+            name,
+            columns,
+            incremental,
+            query,
+            query_text,
+            with_data,
+        })
+    }
+
+    fn parse_refresh_materialized_view(
+        &mut self,
+    ) -> Result<RefreshMaterializedViewStatement, ParserError> {
+        self.expect_keyword(Keyword::Refresh)?;
+        self.expect_keyword(Keyword::Materialized)?;
+        self.expect_keyword(Keyword::View)?;
+
+        let concurrently = self.consume_keyword(Keyword::Concurrently);
+        let name = self.parse_object_name()?;
+        let with_data = self.parse_with_data()?;
+
+        Ok(RefreshMaterializedViewStatement {
+            concurrently,
+            name,
+            with_data,
+        })
+    }
+
+    fn parse_create_view(&mut self, or_replace: bool) -> Result<CreateViewStatement, ParserError> {
+        self.expect_keyword(Keyword::View)?;
+
+        let name = self.parse_object_name()?;
+        let columns = self.parse_optional_column_list()?;
+
+        self.expect_keyword(Keyword::As)?;
+
+        let query_start = self.position;
+        let query = Box::new(self.parse_select()?);
+        let query_text = self.text_since(query_start).to_string();
+
+        let check_option = if self.consume_keyword(Keyword::With) {
+            let check_option = if self.consume_keyword(Keyword::Local) {
+                CheckOption::Local
+            } else {
+                self.consume_keyword(Keyword::Cascaded);
+                CheckOption::Cascaded
+            };
+
+            self.expect_keyword(Keyword::Check)?;
+            self.expect_keyword(Keyword::Option)?;
+
+            Some(check_option)
+        } else {
+            None
+        };
+
+        Ok(CreateViewStatement {
+            or_replace,
+            name,
+            columns,
+            query,
+            query_text,
+            check_option,
+        })
+    }
+
+    fn parse_drop(&mut self) -> Result<Statement, ParserError> {
+        self.expect_keyword(Keyword::Drop)?;
+
+        let materialized = match self.peek_kind() {
+            TokenKind::Keyword(Keyword::Schema) => {
+                return Ok(Statement::DropSchema(self.parse_drop_schema()?));
+            }
+            TokenKind::Keyword(Keyword::Function) => {
+                self.next_token();
+
+                return Ok(Statement::DropFunction(DropFunctionStatement {
+                    if_exists: self.parse_if_exists()?,
+                    name: self.parse_identifier()?,
+                    params: self.parse_function_params(Self::parse_type_name)?,
+                    cascade: self.parse_drop_behavior(),
+                }));
+            }
+            TokenKind::Keyword(Keyword::Materialized) => {
+                self.next_token();
+                true
+            }
+            TokenKind::Keyword(Keyword::View) => false,
+            _ => {
+                return self.unexpected_keyword(
+                    "`SCHEMA`, `VIEW`, `MATERIALIZED VIEW` or `FUNCTION`",
+                    &[
+                        Keyword::Schema,
+                        Keyword::View,
+                        Keyword::Materialized,
+                        Keyword::Function,
+                    ],
+                )
+            }
+        };
+
+        self.expect_keyword(Keyword::View)?;
+
+        let if_exists = self.parse_if_exists()?;
+        let names = self.parse_comma_separated(Self::parse_object_name)?;
+        let cascade = self.parse_drop_behavior();
+
+        let statement = DropViewStatement {
+            if_exists,
+            names,
+            cascade,
+        };
+
+        Ok(match materialized {
+            true => Statement::DropMaterializedView(statement),
+            false => Statement::DropView(statement),
+        })
+    }
+
+    fn parse_drop_schema(&mut self) -> Result<DropSchemaStatement, ParserError> {
+        self.expect_keyword(Keyword::Schema)?;
+
+        let if_exists = self.parse_if_exists()?;
+        let names = self.parse_comma_separated(Self::parse_schema_name)?;
+        let cascade = self.parse_drop_behavior();
+
+        Ok(DropSchemaStatement {
+            if_exists,
+            names,
+            cascade,
+        })
+    }
+
+    /// Parses `[CASCADE | RESTRICT]`, returning whether dependent objects are dropped too.
+    fn parse_drop_behavior(&mut self) -> bool {
+        let cascade = self.consume_keyword(Keyword::Cascade);
+
+        if !cascade {
+            self.consume_keyword(Keyword::Restrict);
+        }
+
+        cascade
+    }
+
+    fn parse_set(&mut self) -> Result<Statement, ParserError> {
+        self.expect_keyword(Keyword::Set)?;
+
+        if self.consume_keyword(Keyword::Time) {
+            self.expect_keyword(Keyword::Zone)?;
+
+            return Ok(Statement::SetTimeZone(self.parse_time_zone()?));
+        }
+
+        let search_path = match self.peek_kind() {
+            _ if self.consume_word("search_path") => true,
+            _ if self.consume_word("timezone") => false,
+            _ => return self.unexpected("`search_path`, `timezone` or `TIME ZONE`"),
+        };
+
+        if !self.consume_keyword(Keyword::To) && !self.consume_if(TokenKind::Equals) {
+            return self.unexpected("`TO` or `=`");
+        }
+
+        if !search_path {
+            return Ok(Statement::SetTimeZone(self.parse_time_zone()?));
+        }
+
+        if self.consume_keyword(Keyword::Default) {
+            return Ok(Statement::SetSearchPath(SetSearchPathStatement {
+                schemas: None,
+            }));
+        }
+
+        // Schemas can also be given as string literals, like `SET search_path = 'a', 'b'`
+        let schemas = self.parse_comma_separated(|parser| match parser.peek_kind() {
+            TokenKind::StringLiteral => Ok(unquote(parser.next_token().literal())),
+            _ => parser.parse_identifier(),
+        })?;
+
+        Ok(Statement::SetSearchPath(SetSearchPathStatement {
+            schemas: Some(schemas),
+        }))
+    }
+
+    /// Parses the time zone of `SET TIME ZONE` or `SET timezone`, which is a string literal or
+    /// `LOCAL` or `DEFAULT` for the server's time zone.
+    fn parse_time_zone(&mut self) -> Result<SetTimeZoneStatement, ParserError> {
+        match self.peek_kind() {
+            TokenKind::StringLiteral => Ok(SetTimeZoneStatement {
+                time_zone: Some(unquote(self.next_token().literal())),
+            }),
+            TokenKind::Keyword(Keyword::Local | Keyword::Default) => {
+                self.next_token();
+                Ok(SetTimeZoneStatement { time_zone: None })
+            }
+            _ => self.unexpected_keyword(
+                "a time zone, `LOCAL` or `DEFAULT`",
+                &[Keyword::Local, Keyword::Default],
+            ),
+        }
+    }
+
+    /// Parses an optional `IF NOT EXISTS`.
+    fn parse_if_not_exists(&mut self) -> Result<bool, ParserError> {
+        if self.consume_keyword(Keyword::If) {
+            self.expect_keyword(Keyword::Not)?;
+            self.expect_keyword(Keyword::Exists)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Parses an optional `IF EXISTS`.
+    fn parse_if_exists(&mut self) -> Result<bool, ParserError> {
+        if self.consume_keyword(Keyword::If) {
+            self.expect_keyword(Keyword::Exists)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn parse_explain(&mut self) -> Result<ExplainStatement, ParserError> {
+        self.expect_keyword(Keyword::Explain)?;
+
+        let mut analyze = false;
+        let mut verbose = false;
+        let mut format = ExplainFormat::default();
+
+        if self.consume_if(TokenKind::OpeningParen) {
+            // EXPLAIN (ANALYZE, FORMAT JSON) takes its options in any order
+            loop {
+                match self.peek_kind() {
+                    TokenKind::Keyword(Keyword::Analyze) => {
+                        self.next_token();
+                        analyze = self.parse_explain_switch();
+                    }
+                    TokenKind::Keyword(Keyword::Verbose) => {
+                        self.next_token();
+                        verbose = self.parse_explain_switch();
+                    }
+                    TokenKind::Keyword(Keyword::Format) => {
+                        self.next_token();
+                        format = self.parse_explain_format()?;
+                    }
+                    _ => {
+                        return self.unexpected_keyword(
+                            "`ANALYZE`, `VERBOSE` or `FORMAT`",
+                            &[Keyword::Analyze, Keyword::Verbose, Keyword::Format],
+                        )
+                    }
+                }
+
+                if !self.consume_if(TokenKind::Comma) {
+                    break;
+                }
+            }
+
+            self.expect(TokenKind::ClosingParen, "`,` or `)`")?;
+        } else {
+            analyze = self.consume_keyword(Keyword::Analyze);
+            verbose = self.consume_keyword(Keyword::Verbose);
+
+            if self.consume_keyword(Keyword::Format) {
+                format = self.parse_explain_format()?;
+            }
+        }
+
+        let statement = match self.peek_kind() {
+            TokenKind::Keyword(Keyword::Select) => Statement::Select(self.parse_select()?),
+            TokenKind::Keyword(Keyword::Insert) => Statement::Insert(self.parse_insert()?),
+            TokenKind::Keyword(Keyword::Update) => Statement::Update(self.parse_update()?),
+            TokenKind::Keyword(Keyword::Delete) => Statement::Delete(self.parse_delete()?),
+            _ => {
+                return self.unexpected_keyword(
+                    "an explainable statement",
+                    &[
+                        Keyword::Select,
+                        Keyword::Insert,
+                        Keyword::Update,
+                        Keyword::Delete,
+                    ],
+                )
+            }
+        };
+
+        Ok(ExplainStatement {
+            analyze,
+            verbose,
+            format,
+            statement: Box::new(statement),
+        })
+    }
+
+    /// Parses the `TRUE` or `FALSE` which can follow an option in parentheses, which is on when
+    /// neither does.
+    fn parse_explain_switch(&mut self) -> bool {
+        if self.consume_keyword(Keyword::False) {
+            return false;
+        }
+
+        self.consume_keyword(Keyword::True);
+        true
+    }
+
+    fn parse_explain_format(&mut self) -> Result<ExplainFormat, ParserError> {
+        let format = match self.peek_kind() {
+            TokenKind::Keyword(Keyword::Text) => ExplainFormat::Text,
+            TokenKind::Keyword(Keyword::Json) => ExplainFormat::Json,
+            _ => {
+                return self.unexpected_keyword("`TEXT` or `JSON`", &[Keyword::Text, Keyword::Json])
+            }
+        };
+
+        self.next_token();
+        Ok(format)
+    }
+
+    fn parse_select(&mut self) -> Result<SelectStatement, ParserError> {
+        let mut select = self.parse_select_core()?;
+
+        while let Some(operator) = self.parse_set_operator() {
+            let all = self.consume_keyword(Keyword::All);
+            if !all {
+                self.consume_keyword(Keyword::Distinct);
+            }
+
+            // Only a parenthesized operand can have ORDER BY, OFFSET, LIMIT or set operations of
+            // its own
+            let query = if self.consume_if(TokenKind::OpeningParen) {
+                let query = self.parse_select()?;
+                self.expect(TokenKind::ClosingParen, "`)`")?;
+                query
+            } else {
+                self.parse_select_core()?
+            };
+
+            select.set_operations.push(SetOperation {
+                operator,
+                all,
+                query,
+            });
+        }
+
+        select.order_by_clause = if self.consume_keyword(Keyword::Order) {
+            self.expect_keyword(Keyword::By)?;
+
+            Some(OrderByClause {
+                orderings: self.parse_comma_separated(Self::parse_order_by_item)?,
+            })
+        } else {
+            None
+        };
+
+        // OFFSET and LIMIT may be given in either order
+        loop {
+            if select.offset.is_none() && self.consume_keyword(Keyword::Offset) {
+                select.offset = Some(OffsetClause {
+                    offset: self.parse_unsigned_integer()?,
+                });
+            } else if select.limit.is_none() && self.consume_keyword(Keyword::Limit) {
+                select.limit = Some(LimitClause {
+                    limit: self.parse_unsigned_integer()?,
+                });
+            } else {
+                break;
+            }
+        }
+
+        Ok(select)
+    }
+
+    fn parse_where_clause(&mut self) -> Result<Option<WhereClause>, ParserError> {
+        if !self.consume_keyword(Keyword::Where) {
+            return Ok(None);
+        }
+
+        Ok(Some(WhereClause {
+            condition: self.parse_expr()?,
+        }))
+    }
+
+    fn parse_set_operator(&mut self) -> Option<SetOperator> {
+        if self.consume_keyword(Keyword::Union) {
+            Some(SetOperator::Union)
+        } else if self.consume_keyword(Keyword::Intersect) {
+            Some(SetOperator::Intersect)
+        } else if self.consume_keyword(Keyword::Except) {
+            Some(SetOperator::Except)
+        } else {
+            None
+        }
+    }
+
+    /// Parses a SELECT up to and including HAVING.
+    fn parse_select_core(&mut self) -> Result<SelectStatement, ParserError> {
+        self.expect_keyword(Keyword::Select)?;
+
+        let distinct = self.consume_keyword(Keyword::Distinct);
+        if !distinct {
+            self.consume_keyword(Keyword::All);
+        }
+
+        let columns = self.parse_result_columns()?;
+
+        self.expect_keyword(Keyword::From)?;
+        let from_clause = self.parse_from_clause()?;
+
+        let where_clause = self.parse_where_clause()?;
+
+        let group_by_clause = if self.consume_keyword(Keyword::Group) {
+            self.expect_keyword(Keyword::By)?;
+
+            Some(GroupByClause {
+                groupings: self.parse_comma_separated(Self::parse_expr)?,
+            })
+        } else {
+            None
+        };
+
+        let having_clause = if self.consume_keyword(Keyword::Having) {
+            Some(HavingClause {
+                condition: self.parse_expr()?,
+            })
+        } else {
+            None
+        };
+
+        Ok(SelectStatement {
+            distinct,
+            columns,
+            from_clause,
+            where_clause,
+            group_by_clause,
+            having_clause,
+            set_operations: Vec::new(),
+            order_by_clause: None,
+            offset: None,
+            limit: None,
+        })
+    }
+
+    /* SELECT */
+
+    fn parse_result_columns(&mut self) -> Result<ResultColumns, ParserError> {
+        if self.consume_if(TokenKind::Asterisk) {
+            return Ok(ResultColumns::All);
+        }
+
+        Ok(ResultColumns::Specific(
+            self.parse_comma_separated(Self::parse_aliased_expr)?,
+        ))
+    }
+
+    fn parse_aliased_expr(&mut self) -> Result<AliasedExpr, ParserError> {
+        let expr = self.parse_expr()?;
+        let alias = self.parse_optional_alias()?;
+
+        Ok(AliasedExpr { expr, alias })
+    }
+
+    /// Parses a column name, which can be qualified by any name its table can be referred to by.
+    fn parse_column_name(&mut self) -> Result<ColumnName, ParserError> {
+        let start_position = self.position;
+        let mut parts = self.parse_dotted_name(ObjectName::MAX_PARTS + 1)?;
+        let name = parts.pop().unwrap();
+        let span = self.span_since(start_position);
+
+        if parts.is_empty() {
+            return Ok(ColumnName::Direct { name, span });
+        }
+
+        // The qualifier ends before the period preceding the column name
+        let table_name = ObjectName {
+            parts,
+            span: Span::new(span.start(), self.tokens[self.position - 3].span().end()),
+        };
+
+        Ok(ColumnName::Qualified {
+            table_name,
+            column_name: name,
+            span,
+        })
+    }
+
+    /// Parses `[AS] alias`, where the `AS` keyword is optional.
+    ///
+    /// Keywords can only be used as aliases after `AS`, as otherwise `FROM t LIMIT 1` would alias
+    /// `t` as `LIMIT`.
+    fn parse_optional_alias(&mut self) -> Result<Option<String>, ParserError> {
+        if self.consume_keyword(Keyword::As) {
+            return Ok(Some(self.parse_identifier()?));
+        }
+
+        match self.peek_kind() {
+            TokenKind::Identifier | TokenKind::QuotedIdentifier => {
+                self.implicit_alias = Some(self.position);
+                Ok(Some(self.parse_identifier()?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn parse_schema_name(&mut self) -> Result<SchemaName, ParserError> {
+        let mut parts = self.parse_dotted_name(2)?;
+        let name = parts.pop().unwrap();
+
+        Ok(SchemaName {
+            database: parts.pop(),
+            name,
+        })
+    }
+
+    fn parse_object_name(&mut self) -> Result<ObjectName, ParserError> {
+        let start_position = self.position;
+        let parts = self.parse_dotted_name(ObjectName::MAX_PARTS)?;
+
+        Ok(ObjectName {
+            parts,
+            span: self.span_since(start_position),
+        })
+    }
+
+    /// Parses up to `max_parts` identifiers separated by periods.
+    fn parse_dotted_name(&mut self, max_parts: usize) -> Result<Vec<String>, ParserError> {
+        let mut parts = vec![self.parse_identifier()?];
+
+        while self.peek_kind() == TokenKind::Period {
+            if parts.len() == max_parts {
+                return Err(ParserError::TooManyNameParts {
+                    max: max_parts,
+                    span: self.peek().span(),
+                });
+            }
+
+            self.next_token();
+            parts.push(self.parse_identifier()?);
+        }
+
+        Ok(parts)
+    }
+
+    /// Parses a plain or quoted identifier, or a non-reserved keyword used as one.
+    fn parse_identifier(&mut self) -> Result<String, ParserError> {
+        match self.peek_kind() {
+            TokenKind::QuotedIdentifier => Ok(unquote(self.next_token().literal())),
+            _ if self.peek_is_identifier() => Ok(self.next_token().literal().to_string()),
+            _ => self.unexpected("an identifier"),
+        }
+    }
+
+    /// Parses the name following `COLLATE`, which may also be a keyword such as `binary`.
+    fn parse_collation_name(&mut self) -> Result<String, ParserError> {
+        match self.peek_kind() {
+            TokenKind::Keyword(_) => Ok(self.next_token().literal().to_string()),
+            _ => self.parse_identifier(),
+        }
+    }
+
+    /* FROM */
+
+    fn parse_from_clause(&mut self) -> Result<FromClause, ParserError> {
+        let mut from_clause = FromClause::Direct {
+            table: self.parse_aliased_from_table()?,
+        };
+
+        while let Some(join_type) = self.parse_join_type()? {
+            let right = self.parse_aliased_from_table()?;
+
+            let join_condition = if join_type == JoinType::Cross {
+                None
+            } else if self.consume_keyword(Keyword::On) {
+                Some(JoinCondition::On(self.parse_expr()?))
+            } else if self.consume_keyword(Keyword::Using) {
+                self.expect(TokenKind::OpeningParen, "`(`")?;
+                let columns = self.parse_comma_separated(Self::parse_identifier)?;
+                self.expect(TokenKind::ClosingParen, "`)`")?;
+
+                Some(JoinCondition::Using(columns))
+            } else {
+                return self.unexpected("`ON` or `USING`");
+            };
+
+            from_clause = FromClause::Join {
+                left: Box::new(from_clause),
+                right: Box::new(right),
+                join_type,
+                join_condition,
+            };
+        }
+
+        Ok(from_clause)
+    }
+
+    /// Parses `[INNER] JOIN`, `LEFT|RIGHT|FULL [OUTER] JOIN`, `CROSS JOIN` or `,`, if present.
+    fn parse_join_type(&mut self) -> Result<Option<JoinType>, ParserError> {
+        let join_type = match self.peek_kind() {
+            TokenKind::Comma => {
+                self.next_token();
+                return Ok(Some(JoinType::Cross));
+            }
+            TokenKind::Keyword(Keyword::Join) => JoinType::Inner,
+            TokenKind::Keyword(Keyword::Inner) => {
+                self.next_token();
+                JoinType::Inner
+            }
+            TokenKind::Keyword(Keyword::Cross) => {
+                self.next_token();
+                JoinType::Cross
+            }
+            TokenKind::Keyword(keyword @ (Keyword::Left | Keyword::Right | Keyword::Full)) => {
+                self.next_token();
+                self.consume_keyword(Keyword::Outer);
+
+                match keyword {
+                    Keyword::Left => JoinType::LeftOuter,
+                    Keyword::Right => JoinType::RightOuter,
+                    _ => JoinType::FullOuter,
+                }
+            }
+            _ => return Ok(None),
+        };
+
+        self.expect_keyword(Keyword::Join)?;
+
+        Ok(Some(join_type))
+    }
+
+    /// Parses the rows after `VALUES`, like `(1, 'a'), (2, 'b')`.
+    fn parse_values_rows(&mut self) -> Result<Vec<Vec<Expr>>, ParserError> {
+        self.parse_comma_separated(|parser| {
+            parser.expect(TokenKind::OpeningParen, "`(`")?;
+            let row = parser.parse_comma_separated(Self::parse_expr)?;
+            parser.expect(TokenKind::ClosingParen, "`)`")?;
+            Ok(row)
+        })
+    }
+
+    fn parse_aliased_from_table(&mut self) -> Result<AliasedFromTable, ParserError> {
+        let start_position = self.position;
+        let lateral = self.consume_keyword(Keyword::Lateral);
+
+        let table = if !lateral
+            && self.peek_kind() == TokenKind::OpeningParen
+            && self.peek_nth_kind(1) == TokenKind::Keyword(Keyword::Values)
+        {
+            self.next_token();
+            self.next_token();
+
+            let rows = self.parse_values_rows()?;
+
+            self.expect(TokenKind::ClosingParen, "`)`")?;
+
+            FromTable::Values { rows }
+        } else if self.consume_if(TokenKind::OpeningParen) {
+            let query = Box::new(self.parse_select()?);
+            self.expect(TokenKind::ClosingParen, "`)`")?;
+
+            FromTable::DerivedTable { query, lateral }
+        } else if self.peek_is_identifier() && self.peek_nth_kind(1) == TokenKind::OpeningParen {
+            let name = self.parse_identifier()?;
+            self.expect(TokenKind::OpeningParen, "`(`")?;
+
+            let args = match self.peek_kind() {
+                TokenKind::ClosingParen => Vec::new(),
+                _ => self.parse_comma_separated(Self::parse_expr)?,
+            };
+
+            self.expect(TokenKind::ClosingParen, "`)`")?;
+
+            FromTable::Function {
+                name,
+                args,
+                lateral,
+            }
+        } else if lateral {
+            return self.unexpected("`(` or a function call");
+        } else {
+            FromTable::TableName {
+                name: self.parse_object_name()?,
+            }
+        };
+
+        let alias = self.parse_optional_alias()?;
+
+        Ok(AliasedFromTable {
+            table,
+            alias,
+            span: self.span_since(start_position),
+        })
+    }
+
+    /* ORDER BY / OFFSET / LIMIT */
+
+    fn parse_order_by_item(&mut self) -> Result<OrderByItem, ParserError> {
+        let column_name = self.parse_column_name()?;
+
+        let collation = match self.consume_keyword(Keyword::Collate) {
+            true => Some(self.parse_collation_name()?),
+            false => None,
+        };
+
+        let ordering = if self.consume_keyword(Keyword::Asc) {
+            Some(Ordering::Ascending)
+        } else if self.consume_keyword(Keyword::Desc) {
+            Some(Ordering::Descending)
+        } else {
+            None
+        };
+
+        Ok(OrderByItem {
+            column_name,
+            collation,
+            ordering,
+        })
+    }
+
+    fn parse_unsigned_integer(&mut self) -> Result<u64, ParserError> {
+        let token = self.expect(TokenKind::IntegerLiteral, "an integer")?;
+
+        token
+            .literal()
+            .parse()
+            .map_err(|_| ParserError::InvalidInteger {
+                literal: token.literal().to_string(),
+                span: token.span(),
+            })
+    }
+
+    /* Expressions */
+
+    pub fn parse_expr(&mut self) -> Result<Expr, ParserError> {
+        self.parse_expr_with_precedence(Precedence::Lowest)
+    }
+
+    fn parse_expr_with_precedence(&mut self, precedence: Precedence) -> Result<Expr, ParserError> {
+        let mut expr = self.parse_prefix_expr()?;
+
+        loop {
+            self.expect_keywords(&[Keyword::Or, Keyword::And, Keyword::Is, Keyword::Collate]);
+            let next_precedence = self.peek_infix_precedence();
+
+            if next_precedence <= precedence {
+                break;
+            }
+
+            expr = self.parse_infix_expr(expr, next_precedence)?;
+        }
+
+        Ok(expr)
+    }
+
+    fn peek_infix_precedence(&self) -> Precedence {
+        match self.peek_kind() {
+            TokenKind::Keyword(Keyword::Or) => Precedence::Or,
+            TokenKind::Keyword(Keyword::And) => Precedence::And,
+            TokenKind::Keyword(Keyword::Is) => Precedence::Is,
+            TokenKind::Keyword(Keyword::Collate) => Precedence::Collate,
+            TokenKind::DoubleColon => Precedence::Cast,
+            TokenKind::Equals
+            | TokenKind::NotEquals
+            | TokenKind::LessThan
+            | TokenKind::LessThanOrEqual
+            | TokenKind::GreaterThan
+            | TokenKind::GreaterThanOrEqual => Precedence::Comparison,
+            TokenKind::Arrow
+            | TokenKind::LongArrow
+            | TokenKind::HashArrow
+            | TokenKind::HashLongArrow
+            | TokenKind::AtArrow
+            | TokenKind::ArrowAt
+            | TokenKind::Question
+            | TokenKind::QuestionPipe
+            | TokenKind::QuestionAmpersand => Precedence::Other,
+            TokenKind::Plus | TokenKind::Minus => Precedence::Additive,
+            TokenKind::Asterisk | TokenKind::ForwardSlash | TokenKind::Percent => {
+                Precedence::Multiplicative
+            }
+            _ => Precedence::Lowest,
+        }
+    }
+
+    fn parse_infix_expr(
+        &mut self,
+        left: Expr,
+        precedence: Precedence,
+    ) -> Result<Expr, ParserError> {
+        let token = self.next_token();
+
+        let operator = match token.kind() {
+            TokenKind::Keyword(Keyword::Is) => {
+                let negated = self.consume_keyword(Keyword::Not);
+                self.expect_keyword(Keyword::Null)?;
+
+                return Ok(Expr::IsNull {
+                    operand: Box::new(left),
+                    negated,
+                });
+            }
+            TokenKind::Keyword(Keyword::Collate) => {
+                return Ok(Expr::Collate {
+                    operand: Box::new(left),
+                    collation: self.parse_collation_name()?,
+                });
+            }
+            TokenKind::DoubleColon => {
+                return Ok(Expr::Cast {
+                    operand: Box::new(left),
+                    data_type: self.parse_type_name()?,
+                });
+            }
+            TokenKind::Keyword(Keyword::Or) => BinaryOperator::Or,
+            TokenKind::Keyword(Keyword::And) => BinaryOperator::And,
+            TokenKind::Equals => BinaryOperator::Equals,
+            TokenKind::NotEquals => BinaryOperator::NotEquals,
+            TokenKind::LessThan => BinaryOperator::LessThan,
+            TokenKind::LessThanOrEqual => BinaryOperator::LessThanOrEqual,
+            TokenKind::GreaterThan => BinaryOperator::GreaterThan,
+            TokenKind::GreaterThanOrEqual => BinaryOperator::GreaterThanOrEqual,
+            TokenKind::Plus => BinaryOperator::Plus,
+            TokenKind::Minus => BinaryOperator::Minus,
+            TokenKind::Asterisk => BinaryOperator::Multiply,
+            TokenKind::ForwardSlash => BinaryOperator::Divide,
+            TokenKind::Percent => BinaryOperator::Modulo,
+            TokenKind::Arrow => BinaryOperator::JsonGet,
+            TokenKind::LongArrow => BinaryOperator::JsonGetText,
+            TokenKind::HashArrow => BinaryOperator::JsonGetPath,
+            TokenKind::HashLongArrow => BinaryOperator::JsonGetPathText,
+            TokenKind::AtArrow => BinaryOperator::Contains,
+            TokenKind::ArrowAt => BinaryOperator::ContainedBy,
+            TokenKind::Question => BinaryOperator::HasKey,
+            TokenKind::QuestionPipe => BinaryOperator::HasAnyKey,
+            TokenKind::QuestionAmpersand => BinaryOperator::HasAllKeys,
+            _ => unreachable!("Token {:?} has no infix precedence", token),
+        };
+
+        let right = self.parse_expr_with_precedence(precedence)?;
+
+        Ok(Expr::Binary {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        })
+    }
+
+    fn parse_prefix_expr(&mut self) -> Result<Expr, ParserError> {
+        let operator = match self.peek_kind() {
+            TokenKind::Keyword(Keyword::Not) => UnaryOperator::Not,
+            TokenKind::Plus => UnaryOperator::Plus,
+            TokenKind::Minus => UnaryOperator::Minus,
+            _ => return self.parse_primary_expr(),
+        };
+
+        self.next_token();
+
+        Ok(Expr::Unary {
+            operator,
+            operand: Box::new(self.parse_expr_with_precedence(operator.precedence())?),
+        })
+    }
+
+    fn parse_primary_expr(&mut self) -> Result<Expr, ParserError> {
+        match self.peek_kind() {
+            TokenKind::IntegerLiteral | TokenKind::FloatLiteral => Ok(Expr::Literal(
+                Literal::Number(self.next_token().literal().to_string()),
+            )),
+            TokenKind::StringLiteral => Ok(Expr::Literal(Literal::String(unquote(
+                self.next_token().literal(),
+            )))),
+            TokenKind::Keyword(Keyword::True) => {
+                self.next_token();
+                Ok(Expr::Literal(Literal::Boolean(true)))
+            }
+            TokenKind::Keyword(Keyword::False) => {
+                self.next_token();
+                Ok(Expr::Literal(Literal::Boolean(false)))
+            }
+            TokenKind::Keyword(Keyword::Null) => {
+                self.next_token();
+                Ok(Expr::Literal(Literal::Null))
+            }
+            TokenKind::OpeningParen => {
+                self.next_token();
+                let expr = self.parse_expr()?;
+                self.expect(TokenKind::ClosingParen, "`)`")?;
+
+                Ok(Expr::Nested(Box::new(expr)))
+            }
+            TokenKind::Keyword(Keyword::Extract)
+                if self.peek_nth_kind(1) == TokenKind::OpeningParen =>
+            {
+                self.parse_extract()
+            }
+            TokenKind::Keyword(Keyword::Cast) => self.parse_cast(),
+            // A type name followed by a string, like `DATE '2024-01-01'`
+            _ if self.peek_is_identifier() && self.peek_nth_kind(1) == TokenKind::StringLiteral => {
+                let data_type = self.parse_type_name()?;
+                let text = unquote(self.next_token().literal());
+
+                Ok(Expr::Cast {
+                    operand: Box::new(Expr::Literal(Literal::String(text))),
+                    data_type,
+                })
+            }
+            _ if self.peek_is_identifier() && self.peek_nth_kind(1) == TokenKind::OpeningParen => {
+                self.parse_function_call()
+            }
+            _ if self.peek_is_identifier() => Ok(Expr::Column(self.parse_column_name()?)),
+            _ => self.unexpected("an expression"),
+        }
+    }
+
+    fn parse_function_call(&mut self) -> Result<Expr, ParserError> {
+        let name = self.parse_identifier()?;
+        self.expect(TokenKind::OpeningParen, "`(`")?;
+
+        let distinct = self.consume_keyword(Keyword::Distinct);
+
+        let args = if !distinct && self.consume_if(TokenKind::Asterisk) {
+            FunctionArgs::Wildcard
+        } else if !distinct && self.peek_kind() == TokenKind::ClosingParen {
+            FunctionArgs::List(Vec::new())
+        } else {
+            FunctionArgs::List(self.parse_comma_separated(Self::parse_expr)?)
+        };
+
+        let order_by = match self.consume_keyword(Keyword::Order) {
+            true => {
+                self.expect_keyword(Keyword::By)?;
+                self.parse_comma_separated(Self::parse_order_by_expr)?
+            }
+            false => Vec::new(),
+        };
+
+        self.expect(TokenKind::ClosingParen, "`)`")?;
+
+        // `FILTER` is not reserved, so it is only taken to start a filter when `(` follows it
+        let filter = match self.peek_kind() == TokenKind::Keyword(Keyword::Filter)
+            && self.peek_nth_kind(1) == TokenKind::OpeningParen
+        {
+            true => {
+                self.next_token();
+                self.next_token();
+                self.expect_keyword(Keyword::Where)?;
+                let condition = self.parse_expr()?;
+                self.expect(TokenKind::ClosingParen, "`)`")?;
+
+                Some(Box::new(condition))
+            }
+            false => None,
+        };
+
+        Ok(Expr::Function {
+            name,
+            args,
+            distinct,
+            order_by,
+            filter,
+        })
+    }
+
+    fn parse_order_by_expr(&mut self) -> Result<OrderByExpr, ParserError> {
+        let expr = self.parse_expr()?;
+
+        let ordering = if self.consume_keyword(Keyword::Asc) {
+            Some(Ordering::Ascending)
+        } else if self.consume_keyword(Keyword::Desc) {
+            Some(Ordering::Descending)
+        } else {
+            None
+        };
+
+        Ok(OrderByExpr { expr, ordering })
+    }
+
+    /// Parses `EXTRACT(field FROM a)`, which is another way of writing `date_part('field', a)`.
+    fn parse_extract(&mut self) -> Result<Expr, ParserError> {
+        self.expect_keyword(Keyword::Extract)?;
+        self.expect(TokenKind::OpeningParen, "`(`")?;
+
+        // Fields such as `YEAR` and `HOUR` are keywords, so any word is accepted
+        let field = match self.peek_kind() {
+            TokenKind::Identifier | TokenKind::Keyword(_) => {
+                self.next_token().literal().to_ascii_lowercase()
+            }
+            TokenKind::StringLiteral => unquote(self.next_token().literal()),
+            _ => return self.unexpected("a field such as `YEAR` or `EPOCH`"),
+        };
+
+        self.expect_keyword(Keyword::From)?;
+        let source = self.parse_expr()?;
+        self.expect(TokenKind::ClosingParen, "`)`")?;
+
+        Ok(Expr::Function {
+            name: "date_part".to_string(),
+            args: FunctionArgs::List(vec![Expr::Literal(Literal::String(field)), source]),
+            distinct: false,
+            order_by: Vec::new(),
+            filter: None,
+        })
+    }
+
+    /// Parses `CAST(a AS INT)`.
+    fn parse_cast(&mut self) -> Result<Expr, ParserError> {
+        self.expect_keyword(Keyword::Cast)?;
+        self.expect(TokenKind::OpeningParen, "`(`")?;
+        let operand = self.parse_expr()?;
+        self.expect_keyword(Keyword::As)?;
+        let data_type = self.parse_type_name()?;
+        self.expect(TokenKind::ClosingParen, "`)`")?;
+
+        Ok(Expr::Cast {
+            operand: Box::new(operand),
+            data_type,
+        })
+    }
+
+    /* Utilities */
+
+    fn parse_comma_separated<T>(
+        &mut self,
+        mut parse_fn: impl FnMut(&mut Self) -> Result<T, ParserError>,
+    ) -> Result<Vec<T>, ParserError> {
+        let mut items = vec![parse_fn(self)?];
+
+        while self.consume_if(TokenKind::Comma) {
+            items.push(parse_fn(self)?);
+        }
+
+        Ok(items)
+    }
+}
+
+impl ParserError {
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            ParserError::UnexpectedToken { suggestion, .. } => suggestion.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Strips the surrounding quotes from a quoted literal and collapses doubled quote characters.
+fn unquote(literal: &str) -> String {
+    let quote = &literal[..1];
+    let inner = &literal[1..literal.len() - 1];
+
+    inner.replace(&quote.repeat(2), quote)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql::parser::{StatementParseError, StatementParser};
+
+    /// The keyword suggested for the error parsing `sql` fails with, and the text it is at.
+    fn suggestion(sql: &str) -> (Option<&str>, &str) {
+        match StatementParser::parse(sql) {
+            Err(StatementParseError::ParserError(ParserError::UnexpectedToken {
+                span,
+                suggestion,
+                ..
+            })) => (
+                suggestion.map(|keyword| Keyword::lookup(&keyword).unwrap().as_str()),
+                &sql[span.start()..span.end()],
+            ),
+            result => panic!("{sql}: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn misspelled_keywords() {
+        assert_eq!(
+            suggestion("SELECT a FROM t WHERE a = 1 ANDD b = 2"),
+            (Some("AND"), "ANDD")
+        );
+        assert_eq!(suggestion("SELCT a FROM t"), (Some("SELECT"), "SELCT"));
+        assert_eq!(
+            suggestion("CREATE TABEL t (a INT)"),
+            (Some("TABLE"), "TABEL")
+        );
+        assert_eq!(
+            suggestion("EXPLAIN (FORMAT JSNO) SELECT a FROM t"),
+            (Some("JSON"), "JSNO")
+        );
+        assert_eq!(
+            suggestion("SELECT a FROM t JOIN u USNG (a)"),
+            (Some("USING"), "USNG")
+        );
+
+        // Taken as aliases, misspelled keywords are only found out by the token after them
+        assert_eq!(suggestion("SELECT a FORM users"), (Some("FROM"), "FORM"));
+        assert_eq!(
+            suggestion("SELECT a FROM t WHRE a = 1"),
+            (Some("WHERE"), "WHRE")
+        );
+        assert_eq!(
+            suggestion("SELECT a FROM t ORDR BY a"),
+            (Some("ORDER"), "ORDR")
+        );
+    }
+
+    #[test]
+    fn no_suggestions() {
+        // Identifiers which fail for other reasons are not taken for keywords
+        assert_eq!(suggestion("SELECT a FROM t x y"), (None, "y"));
+        assert_eq!(suggestion("SELECT a b c FROM t"), (None, "c"));
+        assert_eq!(suggestion("SELECT a FROM t WHERE a = 1 b"), (None, "b"));
+        assert_eq!(suggestion("SELECT a FROM t WHERE a = 1 AS"), (None, "AS"));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::keyword::Keyword;
+use crate::sql::keyword::Keyword;
 
 #[derive(Debug, Clone)]
 pub struct Token<'a> {
@@ -16,21 +16,54 @@ pub enum TokenKind {
     Minus,
     Asterisk,
     ForwardSlash,
+    Percent,
     Equals,
     Comma,
     Period,
+    OpeningParen,
+    ClosingParen,
     OpeningBracket,
     ClosingBracket,
     OpeningBrace,
     ClosingBrace,
+    LessThan,
+    GreaterThan,
+    Question,
+    /* Multi Char */
+    NotEquals,
+    LessThanOrEqual,
+    GreaterThanOrEqual,
+    /// `->`
+    Arrow,
+    /// `->>`
+    LongArrow,
+    /// `#>`
+    HashArrow,
+    /// `#>>`
+    HashLongArrow,
+    /// `@>`
+    AtArrow,
+    /// `<@`
+    ArrowAt,
+    /// `?|`
+    QuestionPipe,
+    /// `?&`
+    QuestionAmpersand,
+    /// `::`
+    DoubleColon,
     /* Other */
+    /// `-- comment` or `/* comment */`, which the parser never sees
+    Comment,
     Identifier,
+    QuotedIdentifier,
     Keyword(Keyword),
-    /* Numbers */
+    /* Literals */
     IntegerLiteral,
     FloatLiteral,
+    StringLiteral,
 }
 
+/// A range of byte offsets into the input, which are always at character boundaries.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Span {
     start: usize,
@@ -69,10 +102,9 @@ impl<'a> Token<'a> {
 
 impl TokenKind {
     pub fn from_keyword_or_identifier(literal: &str) -> Self {
-        if let Ok(keyword) = literal.parse::<Keyword>() {
-            Self::Keyword(keyword)
-        } else {
-            Self::Identifier
+        match Keyword::lookup(literal) {
+            Some(keyword) => Self::Keyword(keyword),
+            None => Self::Identifier,
         }
     }
 }
@@ -99,4 +131,18 @@ impl Span {
             end: position,
         }
     }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
 }
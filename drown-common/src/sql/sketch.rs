@@ -0,0 +1,770 @@
+use std::{f64::consts::PI, hash::Hasher};
+
+use thiserror::Error;
+
+use crate::sql::{
+    datetime::MICROS_PER_DAY,
+    jsonb::{JsonbKind, JsonbRef},
+    value::{Decimal, Value},
+};
+
+/// Sketches are stored as `BYTEA` values starting with one of these tags, so that a sketch of
+/// one kind is never mistaken for another.
+const SPARSE_HLL: u8 = 1;
+const DENSE_HLL: u8 = 2;
+const TDIGEST: u8 = 3;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SketchError {
+    #[error("Value is not a valid {0} sketch")]
+    Invalid(&'static str),
+}
+
+/// A HyperLogLog sketch, which estimates the number of distinct values it has seen with a
+/// standard error of about 0.8% in at most 16 KiB.
+///
+/// Values are hashed to 64 bits, as in HLL++, so that there is no need to correct for hash
+/// collisions however many values there are, and cardinalities below `5/2 m` for `m` registers
+/// are estimated with linear counting, where the raw estimate is biased. Registers are kept in a
+/// sparse list of those which are set until the list grows as large as the dense registers.
+///
+/// The sketch is kept in its serialized form, so that it can be updated in place as the state
+/// of an aggregate, stored in a `BYTEA` column, and merged with others later.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HyperLogLog {
+    /// A tag, the precision, and either sorted 3-byte entries of a 2-byte register index and
+    /// its value or a byte for every register
+    bytes: Vec<u8>,
+}
+
+impl HyperLogLog {
+    /// The number of bits of the hash which choose a register.
+    const PRECISION: u8 = 14;
+    const REGISTERS: usize = 1 << Self::PRECISION;
+    const HEADER: usize = 2;
+    const ENTRY: usize = 3;
+
+    pub fn new() -> HyperLogLog {
+        HyperLogLog {
+            bytes: vec![SPARSE_HLL, Self::PRECISION],
+        }
+    }
+
+    /// Reads a sketch written by `into_bytes`, checking that it is well formed.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<HyperLogLog, SketchError> {
+        let invalid = SketchError::Invalid("HyperLogLog");
+
+        let (tag, precision, registers) = match &bytes[..] {
+            [tag, precision, registers @ ..] => (*tag, *precision, registers),
+            _ => return Err(invalid),
+        };
+
+        if precision != Self::PRECISION {
+            return Err(invalid);
+        }
+
+        let max_rank = 64 - Self::PRECISION + 1;
+
+        let valid = match tag {
+            SPARSE_HLL => {
+                registers.len().is_multiple_of(Self::ENTRY)
+                    && registers
+                        .chunks_exact(Self::ENTRY)
+                        .all(|entry| (1..=max_rank).contains(&entry[2]))
+                    && registers
+                        .chunks_exact(Self::ENTRY)
+                        .map(|entry| u16::from_be_bytes([entry[0], entry[1]]) as usize)
+                        .try_fold(None, |previous, index| {
+                            (previous < Some(index) && index < Self::REGISTERS)
+                                .then_some(Some(index))
+                        })
+                        .is_some()
+            }
+            DENSE_HLL => {
+                registers.len() == Self::REGISTERS && registers.iter().all(|rank| *rank <= max_rank)
+            }
+            _ => false,
+        };
+
+        match valid {
+            true => Ok(HyperLogLog { bytes }),
+            false => Err(invalid),
+        }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Wraps the bytes of a sketch kept as the state of an aggregate, which need no checking.
+    pub(crate) fn from_state(bytes: Vec<u8>) -> HyperLogLog {
+        HyperLogLog { bytes }
+    }
+
+    pub fn insert(&mut self, value: &Value) {
+        let hash = stable_hash(value);
+
+        let index = (hash >> (64 - Self::PRECISION)) as usize;
+        // The position of the first set bit among the rest, which is how many values the
+        // register has probably seen in logarithmic terms
+        let rank = ((hash << Self::PRECISION) | (1 << (Self::PRECISION - 1))).leading_zeros() + 1;
+
+        self.set(index, rank as u8);
+    }
+
+    /// Adds the values another sketch has seen to this one.
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        match other.bytes[0] {
+            SPARSE_HLL => {
+                for (index, rank) in other.sparse_entries() {
+                    self.set(index, rank);
+                }
+            }
+            _ => {
+                self.densify();
+
+                for (register, rank) in self.bytes[Self::HEADER..]
+                    .iter_mut()
+                    .zip(&other.bytes[Self::HEADER..])
+                {
+                    *register = (*register).max(*rank);
+                }
+            }
+        }
+    }
+
+    /// The estimated number of distinct values the sketch has seen.
+    pub fn estimate(&self) -> u64 {
+        let m = Self::REGISTERS as f64;
+
+        let ranks: Box<dyn Iterator<Item = u8>> = match self.bytes[0] {
+            SPARSE_HLL => Box::new(
+                self.sparse_entries()
+                    .map(|(_, rank)| rank)
+                    .chain(std::iter::repeat_n(0, Self::REGISTERS - self.sparse_len())),
+            ),
+            _ => Box::new(self.bytes[Self::HEADER..].iter().copied()),
+        };
+
+        let (sum, zeros) = ranks.fold((0.0, 0), |(sum, zeros), rank| {
+            (
+                sum + 2f64.powi(-(rank as i32)),
+                zeros + (rank == 0) as usize,
+            )
+        });
+
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let raw = alpha * m * m / sum;
+
+        match raw <= 2.5 * m && zeros > 0 {
+            true => (m * (m / zeros as f64).ln()).round() as u64,
+            false => raw.round() as u64,
+        }
+    }
+
+    fn set(&mut self, index: usize, rank: u8) {
+        if self.bytes[0] == DENSE_HLL {
+            let register = &mut self.bytes[Self::HEADER + index];
+            *register = (*register).max(rank);
+            return;
+        }
+
+        let position = self.sparse_search(index);
+
+        match position {
+            Ok(position) => {
+                let entry = Self::HEADER + position * Self::ENTRY + 2;
+                self.bytes[entry] = self.bytes[entry].max(rank);
+            }
+            Err(position) => {
+                let offset = Self::HEADER + position * Self::ENTRY;
+                let [high, low] = (index as u16).to_be_bytes();
+                self.bytes.splice(offset..offset, [high, low, rank]);
+
+                // Past this size the dense registers take less space
+                if self.sparse_len() * Self::ENTRY > Self::REGISTERS {
+                    self.densify();
+                }
+            }
+        }
+    }
+
+    fn densify(&mut self) {
+        if self.bytes[0] == DENSE_HLL {
+            return;
+        }
+
+        let mut bytes = vec![0; Self::HEADER + Self::REGISTERS];
+        bytes[0] = DENSE_HLL;
+        bytes[1] = Self::PRECISION;
+
+        for (index, rank) in self.sparse_entries() {
+            bytes[Self::HEADER + index] = rank;
+        }
+
+        self.bytes = bytes;
+    }
+
+    fn sparse_len(&self) -> usize {
+        (self.bytes.len() - Self::HEADER) / Self::ENTRY
+    }
+
+    fn sparse_entries(&self) -> impl Iterator<Item = (usize, u8)> + '_ {
+        self.bytes[Self::HEADER..]
+            .chunks_exact(Self::ENTRY)
+            .map(|entry| (u16::from_be_bytes([entry[0], entry[1]]) as usize, entry[2]))
+    }
+
+    /// Finds the entry of a register in the sparse list, or where it would be inserted.
+    fn sparse_search(&self, index: usize) -> Result<usize, usize> {
+        let (mut low, mut high) = (0, self.sparse_len());
+
+        while low < high {
+            let middle = (low + high) / 2;
+            let offset = Self::HEADER + middle * Self::ENTRY;
+            let entry = u16::from_be_bytes([self.bytes[offset], self.bytes[offset + 1]]) as usize;
+
+            match entry.cmp(&index) {
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater => high = middle,
+                std::cmp::Ordering::Equal => return Ok(middle),
+            }
+        }
+
+        Err(low)
+    }
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A t-digest, which estimates quantiles of the numbers it has seen, most accurately close to
+/// the minimum and maximum, in a few kilobytes.
+///
+/// Numbers are clustered into centroids with a mean and a weight, where the scale function
+/// `k(q) = δ/2π · asin(2q - 1)` keeps the centroids near the tails small. Numbers are appended
+/// as centroids of their own and only merged once enough of them have been buffered.
+///
+/// Like `HyperLogLog`, the digest is kept in its serialized form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TDigest {
+    /// A tag, the minimum and the maximum, and the mean and weight of every centroid
+    bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+impl TDigest {
+    /// The compression δ, which bounds the number of merged centroids to about δ/2.
+    const COMPRESSION: f64 = 100.0;
+    /// The number of centroids after which the digest is merged.
+    const BUFFER: usize = 500;
+    const HEADER: usize = 17;
+    const CENTROID: usize = 16;
+
+    pub fn new() -> TDigest {
+        let mut bytes = vec![TDIGEST];
+        bytes.extend(f64::INFINITY.to_le_bytes());
+        bytes.extend(f64::NEG_INFINITY.to_le_bytes());
+
+        TDigest { bytes }
+    }
+
+    /// Reads a digest written by `into_bytes`, checking that it is well formed.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<TDigest, SketchError> {
+        let digest = TDigest { bytes };
+
+        let valid = digest.bytes.len() >= Self::HEADER
+            && digest.bytes[0] == TDIGEST
+            && (digest.bytes.len() - Self::HEADER).is_multiple_of(Self::CENTROID)
+            && digest
+                .centroids()
+                .all(|centroid| !centroid.mean.is_nan() && centroid.weight > 0.0);
+
+        match valid {
+            true => Ok(digest),
+            false => Err(SketchError::Invalid("t-digest")),
+        }
+    }
+
+    /// The digest with its centroids merged.
+    pub fn into_bytes(mut self) -> Vec<u8> {
+        self.compress();
+        self.bytes
+    }
+
+    /// Wraps the bytes of a digest kept as the state of an aggregate, which need no checking.
+    pub(crate) fn from_state(bytes: Vec<u8>) -> TDigest {
+        TDigest { bytes }
+    }
+
+    /// The digest without merging its buffered centroids.
+    pub(crate) fn into_state(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Adds a number, where `NaN` is ignored.
+    pub fn insert(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+
+        let (min, max) = self.bounds();
+        self.set_bounds(min.min(value), max.max(value));
+        self.push(Centroid {
+            mean: value,
+            weight: 1.0,
+        });
+    }
+
+    /// Adds the numbers another digest has seen to this one.
+    pub fn merge(&mut self, other: &TDigest) {
+        let ((min, max), (other_min, other_max)) = (self.bounds(), other.bounds());
+        self.set_bounds(min.min(other_min), max.max(other_max));
+
+        for centroid in other.centroids() {
+            self.push(centroid);
+        }
+    }
+
+    /// Estimates the number below which `fraction` of the numbers lie, interpolating between
+    /// the means of neighbouring centroids, or `None` if the digest has seen no numbers.
+    pub fn quantile(&self, fraction: f64) -> Option<f64> {
+        let mut digest = self.clone();
+        digest.compress();
+
+        let centroids = digest.centroids().collect::<Vec<_>>();
+        let (min, max) = digest.bounds();
+
+        let (first, last) = (centroids.first()?, centroids.last()?);
+        let total = centroids
+            .iter()
+            .map(|centroid| centroid.weight)
+            .sum::<f64>();
+        let target = fraction * total;
+
+        // The numbers of half a centroid lie on either side of its mean, so the quantiles
+        // before the first mean and after the last are interpolated from the extremes
+        if target < first.weight / 2.0 {
+            return Some(min + (first.mean - min) * target / (first.weight / 2.0));
+        }
+
+        let mut cumulative = first.weight / 2.0;
+
+        for pair in centroids.windows(2) {
+            let span = (pair[0].weight + pair[1].weight) / 2.0;
+
+            if target < cumulative + span {
+                let t = (target - cumulative) / span;
+                return Some(pair[0].mean + (pair[1].mean - pair[0].mean) * t);
+            }
+
+            cumulative += span;
+        }
+
+        let rest = (target - cumulative) / (last.weight / 2.0);
+
+        Some((last.mean + (max - last.mean) * rest).min(max))
+    }
+
+    fn push(&mut self, centroid: Centroid) {
+        self.bytes.extend(centroid.mean.to_le_bytes());
+        self.bytes.extend(centroid.weight.to_le_bytes());
+
+        if self.len() > Self::BUFFER {
+            self.compress();
+        }
+    }
+
+    /// Sorts the centroids and merges neighbours while the merged centroid spans at most one
+    /// unit of the scale function.
+    fn compress(&mut self) {
+        let mut centroids = self.centroids().collect::<Vec<_>>();
+        centroids.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+
+        let total = centroids
+            .iter()
+            .map(|centroid| centroid.weight)
+            .sum::<f64>();
+
+        let k = |q: f64| Self::COMPRESSION / (2.0 * PI) * (2.0 * q - 1.0).asin();
+        let k_inverse = |k: f64| {
+            let k = k.min(Self::COMPRESSION / 4.0);
+            ((2.0 * PI * k / Self::COMPRESSION).sin() + 1.0) / 2.0
+        };
+
+        let mut merged = Vec::<Centroid>::new();
+        let mut weight_before = 0.0;
+        let mut limit = 0.0;
+
+        for centroid in centroids {
+            match merged.last_mut() {
+                Some(last) if weight_before + last.weight + centroid.weight <= limit => {
+                    let weight = last.weight + centroid.weight;
+                    last.mean += (centroid.mean - last.mean) * centroid.weight / weight;
+                    last.weight = weight;
+                }
+                _ => {
+                    if let Some(last) = merged.last() {
+                        weight_before += last.weight;
+                    }
+
+                    limit = total * k_inverse(k(weight_before / total) + 1.0);
+                    merged.push(centroid);
+                }
+            }
+        }
+
+        self.bytes.truncate(Self::HEADER);
+
+        for centroid in merged {
+            self.bytes.extend(centroid.mean.to_le_bytes());
+            self.bytes.extend(centroid.weight.to_le_bytes());
+        }
+    }
+
+    fn len(&self) -> usize {
+        (self.bytes.len() - Self::HEADER) / Self::CENTROID
+    }
+
+    fn centroids(&self) -> impl Iterator<Item = Centroid> + '_ {
+        self.bytes[Self::HEADER..]
+            .chunks_exact(Self::CENTROID)
+            .map(|chunk| Centroid {
+                mean: read_f64(&chunk[..8]),
+                weight: read_f64(&chunk[8..]),
+            })
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        (read_f64(&self.bytes[1..9]), read_f64(&self.bytes[9..17]))
+    }
+
+    fn set_bounds(&mut self, min: f64, max: f64) {
+        self.bytes[1..9].copy_from_slice(&min.to_le_bytes());
+        self.bytes[9..17].copy_from_slice(&max.to_le_bytes());
+    }
+}
+
+impl Default for TDigest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn read_f64(bytes: &[u8]) -> f64 {
+    f64::from_le_bytes(bytes.try_into().unwrap())
+}
+
+/// Hashes a value the same way in every process and release, as sketches are stored.
+fn stable_hash(value: &Value) -> u64 {
+    let mut bytes = Vec::new();
+    write_value(value, &mut bytes);
+
+    let mut hasher = Fnv1a(0xcbf29ce484222325);
+    hasher.write(&bytes);
+    hasher.finish()
+}
+
+/// Writes a value as bytes which only depend on the value, rather than on how `Hash` is
+/// implemented on the platform, so that values equal across types, like `1` as an `INT` and as
+/// a `BIGINT`, are written the same.
+fn write_value(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Null => out.push(0),
+        Value::Boolean(value) => out.extend([1, *value as u8]),
+        Value::SmallInt(value) => write_integer(*value as i64, out),
+        Value::Int(value) => write_integer(*value as i64, out),
+        Value::BigInt(value) => write_integer(*value, out),
+        Value::Decimal(value) => write_decimal(value, out),
+        Value::Real(value) => write_float(*value as f64, out),
+        Value::Double(value) => write_float(*value, out),
+        Value::Text(value) => {
+            out.push(5);
+            out.extend(value.as_bytes());
+        }
+        Value::Bytea(value) => {
+            out.push(6);
+            out.extend(value);
+        }
+        Value::Date(days) => {
+            out.push(7);
+            out.extend(days.to_le_bytes());
+        }
+        Value::Time(micros) => {
+            out.push(8);
+            out.extend(micros.to_le_bytes());
+        }
+        Value::Timestamp(micros) => {
+            out.push(9);
+            out.extend(micros.to_le_bytes());
+        }
+        Value::TimestampTz(micros) => {
+            out.push(10);
+            out.extend(micros.to_le_bytes());
+        }
+        // Intervals of the same length are equal, like `1 mon` and `30 days`
+        Value::Interval(interval) => {
+            let micros = (interval.months as i128 * 30 + interval.days as i128)
+                * MICROS_PER_DAY as i128
+                + interval.micros as i128;
+
+            out.push(11);
+            out.extend(micros.to_le_bytes());
+        }
+        Value::Uuid(bytes) => {
+            out.push(12);
+            out.extend(bytes);
+        }
+        Value::Jsonb(jsonb) => {
+            out.push(13);
+            write_jsonb(jsonb.root(), out);
+        }
+    }
+}
+
+fn write_integer(value: i64, out: &mut Vec<u8>) {
+    out.push(2);
+    out.extend(value.to_le_bytes());
+}
+
+/// Writes whole decimals like integers, and others without their trailing zeros.
+fn write_decimal(value: &Decimal, out: &mut Vec<u8>) {
+    let value = value.normalized();
+
+    match i64::try_from(value.mantissa()) {
+        Ok(integer) if value.scale() == 0 => write_integer(integer, out),
+        _ => {
+            out.push(3);
+            out.extend(value.mantissa().to_le_bytes());
+            out.extend(value.scale().to_le_bytes());
+        }
+    }
+}
+
+fn write_float(value: f64, out: &mut Vec<u8>) {
+    let value = match value {
+        _ if value.is_nan() => f64::NAN,
+        _ if value == 0.0 => 0.0,
+        _ => value,
+    };
+
+    out.push(4);
+    out.extend(value.to_bits().to_le_bytes());
+}
+
+/// Writes a JSON value with the lengths of strings, arrays and objects, so that the bytes of
+/// different documents never run together the same way.
+fn write_jsonb(json: JsonbRef<'_>, out: &mut Vec<u8>) {
+    match json.kind() {
+        JsonbKind::Null => out.push(0),
+        JsonbKind::Boolean => out.extend([1, (json.to_text().as_deref() == Some("true")) as u8]),
+        JsonbKind::Number => {
+            let text = json.to_string();
+
+            match Decimal::parse(&text) {
+                Ok(decimal) => write_decimal(&decimal, out),
+                Err(_) => write_float(text.parse().unwrap_or(f64::NAN), out),
+            }
+        }
+        JsonbKind::String => {
+            let text = json.as_str().unwrap_or_default();
+
+            out.push(5);
+            out.extend((text.len() as u64).to_le_bytes());
+            out.extend(text.as_bytes());
+        }
+        JsonbKind::Array => {
+            out.push(14);
+            out.extend((json.len() as u64).to_le_bytes());
+
+            for element in json.elements() {
+                write_jsonb(element, out);
+            }
+        }
+        JsonbKind::Object => {
+            out.push(15);
+            out.extend((json.len() as u64).to_le_bytes());
+
+            for (key, value) in json.entries() {
+                out.extend((key.len() as u64).to_le_bytes());
+                out.extend(key.as_bytes());
+                write_jsonb(value, out);
+            }
+        }
+    }
+}
+
+/// The FNV-1a hash, finished with the finalizer of MurmurHash3 so that every bit of the result
+/// depends on every bit of the input, which HyperLogLog relies on.
+struct Fnv1a(u64);
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        let mut hash = self.0;
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+        hash ^ (hash >> 33)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hll(values: impl IntoIterator<Item = i64>) -> HyperLogLog {
+        let mut sketch = HyperLogLog::new();
+
+        for value in values {
+            sketch.insert(&Value::BigInt(value));
+        }
+
+        sketch
+    }
+
+    fn digest(values: impl IntoIterator<Item = i64>) -> TDigest {
+        let mut digest = TDigest::new();
+
+        for value in values {
+            digest.insert(value as f64);
+        }
+
+        digest
+    }
+
+    /// Asserts that an estimate is within `tolerance` of the actual count, relatively.
+    fn assert_close(estimate: f64, actual: f64, tolerance: f64) {
+        let error = (estimate - actual).abs() / actual;
+        assert!(error <= tolerance, "estimated {estimate} for {actual}");
+    }
+
+    #[test]
+    fn hll_estimates() {
+        assert_eq!(HyperLogLog::new().estimate(), 0);
+        assert_eq!(hll([1, 1, 2, 2, 3]).estimate(), 3);
+
+        assert_close(hll(0..1_000).estimate() as f64, 1_000.0, 0.02);
+        assert_close(hll(0..100_000).estimate() as f64, 100_000.0, 0.03);
+    }
+
+    #[test]
+    fn hll_merge() {
+        // Sparse into sparse, dense into sparse, and sparse into dense
+        for (a, b) in [
+            (0..500, 250..750),
+            (0..500, 250..50_000),
+            (0..50_000, 250..750),
+        ] {
+            let mut merged = hll(a.clone());
+            merged.merge(&hll(b.clone()));
+
+            let union = hll(a.start.min(b.start)..a.end.max(b.end));
+            assert_eq!(merged.estimate(), union.estimate());
+        }
+    }
+
+    #[test]
+    fn hll_bytes() {
+        for sketch in [hll(0..10), hll(0..50_000)] {
+            let estimate = sketch.estimate();
+            let sketch = HyperLogLog::from_bytes(sketch.into_bytes()).unwrap();
+            assert_eq!(sketch.estimate(), estimate);
+        }
+
+        let mut dense = vec![DENSE_HLL, HyperLogLog::PRECISION];
+        dense.resize(HyperLogLog::HEADER + HyperLogLog::REGISTERS, 0);
+        assert!(HyperLogLog::from_bytes(dense.clone()).is_ok());
+
+        let invalid = [
+            vec![],
+            vec![SPARSE_HLL],
+            vec![SPARSE_HLL, 10],
+            vec![TDIGEST, HyperLogLog::PRECISION],
+            // Entries which are cut short, have rank 0 or a rank past the largest possible one
+            vec![SPARSE_HLL, HyperLogLog::PRECISION, 0, 1],
+            vec![SPARSE_HLL, HyperLogLog::PRECISION, 0, 1, 0],
+            vec![SPARSE_HLL, HyperLogLog::PRECISION, 0, 1, 52],
+            // Registers which are out of order, repeated or out of range
+            vec![SPARSE_HLL, HyperLogLog::PRECISION, 0, 2, 1, 0, 1, 1],
+            vec![SPARSE_HLL, HyperLogLog::PRECISION, 0, 1, 1, 0, 1, 1],
+            vec![SPARSE_HLL, HyperLogLog::PRECISION, 0x40, 0, 1],
+            dense[..dense.len() - 1].to_vec(),
+            [&dense[..], &[0]].concat(),
+            [&dense[..dense.len() - 1], &[52]].concat(),
+        ];
+
+        for bytes in invalid {
+            assert!(HyperLogLog::from_bytes(bytes.clone()).is_err(), "{bytes:?}");
+        }
+    }
+
+    #[test]
+    fn tdigest_quantiles() {
+        assert_eq!(TDigest::new().quantile(0.5), None);
+        assert_eq!(digest([7]).quantile(0.5), Some(7.0));
+
+        let digest = digest(1..=10_000);
+        assert_eq!(digest.quantile(0.0), Some(1.0));
+        assert_eq!(digest.quantile(1.0), Some(10_000.0));
+        assert_close(digest.quantile(0.5).unwrap(), 5_000.0, 0.01);
+        assert_close(digest.quantile(0.99).unwrap(), 9_900.0, 0.001);
+        assert_close(digest.quantile(0.01).unwrap(), 100.0, 0.05);
+    }
+
+    #[test]
+    fn tdigest_merge() {
+        let mut merged = digest((1..=10_000).filter(|value| value % 2 == 0));
+        merged.merge(&digest((1..=10_000).filter(|value| value % 2 == 1)));
+        merged.merge(&TDigest::new());
+
+        assert_eq!(merged.quantile(0.0), Some(1.0));
+        assert_eq!(merged.quantile(1.0), Some(10_000.0));
+        assert_close(merged.quantile(0.5).unwrap(), 5_000.0, 0.01);
+        assert_close(merged.quantile(0.9).unwrap(), 9_000.0, 0.01);
+    }
+
+    #[test]
+    fn tdigest_bytes() {
+        let digest = digest(1..=1_000);
+        let median = digest.quantile(0.5);
+        let bytes = digest.into_bytes();
+
+        assert_eq!(
+            TDigest::from_bytes(bytes.clone()).unwrap().quantile(0.5),
+            median
+        );
+        assert!(TDigest::from_bytes(TDigest::new().into_bytes()).is_ok());
+
+        let centroid = |mean: f64, weight: f64| [mean.to_le_bytes(), weight.to_le_bytes()].concat();
+        let header = &bytes[..TDigest::HEADER];
+
+        let invalid = [
+            vec![],
+            [&[SPARSE_HLL], &header[1..]].concat(),
+            header[..TDigest::HEADER - 1].to_vec(),
+            [&bytes[..], &[0]].concat(),
+            [header, &centroid(f64::NAN, 1.0)].concat(),
+            [header, &centroid(1.0, 0.0)].concat(),
+            [header, &centroid(1.0, -1.0)].concat(),
+        ];
+
+        for bytes in invalid {
+            assert!(TDigest::from_bytes(bytes.clone()).is_err(), "{bytes:?}");
+        }
+    }
+}
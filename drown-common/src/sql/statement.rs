@@ -0,0 +1,817 @@
+use crate::sql::parser::Span;
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum Statement {
+    Select(SelectStatement),
+    Insert(InsertStatement),
+    Update(UpdateStatement),
+    Delete(DeleteStatement),
+    Explain(ExplainStatement),
+    CreateView(CreateViewStatement),
+    DropView(DropViewStatement),
+    CreateMaterializedView(CreateMaterializedViewStatement),
+    RefreshMaterializedView(RefreshMaterializedViewStatement),
+    DropMaterializedView(DropViewStatement),
+    CreateDatabase(CreateDatabaseStatement),
+    CreateSchema(CreateSchemaStatement),
+    DropSchema(DropSchemaStatement),
+    CreateTable(CreateTableStatement),
+    CreateFunction(CreateFunctionStatement),
+    DropFunction(DropFunctionStatement),
+    Use(UseStatement),
+    SetSearchPath(SetSearchPathStatement),
+    SetTimeZone(SetTimeZoneStatement),
+    ShowDatabases,
+    ShowSchemas,
+    ShowTables,
+    ShowColumns(ShowColumnsStatement),
+    ShowCreateTable(ShowCreateTableStatement),
+    ShowSearchPath,
+    ShowTimeZone,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectStatement {
+    /// SELECT DISTINCT
+    pub distinct: bool,
+    pub columns: ResultColumns,
+    pub from_clause: FromClause,
+    pub where_clause: Option<WhereClause>,
+    pub group_by_clause: Option<GroupByClause>,
+    pub having_clause: Option<HavingClause>,
+    /// The queries combined with this one, in the order they are written. ORDER BY, OFFSET and
+    /// LIMIT apply to the combined rows.
+    pub set_operations: Vec<SetOperation>,
+    pub order_by_clause: Option<OrderByClause>,
+    pub offset: Option<OffsetClause>,
+    pub limit: Option<LimitClause>,
+}
+
+/* SELECT */
+
+/// UNION SELECT ...
+/// INTERSECT ALL (SELECT ... LIMIT 1)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetOperation {
+    pub operator: SetOperator,
+    /// Whether duplicate rows are kept
+    pub all: bool,
+    pub query: SelectStatement,
+}
+
+/// INTERSECT binds more tightly than UNION and EXCEPT, which are evaluated from left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOperator {
+    Union,
+    Intersect,
+    Except,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResultColumns {
+    /// SELECT *
+    All,
+    /// SELECT a, b, c
+    /// or
+    /// SELECT a AS b, c + 1 AS d
+    Specific(Vec<AliasedExpr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AliasedExpr {
+    pub expr: Expr,
+    pub alias: Option<String>,
+}
+
+/// `span` covers the whole name, including its qualifier.
+#[derive(Debug, Clone)]
+pub enum ColumnName {
+    /// a
+    Direct { name: String, span: Span },
+    /// a.b
+    /// c.a.b
+    /// d.c.a.b
+    Qualified {
+        table_name: ObjectName,
+        column_name: String,
+        span: Span,
+    },
+}
+
+impl ColumnName {
+    /// The name of the column itself, without any qualifier.
+    pub fn name(&self) -> &str {
+        match self {
+            ColumnName::Direct { name, .. } => name,
+            ColumnName::Qualified { column_name, .. } => column_name,
+        }
+    }
+
+    pub fn qualifier(&self) -> Option<&ObjectName> {
+        match self {
+            ColumnName::Direct { .. } => None,
+            ColumnName::Qualified { table_name, .. } => Some(table_name),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            ColumnName::Direct { span, .. } | ColumnName::Qualified { span, .. } => *span,
+        }
+    }
+}
+
+/// Spans are not compared, so that the same name written in two places is equal.
+impl PartialEq for ColumnName {
+    fn eq(&self, other: &Self) -> bool {
+        self.qualifier() == other.qualifier() && self.name() == other.name()
+    }
+}
+
+/* FROM */
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FromClause {
+    /// FROM a
+    /// FROM a AS b
+    /// FROM (SELECT ...)
+    /// FROM (SELECT ...) AS a
+    Direct { table: AliasedFromTable },
+    /// FROM a JOIN b ON ...
+    /// FROM a CROSS JOIN b
+    /// FROM a, b
+    ///
+    /// Joins nest to the left, so that `a, b JOIN c ON ...` joins `c` to the join of `a` and `b`.
+    Join {
+        left: Box<FromClause>,
+        right: Box<AliasedFromTable>,
+        join_type: JoinType,
+        /// `None` for cross joins, which have no condition
+        join_condition: Option<JoinCondition>,
+    },
+}
+
+impl FromClause {
+    /// Every table of the clause, in the order they are written.
+    pub fn tables(&self) -> Vec<&AliasedFromTable> {
+        match self {
+            FromClause::Direct { table } => vec![table],
+            FromClause::Join { left, right, .. } => {
+                let mut tables = left.tables();
+                tables.push(right);
+                tables
+            }
+        }
+    }
+}
+
+/// (SELECT ...) [AS a]
+/// a [AS b]
+/// f(...) [AS a]
+#[derive(Debug, Clone)]
+pub struct AliasedFromTable {
+    pub table: FromTable,
+    pub alias: Option<String>,
+    /// Covers the table and its alias
+    pub span: Span,
+}
+
+/// Spans are not compared, so that the same table written in two places is equal.
+impl PartialEq for AliasedFromTable {
+    fn eq(&self, other: &Self) -> bool {
+        self.table == other.table && self.alias == other.alias
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FromTable {
+    /// (SELECT ...)
+    /// LATERAL (SELECT ...)
+    DerivedTable {
+        query: Box<SelectStatement>,
+        /// Whether the query can refer to the columns of the tables before it in FROM
+        lateral: bool,
+    },
+    /// (VALUES (1, 'a'), (2, 'b'))
+    ///
+    /// The columns are named column1, column2, ...
+    Values { rows: Vec<Vec<Expr>> },
+    /// a
+    /// b.a
+    /// c.b.a
+    TableName { name: ObjectName },
+    /// f(...)
+    /// LATERAL f(...)
+    Function {
+        name: String,
+        args: Vec<Expr>,
+        /// Whether `LATERAL` was written, which changes nothing, as the arguments can always
+        /// refer to the columns of the tables before the function in FROM
+        lateral: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JoinType {
+    Inner,
+    Cross,
+    LeftOuter,
+    RightOuter,
+    FullOuter,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JoinCondition {
+    On(Expr),
+    Using(Vec<String>),
+}
+
+/* NAMES */
+
+/// The name of a table or view, optionally qualified by its schema, or by its database and
+/// schema.
+///
+/// a
+/// b.a
+/// c.b.a
+#[derive(Debug, Clone)]
+pub struct ObjectName {
+    /// Never empty, and never longer than `ObjectName::MAX_PARTS`
+    pub parts: Vec<String>,
+    /// Empty for names which were not written in a statement
+    pub span: Span,
+}
+
+/// Spans are not compared, so that the same name written in two places is equal.
+impl PartialEq for ObjectName {
+    fn eq(&self, other: &Self) -> bool {
+        self.parts == other.parts
+    }
+}
+
+impl Eq for ObjectName {}
+
+impl std::hash::Hash for ObjectName {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.parts.hash(state);
+    }
+}
+
+impl ObjectName {
+    /// The number of parts in a fully qualified name, `database.schema.name`
+    pub const MAX_PARTS: usize = 3;
+
+    pub fn unqualified(name: impl Into<String>) -> Self {
+        Self {
+            parts: vec![name.into()],
+            span: Span::empty(),
+        }
+    }
+
+    /// The name of the object itself, without any qualifiers.
+    pub fn name(&self) -> &str {
+        &self.parts[self.parts.len() - 1]
+    }
+
+    pub fn schema(&self) -> Option<&str> {
+        self.parts.iter().rev().nth(1).map(String::as_str)
+    }
+
+    pub fn database(&self) -> Option<&str> {
+        self.parts.iter().rev().nth(2).map(String::as_str)
+    }
+}
+
+/* EXPRESSIONS */
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// 1, 'a', TRUE, NULL
+    Literal(Literal),
+    /// a
+    /// a.b
+    Column(ColumnName),
+    /// -a
+    /// NOT a
+    Unary {
+        operator: UnaryOperator,
+        operand: Box<Expr>,
+    },
+    /// a + b
+    /// a AND b
+    Binary {
+        left: Box<Expr>,
+        operator: BinaryOperator,
+        right: Box<Expr>,
+    },
+    /// a IS NULL
+    /// a IS NOT NULL
+    IsNull { operand: Box<Expr>, negated: bool },
+    /// f(a, b)
+    /// f(*)
+    /// f(DISTINCT a ORDER BY b) FILTER (WHERE c)
+    Function {
+        name: String,
+        args: FunctionArgs,
+        /// Whether only distinct arguments are aggregated
+        distinct: bool,
+        /// The order an aggregate receives its rows in
+        order_by: Vec<OrderByExpr>,
+        /// The condition rows have to meet to be aggregated
+        filter: Option<Box<Expr>>,
+    },
+    /// CAST(a AS INT)
+    /// a::INT
+    /// DATE '2024-01-01'
+    Cast {
+        operand: Box<Expr>,
+        data_type: TypeName,
+    },
+    /// a COLLATE nocase
+    Collate {
+        operand: Box<Expr>,
+        collation: String,
+    },
+    /// (a)
+    Nested(Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    /// Numeric literals are kept as written so that no precision is lost before they are typed
+    Number(String),
+    String(String),
+    Boolean(bool),
+    Null,
+}
+
+/// An expression sorted by, `a [ASC | DESC]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderByExpr {
+    pub expr: Expr,
+    pub ordering: Option<Ordering>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FunctionArgs {
+    /// f(*)
+    Wildcard,
+    /// f(a, b)
+    List(Vec<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOperator {
+    Plus,
+    Minus,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Or,
+    And,
+    Equals,
+    NotEquals,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    Plus,
+    Minus,
+    Multiply,
+    Divide,
+    Modulo,
+    /// `->`, the value of a key of a JSON object or an element of a JSON array
+    JsonGet,
+    /// `->>`, like `->` but as text
+    JsonGetText,
+    /// `#>`, the value at a path of keys and array indexes
+    JsonGetPath,
+    /// `#>>`, like `#>` but as text
+    JsonGetPathText,
+    /// `@>`
+    Contains,
+    /// `<@`
+    ContainedBy,
+    /// `?`, whether a JSON value has a key
+    HasKey,
+    /// `?|`
+    HasAnyKey,
+    /// `?&`
+    HasAllKeys,
+}
+
+/// Binding power of operators, from loosest to tightest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Precedence {
+    Lowest,
+    Or,
+    And,
+    Not,
+    Is,
+    Comparison,
+    /// Operators without a precedence of their own, like the JSON operators, which bind more
+    /// loosely than arithmetic as in PostgreSQL
+    Other,
+    Additive,
+    Multiplicative,
+    Collate,
+    Unary,
+    /// `a::INT`, which binds more tightly than a sign, so that `-a::INT` casts `a`
+    Cast,
+    /// Literals, columns, function calls and parenthesized expressions
+    Primary,
+}
+
+impl Expr {
+    /// How tightly the expression binds, which decides whether it has to be parenthesized when
+    /// it is the operand of another operator.
+    pub fn precedence(&self) -> Precedence {
+        match self {
+            Expr::Unary { operator, .. } => operator.precedence(),
+            Expr::Binary { operator, .. } => operator.precedence(),
+            Expr::IsNull { .. } => Precedence::Is,
+            Expr::Collate { .. } => Precedence::Collate,
+            // Casts are always written as `CAST(a AS INT)` or as typed literals
+            Expr::Literal(_)
+            | Expr::Column(_)
+            | Expr::Function { .. }
+            | Expr::Cast { .. }
+            | Expr::Nested(_) => Precedence::Primary,
+        }
+    }
+}
+
+impl UnaryOperator {
+    pub fn precedence(&self) -> Precedence {
+        match self {
+            UnaryOperator::Not => Precedence::Not,
+            UnaryOperator::Plus | UnaryOperator::Minus => Precedence::Unary,
+        }
+    }
+}
+
+impl BinaryOperator {
+    pub fn precedence(&self) -> Precedence {
+        match self {
+            BinaryOperator::Or => Precedence::Or,
+            BinaryOperator::And => Precedence::And,
+            BinaryOperator::Equals
+            | BinaryOperator::NotEquals
+            | BinaryOperator::LessThan
+            | BinaryOperator::LessThanOrEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterThanOrEqual => Precedence::Comparison,
+            BinaryOperator::Plus | BinaryOperator::Minus => Precedence::Additive,
+            BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => {
+                Precedence::Multiplicative
+            }
+            BinaryOperator::JsonGet
+            | BinaryOperator::JsonGetText
+            | BinaryOperator::JsonGetPath
+            | BinaryOperator::JsonGetPathText
+            | BinaryOperator::Contains
+            | BinaryOperator::ContainedBy
+            | BinaryOperator::HasKey
+            | BinaryOperator::HasAnyKey
+            | BinaryOperator::HasAllKeys => Precedence::Other,
+        }
+    }
+}
+
+/* WHERE */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhereClause {
+    pub condition: Expr,
+}
+
+/* GROUP BY */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupByClause {
+    /// Expressions over the input columns, where a bare name which is not an input column
+    /// names a result column
+    pub groupings: Vec<Expr>,
+}
+
+/* HAVING */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HavingClause {
+    pub condition: Expr,
+}
+
+/* ORDER BY */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderByClause {
+    pub orderings: Vec<OrderByItem>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderByItem {
+    pub column_name: ColumnName,
+    pub collation: Option<String>,
+    pub ordering: Option<Ordering>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ordering {
+    Ascending,
+    Descending,
+}
+
+/* OFFSET */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OffsetClause {
+    pub offset: u64,
+}
+
+/* LIMIT */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LimitClause {
+    pub limit: u64,
+}
+
+/* INSERT */
+
+/// INSERT INTO a [(b, c)] VALUES (1, 'a'), (2, 'b')
+/// INSERT INTO a [(b, c)] SELECT ...
+#[derive(Debug, Clone, PartialEq)]
+pub struct InsertStatement {
+    pub table: ObjectName,
+    /// The columns the values are given for, in order, or empty if they are given for every
+    /// column of the table
+    pub columns: Vec<String>,
+    pub source: InsertSource,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InsertSource {
+    Values { rows: Vec<Vec<Expr>> },
+    Select(Box<SelectStatement>),
+}
+
+/* UPDATE */
+
+/// UPDATE a SET b = 1, c = c + 1 [WHERE ...]
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdateStatement {
+    pub table: ObjectName,
+    pub assignments: Vec<Assignment>,
+    pub where_clause: Option<WhereClause>,
+}
+
+/// b = c + 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+    pub column: String,
+    pub value: Expr,
+}
+
+/* DELETE */
+
+/// DELETE FROM a [WHERE ...]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeleteStatement {
+    pub table: ObjectName,
+    pub where_clause: Option<WhereClause>,
+}
+
+/* EXPLAIN */
+
+/// EXPLAIN [ANALYZE] [VERBOSE] [FORMAT TEXT|JSON] <statement>, where the statement is a SELECT,
+/// INSERT, UPDATE or DELETE, also written with its options in parentheses, like
+/// EXPLAIN (FORMAT JSON, ANALYZE) <statement>
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplainStatement {
+    pub analyze: bool,
+    pub verbose: bool,
+    pub format: ExplainFormat,
+    pub statement: Box<Statement>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExplainFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/* CREATE VIEW */
+
+/// CREATE [OR REPLACE] VIEW a [(b, c)] AS SELECT ... [WITH [CASCADED | LOCAL] CHECK OPTION]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateViewStatement {
+    pub or_replace: bool,
+    pub name: ObjectName,
+    pub columns: Vec<String>,
+    pub query: Box<SelectStatement>,
+    /// The SQL text of `query` exactly as it appeared in the input
+    pub query_text: String,
+    pub check_option: Option<CheckOption>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckOption {
+    /// WITH LOCAL CHECK OPTION
+    Local,
+    /// WITH [CASCADED] CHECK OPTION
+    Cascaded,
+}
+
+/* CREATE MATERIALIZED VIEW */
+
+/// CREATE MATERIALIZED VIEW a [(b, c)] [WITH (incremental)] AS SELECT ... [WITH [NO] DATA]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateMaterializedViewStatement {
+    pub name: ObjectName,
+    pub columns: Vec<String>,
+    /// Whether the view is kept up to date as its base tables change, instead of on refresh
+    pub incremental: bool,
+    pub query: Box<SelectStatement>,
+    /// The SQL text of `query` exactly as it appeared in the input
+    pub query_text: String,
+    pub with_data: bool,
+}
+
+/* REFRESH MATERIALIZED VIEW */
+
+/// REFRESH MATERIALIZED VIEW [CONCURRENTLY] a [WITH [NO] DATA]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefreshMaterializedViewStatement {
+    /// Only checks that the view is populated, since statements run one at a time and every
+    /// refresh keeps the old rows readable until the new ones replace them
+    pub concurrently: bool,
+    pub name: ObjectName,
+    pub with_data: bool,
+}
+
+/* DROP [MATERIALIZED] VIEW */
+
+/// DROP [MATERIALIZED] VIEW [IF EXISTS] a [, b ...] [CASCADE | RESTRICT]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropViewStatement {
+    pub if_exists: bool,
+    pub names: Vec<ObjectName>,
+    /// Whether the views reading from the views are dropped with them, rather than preventing
+    /// the views from being dropped
+    pub cascade: bool,
+}
+
+/* CREATE DATABASE */
+
+/// CREATE DATABASE [IF NOT EXISTS] a
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateDatabaseStatement {
+    pub if_not_exists: bool,
+    pub name: String,
+}
+
+/* SCHEMAS */
+
+/// a
+/// b.a
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaName {
+    pub database: Option<String>,
+    pub name: String,
+}
+
+/// CREATE SCHEMA [IF NOT EXISTS] a
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateSchemaStatement {
+    pub if_not_exists: bool,
+    pub name: SchemaName,
+}
+
+/// DROP SCHEMA [IF EXISTS] a [, b ...] [CASCADE | RESTRICT]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropSchemaStatement {
+    pub if_exists: bool,
+    pub names: Vec<SchemaName>,
+    /// Whether the tables and views in the schemas are dropped with them, rather than preventing
+    /// the schemas from being dropped
+    pub cascade: bool,
+}
+
+/* CREATE TABLE */
+
+/// CREATE TABLE [IF NOT EXISTS] a (b INT [NOT NULL], c VARCHAR(20) [COLLATE nocase] [NULL], ...)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateTableStatement {
+    pub if_not_exists: bool,
+    pub name: ObjectName,
+    pub columns: Vec<ColumnDefinition>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnDefinition {
+    pub name: String,
+    pub data_type: TypeName,
+    /// The collation text in the column is compared with, if it is not the default
+    pub collation: Option<String>,
+    pub nullable: bool,
+}
+
+/// INT
+/// VARCHAR(20)
+/// DECIMAL(10, 2)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeName {
+    /// Always upper case
+    pub name: String,
+    pub modifiers: Vec<u64>,
+}
+
+/* FUNCTIONS */
+
+/// CREATE [OR REPLACE] FUNCTION a ([b] INT, ...) RETURNS INT LANGUAGE wasm
+/// [IMMUTABLE | STABLE | VOLATILE] AS '\x0061736d...' [, 'c']
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateFunctionStatement {
+    pub or_replace: bool,
+    pub name: String,
+    pub params: Vec<FunctionParam>,
+    pub returns: TypeName,
+    /// Always lower case
+    pub language: String,
+    pub volatility: Volatility,
+    /// The body of the function, which for `wasm` is the module written as a `BYTEA` value
+    pub definition: String,
+    /// The name of the export implementing the function, if it is not the function's own name
+    pub symbol: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionParam {
+    pub name: Option<String>,
+    pub data_type: TypeName,
+}
+
+/// What the result of a function can depend on other than its arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Volatility {
+    /// Nothing, so calls with constant arguments can be evaluated once while planning
+    Immutable,
+    /// The state of the database and the session, which does not change within a statement
+    Stable,
+    /// Anything, which is the default
+    Volatile,
+}
+
+/// DROP FUNCTION [IF EXISTS] a (INT, ...) [CASCADE | RESTRICT]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropFunctionStatement {
+    pub if_exists: bool,
+    pub name: String,
+    pub params: Vec<TypeName>,
+    /// Whether the views calling the function are dropped with it, rather than preventing the
+    /// function from being dropped
+    pub cascade: bool,
+}
+
+/* USE */
+
+/// USE a
+#[derive(Debug, Clone, PartialEq)]
+pub struct UseStatement {
+    pub database: String,
+}
+
+/* SET */
+
+/// SET search_path {TO | =} {a [, b ...] | DEFAULT}
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetSearchPathStatement {
+    /// `None` for `DEFAULT`
+    pub schemas: Option<Vec<String>>,
+}
+
+/// SET TIME ZONE {'a' | LOCAL | DEFAULT}
+/// SET timezone {TO | =} {'a' | DEFAULT}
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetTimeZoneStatement {
+    /// `None` for `LOCAL` and `DEFAULT`
+    pub time_zone: Option<String>,
+}
+
+/* SHOW */
+
+/// SHOW COLUMNS FROM a
+/// DESCRIBE a
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShowColumnsStatement {
+    pub table: ObjectName,
+}
+
+/// SHOW CREATE TABLE a
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShowCreateTableStatement {
+    pub table: ObjectName,
+}
@@ -0,0 +1,33 @@
+/// Finds the candidate closest to `name` by edit distance, ignoring ASCII case, as long as it is
+/// close enough to plausibly be what was meant.
+///
+/// Ties are resolved in favour of the earliest candidate.
+pub fn closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (candidate, edit_distance(name, candidate)))
+        .filter(|(candidate, distance)| *distance <= name.len().max(candidate.len()) / 2)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+/// The Levenshtein distance between two strings, ignoring ASCII case.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.as_bytes();
+
+    // Distances from the current prefix of `a` to every prefix of `b`
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a_byte) in a.bytes().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, b_byte) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(!a_byte.eq_ignore_ascii_case(b_byte));
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+
+    row[b.len()]
+}
@@ -0,0 +1,1459 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Write},
+    hash::{Hash, Hasher},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::sql::{
+    data_type::DataType,
+    datetime::{
+        civil_from_days, days_from_civil, days_in_month, split, write_offset, TimeZone,
+        MICROS_PER_DAY, MICROS_PER_HOUR, MICROS_PER_MINUTE, MICROS_PER_SECOND,
+    },
+    jsonb::Jsonb,
+};
+
+/// A single value of any `DataType`, as stored in a table, produced by an expression or sent to
+/// a client.
+///
+/// Values are ordered, compared and hashed the way `GROUP BY` and `ORDER BY` treat them: `NULL`
+/// equals itself and sorts after everything else, and floating point values are totally
+/// ordered, with `NaN` after every number. Values of different types are never equal, so they
+/// have to be coerced to a common type before they are compared.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Value {
+    Null,
+    Boolean(bool),
+    SmallInt(i16),
+    Int(i32),
+    BigInt(i64),
+    Decimal(Decimal),
+    Real(f32),
+    Double(f64),
+    /// A `TEXT` or `VARCHAR` value
+    Text(String),
+    Bytea(Vec<u8>),
+    /// Days since 1970-01-01
+    Date(i32),
+    /// Microseconds since midnight
+    Time(i64),
+    /// Microseconds since 1970-01-01 00:00:00, in no particular time zone
+    Timestamp(i64),
+    /// Microseconds since 1970-01-01 00:00:00 UTC
+    TimestampTz(i64),
+    Interval(Interval),
+    Uuid([u8; 16]),
+    Jsonb(Jsonb),
+}
+
+/// An exact decimal number, `mantissa / 10^scale`, of at most `Decimal::MAX_PRECISION` digits.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+/// A span of time, whose months and days are kept apart from its time of day as their length
+/// varies.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub micros: i64,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ValueError {
+    #[error("Invalid {data_type} value `{text}`")]
+    InvalidText { data_type: DataType, text: String },
+    #[error("{data_type} value `{text}` is out of range")]
+    OutOfRange { data_type: DataType, text: String },
+    #[error("Value `{text}` is too long for {data_type}")]
+    TooLong { data_type: DataType, text: String },
+    #[error("{from} cannot be cast to {to}")]
+    InvalidCast { from: DataType, to: DataType },
+}
+
+impl Value {
+    /// The type of the value, which is `TEXT` for both `TEXT` and `VARCHAR` values.
+    pub fn data_type(&self) -> DataType {
+        match self {
+            Value::Null => DataType::Null,
+            Value::Boolean(_) => DataType::Boolean,
+            Value::SmallInt(_) => DataType::SmallInt,
+            Value::Int(_) => DataType::Int,
+            Value::BigInt(_) => DataType::BigInt,
+            Value::Decimal(_) => DataType::Decimal(None),
+            Value::Real(_) => DataType::Real,
+            Value::Double(_) => DataType::Double,
+            Value::Text(_) => DataType::Text,
+            Value::Bytea(_) => DataType::Bytea,
+            Value::Date(_) => DataType::Date,
+            Value::Time(_) => DataType::Time,
+            Value::Timestamp(_) => DataType::Timestamp,
+            Value::TimestampTz(_) => DataType::TimestampTz,
+            Value::Interval(_) => DataType::Interval,
+            Value::Uuid(_) => DataType::Uuid,
+            Value::Jsonb(_) => DataType::Jsonb,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// The number of bytes the value takes up in memory, including those it owns.
+    pub fn size(&self) -> usize {
+        let owned = match self {
+            Value::Text(text) => text.capacity(),
+            Value::Bytea(bytes) => bytes.capacity(),
+            Value::Jsonb(jsonb) => jsonb.size(),
+            _ => 0,
+        };
+
+        std::mem::size_of::<Value>() + owned
+    }
+
+    /// Parses the text representation of a value of type `data_type`, which is what `Display`
+    /// writes as well as the usual ways of writing values in string literals.
+    pub fn parse(text: &str, data_type: DataType) -> Result<Value, ValueError> {
+        Self::parse_in(text, data_type, &TimeZone::UTC)
+    }
+
+    /// Parses the text representation of a value like `parse`, reading `TIMESTAMPTZ` values
+    /// without an offset or time zone in the local time of `time_zone`.
+    pub fn parse_in(
+        text: &str,
+        data_type: DataType,
+        time_zone: &TimeZone,
+    ) -> Result<Value, ValueError> {
+        let invalid = || ValueError::InvalidText {
+            data_type,
+            text: text.to_string(),
+        };
+
+        let out_of_range = || ValueError::OutOfRange {
+            data_type,
+            text: text.to_string(),
+        };
+
+        // Integers which are too large are out of range rather than invalid
+        let integer = |text: &str| text.trim().parse::<i128>().map_err(|_| invalid());
+
+        let value = match data_type {
+            DataType::Null => return Err(invalid()),
+            DataType::Boolean => match text.trim().to_ascii_lowercase().as_str() {
+                "true" | "t" | "yes" | "y" | "on" | "1" => Value::Boolean(true),
+                "false" | "f" | "no" | "n" | "off" | "0" => Value::Boolean(false),
+                _ => return Err(invalid()),
+            },
+            DataType::SmallInt => {
+                Value::SmallInt(i16::try_from(integer(text)?).map_err(|_| out_of_range())?)
+            }
+            DataType::Int => Value::Int(i32::try_from(integer(text)?).map_err(|_| out_of_range())?),
+            DataType::BigInt => {
+                Value::BigInt(i64::try_from(integer(text)?).map_err(|_| out_of_range())?)
+            }
+            DataType::Decimal(limits) => {
+                let decimal = Decimal::parse(text.trim()).and_then(|decimal| match limits {
+                    Some((precision, scale)) => decimal.fit(precision, scale),
+                    None => Ok(decimal),
+                });
+
+                match decimal {
+                    Ok(decimal) => Value::Decimal(decimal),
+                    Err(DecimalError::Invalid) => return Err(invalid()),
+                    Err(_) => return Err(out_of_range()),
+                }
+            }
+            DataType::Real => Value::Real(text.trim().parse().map_err(|_| invalid())?),
+            DataType::Double => Value::Double(text.trim().parse().map_err(|_| invalid())?),
+            DataType::Text => Value::Text(text.to_string()),
+            DataType::Varchar(limit) => {
+                if limit.is_some_and(|limit| text.chars().count() > limit as usize) {
+                    return Err(ValueError::TooLong {
+                        data_type,
+                        text: text.to_string(),
+                    });
+                }
+
+                Value::Text(text.to_string())
+            }
+            DataType::Bytea => Value::Bytea(parse_bytea(text).ok_or_else(invalid)?),
+            DataType::Date => Value::Date(parse_date(text.trim()).ok_or_else(invalid)?),
+            DataType::Time => Value::Time(parse_time(text.trim()).ok_or_else(invalid)?),
+            DataType::Timestamp => {
+                let (micros, offset) = parse_timestamp(text.trim()).ok_or_else(invalid)?;
+
+                match offset {
+                    Some(_) => return Err(invalid()),
+                    None => Value::Timestamp(micros),
+                }
+            }
+            DataType::TimestampTz => {
+                let (micros, zone) = parse_timestamp(text.trim()).ok_or_else(invalid)?;
+                Value::TimestampTz(zone.as_ref().unwrap_or(time_zone).to_utc(micros))
+            }
+            DataType::Interval => Value::Interval(Interval::parse(text.trim())?),
+            DataType::Uuid => Value::Uuid(parse_uuid(text.trim()).ok_or_else(invalid)?),
+            DataType::Jsonb => Value::Jsonb(Jsonb::parse(text).map_err(|_| invalid())?),
+        };
+
+        Ok(value)
+    }
+
+    /// Writes the value like `Display`, showing `TIMESTAMPTZ` values in the local time of
+    /// `time_zone`.
+    pub fn to_string_in(&self, time_zone: &TimeZone) -> String {
+        match self {
+            Value::TimestampTz(micros) => {
+                let mut text = String::new();
+                write_timestamp_tz(&mut text, *micros, time_zone).unwrap();
+                text
+            }
+            value => value.to_string(),
+        }
+    }
+
+    /// Converts the value to `target` the way `CAST` does, reading `TIMESTAMPTZ` values in and
+    /// writing them as the local time of `time_zone`.
+    ///
+    /// Values are converted through their text form unless both types are numbers or dates and
+    /// times. Unlike when values are stored, text is cut short to fit into a `VARCHAR` and
+    /// numbers are rounded to fit into an integer.
+    pub fn cast(&self, target: DataType, time_zone: &TimeZone) -> Result<Value, ValueError> {
+        let out_of_range = || ValueError::OutOfRange {
+            data_type: target,
+            text: self.to_string_in(time_zone),
+        };
+
+        let value = match (self, target) {
+            (Value::Null, _) => Value::Null,
+            (value, DataType::Varchar(Some(limit))) => Value::Text(
+                value
+                    .to_string_in(time_zone)
+                    .chars()
+                    .take(limit as usize)
+                    .collect(),
+            ),
+            (value, DataType::Text | DataType::Varchar(None)) => {
+                Value::Text(value.to_string_in(time_zone))
+            }
+            (Value::Text(text), _) => Value::parse_in(text, target, time_zone)?,
+            (value, _) if value.data_type() == target => value.clone(),
+            (Value::Boolean(value), _) if target.is_integer() => {
+                Value::Int(*value as i32).cast(target, time_zone)?
+            }
+            (value, DataType::Boolean) if value.data_type().is_integer() => {
+                Value::Boolean(value.to_string() != "0")
+            }
+            (value, _) if value.data_type().is_numeric() && target.is_integer() => {
+                let integer = match value {
+                    Value::Decimal(decimal) => decimal
+                        .round(0, RoundingMode::HalfUp)
+                        .map_err(|_| out_of_range())?
+                        .mantissa(),
+                    Value::Real(value) => {
+                        float_to_integer(*value as f64).ok_or_else(out_of_range)?
+                    }
+                    Value::Double(value) => float_to_integer(*value).ok_or_else(out_of_range)?,
+                    value => value.to_string().parse().unwrap(),
+                };
+
+                Value::parse(&integer.to_string(), target)?
+            }
+            (value, DataType::Decimal(limits)) if value.data_type().is_numeric() => {
+                let decimal = match value {
+                    Value::Decimal(decimal) => *decimal,
+                    // Floats are written without an exponent, which can have too many digits
+                    Value::Real(value) if value.is_finite() => {
+                        Decimal::parse(&value.to_string()).map_err(|_| out_of_range())?
+                    }
+                    Value::Double(value) if value.is_finite() => {
+                        Decimal::parse(&value.to_string()).map_err(|_| out_of_range())?
+                    }
+                    Value::Real(_) | Value::Double(_) => return Err(out_of_range()),
+                    value => Decimal::parse(&value.to_string()).unwrap(),
+                };
+
+                match limits {
+                    Some((precision, scale)) => {
+                        Value::Decimal(decimal.fit(precision, scale).map_err(|_| out_of_range())?)
+                    }
+                    None => Value::Decimal(decimal),
+                }
+            }
+            (value, DataType::Real | DataType::Double) if value.data_type().is_numeric() => {
+                Value::parse(&value.to_string(), target)?
+            }
+            (Value::Date(days), DataType::Timestamp) => {
+                Value::Timestamp(*days as i64 * MICROS_PER_DAY)
+            }
+            (Value::Date(days), DataType::TimestampTz) => {
+                Value::TimestampTz(time_zone.to_utc(*days as i64 * MICROS_PER_DAY))
+            }
+            (Value::Timestamp(micros), DataType::Date) => {
+                Value::Date(i32::try_from(micros.div_euclid(MICROS_PER_DAY)).unwrap())
+            }
+            (Value::Timestamp(micros), DataType::Time) => {
+                Value::Time(micros.rem_euclid(MICROS_PER_DAY))
+            }
+            (Value::Timestamp(micros), DataType::TimestampTz) => {
+                Value::TimestampTz(time_zone.to_utc(*micros))
+            }
+            (Value::TimestampTz(micros), DataType::Date | DataType::Time | DataType::Timestamp) => {
+                Value::Timestamp(micros + time_zone.offset_at(*micros)).cast(target, time_zone)?
+            }
+            (Value::Time(micros), DataType::Interval) => Value::Interval(Interval {
+                months: 0,
+                days: 0,
+                micros: *micros,
+            }),
+            (value, _) => {
+                return Err(ValueError::InvalidCast {
+                    from: value.data_type(),
+                    to: target,
+                })
+            }
+        };
+
+        Ok(value)
+    }
+
+    /// The position of the value's type when values of different types are ordered.
+    fn type_order(&self) -> usize {
+        match self {
+            Value::Boolean(_) => 0,
+            Value::SmallInt(_) => 1,
+            Value::Int(_) => 2,
+            Value::BigInt(_) => 3,
+            Value::Decimal(_) => 4,
+            Value::Real(_) => 5,
+            Value::Double(_) => 6,
+            Value::Text(_) => 7,
+            Value::Bytea(_) => 8,
+            Value::Date(_) => 9,
+            Value::Time(_) => 10,
+            Value::Timestamp(_) => 11,
+            Value::TimestampTz(_) => 12,
+            Value::Interval(_) => 13,
+            Value::Uuid(_) => 14,
+            Value::Jsonb(_) => 15,
+            Value::Null => 16,
+        }
+    }
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Value::Null, Value::Null) => Ordering::Equal,
+            (Value::Boolean(a), Value::Boolean(b)) => a.cmp(b),
+            (Value::SmallInt(a), Value::SmallInt(b)) => a.cmp(b),
+            (Value::Int(a), Value::Int(b)) => a.cmp(b),
+            (Value::BigInt(a), Value::BigInt(b)) => a.cmp(b),
+            (Value::Decimal(a), Value::Decimal(b)) => a.cmp(b),
+            (Value::Real(a), Value::Real(b)) => canonical_f32(*a).total_cmp(&canonical_f32(*b)),
+            (Value::Double(a), Value::Double(b)) => canonical_f64(*a).total_cmp(&canonical_f64(*b)),
+            (Value::Text(a), Value::Text(b)) => a.cmp(b),
+            (Value::Bytea(a), Value::Bytea(b)) => a.cmp(b),
+            (Value::Date(a), Value::Date(b)) => a.cmp(b),
+            (Value::Time(a), Value::Time(b))
+            | (Value::Timestamp(a), Value::Timestamp(b))
+            | (Value::TimestampTz(a), Value::TimestampTz(b)) => a.cmp(b),
+            (Value::Interval(a), Value::Interval(b)) => a.cmp(b),
+            (Value::Uuid(a), Value::Uuid(b)) => a.cmp(b),
+            (Value::Jsonb(a), Value::Jsonb(b)) => a.cmp(b),
+            _ => self.type_order().cmp(&other.type_order()),
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.type_order().hash(state);
+
+        match self {
+            Value::Null => {}
+            Value::Boolean(value) => value.hash(state),
+            Value::SmallInt(value) => value.hash(state),
+            Value::Int(value) => value.hash(state),
+            Value::BigInt(value) => value.hash(state),
+            Value::Decimal(value) => value.hash(state),
+            Value::Real(value) => canonical_f32(*value).to_bits().hash(state),
+            Value::Double(value) => canonical_f64(*value).to_bits().hash(state),
+            Value::Text(value) => value.hash(state),
+            Value::Bytea(value) => value.hash(state),
+            Value::Date(value) => value.hash(state),
+            Value::Time(value) | Value::Timestamp(value) | Value::TimestampTz(value) => {
+                value.hash(state)
+            }
+            Value::Interval(value) => value.hash(state),
+            Value::Uuid(value) => value.hash(state),
+            Value::Jsonb(value) => value.hash(state),
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("NULL"),
+            Value::Boolean(value) => value.fmt(f),
+            Value::SmallInt(value) => value.fmt(f),
+            Value::Int(value) => value.fmt(f),
+            Value::BigInt(value) => value.fmt(f),
+            Value::Decimal(value) => value.fmt(f),
+            Value::Real(value) => write_float(f, *value as f64, value.is_infinite()),
+            Value::Double(value) => write_float(f, *value, value.is_infinite()),
+            Value::Text(value) => f.write_str(value),
+            Value::Bytea(bytes) => {
+                f.write_str("\\x")?;
+                bytes.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+            }
+            Value::Date(days) => write_date(f, *days as i64),
+            Value::Time(micros) => write_time(f, *micros),
+            Value::Timestamp(micros) => write_timestamp(f, *micros),
+            Value::TimestampTz(micros) => write_timestamp_tz(f, *micros, &TimeZone::UTC),
+            Value::Interval(interval) => interval.fmt(f),
+            Value::Uuid(bytes) => {
+                for (i, byte) in bytes.iter().enumerate() {
+                    if matches!(i, 4 | 6 | 8 | 10) {
+                        f.write_char('-')?;
+                    }
+
+                    write!(f, "{:02x}", byte)?;
+                }
+
+                Ok(())
+            }
+            Value::Jsonb(jsonb) => jsonb.fmt(f),
+        }
+    }
+}
+
+/// Rounds a float to the nearest integer, or away from zero if it is halfway between two, if
+/// it is finite and fits into an `i128`.
+fn float_to_integer(value: f64) -> Option<i128> {
+    let rounded = value.round();
+
+    match rounded.is_finite() && rounded.abs() < 2f64.powi(127) {
+        true => Some(rounded as i128),
+        false => None,
+    }
+}
+
+/* DECIMAL */
+
+impl Decimal {
+    /// The most significant digits a decimal can hold, which is as many as always fit into its
+    /// mantissa.
+    pub const MAX_PRECISION: u32 = 38;
+
+    pub fn new(mantissa: i128, scale: u32) -> Result<Decimal, DecimalError> {
+        let decimal = Decimal { mantissa, scale };
+
+        match scale <= Self::MAX_PRECISION && decimal.precision() <= Self::MAX_PRECISION {
+            true => Ok(decimal),
+            false => Err(DecimalError::Overflow),
+        }
+    }
+
+    /// Parses `[+-]digits[.digits][e[+-]digits]` without going through a float, so that every
+    /// digit is kept.
+    pub fn parse(text: &str) -> Result<Decimal, DecimalError> {
+        let (negative, text) = match text.as_bytes().first() {
+            Some(b'-') => (true, &text[1..]),
+            Some(b'+') => (false, &text[1..]),
+            _ => (false, text),
+        };
+
+        let (number, exponent) = match text.find(['e', 'E']) {
+            Some(i) => (
+                &text[..i],
+                text[i + 1..]
+                    .parse::<i32>()
+                    .map_err(|_| DecimalError::Invalid)?,
+            ),
+            None => (text, 0),
+        };
+
+        let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(DecimalError::Invalid);
+        }
+
+        if !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|byte| byte.is_ascii_digit())
+        {
+            return Err(DecimalError::Invalid);
+        }
+
+        let mut mantissa = 0i128;
+
+        for byte in integer.bytes().chain(fraction.bytes()) {
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|mantissa| mantissa.checked_add((byte - b'0') as i128))
+                .ok_or(DecimalError::Overflow)?;
+        }
+
+        let mantissa = if negative { -mantissa } else { mantissa };
+        let scale = fraction.len() as i64 - exponent as i64;
+
+        // A negative scale is moved into the mantissa, as `1e3` is written as `1000`
+        match u32::try_from(scale) {
+            Ok(scale) => Decimal::new(mantissa, scale),
+            Err(_) => {
+                let shift = u32::try_from(-scale).map_err(|_| DecimalError::Overflow)?;
+                let mantissa = mantissa
+                    .checked_mul(pow10(shift)?)
+                    .ok_or(DecimalError::Overflow)?;
+
+                Decimal::new(mantissa, 0)
+            }
+        }
+    }
+
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    /// The number of digits after the decimal point.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// The number of significant digits, counting those after the decimal point.
+    pub fn precision(&self) -> u32 {
+        self.mantissa
+            .unsigned_abs()
+            .checked_ilog10()
+            .map_or(1, |log| log + 1)
+    }
+
+    pub fn checked_add(&self, other: &Decimal) -> Result<Decimal, DecimalError> {
+        let scale = self.scale.max(other.scale);
+        let (a, b) = (self.rescaled(scale)?, other.rescaled(scale)?);
+
+        Decimal::new(a.checked_add(b).ok_or(DecimalError::Overflow)?, scale)
+    }
+
+    pub fn checked_sub(&self, other: &Decimal) -> Result<Decimal, DecimalError> {
+        self.checked_add(&Decimal {
+            mantissa: -other.mantissa,
+            scale: other.scale,
+        })
+    }
+
+    /// Multiplies exactly, keeping every digit after the decimal point of both operands.
+    pub fn checked_mul(&self, other: &Decimal) -> Result<Decimal, DecimalError> {
+        let mantissa = self
+            .mantissa
+            .checked_mul(other.mantissa)
+            .ok_or(DecimalError::Overflow)?;
+
+        // Digits beyond the largest scale a decimal can have are rounded away
+        let scale = self.scale + other.scale;
+        let excess = scale.saturating_sub(Self::MAX_PRECISION);
+        let mantissa = divide(mantissa, pow10(excess)?, RoundingMode::HalfUp)?;
+
+        Decimal::new(mantissa, scale - excess)
+    }
+
+    /// Divides, rounding the quotient to `scale` digits after the decimal point with `mode`.
+    pub fn checked_div(
+        &self,
+        other: &Decimal,
+        scale: u32,
+        mode: RoundingMode,
+    ) -> Result<Decimal, DecimalError> {
+        if other.mantissa == 0 {
+            return Err(DecimalError::DivisionByZero);
+        }
+
+        // The quotient of the mantissas has the difference of the scales, so the dividend is
+        // scaled up to make up the rest, or the divisor if there is nothing to make up
+        let shift = scale as i64 + other.scale as i64 - self.scale as i64;
+
+        let (dividend, divisor) = match u32::try_from(shift) {
+            Ok(shift) => (
+                self.mantissa
+                    .checked_mul(pow10(shift)?)
+                    .ok_or(DecimalError::Overflow)?,
+                other.mantissa,
+            ),
+            Err(_) => match other.mantissa.checked_mul(pow10(-shift as u32)?) {
+                Some(divisor) => (self.mantissa, divisor),
+                // The divisor is so large that the quotient rounds to zero
+                None => return Decimal::new(0, scale),
+            },
+        };
+
+        Decimal::new(divide(dividend, divisor, mode)?, scale)
+    }
+
+    /// Rounds the number to `scale` digits after the decimal point, or pads it with zeros if it
+    /// has fewer.
+    pub fn round(&self, scale: u32, mode: RoundingMode) -> Result<Decimal, DecimalError> {
+        match scale.checked_sub(self.scale) {
+            Some(_) => Decimal::new(self.rescaled(scale)?, scale),
+            None => Decimal::new(
+                divide(self.mantissa, pow10(self.scale - scale)?, mode)?,
+                scale,
+            ),
+        }
+    }
+
+    /// Rounds the number to the scale of `DECIMAL(precision, scale)`, and checks that it has no
+    /// more than `precision - scale` digits before the decimal point.
+    pub fn fit(&self, precision: u32, scale: u32) -> Result<Decimal, DecimalError> {
+        let decimal = self.round(scale, RoundingMode::HalfUp)?;
+
+        match decimal.mantissa == 0 || decimal.precision() <= precision {
+            true => Ok(decimal),
+            false => Err(DecimalError::Overflow),
+        }
+    }
+
+    /// The mantissa of the same number at a scale at least as large as its own.
+    fn rescaled(&self, scale: u32) -> Result<i128, DecimalError> {
+        self.mantissa
+            .checked_mul(pow10(scale - self.scale)?)
+            .ok_or(DecimalError::Overflow)
+    }
+
+    /// The same number without trailing zeros after the decimal point.
+    pub(crate) fn normalized(&self) -> Decimal {
+        let mut decimal = *self;
+
+        while decimal.scale > 0 && decimal.mantissa % 10 == 0 {
+            decimal.mantissa /= 10;
+            decimal.scale -= 1;
+        }
+
+        decimal
+    }
+
+    /// The digits before and after the decimal point, both with the sign of the number, where
+    /// the digits after it are scaled to `scale`.
+    fn split(&self, scale: u32) -> (i128, i128) {
+        let factor = 10i128.pow(self.scale);
+
+        (
+            self.mantissa / factor,
+            self.mantissa % factor * 10i128.pow(scale - self.scale),
+        )
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Self {
+        Decimal {
+            mantissa: value as i128,
+            scale: 0,
+        }
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Scaling both numbers to the same scale could overflow, but the digits after the
+        // decimal point alone always fit
+        let scale = self.scale.max(other.scale);
+        self.split(scale).cmp(&other.split(scale))
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+/// Numbers which only differ in their trailing zeros are equal, so they are hashed the same.
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let decimal = self.normalized();
+        decimal.mantissa.hash(state);
+        decimal.scale.hash(state);
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+
+        if self.mantissa < 0 {
+            f.write_char('-')?;
+        }
+
+        if scale == 0 {
+            return f.write_str(&digits);
+        }
+
+        // Pad with zeros so that there is at least one digit before the decimal point
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+
+        write!(f, "{}.{}", integer, fraction)
+    }
+}
+
+/// How the digits a number is rounded to are chosen when the ones that are dropped are not
+/// zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Away from zero
+    Up,
+    /// Towards zero, which truncates the number
+    Down,
+    /// Towards positive infinity
+    Ceiling,
+    /// Towards negative infinity
+    Floor,
+    /// To the nearest neighbour, or away from zero if both are as near
+    HalfUp,
+    /// To the nearest neighbour, or towards zero if both are as near
+    HalfDown,
+    /// To the nearest neighbour, or to the even one if both are as near
+    HalfEven,
+}
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalError {
+    #[error("Invalid number")]
+    Invalid,
+    #[error(
+        "Number exceeds the precision of DECIMAL, which is at most {max} digits",
+        max = Decimal::MAX_PRECISION
+    )]
+    Overflow,
+    #[error("Division by zero")]
+    DivisionByZero,
+}
+
+fn pow10(exponent: u32) -> Result<i128, DecimalError> {
+    10i128.checked_pow(exponent).ok_or(DecimalError::Overflow)
+}
+
+/// Divides `dividend` by `divisor`, rounding the quotient with `mode`.
+fn divide(dividend: i128, divisor: i128, mode: RoundingMode) -> Result<i128, DecimalError> {
+    let quotient = dividend
+        .checked_div(divisor)
+        .ok_or(DecimalError::Overflow)?;
+    let remainder = dividend % divisor;
+
+    if remainder == 0 {
+        return Ok(quotient);
+    }
+
+    let negative = (dividend < 0) != (divisor < 0);
+    let (remainder, divisor) = (remainder.unsigned_abs(), divisor.unsigned_abs());
+
+    // Compares the dropped part with half of the divisor, which cannot overflow as the
+    // remainder is smaller than the divisor
+    let half = (remainder * 2).cmp(&divisor);
+
+    let away_from_zero = match mode {
+        RoundingMode::Up => true,
+        RoundingMode::Down => false,
+        RoundingMode::Ceiling => !negative,
+        RoundingMode::Floor => negative,
+        RoundingMode::HalfUp => half != Ordering::Less,
+        RoundingMode::HalfDown => half == Ordering::Greater,
+        RoundingMode::HalfEven => match half {
+            Ordering::Equal => quotient % 2 != 0,
+            half => half == Ordering::Greater,
+        },
+    };
+
+    match (away_from_zero, negative) {
+        (false, _) => Ok(quotient),
+        (true, false) => quotient.checked_add(1).ok_or(DecimalError::Overflow),
+        (true, true) => quotient.checked_sub(1).ok_or(DecimalError::Overflow),
+    }
+}
+
+/* INTERVAL */
+
+impl Interval {
+    /// Parses a list of quantities such as `1 year 2 mons -3 days`, optionally followed by a
+    /// time of day such as `04:05:06.5`.
+    pub fn parse(text: &str) -> Result<Interval, ValueError> {
+        let invalid = || ValueError::InvalidText {
+            data_type: DataType::Interval,
+            text: text.to_string(),
+        };
+
+        let out_of_range = || ValueError::OutOfRange {
+            data_type: DataType::Interval,
+            text: text.to_string(),
+        };
+
+        let mut interval = Interval {
+            months: 0,
+            days: 0,
+            micros: 0,
+        };
+
+        let mut words = text.split_whitespace().peekable();
+        words.peek().ok_or_else(invalid)?;
+
+        while let Some(word) = words.next() {
+            if word.contains(':') {
+                let (negative, time) = match word.strip_prefix('-') {
+                    Some(time) => (true, time),
+                    None => (false, word),
+                };
+
+                let micros = parse_time_of_day(time, true).ok_or_else(invalid)?;
+                let micros = i64::try_from(if negative { -micros } else { micros })
+                    .map_err(|_| out_of_range())?;
+
+                interval.micros = interval
+                    .micros
+                    .checked_add(micros)
+                    .ok_or_else(out_of_range)?;
+                continue;
+            }
+
+            let unit = words.next().ok_or_else(invalid)?.to_ascii_lowercase();
+
+            // Abbreviations ending in `s` are matched before plurals lose their `s`
+            let unit = match unit.as_str() {
+                "s" => "second",
+                "ms" => "millisecond",
+                "us" => "microsecond",
+                unit => unit.strip_suffix('s').unwrap_or(unit),
+            };
+
+            // Only seconds can be fractional, as the other units do not divide evenly
+            if matches!(unit, "second" | "sec") {
+                let seconds = word
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|seconds| seconds.is_finite())
+                    .ok_or_else(invalid)?;
+
+                let micros = (seconds * MICROS_PER_SECOND as f64).round();
+
+                // Casts saturate, so values past the range of `i64` are caught before
+                if micros.abs() >= i64::MAX as f64 {
+                    return Err(out_of_range());
+                }
+
+                interval.micros = interval
+                    .micros
+                    .checked_add(micros as i64)
+                    .ok_or_else(out_of_range)?;
+                continue;
+            }
+
+            let count = word.parse::<i64>().map_err(|_| invalid())?;
+
+            let (months, days, micros) = match unit {
+                "year" => (count.checked_mul(12), Some(0), Some(0)),
+                "mon" | "month" => (Some(count), Some(0), Some(0)),
+                "week" => (Some(0), count.checked_mul(7), Some(0)),
+                "day" => (Some(0), Some(count), Some(0)),
+                "hour" => (Some(0), Some(0), count.checked_mul(MICROS_PER_HOUR)),
+                "minute" | "min" | "m" => (Some(0), Some(0), count.checked_mul(MICROS_PER_MINUTE)),
+                "millisecond" | "msec" => (Some(0), Some(0), count.checked_mul(1000)),
+                "microsecond" | "usec" => (Some(0), Some(0), Some(count)),
+                _ => return Err(invalid()),
+            };
+
+            let add_months = months.and_then(|months| i32::try_from(months).ok());
+            let add_days = days.and_then(|days| i32::try_from(days).ok());
+
+            interval = Interval {
+                months: add_months
+                    .and_then(|months| interval.months.checked_add(months))
+                    .ok_or_else(out_of_range)?,
+                days: add_days
+                    .and_then(|days| interval.days.checked_add(days))
+                    .ok_or_else(out_of_range)?,
+                micros: micros
+                    .and_then(|micros| interval.micros.checked_add(micros))
+                    .ok_or_else(out_of_range)?,
+            };
+        }
+
+        Ok(interval)
+    }
+
+    /// The length of the interval in microseconds, counting a month as 30 days, which is how
+    /// intervals are compared.
+    fn total_micros(&self) -> i128 {
+        (self.months as i128 * 30 + self.days as i128) * MICROS_PER_DAY as i128
+            + self.micros as i128
+    }
+}
+
+impl Ord for Interval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total_micros().cmp(&other.total_micros())
+    }
+}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Interval {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Interval {}
+
+/// Intervals of the same length are equal, such as `1 mon` and `30 days`, so they are hashed the
+/// same.
+impl Hash for Interval {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.total_micros().hash(state);
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+
+        let plural = |count: i32, unit: &str| match count.abs() {
+            1 => format!("{} {}", count, unit),
+            _ => format!("{} {}s", count, unit),
+        };
+
+        if self.months / 12 != 0 {
+            parts.push(plural(self.months / 12, "year"));
+        }
+
+        if self.months % 12 != 0 {
+            parts.push(plural(self.months % 12, "mon"));
+        }
+
+        if self.days != 0 {
+            parts.push(plural(self.days, "day"));
+        }
+
+        if self.micros != 0 || parts.is_empty() {
+            let mut time = String::new();
+
+            if self.micros < 0 {
+                time.push('-');
+            }
+
+            write_time_of_day(&mut time, self.micros.abs())?;
+            parts.push(time);
+        }
+
+        f.write_str(&parts.join(" "))
+    }
+}
+
+/* PARSING */
+
+fn parse_bytea(text: &str) -> Option<Vec<u8>> {
+    let hex = text.strip_prefix("\\x")?;
+
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn parse_uuid(text: &str) -> Option<[u8; 16]> {
+    let hyphenated = text.len() == 36
+        && text
+            .char_indices()
+            .all(|(i, c)| matches!(i, 8 | 13 | 18 | 23) == (c == '-'));
+
+    let hex = match hyphenated {
+        true => text.replace('-', ""),
+        false => text.to_string(),
+    };
+
+    if hex.len() != 32 {
+        return None;
+    }
+
+    let mut bytes = [0; 16];
+
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+
+    Some(bytes)
+}
+
+/// Parses `YYYY-MM-DD` into days since 1970-01-01.
+fn parse_date(text: &str) -> Option<i32> {
+    let mut parts = text.splitn(3, '-');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+
+    let digits = |part: &str, len: usize| {
+        part.len() == len && part.bytes().all(|byte| byte.is_ascii_digit())
+    };
+
+    if !digits(year, 4) || !digits(month, 2) || !digits(day, 2) {
+        return None;
+    }
+
+    let (year, month, day) = (
+        year.parse::<i64>().ok()?,
+        month.parse::<u32>().ok()?,
+        day.parse::<u32>().ok()?,
+    );
+
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+
+    i32::try_from(days_from_civil(year, month, day)).ok()
+}
+
+/// Parses `HH:MM[:SS[.ffffff]]` into microseconds since midnight.
+fn parse_time(text: &str) -> Option<i64> {
+    i64::try_from(parse_time_of_day(text, false)?).ok()
+}
+
+/// Parses `HH:MM[:SS[.ffffff]]`, where the hours can exceed a day if `unbounded` is set, as in
+/// intervals, in which case the result may not fit in an `i64`.
+fn parse_time_of_day(text: &str, unbounded: bool) -> Option<i128> {
+    let mut parts = text.splitn(3, ':');
+    let hours = parts.next()?.parse::<i64>().ok()?;
+    let minutes = parts.next()?.parse::<i64>().ok()?;
+
+    let (seconds, fraction) = match parts.next() {
+        Some(seconds) => seconds.split_once('.').unwrap_or((seconds, "")),
+        None => ("0", ""),
+    };
+
+    let seconds = seconds.parse::<i64>().ok()?;
+
+    if fraction.len() > 6 || !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let fraction = format!("{:0<6}", fraction).parse::<i64>().ok()?;
+
+    if hours < 0 || (!unbounded && hours > 23) || !(0..60).contains(&minutes) {
+        return None;
+    }
+
+    if !(0..60).contains(&seconds) {
+        return None;
+    }
+
+    Some(
+        hours as i128 * MICROS_PER_HOUR as i128
+            + (minutes * MICROS_PER_MINUTE + seconds * MICROS_PER_SECOND + fraction) as i128,
+    )
+}
+
+/// Parses `YYYY-MM-DD[( |T)HH:MM[:SS[.ffffff]]]`, optionally followed by `Z`, an offset such as
+/// `+HH[:MM]` or the name of a time zone, into microseconds since 1970-01-01 00:00:00 in local
+/// time, and the time zone if one is given.
+fn parse_timestamp(text: &str) -> Option<(i64, Option<TimeZone>)> {
+    let (date, rest) = match text.find([' ', 'T']) {
+        Some(i) => (&text[..i], text[i + 1..].trim()),
+        None => (text, ""),
+    };
+
+    let days = parse_date(date)? as i64;
+    let (before_last_word, last_word) = rest.rsplit_once(' ').unwrap_or(("", rest));
+
+    let (time, zone) = if last_word.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        (
+            before_last_word.trim(),
+            Some(TimeZone::parse(last_word).ok()?),
+        )
+    } else if let Some(time) = rest.strip_suffix('Z') {
+        (time, Some(TimeZone::UTC))
+    } else if let Some(i) = rest.rfind(['+', '-']) {
+        (rest[..i].trim(), Some(TimeZone::parse(&rest[i..]).ok()?))
+    } else {
+        (rest, None)
+    };
+
+    let micros = match time {
+        "" => 0,
+        time => parse_time(time)?,
+    };
+
+    Some((days * MICROS_PER_DAY + micros, zone))
+}
+
+/* FORMATTING */
+
+fn write_float(f: &mut fmt::Formatter<'_>, value: f64, infinite: bool) -> fmt::Result {
+    match (infinite, value.is_sign_negative()) {
+        (true, false) => f.write_str("Infinity"),
+        (true, true) => f.write_str("-Infinity"),
+        (false, _) => value.fmt(f),
+    }
+}
+
+fn write_date(f: &mut impl Write, days: i64) -> fmt::Result {
+    let (year, month, day) = civil_from_days(days);
+    write!(f, "{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn write_time(f: &mut impl Write, micros: i64) -> fmt::Result {
+    write_time_of_day(f, micros)
+}
+
+/// Writes `HH:MM:SS`, followed by the fraction of a second without trailing zeros if there is
+/// one.
+fn write_time_of_day(f: &mut impl Write, micros: i64) -> fmt::Result {
+    let hours = micros / MICROS_PER_HOUR;
+    let minutes = micros % MICROS_PER_HOUR / MICROS_PER_MINUTE;
+    let seconds = micros % MICROS_PER_MINUTE / MICROS_PER_SECOND;
+    let fraction = micros % MICROS_PER_SECOND;
+
+    write!(f, "{:02}:{:02}:{:02}", hours, minutes, seconds)?;
+
+    if fraction != 0 {
+        let fraction = format!("{:06}", fraction);
+        write!(f, ".{}", fraction.trim_end_matches('0'))?;
+    }
+
+    Ok(())
+}
+
+fn write_timestamp(f: &mut impl Write, micros: i64) -> fmt::Result {
+    let (days, time) = split(micros);
+    write_date(f, days)?;
+    f.write_char(' ')?;
+    write_time(f, time)
+}
+
+/// Writes a UTC timestamp as local time in `time_zone` followed by its offset from UTC.
+fn write_timestamp_tz(f: &mut impl Write, utc: i64, time_zone: &TimeZone) -> fmt::Result {
+    let offset = time_zone.offset_at(utc);
+    write_timestamp(f, utc + offset)?;
+    write_offset(f, offset)
+}
+
+/// Folds `-0.0` into `0.0` and every `NaN` into one, so that equal values compare and hash the
+/// same.
+fn canonical_f32(value: f32) -> f32 {
+    match value {
+        _ if value.is_nan() => f32::NAN,
+        _ if value == 0.0 => 0.0,
+        _ => value,
+    }
+}
+
+fn canonical_f64(value: f64) -> f64 {
+    match value {
+        _ if value.is_nan() => f64::NAN,
+        _ if value == 0.0 => 0.0,
+        _ => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{BuildHasher, RandomState};
+
+    use super::*;
+
+    fn decimal(text: &str) -> Decimal {
+        Decimal::parse(text).unwrap()
+    }
+
+    fn interval(text: &str) -> Result<Interval, ValueError> {
+        Interval::parse(text)
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let values = [
+            ("true", DataType::Boolean),
+            ("-32768", DataType::SmallInt),
+            ("2147483647", DataType::Int),
+            ("-9223372036854775808", DataType::BigInt),
+            ("12.50", DataType::Decimal(None)),
+            ("1.5", DataType::Real),
+            ("-0.25", DataType::Double),
+            ("it's", DataType::Text),
+            ("\\x00ff", DataType::Bytea),
+            ("2024-02-29", DataType::Date),
+            ("23:59:59.5", DataType::Time),
+            ("2024-01-01 12:00:00", DataType::Timestamp),
+            ("2024-01-01 12:00:00+00", DataType::TimestampTz),
+            ("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11", DataType::Uuid),
+            ("{\"a\": [1, null]}", DataType::Jsonb),
+        ];
+
+        for (text, data_type) in values {
+            let value = Value::parse(text, data_type).unwrap();
+            assert_eq!(value.to_string(), text);
+            assert_eq!(Value::parse(&value.to_string(), data_type).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn invalid_text() {
+        assert!(matches!(
+            Value::parse("32768", DataType::SmallInt),
+            Err(ValueError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            Value::parse("1.5", DataType::Int),
+            Err(ValueError::InvalidText { .. })
+        ));
+        assert!(matches!(
+            Value::parse("2023-02-29", DataType::Date),
+            Err(ValueError::InvalidText { .. })
+        ));
+        assert!(matches!(
+            Value::parse("abc", DataType::Varchar(Some(2))),
+            Err(ValueError::TooLong { .. })
+        ));
+        assert!(matches!(
+            Value::parse("123.456", DataType::Decimal(Some((4, 2)))),
+            Err(ValueError::OutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn ordering_and_hashing() {
+        let hasher = RandomState::new();
+
+        // Floats are totally ordered, with every NaN equal and after every other number
+        assert_eq!(Value::Double(0.0), Value::Double(-0.0));
+        assert_eq!(
+            hasher.hash_one(Value::Double(0.0)),
+            hasher.hash_one(Value::Double(-0.0))
+        );
+        assert_eq!(Value::Double(f64::NAN), Value::Double(-f64::NAN));
+        assert!(Value::Double(f64::NAN) > Value::Double(f64::INFINITY));
+
+        // NULL sorts after every other value
+        assert!(Value::Null > Value::Int(i32::MAX));
+        assert!(Value::Null > Value::Text("z".to_string()));
+        assert!(Value::Text("a".to_string()) < Value::Text("b".to_string()));
+    }
+
+    #[test]
+    fn casts() {
+        let cast = |value: Value, target| value.cast(target, &TimeZone::UTC);
+
+        assert_eq!(cast(Value::Double(2.5), DataType::Int), Ok(Value::Int(3)));
+        assert_eq!(cast(Value::Double(-2.5), DataType::Int), Ok(Value::Int(-3)));
+        assert_eq!(
+            cast(Value::Int(7), DataType::Text),
+            Ok(Value::Text("7".to_string()))
+        );
+        assert_eq!(
+            cast(Value::Text("hello".to_string()), DataType::Varchar(Some(2))),
+            Ok(Value::Text("he".to_string()))
+        );
+        assert_eq!(
+            cast(Value::Boolean(true), DataType::BigInt),
+            Ok(Value::BigInt(1))
+        );
+        assert_eq!(cast(Value::Null, DataType::Date), Ok(Value::Null));
+        assert!(matches!(
+            cast(Value::BigInt(i64::MAX), DataType::Int),
+            Err(ValueError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            cast(Value::Double(f64::NAN), DataType::Decimal(None)),
+            Err(ValueError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            cast(Value::Date(0), DataType::Uuid),
+            Err(ValueError::InvalidCast { .. })
+        ));
+    }
+
+    #[test]
+    fn decimal_parsing() {
+        assert_eq!(decimal("12.50").to_string(), "12.50");
+        assert_eq!(decimal("-.5").to_string(), "-0.5");
+        assert_eq!(decimal("+1.5e3").to_string(), "1500");
+        assert_eq!(decimal("15e-3").to_string(), "0.015");
+
+        assert_eq!(Decimal::parse(""), Err(DecimalError::Invalid));
+        assert_eq!(Decimal::parse("."), Err(DecimalError::Invalid));
+        assert_eq!(Decimal::parse("1.2.3"), Err(DecimalError::Invalid));
+        assert_eq!(Decimal::parse("1e"), Err(DecimalError::Invalid));
+        assert_eq!(Decimal::parse(&"9".repeat(39)), Err(DecimalError::Overflow));
+        assert_eq!(Decimal::parse("1e38"), Err(DecimalError::Overflow));
+    }
+
+    #[test]
+    fn decimal_arithmetic() {
+        let sum = decimal("1.25").checked_add(&decimal("2.5")).unwrap();
+        assert_eq!(sum.to_string(), "3.75");
+
+        let difference = decimal("1").checked_sub(&decimal("2.50")).unwrap();
+        assert_eq!(difference.to_string(), "-1.50");
+
+        let product = decimal("1.5").checked_mul(&decimal("-0.25")).unwrap();
+        assert_eq!(product.to_string(), "-0.375");
+
+        let max = decimal(&"9".repeat(38));
+        assert_eq!(max.checked_add(&decimal("1")), Err(DecimalError::Overflow));
+        assert_eq!(max.checked_mul(&decimal("10")), Err(DecimalError::Overflow));
+    }
+
+    #[test]
+    fn decimal_division() {
+        let divide = |a: &str, b: &str, scale, mode| {
+            decimal(a)
+                .checked_div(&decimal(b), scale, mode)
+                .map(|quotient| quotient.to_string())
+        };
+
+        assert_eq!(divide("1", "3", 4, RoundingMode::HalfUp).unwrap(), "0.3333");
+        assert_eq!(divide("2", "3", 2, RoundingMode::Down).unwrap(), "0.66");
+        assert_eq!(divide("2", "3", 2, RoundingMode::HalfUp).unwrap(), "0.67");
+        assert_eq!(divide("-2", "3", 0, RoundingMode::Floor).unwrap(), "-1");
+        assert_eq!(divide("-2", "3", 0, RoundingMode::Ceiling).unwrap(), "0");
+        assert_eq!(divide("2.5", "1", 0, RoundingMode::HalfEven).unwrap(), "2");
+        assert_eq!(divide("3.5", "1", 0, RoundingMode::HalfEven).unwrap(), "4");
+        assert_eq!(divide("2.5", "1", 0, RoundingMode::HalfDown).unwrap(), "2");
+        assert_eq!(divide("1.00", "0.5", 1, RoundingMode::Up).unwrap(), "2.0");
+        assert_eq!(
+            divide("1", "0.0", 2, RoundingMode::HalfUp),
+            Err(DecimalError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn decimal_rounding() {
+        assert_eq!(
+            decimal("1.005")
+                .round(2, RoundingMode::HalfUp)
+                .unwrap()
+                .to_string(),
+            "1.01"
+        );
+        assert_eq!(
+            decimal("-1.005")
+                .round(2, RoundingMode::HalfUp)
+                .unwrap()
+                .to_string(),
+            "-1.01"
+        );
+        assert_eq!(
+            decimal("1.5")
+                .round(3, RoundingMode::Down)
+                .unwrap()
+                .to_string(),
+            "1.500"
+        );
+
+        assert_eq!(decimal("123.456").fit(5, 2).unwrap().to_string(), "123.46");
+        assert_eq!(decimal("999.995").fit(5, 2), Err(DecimalError::Overflow));
+        assert_eq!(decimal("0.001").fit(1, 0).unwrap().to_string(), "0");
+    }
+
+    #[test]
+    fn decimal_equality() {
+        let hasher = RandomState::new();
+
+        assert_eq!(decimal("1.50"), decimal("1.5"));
+        assert_eq!(
+            hasher.hash_one(decimal("1.50")),
+            hasher.hash_one(decimal("1.5"))
+        );
+        assert!(decimal("-0.1") < decimal("0"));
+        assert!(decimal("10") > decimal("9.999"));
+
+        // The digits after the decimal point would overflow if both were scaled to 38 digits
+        let large = decimal(&"9".repeat(38));
+        assert!(large > decimal("0.00000000000000000000000000000000000001"));
+    }
+
+    #[test]
+    fn interval_parsing() {
+        let parsed = interval("1 year 2 mons -3 days 04:05:06.5").unwrap();
+        assert_eq!(
+            (parsed.months, parsed.days, parsed.micros),
+            (14, -3, 14_706_500_000)
+        );
+        assert_eq!(parsed.to_string(), "1 year 2 mons -3 days 04:05:06.5");
+
+        let parsed = interval("2 weeks 1.5 seconds 3 ms 7 us").unwrap();
+        assert_eq!((parsed.days, parsed.micros), (14, 1_503_007));
+
+        assert_eq!(interval("-01:30").unwrap().micros, -90 * MICROS_PER_MINUTE);
+        assert_eq!(interval("1 mon").unwrap(), interval("30 days").unwrap());
+        assert_eq!(interval("0 days").unwrap().to_string(), "00:00:00");
+    }
+
+    #[test]
+    fn invalid_intervals() {
+        for text in [
+            "",
+            "1",
+            "day",
+            "1 fortnight",
+            "1.5 days",
+            "1 second second",
+            "1:xx",
+        ] {
+            assert!(
+                matches!(interval(text), Err(ValueError::InvalidText { .. })),
+                "{text:?}"
+            );
+        }
+
+        for text in [
+            "178956971 years",
+            "2147483647 mons 1 mon",
+            "2147483648 days",
+            "300 weeks 2147483647 days",
+            "9223372036854775807 us 1 us",
+            "2562047788016 hours",
+            "1e300 seconds",
+        ] {
+            assert!(
+                matches!(interval(text), Err(ValueError::OutOfRange { .. })),
+                "{text:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,666 @@
+use crate::sql::statement::{
+    AliasedExpr, AliasedFromTable, Assignment, BinaryOperator, CheckOption, ColumnDefinition,
+    ColumnName, CreateDatabaseStatement, CreateFunctionStatement, CreateMaterializedViewStatement,
+    CreateSchemaStatement, CreateTableStatement, CreateViewStatement, DeleteStatement,
+    DropFunctionStatement, DropSchemaStatement, DropViewStatement, ExplainFormat, ExplainStatement,
+    Expr, FromClause, FromTable, FunctionArgs, FunctionParam, GroupByClause, HavingClause,
+    InsertSource, InsertStatement, JoinCondition, JoinType, LimitClause, Literal, ObjectName,
+    OffsetClause, OrderByClause, OrderByExpr, OrderByItem, Ordering,
+    RefreshMaterializedViewStatement, ResultColumns, SchemaName, SelectStatement, SetOperation,
+    SetSearchPathStatement, SetTimeZoneStatement, ShowColumnsStatement, ShowCreateTableStatement,
+    Statement, TypeName, UnaryOperator, UpdateStatement, UseStatement, WhereClause,
+};
+
+/// Walks a syntax tree by shared reference.
+///
+/// Every method defaults to visiting the node's children through the matching `walk_*`
+/// function, so an implementation only overrides the nodes it is interested in, and calls the
+/// `walk_*` function itself to keep descending into them.
+pub trait Visitor {
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement)
+    }
+
+    fn visit_select(&mut self, select: &SelectStatement) {
+        walk_select(self, select)
+    }
+
+    fn visit_insert(&mut self, insert: &InsertStatement) {
+        walk_insert(self, insert)
+    }
+
+    fn visit_insert_source(&mut self, source: &InsertSource) {
+        walk_insert_source(self, source)
+    }
+
+    fn visit_update(&mut self, update: &UpdateStatement) {
+        walk_update(self, update)
+    }
+
+    fn visit_assignment(&mut self, assignment: &Assignment) {
+        walk_assignment(self, assignment)
+    }
+
+    fn visit_delete(&mut self, delete: &DeleteStatement) {
+        walk_delete(self, delete)
+    }
+
+    fn visit_result_columns(&mut self, columns: &ResultColumns) {
+        walk_result_columns(self, columns)
+    }
+
+    fn visit_aliased_expr(&mut self, aliased_expr: &AliasedExpr) {
+        walk_aliased_expr(self, aliased_expr)
+    }
+
+    fn visit_column_name(&mut self, column_name: &ColumnName) {
+        walk_column_name(self, column_name)
+    }
+
+    fn visit_object_name(&mut self, _object_name: &ObjectName) {}
+
+    fn visit_from_clause(&mut self, from_clause: &FromClause) {
+        walk_from_clause(self, from_clause)
+    }
+
+    fn visit_aliased_from_table(&mut self, table: &AliasedFromTable) {
+        walk_aliased_from_table(self, table)
+    }
+
+    fn visit_from_table(&mut self, table: &FromTable) {
+        walk_from_table(self, table)
+    }
+
+    fn visit_join_type(&mut self, _join_type: &JoinType) {}
+
+    fn visit_join_condition(&mut self, join_condition: &JoinCondition) {
+        walk_join_condition(self, join_condition)
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        walk_expr(self, expr)
+    }
+
+    fn visit_literal(&mut self, _literal: &Literal) {}
+
+    fn visit_function_args(&mut self, args: &FunctionArgs) {
+        walk_function_args(self, args)
+    }
+
+    fn visit_unary_operator(&mut self, _operator: &UnaryOperator) {}
+
+    fn visit_binary_operator(&mut self, _operator: &BinaryOperator) {}
+
+    fn visit_where_clause(&mut self, where_clause: &WhereClause) {
+        walk_where_clause(self, where_clause)
+    }
+
+    fn visit_group_by_clause(&mut self, group_by_clause: &GroupByClause) {
+        walk_group_by_clause(self, group_by_clause)
+    }
+
+    fn visit_having_clause(&mut self, having_clause: &HavingClause) {
+        walk_having_clause(self, having_clause)
+    }
+
+    fn visit_set_operation(&mut self, set_operation: &SetOperation) {
+        walk_set_operation(self, set_operation)
+    }
+
+    fn visit_order_by_clause(&mut self, order_by_clause: &OrderByClause) {
+        walk_order_by_clause(self, order_by_clause)
+    }
+
+    fn visit_order_by_item(&mut self, item: &OrderByItem) {
+        walk_order_by_item(self, item)
+    }
+
+    fn visit_order_by_expr(&mut self, item: &OrderByExpr) {
+        walk_order_by_expr(self, item)
+    }
+
+    fn visit_ordering(&mut self, _ordering: &Ordering) {}
+
+    fn visit_limit_clause(&mut self, _limit: &LimitClause) {}
+
+    fn visit_offset_clause(&mut self, _offset: &OffsetClause) {}
+
+    fn visit_explain(&mut self, explain: &ExplainStatement) {
+        walk_explain(self, explain)
+    }
+
+    fn visit_explain_format(&mut self, _format: &ExplainFormat) {}
+
+    fn visit_create_view(&mut self, create_view: &CreateViewStatement) {
+        walk_create_view(self, create_view)
+    }
+
+    fn visit_check_option(&mut self, _check_option: &CheckOption) {}
+
+    fn visit_create_materialized_view(&mut self, create_view: &CreateMaterializedViewStatement) {
+        walk_create_materialized_view(self, create_view)
+    }
+
+    fn visit_refresh_materialized_view(&mut self, refresh: &RefreshMaterializedViewStatement) {
+        walk_refresh_materialized_view(self, refresh)
+    }
+
+    fn visit_drop_view(&mut self, drop_view: &DropViewStatement) {
+        walk_drop_view(self, drop_view)
+    }
+
+    fn visit_create_database(&mut self, _create_database: &CreateDatabaseStatement) {}
+
+    fn visit_schema_name(&mut self, _schema_name: &SchemaName) {}
+
+    fn visit_create_schema(&mut self, create_schema: &CreateSchemaStatement) {
+        walk_create_schema(self, create_schema)
+    }
+
+    fn visit_drop_schema(&mut self, drop_schema: &DropSchemaStatement) {
+        walk_drop_schema(self, drop_schema)
+    }
+
+    fn visit_create_table(&mut self, create_table: &CreateTableStatement) {
+        walk_create_table(self, create_table)
+    }
+
+    fn visit_column_definition(&mut self, column: &ColumnDefinition) {
+        walk_column_definition(self, column)
+    }
+
+    fn visit_type_name(&mut self, _type_name: &TypeName) {}
+
+    fn visit_create_function(&mut self, create_function: &CreateFunctionStatement) {
+        walk_create_function(self, create_function)
+    }
+
+    fn visit_function_param(&mut self, param: &FunctionParam) {
+        walk_function_param(self, param)
+    }
+
+    fn visit_drop_function(&mut self, drop_function: &DropFunctionStatement) {
+        walk_drop_function(self, drop_function)
+    }
+
+    fn visit_use(&mut self, _use_statement: &UseStatement) {}
+
+    fn visit_set_search_path(&mut self, _set_search_path: &SetSearchPathStatement) {}
+
+    fn visit_set_time_zone(&mut self, _set_time_zone: &SetTimeZoneStatement) {}
+
+    fn visit_show_columns(&mut self, show_columns: &ShowColumnsStatement) {
+        walk_show_columns(self, show_columns)
+    }
+
+    fn visit_show_create_table(&mut self, show_create_table: &ShowCreateTableStatement) {
+        walk_show_create_table(self, show_create_table)
+    }
+}
+
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &Statement) {
+    match statement {
+        Statement::Select(select) => visitor.visit_select(select),
+        Statement::Insert(insert) => visitor.visit_insert(insert),
+        Statement::Update(update) => visitor.visit_update(update),
+        Statement::Delete(delete) => visitor.visit_delete(delete),
+        Statement::Explain(explain) => visitor.visit_explain(explain),
+        Statement::CreateView(create_view) => visitor.visit_create_view(create_view),
+        Statement::DropView(drop_view) | Statement::DropMaterializedView(drop_view) => {
+            visitor.visit_drop_view(drop_view)
+        }
+        Statement::CreateMaterializedView(create_view) => {
+            visitor.visit_create_materialized_view(create_view)
+        }
+        Statement::RefreshMaterializedView(refresh) => {
+            visitor.visit_refresh_materialized_view(refresh)
+        }
+        Statement::CreateDatabase(create_database) => {
+            visitor.visit_create_database(create_database)
+        }
+        Statement::CreateSchema(create_schema) => visitor.visit_create_schema(create_schema),
+        Statement::DropSchema(drop_schema) => visitor.visit_drop_schema(drop_schema),
+        Statement::CreateTable(create_table) => visitor.visit_create_table(create_table),
+        Statement::CreateFunction(create_function) => {
+            visitor.visit_create_function(create_function)
+        }
+        Statement::DropFunction(drop_function) => visitor.visit_drop_function(drop_function),
+        Statement::Use(use_statement) => visitor.visit_use(use_statement),
+        Statement::SetSearchPath(set_search_path) => visitor.visit_set_search_path(set_search_path),
+        Statement::SetTimeZone(set_time_zone) => visitor.visit_set_time_zone(set_time_zone),
+        Statement::ShowDatabases
+        | Statement::ShowSchemas
+        | Statement::ShowTables
+        | Statement::ShowSearchPath
+        | Statement::ShowTimeZone => {}
+        Statement::ShowColumns(show_columns) => visitor.visit_show_columns(show_columns),
+        Statement::ShowCreateTable(show_create_table) => {
+            visitor.visit_show_create_table(show_create_table)
+        }
+    }
+}
+
+/// Visits the clauses of a `SELECT` in the order they are written.
+pub fn walk_select<V: Visitor + ?Sized>(visitor: &mut V, select: &SelectStatement) {
+    visitor.visit_result_columns(&select.columns);
+    visitor.visit_from_clause(&select.from_clause);
+
+    if let Some(where_clause) = &select.where_clause {
+        visitor.visit_where_clause(where_clause);
+    }
+
+    if let Some(group_by_clause) = &select.group_by_clause {
+        visitor.visit_group_by_clause(group_by_clause);
+    }
+
+    if let Some(having_clause) = &select.having_clause {
+        visitor.visit_having_clause(having_clause);
+    }
+
+    for set_operation in &select.set_operations {
+        visitor.visit_set_operation(set_operation);
+    }
+
+    if let Some(order_by_clause) = &select.order_by_clause {
+        visitor.visit_order_by_clause(order_by_clause);
+    }
+
+    if let Some(limit) = &select.limit {
+        visitor.visit_limit_clause(limit);
+    }
+
+    if let Some(offset) = &select.offset {
+        visitor.visit_offset_clause(offset);
+    }
+}
+
+pub fn walk_insert<V: Visitor + ?Sized>(visitor: &mut V, insert: &InsertStatement) {
+    visitor.visit_object_name(&insert.table);
+    visitor.visit_insert_source(&insert.source);
+}
+
+pub fn walk_insert_source<V: Visitor + ?Sized>(visitor: &mut V, source: &InsertSource) {
+    match source {
+        InsertSource::Values { rows } => {
+            for row in rows {
+                for expr in row {
+                    visitor.visit_expr(expr);
+                }
+            }
+        }
+        InsertSource::Select(query) => visitor.visit_select(query),
+    }
+}
+
+pub fn walk_update<V: Visitor + ?Sized>(visitor: &mut V, update: &UpdateStatement) {
+    visitor.visit_object_name(&update.table);
+
+    for assignment in &update.assignments {
+        visitor.visit_assignment(assignment);
+    }
+
+    if let Some(where_clause) = &update.where_clause {
+        visitor.visit_where_clause(where_clause);
+    }
+}
+
+pub fn walk_assignment<V: Visitor + ?Sized>(visitor: &mut V, assignment: &Assignment) {
+    visitor.visit_expr(&assignment.value);
+}
+
+pub fn walk_delete<V: Visitor + ?Sized>(visitor: &mut V, delete: &DeleteStatement) {
+    visitor.visit_object_name(&delete.table);
+
+    if let Some(where_clause) = &delete.where_clause {
+        visitor.visit_where_clause(where_clause);
+    }
+}
+
+pub fn walk_result_columns<V: Visitor + ?Sized>(visitor: &mut V, columns: &ResultColumns) {
+    match columns {
+        ResultColumns::All => {}
+        ResultColumns::Specific(columns) => {
+            for column in columns {
+                visitor.visit_aliased_expr(column);
+            }
+        }
+    }
+}
+
+pub fn walk_aliased_expr<V: Visitor + ?Sized>(visitor: &mut V, aliased_expr: &AliasedExpr) {
+    visitor.visit_expr(&aliased_expr.expr);
+}
+
+pub fn walk_column_name<V: Visitor + ?Sized>(visitor: &mut V, column_name: &ColumnName) {
+    match column_name {
+        ColumnName::Direct { .. } => {}
+        ColumnName::Qualified { table_name, .. } => visitor.visit_object_name(table_name),
+    }
+}
+
+pub fn walk_from_clause<V: Visitor + ?Sized>(visitor: &mut V, from_clause: &FromClause) {
+    match from_clause {
+        FromClause::Direct { table } => visitor.visit_aliased_from_table(table),
+        FromClause::Join {
+            left,
+            right,
+            join_type,
+            join_condition,
+        } => {
+            visitor.visit_from_clause(left);
+            visitor.visit_join_type(join_type);
+            visitor.visit_aliased_from_table(right);
+
+            if let Some(join_condition) = join_condition {
+                visitor.visit_join_condition(join_condition);
+            }
+        }
+    }
+}
+
+pub fn walk_aliased_from_table<V: Visitor + ?Sized>(visitor: &mut V, table: &AliasedFromTable) {
+    visitor.visit_from_table(&table.table);
+}
+
+pub fn walk_from_table<V: Visitor + ?Sized>(visitor: &mut V, table: &FromTable) {
+    match table {
+        FromTable::DerivedTable { query, .. } => visitor.visit_select(query),
+        FromTable::Values { rows } => {
+            for row in rows {
+                for expr in row {
+                    visitor.visit_expr(expr);
+                }
+            }
+        }
+        FromTable::TableName { name } => visitor.visit_object_name(name),
+        FromTable::Function { args, .. } => {
+            for arg in args {
+                visitor.visit_expr(arg);
+            }
+        }
+    }
+}
+
+pub fn walk_join_condition<V: Visitor + ?Sized>(visitor: &mut V, join_condition: &JoinCondition) {
+    match join_condition {
+        JoinCondition::On(condition) => visitor.visit_expr(condition),
+        JoinCondition::Using(_) => {}
+    }
+}
+
+pub fn walk_expr<V: Visitor + ?Sized>(visitor: &mut V, expr: &Expr) {
+    match expr {
+        Expr::Literal(literal) => visitor.visit_literal(literal),
+        Expr::Column(column_name) => visitor.visit_column_name(column_name),
+        Expr::Unary { operator, operand } => {
+            visitor.visit_unary_operator(operator);
+            visitor.visit_expr(operand);
+        }
+        Expr::Binary {
+            left,
+            operator,
+            right,
+        } => {
+            visitor.visit_expr(left);
+            visitor.visit_binary_operator(operator);
+            visitor.visit_expr(right);
+        }
+        Expr::IsNull { operand, .. } => visitor.visit_expr(operand),
+        Expr::Cast { operand, data_type } => {
+            visitor.visit_expr(operand);
+            visitor.visit_type_name(data_type);
+        }
+        Expr::Collate { operand, .. } => visitor.visit_expr(operand),
+        Expr::Function {
+            args,
+            order_by,
+            filter,
+            ..
+        } => {
+            visitor.visit_function_args(args);
+
+            for item in order_by {
+                visitor.visit_order_by_expr(item);
+            }
+
+            if let Some(filter) = filter {
+                visitor.visit_expr(filter);
+            }
+        }
+        Expr::Nested(expr) => visitor.visit_expr(expr),
+    }
+}
+
+pub fn walk_function_args<V: Visitor + ?Sized>(visitor: &mut V, args: &FunctionArgs) {
+    match args {
+        FunctionArgs::Wildcard => {}
+        FunctionArgs::List(args) => {
+            for arg in args {
+                visitor.visit_expr(arg);
+            }
+        }
+    }
+}
+
+pub fn walk_where_clause<V: Visitor + ?Sized>(visitor: &mut V, where_clause: &WhereClause) {
+    visitor.visit_expr(&where_clause.condition);
+}
+
+pub fn walk_group_by_clause<V: Visitor + ?Sized>(visitor: &mut V, group_by_clause: &GroupByClause) {
+    for grouping in &group_by_clause.groupings {
+        visitor.visit_expr(grouping);
+    }
+}
+
+pub fn walk_set_operation<V: Visitor + ?Sized>(visitor: &mut V, set_operation: &SetOperation) {
+    visitor.visit_select(&set_operation.query);
+}
+
+pub fn walk_having_clause<V: Visitor + ?Sized>(visitor: &mut V, having_clause: &HavingClause) {
+    visitor.visit_expr(&having_clause.condition);
+}
+
+pub fn walk_order_by_clause<V: Visitor + ?Sized>(visitor: &mut V, order_by_clause: &OrderByClause) {
+    for item in &order_by_clause.orderings {
+        visitor.visit_order_by_item(item);
+    }
+}
+
+pub fn walk_order_by_item<V: Visitor + ?Sized>(visitor: &mut V, item: &OrderByItem) {
+    visitor.visit_column_name(&item.column_name);
+
+    if let Some(ordering) = &item.ordering {
+        visitor.visit_ordering(ordering);
+    }
+}
+
+pub fn walk_order_by_expr<V: Visitor + ?Sized>(visitor: &mut V, item: &OrderByExpr) {
+    visitor.visit_expr(&item.expr);
+
+    if let Some(ordering) = &item.ordering {
+        visitor.visit_ordering(ordering);
+    }
+}
+
+pub fn walk_explain<V: Visitor + ?Sized>(visitor: &mut V, explain: &ExplainStatement) {
+    visitor.visit_explain_format(&explain.format);
+    visitor.visit_statement(&explain.statement);
+}
+
+pub fn walk_create_view<V: Visitor + ?Sized>(visitor: &mut V, create_view: &CreateViewStatement) {
+    visitor.visit_object_name(&create_view.name);
+    visitor.visit_select(&create_view.query);
+
+    if let Some(check_option) = &create_view.check_option {
+        visitor.visit_check_option(check_option);
+    }
+}
+
+pub fn walk_create_materialized_view<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    create_view: &CreateMaterializedViewStatement,
+) {
+    visitor.visit_object_name(&create_view.name);
+    visitor.visit_select(&create_view.query);
+}
+
+pub fn walk_refresh_materialized_view<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    refresh: &RefreshMaterializedViewStatement,
+) {
+    visitor.visit_object_name(&refresh.name);
+}
+
+pub fn walk_drop_view<V: Visitor + ?Sized>(visitor: &mut V, drop_view: &DropViewStatement) {
+    for name in &drop_view.names {
+        visitor.visit_object_name(name);
+    }
+}
+
+pub fn walk_create_schema<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    create_schema: &CreateSchemaStatement,
+) {
+    visitor.visit_schema_name(&create_schema.name);
+}
+
+pub fn walk_drop_schema<V: Visitor + ?Sized>(visitor: &mut V, drop_schema: &DropSchemaStatement) {
+    for name in &drop_schema.names {
+        visitor.visit_schema_name(name);
+    }
+}
+
+pub fn walk_create_table<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    create_table: &CreateTableStatement,
+) {
+    visitor.visit_object_name(&create_table.name);
+
+    for column in &create_table.columns {
+        visitor.visit_column_definition(column);
+    }
+}
+
+pub fn walk_column_definition<V: Visitor + ?Sized>(visitor: &mut V, column: &ColumnDefinition) {
+    visitor.visit_type_name(&column.data_type);
+}
+
+pub fn walk_create_function<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    create_function: &CreateFunctionStatement,
+) {
+    for param in &create_function.params {
+        visitor.visit_function_param(param);
+    }
+
+    visitor.visit_type_name(&create_function.returns);
+}
+
+pub fn walk_function_param<V: Visitor + ?Sized>(visitor: &mut V, param: &FunctionParam) {
+    visitor.visit_type_name(&param.data_type);
+}
+
+pub fn walk_drop_function<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    drop_function: &DropFunctionStatement,
+) {
+    for param in &drop_function.params {
+        visitor.visit_type_name(param);
+    }
+}
+
+pub fn walk_show_columns<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    show_columns: &ShowColumnsStatement,
+) {
+    visitor.visit_object_name(&show_columns.table);
+}
+
+pub fn walk_show_create_table<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    show_create_table: &ShowCreateTableStatement,
+) {
+    visitor.visit_object_name(&show_create_table.table);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql::parser::StatementParser;
+
+    /// Records the names, tables and literals it visits, in order.
+    #[derive(Default)]
+    struct Recorder {
+        visited: Vec<String>,
+    }
+
+    impl Visitor for Recorder {
+        fn visit_column_name(&mut self, column_name: &ColumnName) {
+            self.visited.push(column_name.name().to_string());
+            walk_column_name(self, column_name);
+        }
+
+        fn visit_object_name(&mut self, object_name: &ObjectName) {
+            self.visited.push(object_name.to_string());
+        }
+
+        fn visit_literal(&mut self, literal: &Literal) {
+            self.visited.push(match literal {
+                Literal::Number(number) => number.clone(),
+                Literal::String(string) => string.clone(),
+                Literal::Boolean(boolean) => boolean.to_string(),
+                Literal::Null => "NULL".to_string(),
+            });
+        }
+    }
+
+    fn visit(sql: &str) -> Vec<String> {
+        let mut recorder = Recorder::default();
+
+        for statement in StatementParser::parse(sql).unwrap() {
+            recorder.visit_statement(&statement);
+        }
+
+        recorder.visited
+    }
+
+    #[test]
+    fn nested_query() {
+        // Every part of a query is visited in the order it is written, qualifiers included
+        let visited = visit(
+            "SELECT c1, count(DISTINCT c2 ORDER BY c3) FILTER (WHERE c4 > 1) AS n, c5::TEXT, \
+             c6 COLLATE nocase, -c7, (c8 IS NULL) \
+             FROM t1 \
+             JOIN (SELECT c9 FROM t2 WHERE c10 = 'x') AS d ON t1.c11 = d.c12, \
+             LATERAL f(c13) AS g \
+             WHERE NOT c14 \
+             GROUP BY c15 \
+             HAVING sum(c16) > 2 \
+             UNION ALL (SELECT c17 FROM t3 ORDER BY c18) \
+             ORDER BY c19 LIMIT 3 OFFSET 4",
+        );
+
+        assert_eq!(
+            visited,
+            [
+                "c1", "c2", "c3", "c4", "1", "c5", "c6", "c7", "c8", "t1", "c9", "t2", "c10", "x",
+                "c11", "t1", "c12", "d", "c13", "c14", "c15", "c16", "2", "c17", "t3", "c18",
+                "c19",
+            ]
+        );
+    }
+
+    #[test]
+    fn statements() {
+        assert_eq!(
+            visit(
+                "INSERT INTO t1 SELECT c1 FROM t2; \
+                 UPDATE t3 SET c2 = c3 + 1 WHERE c4; \
+                 DELETE FROM t4 WHERE c5 IS NOT NULL; \
+                 EXPLAIN SELECT c6 FROM t5; \
+                 CREATE VIEW v AS SELECT c7 FROM t6"
+            ),
+            ["t1", "c1", "t2", "t3", "c3", "1", "c4", "t4", "c5", "c6", "t5", "v", "c7", "t6",]
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! Runs a server with a function of the application, which queries can call like the built-in
+//! ones.
+
+use std::borrow::Cow;
+
+use drown_common::sql::{
+    data_type::DataType,
+    function::{FunctionError, Param, Returns, ScalarOptions, Signature},
+    value::Value,
+};
+use drownd::Server;
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let mut server = Server::default();
+
+    server
+        .functions_mut()
+        .register_scalar(
+            Signature {
+                name: Cow::Borrowed("shout"),
+                params: Some(Cow::Borrowed(&[Param::Type(DataType::Text)])),
+                variadic: 0,
+                returns: Returns::Type(DataType::Text),
+            },
+            ScalarOptions {
+                strict: true,
+                deterministic: true,
+            },
+            |_, args| match &args[0] {
+                Value::Text(text) => Ok(Value::Text(format!("{}!", text.to_uppercase()))),
+                _ => Err(FunctionError::InvalidArgument {
+                    function: "shout",
+                    reason: "expected text",
+                }),
+            },
+        )
+        .expect("`shout` is not a built-in function");
+
+    for sql in [
+        "SELECT shout('hello') FROM generate_series(1, 1) AS one",
+        "SELECT shout(1) FROM generate_series(1, 1) AS one",
+    ] {
+        match server.execute(sql) {
+            Ok(result) => println!("{}: {:?}", sql, result.data),
+            Err(err) => println!("{}: {}", sql, err.error),
+        }
+    }
+
+    server.listen("127.0.0.1:6472").await
+}
@@ -0,0 +1,1651 @@
+use drown_common::sql::{
+    parser::Span,
+    statement::{
+        AliasedExpr, AliasedFromTable, BinaryOperator, ColumnName, Expr, FromClause, FromTable,
+        FunctionArgs, JoinCondition, JoinType, LimitClause, Literal, ObjectName, OffsetClause,
+        OrderByExpr, Ordering, ResultColumns, SelectStatement, SetOperator, TypeName,
+        UnaryOperator,
+    },
+    value::Value,
+};
+use thiserror::Error;
+
+use drown_common::sql::function::{FunctionKind, FunctionRegistry};
+
+use crate::catalog::{closest_name, Catalog, CatalogError, RelationName, SearchPath};
+
+/// Identifies a column produced by one of the relations of a bound query, as an index into
+/// `BoundQuery::columns`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColumnId(pub usize);
+
+/// A query whose names have all been resolved against the catalog.
+#[derive(Debug)]
+pub struct BoundQuery {
+    pub select: BoundSelect,
+    /// Every column produced by a relation of the query, including the relations within views
+    /// and derived tables
+    pub columns: Vec<BoundColumn>,
+}
+
+#[derive(Debug)]
+pub struct BoundColumn {
+    pub name: String,
+    /// The alias or name of the relation producing the column, if it has one
+    pub relation: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct BoundSelect {
+    pub distinct: bool,
+    pub from: BoundFrom,
+    /// The result columns, with `*` expanded
+    pub columns: Vec<BoundResultColumn>,
+    pub where_clause: Option<BoundExpr>,
+    pub group_by: Vec<BoundExpr>,
+    pub having: Option<BoundExpr>,
+    /// The queries combined with this one, in the order they are written
+    pub set_operations: Vec<BoundSetOperation>,
+    /// The columns of the combined rows, which `order_by` refers to, when there are set
+    /// operations
+    pub set_columns: Vec<ColumnId>,
+    pub order_by: Vec<BoundOrderByItem>,
+    pub offset: Option<OffsetClause>,
+    pub limit: Option<LimitClause>,
+}
+
+#[derive(Debug)]
+pub struct BoundSetOperation {
+    pub operator: SetOperator,
+    pub all: bool,
+    pub query: BoundSelect,
+}
+
+#[derive(Debug)]
+pub struct BoundResultColumn {
+    pub expr: BoundExpr,
+    /// The alias, or the name derived from the expression when there is none
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundOrderByItem {
+    pub expr: BoundExpr,
+    pub ordering: Option<Ordering>,
+}
+
+#[derive(Debug)]
+pub enum BoundFrom {
+    Relation(BoundRelation),
+    Join {
+        left: Box<BoundFrom>,
+        right: Box<BoundRelation>,
+        join_type: JoinType,
+        /// `None` for cross joins
+        condition: Option<BoundJoinCondition>,
+    },
+}
+
+#[derive(Debug)]
+pub enum BoundJoinCondition {
+    On(BoundExpr),
+    /// The pairs of left and right columns which must be equal
+    Using(Vec<(ColumnId, ColumnId)>),
+}
+
+#[derive(Debug)]
+pub struct BoundRelation {
+    pub source: RelationSource,
+    pub alias: Option<String>,
+    /// The columns the relation produces, in order
+    pub columns: Vec<ColumnId>,
+}
+
+#[derive(Debug)]
+pub enum RelationSource {
+    Table(RelationName),
+    /// A view, expanded into its bound query
+    View {
+        name: RelationName,
+        query: Box<BoundSelect>,
+    },
+    /// A materialized view, whose stored results are read rather than its query
+    MaterializedView(RelationName),
+    Derived(Box<BoundSelect>),
+    /// Rows of expressions, which can only refer to the queries the FROM clause is in
+    Values(Vec<Vec<BoundExpr>>),
+    /// A call of a table function, whose arguments can refer to the columns of the relations
+    /// before it
+    Function {
+        name: String,
+        args: Vec<BoundExpr>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoundExpr {
+    Literal(Literal),
+    /// A value computed while planning, such as the result of a folded function call
+    Constant(Value),
+    Column(ColumnId),
+    Unary {
+        operator: UnaryOperator,
+        operand: Box<BoundExpr>,
+    },
+    Binary {
+        left: Box<BoundExpr>,
+        operator: BinaryOperator,
+        right: Box<BoundExpr>,
+    },
+    IsNull {
+        operand: Box<BoundExpr>,
+        negated: bool,
+    },
+    Function {
+        name: String,
+        /// `None` for `f(*)`
+        args: Option<Vec<BoundExpr>>,
+        distinct: bool,
+        order_by: Vec<BoundOrderByItem>,
+        filter: Option<Box<BoundExpr>>,
+    },
+    Cast {
+        operand: Box<BoundExpr>,
+        data_type: TypeName,
+    },
+    Collate {
+        operand: Box<BoundExpr>,
+        collation: String,
+    },
+}
+
+#[derive(Debug, Error)]
+pub enum BindError {
+    #[error("{source} at {span}")]
+    Catalog { source: CatalogError, span: Span },
+    #[error("Table or view `{name}` does not exist at {span}")]
+    RelationNotFound {
+        name: String,
+        span: Span,
+        suggestion: Option<String>,
+    },
+    #[error("Table name `{name}` is specified more than once at {span}")]
+    DuplicateTableName { name: String, span: Span },
+    #[error("Table `{name}` is not in the FROM clause at {span}")]
+    TableNotInFrom {
+        name: String,
+        span: Span,
+        suggestion: Option<String>,
+    },
+    #[error("Table reference `{name}` is ambiguous at {span}")]
+    AmbiguousTable { name: String, span: Span },
+    #[error("Column `{name}` does not exist at {span}")]
+    ColumnNotFound {
+        name: String,
+        span: Span,
+        suggestion: Option<String>,
+    },
+    #[error("Column reference `{name}` is ambiguous at {span}")]
+    AmbiguousColumn { name: String, span: Span },
+    #[error("Column `{name}` named in USING does not exist in the {side} table at {span}")]
+    UsingColumnNotFound {
+        name: String,
+        side: &'static str,
+        span: Span,
+    },
+    #[error("Table function `{name}` does not exist at {span}")]
+    TableFunctionNotFound {
+        name: String,
+        span: Span,
+        suggestion: Option<String>,
+    },
+    #[error("Function `{name}` does not return a table, so it cannot be called in FROM at {span}")]
+    NotTableFunction { name: String, span: Span },
+    #[error("The right side of a RIGHT or FULL join cannot refer to the left table at {span}")]
+    LateralOuterJoin { span: Span },
+    #[error("Aggregates are not allowed in {0}")]
+    MisplacedAggregate(&'static str),
+    #[error("Calls of aggregate `{0}` cannot contain other aggregate calls")]
+    NestedAggregate(String),
+    #[error("Column `{0}` must appear in GROUP BY or be used in an aggregate")]
+    UngroupedColumn(String),
+    #[error("Column `{name}` of ORDER BY must be a result column of SELECT DISTINCT at {span}")]
+    DistinctOrderBy { name: String, span: Span },
+    #[error("Each {operator} query must have the same number of columns, but {left} and {right} were given")]
+    SetOperationColumnCount {
+        operator: &'static str,
+        left: usize,
+        right: usize,
+    },
+    #[error("Each row of VALUES must have the same number of columns at {span}")]
+    ValuesColumnCount { span: Span },
+}
+
+impl BindError {
+    /// The existing name a missing one was most likely meant to be.
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            BindError::Catalog { source, .. } => source.suggestion(),
+            BindError::RelationNotFound { suggestion, .. }
+            | BindError::TableNotInFrom { suggestion, .. }
+            | BindError::ColumnNotFound { suggestion, .. }
+            | BindError::TableFunctionNotFound { suggestion, .. } => suggestion.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Resolves every table, column and alias in `select`, resolving relation names with
+/// `search_path` and expanding views and `*`.
+pub fn bind_select(
+    catalog: &Catalog,
+    search_path: &SearchPath,
+    select: &SelectStatement,
+) -> Result<BoundQuery, BindError> {
+    let mut binder = Binder {
+        catalog,
+        columns: Vec::new(),
+    };
+
+    let select = binder.bind_select(search_path, select)?;
+
+    Ok(BoundQuery {
+        select,
+        columns: binder.columns,
+    })
+}
+
+struct Binder<'a> {
+    catalog: &'a Catalog,
+    columns: Vec<BoundColumn>,
+}
+
+/// The relations of a FROM clause, which the names in the rest of the query refer to.
+#[derive(Clone, Default)]
+struct Scope {
+    relations: Vec<ScopeRelation>,
+    /// Right columns of a `USING` join, which are only visible through their qualifier as they
+    /// are merged into the left columns of the same name
+    merged: Vec<ColumnId>,
+    /// The relations of the query a `LATERAL` subquery is in, which names that are not found in
+    /// this scope refer to
+    outer: Option<Box<Scope>>,
+}
+
+#[derive(Clone)]
+struct ScopeRelation {
+    qualifier: Qualifier,
+    columns: Vec<(String, ColumnId)>,
+}
+
+/// How the columns of a relation can be qualified.
+#[derive(Clone)]
+enum Qualifier {
+    /// Only by its alias, once it has one, or by the name of its function for table functions
+    Alias(String),
+    /// By any trailing part of its fully qualified name
+    Name(RelationName),
+    /// Not at all, as for derived tables without an alias
+    None,
+}
+
+impl Qualifier {
+    fn matches(&self, qualifier: &ObjectName) -> bool {
+        match self {
+            Qualifier::Alias(alias) => qualifier.parts.len() == 1 && qualifier.name() == alias,
+            Qualifier::Name(name) => name.is_referred_to_by(qualifier),
+            Qualifier::None => false,
+        }
+    }
+
+    /// Whether two relations of one FROM clause would be referred to by the same name.
+    ///
+    /// Unaliased relations of the same name in different schemas do not conflict, as they can
+    /// still be told apart by qualifying them with their schemas.
+    fn conflicts_with(&self, other: &Qualifier) -> bool {
+        match (self, other) {
+            (Qualifier::Name(a), Qualifier::Name(b)) => a == b,
+            (Qualifier::None, _) | (_, Qualifier::None) => false,
+            _ => self.name() == other.name(),
+        }
+    }
+
+    fn name(&self) -> Option<&String> {
+        match self {
+            Qualifier::Alias(alias) => Some(alias),
+            Qualifier::Name(name) => Some(&name.name),
+            Qualifier::None => None,
+        }
+    }
+}
+
+impl Binder<'_> {
+    fn bind_select(
+        &mut self,
+        search_path: &SearchPath,
+        select: &SelectStatement,
+    ) -> Result<BoundSelect, BindError> {
+        self.bind_subquery(search_path, select, None)
+    }
+
+    /// Binds a query in which names not found in its own FROM clause refer to the relations of
+    /// `outer`.
+    fn bind_subquery(
+        &mut self,
+        search_path: &SearchPath,
+        select: &SelectStatement,
+        outer: Option<Box<Scope>>,
+    ) -> Result<BoundSelect, BindError> {
+        let mut scope = Scope {
+            outer,
+            ..Scope::default()
+        };
+        let from = self.bind_from_clause(search_path, &select.from_clause, &mut scope)?;
+
+        let columns = match &select.columns {
+            ResultColumns::All => scope
+                .visible_columns()
+                .map(|(name, id)| BoundResultColumn {
+                    expr: BoundExpr::Column(id),
+                    name: name.clone(),
+                })
+                .collect(),
+            ResultColumns::Specific(columns) => columns
+                .iter()
+                .map(|column| {
+                    Ok(BoundResultColumn {
+                        expr: scope.bind_expr(&column.expr)?,
+                        name: output_name(column),
+                    })
+                })
+                .collect::<Result<Vec<_>, BindError>>()?,
+        };
+
+        let where_clause = match &select.where_clause {
+            Some(where_clause) => Some(scope.bind_expr(&where_clause.condition)?),
+            None => None,
+        };
+
+        // Groupings refer to input columns first, and only then to result columns
+        let group_by = match &select.group_by_clause {
+            Some(group_by_clause) => group_by_clause
+                .groupings
+                .iter()
+                .map(|grouping| match grouping {
+                    Expr::Column(column_name) => match scope.bind_column(column_name) {
+                        Err(err @ BindError::ColumnNotFound { .. }) => {
+                            result_column(&columns, column_name)?.ok_or(err)
+                        }
+                        result => result.map(BoundExpr::Column),
+                    },
+                    expr => scope.bind_expr(expr),
+                })
+                .collect::<Result<Vec<_>, BindError>>()?,
+            None => Vec::new(),
+        };
+
+        let having = match &select.having_clause {
+            Some(having_clause) => Some(scope.bind_expr(&having_clause.condition)?),
+            None => None,
+        };
+
+        let mut set_operations = Vec::new();
+
+        for set_operation in &select.set_operations {
+            // Each query only shares the queries the whole one is in
+            let query =
+                self.bind_subquery(search_path, &set_operation.query, scope.outer.clone())?;
+
+            if query.columns.len() != columns.len() {
+                return Err(BindError::SetOperationColumnCount {
+                    operator: match set_operation.operator {
+                        SetOperator::Union => "UNION",
+                        SetOperator::Intersect => "INTERSECT",
+                        SetOperator::Except => "EXCEPT",
+                    },
+                    left: columns.len(),
+                    right: query.columns.len(),
+                });
+            }
+
+            set_operations.push(BoundSetOperation {
+                operator: set_operation.operator,
+                all: set_operation.all,
+                query,
+            });
+        }
+
+        // Combined rows are named after the result columns of the first query
+        let set_columns = match set_operations.is_empty() {
+            true => Vec::new(),
+            false => columns
+                .iter()
+                .map(|column| {
+                    self.columns.push(BoundColumn {
+                        name: column.name.clone(),
+                        relation: None,
+                    });
+
+                    ColumnId(self.columns.len() - 1)
+                })
+                .collect(),
+        };
+
+        // Orderings refer to result columns first, and only then to input columns, unless they
+        // order combined rows, which only have result columns
+        let order_by = match &select.order_by_clause {
+            Some(order_by_clause) => order_by_clause
+                .orderings
+                .iter()
+                .map(|item| {
+                    let mut expr = if !set_operations.is_empty() {
+                        let combined = set_columns
+                            .iter()
+                            .zip(&columns)
+                            .map(|(id, column)| BoundResultColumn {
+                                expr: BoundExpr::Column(*id),
+                                name: column.name.clone(),
+                            })
+                            .collect::<Vec<_>>();
+
+                        match result_column(&combined, &item.column_name)? {
+                            Some(expr) => expr,
+                            None => {
+                                return Err(BindError::ColumnNotFound {
+                                    name: item.column_name.to_string(),
+                                    span: item.column_name.span(),
+                                    suggestion: closest_name(
+                                        item.column_name.name(),
+                                        combined.iter().map(|column| &column.name),
+                                    ),
+                                })
+                            }
+                        }
+                    } else {
+                        match result_column(&columns, &item.column_name)? {
+                            Some(expr) => expr,
+                            None => BoundExpr::Column(scope.bind_column(&item.column_name)?),
+                        }
+                    };
+
+                    // Rows that are the same in every result column cannot be told apart by
+                    // anything else
+                    if select.distinct
+                        && set_operations.is_empty()
+                        && !columns.iter().any(|column| column.expr == expr)
+                    {
+                        return Err(BindError::DistinctOrderBy {
+                            name: item.column_name.to_string(),
+                            span: item.column_name.span(),
+                        });
+                    }
+
+                    if let Some(collation) = &item.collation {
+                        expr = BoundExpr::Collate {
+                            operand: Box::new(expr),
+                            collation: collation.clone(),
+                        };
+                    }
+
+                    Ok(BoundOrderByItem {
+                        expr,
+                        ordering: item.ordering.clone(),
+                    })
+                })
+                .collect::<Result<Vec<_>, BindError>>()?,
+            None => Vec::new(),
+        };
+
+        let select = BoundSelect {
+            distinct: select.distinct,
+            from,
+            columns,
+            where_clause,
+            group_by,
+            having,
+            set_operations,
+            set_columns,
+            order_by,
+            offset: select.offset.clone(),
+            limit: select.limit.clone(),
+        };
+
+        self.check_aggregates(&select)?;
+
+        Ok(select)
+    }
+
+    /// Checks that aggregates are only called on the rows a query aggregates, without being
+    /// nested, and that the columns of aggregated rows are only read through aggregates unless
+    /// rows are grouped by them.
+    fn check_aggregates(&self, select: &BoundSelect) -> Result<(), BindError> {
+        let registry = self.catalog.functions();
+
+        let function_args =
+            select
+                .from
+                .relations()
+                .into_iter()
+                .flat_map(|relation| match &relation.source {
+                    RelationSource::Function { args, .. } => {
+                        args.iter().map(|expr| ("FROM", expr)).collect()
+                    }
+                    RelationSource::Values(rows) => {
+                        rows.iter().flatten().map(|expr| ("VALUES", expr)).collect()
+                    }
+                    _ => Vec::new(),
+                });
+
+        let filters = select.where_clause.iter().map(|expr| ("WHERE", expr));
+        let filters = filters.chain(
+            select
+                .from
+                .conditions()
+                .into_iter()
+                .map(|expr| ("JOIN", expr)),
+        );
+        let filters = filters.chain(function_args);
+
+        for (clause, expr) in filters.chain(select.group_by.iter().map(|expr| ("GROUP BY", expr))) {
+            if expr.any(&mut |part| is_aggregate_call(registry, part)) {
+                return Err(BindError::MisplacedAggregate(clause));
+            }
+        }
+
+        let aggregated = select.columns.iter().map(|column| &column.expr);
+        let aggregated = aggregated
+            .chain(&select.having)
+            .chain(select.order_by.iter().map(|item| &item.expr))
+            .collect::<Vec<_>>();
+
+        for expr in &aggregated {
+            if let Some(name) = nested_aggregate(registry, expr) {
+                return Err(BindError::NestedAggregate(name));
+            }
+        }
+
+        let aggregating = !select.group_by.is_empty()
+            || select.having.is_some()
+            || aggregated
+                .iter()
+                .any(|expr| expr.any(&mut |part| is_aggregate_call(registry, part)));
+
+        if !aggregating {
+            return Ok(());
+        }
+
+        // Columns of the queries this one is within are the same for all of its rows
+        let local = select.from.columns();
+
+        for expr in aggregated {
+            // Aggregate calls and grouped expressions are left out of what is looked through
+            let rest = expr.rewrite(&mut |part| {
+                (select.group_by.contains(part) || is_aggregate_call(registry, part))
+                    .then_some(BoundExpr::Literal(Literal::Null))
+            });
+
+            let mut ungrouped = None;
+
+            rest.any(&mut |part| match part {
+                BoundExpr::Column(id) if local.contains(id) => {
+                    ungrouped = Some(*id);
+                    true
+                }
+                _ => false,
+            });
+
+            if let Some(id) = ungrouped {
+                return Err(BindError::UngroupedColumn(
+                    qualified_column_name(&self.columns[id.0]).to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn bind_from_clause(
+        &mut self,
+        search_path: &SearchPath,
+        from_clause: &FromClause,
+        scope: &mut Scope,
+    ) -> Result<BoundFrom, BindError> {
+        match from_clause {
+            FromClause::Direct { table } => Ok(BoundFrom::Relation(self.bind_aliased_table(
+                search_path,
+                table,
+                scope,
+            )?)),
+            FromClause::Join {
+                left,
+                right,
+                join_type,
+                join_condition,
+            } => {
+                let left_from = self.bind_from_clause(search_path, left, scope)?;
+                let left_count = scope.relations.len();
+                let right_relation = self.bind_aliased_table(search_path, right, scope)?;
+
+                // Rows of the right side are computed for each left row, which a RIGHT or FULL
+                // join would have to produce even where there is no left row
+                if matches!(join_type, JoinType::RightOuter | JoinType::FullOuter)
+                    && right_relation.references(&left_from.columns())
+                {
+                    return Err(BindError::LateralOuterJoin { span: right.span });
+                }
+
+                let condition = match join_condition {
+                    None => None,
+                    Some(JoinCondition::On(expr)) => {
+                        Some(BoundJoinCondition::On(scope.bind_expr(expr)?))
+                    }
+                    Some(JoinCondition::Using(names)) => {
+                        let mut pairs = Vec::new();
+
+                        for name in names {
+                            let (left_relation, left_column) =
+                                scope.left_using_column(left_count, name, left)?;
+                            let right_column =
+                                scope.relations[left_count].using_column(name, "right", right)?;
+
+                            scope.merged.push(right_column);
+                            pairs.push((left_relation, left_column, right_column));
+                        }
+
+                        // Merged columns come first in their relation when the join is
+                        // expanded by `*`
+                        for (relation, column, _) in &pairs {
+                            scope.relations[*relation]
+                                .columns
+                                .sort_by_key(|(_, id)| id != column);
+                        }
+
+                        Some(BoundJoinCondition::Using(
+                            pairs
+                                .into_iter()
+                                .map(|(_, left, right)| (left, right))
+                                .collect(),
+                        ))
+                    }
+                };
+
+                Ok(BoundFrom::Join {
+                    left: Box::new(left_from),
+                    right: Box::new(right_relation),
+                    join_type: join_type.clone(),
+                    condition,
+                })
+            }
+        }
+    }
+
+    /// Binds one relation of a FROM clause and adds it to `scope`.
+    fn bind_aliased_table(
+        &mut self,
+        search_path: &SearchPath,
+        table: &AliasedFromTable,
+        scope: &mut Scope,
+    ) -> Result<BoundRelation, BindError> {
+        let (source, names) = match &table.table {
+            FromTable::DerivedTable { query, lateral } => {
+                // Other subqueries can only refer to the queries the whole FROM clause is in
+                let outer = match lateral {
+                    true => Some(Box::new(scope.clone())),
+                    false => scope.outer.clone(),
+                };
+
+                let query = self.bind_subquery(search_path, query, outer)?;
+                let names = query.column_names();
+
+                (RelationSource::Derived(Box::new(query)), names)
+            }
+            FromTable::Values { rows } => {
+                let values_scope = Scope {
+                    outer: scope.outer.clone(),
+                    ..Scope::default()
+                };
+
+                let rows = rows
+                    .iter()
+                    .map(|row| {
+                        if row.len() != rows[0].len() {
+                            return Err(BindError::ValuesColumnCount { span: table.span });
+                        }
+
+                        row.iter()
+                            .map(|expr| values_scope.bind_expr(expr))
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let names = (1..=rows[0].len()).map(|i| format!("column{i}")).collect();
+
+                (RelationSource::Values(rows), names)
+            }
+            FromTable::Function { name, args, .. } => {
+                let registry = self.catalog.functions();
+                let name = name.to_ascii_lowercase();
+
+                let Some(columns) = registry.table_columns(&name) else {
+                    if registry.overloads(&name).is_empty() {
+                        let names = registry
+                            .names()
+                            .filter(|name| registry.table_columns(name).is_some())
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>();
+
+                        return Err(BindError::TableFunctionNotFound {
+                            suggestion: closest_name(&name, &names),
+                            name,
+                            span: table.span,
+                        });
+                    }
+
+                    return Err(BindError::NotTableFunction {
+                        name,
+                        span: table.span,
+                    });
+                };
+
+                let args = args
+                    .iter()
+                    .map(|arg| scope.bind_expr(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                // A single column is named after the alias of the function, as it would be for
+                // a scalar value
+                let names = match (columns, &table.alias) {
+                    ([_], Some(alias)) => vec![alias.clone()],
+                    _ => columns
+                        .iter()
+                        .map(|column| column.name.to_string())
+                        .collect(),
+                };
+
+                (RelationSource::Function { name, args }, names)
+            }
+            FromTable::TableName { name } => {
+                let relation = self.catalog.resolve(name, search_path);
+
+                let schema = self
+                    .catalog
+                    .database(&relation.database)
+                    .and_then(|database| database.schema(&relation.schema))
+                    .map_err(|source| BindError::Catalog {
+                        source,
+                        span: name.span,
+                    })?;
+
+                if let Ok(table) = schema.table(&relation.name) {
+                    let names = table.columns.iter().map(|column| column.name.clone());
+                    (RelationSource::Table(relation), names.collect())
+                } else if let Some(view) = self.catalog.view(&relation) {
+                    // Views are bound with the search path they were created with
+                    let query = self.bind_select(&view.search_path, view.select())?;
+
+                    let names = match view.columns.is_empty() {
+                        true => query.column_names(),
+                        false => view.columns.clone(),
+                    };
+
+                    let source = RelationSource::View {
+                        name: relation,
+                        query: Box::new(query),
+                    };
+
+                    (source, names)
+                } else if let Some(materialized_view) = self.catalog.materialized_view(&relation) {
+                    let view = &materialized_view.definition;
+
+                    // The query is only bound to find the names of the stored columns
+                    let names = match view.columns.is_empty() {
+                        true => bind_select(self.catalog, &view.search_path, view.select())?
+                            .select
+                            .column_names(),
+                        false => view.columns.clone(),
+                    };
+
+                    (RelationSource::MaterializedView(relation), names)
+                } else {
+                    let names = schema
+                        .tables()
+                        .into_iter()
+                        .map(|table| &table.name)
+                        .chain(schema.views().into_iter().map(|view| &view.name))
+                        .chain(
+                            schema
+                                .materialized_views()
+                                .into_iter()
+                                .map(|view| &view.definition.name),
+                        );
+
+                    return Err(BindError::RelationNotFound {
+                        name: name.to_string(),
+                        span: name.span,
+                        suggestion: closest_name(&relation.name, names),
+                    });
+                }
+            }
+        };
+
+        let qualifier = match (&table.alias, &source) {
+            (Some(alias), _) => Qualifier::Alias(alias.clone()),
+            (None, RelationSource::Derived(_) | RelationSource::Values(_)) => Qualifier::None,
+            (None, RelationSource::Function { name, .. }) => Qualifier::Alias(name.clone()),
+            (
+                None,
+                RelationSource::Table(name)
+                | RelationSource::View { name, .. }
+                | RelationSource::MaterializedView(name),
+            ) => Qualifier::Name(name.clone()),
+        };
+
+        if let Some(other) = scope
+            .relations
+            .iter()
+            .find(|relation| relation.qualifier.conflicts_with(&qualifier))
+        {
+            return Err(BindError::DuplicateTableName {
+                name: other.qualifier.name().unwrap().clone(),
+                span: table.span,
+            });
+        }
+
+        let columns = names
+            .into_iter()
+            .map(|name| {
+                let id = ColumnId(self.columns.len());
+
+                self.columns.push(BoundColumn {
+                    name: name.clone(),
+                    relation: qualifier.name().cloned(),
+                });
+
+                (name, id)
+            })
+            .collect::<Vec<_>>();
+
+        scope.relations.push(ScopeRelation {
+            qualifier,
+            columns: columns.clone(),
+        });
+
+        Ok(BoundRelation {
+            source,
+            alias: table.alias.clone(),
+            columns: columns.into_iter().map(|(_, id)| id).collect(),
+        })
+    }
+}
+
+impl Scope {
+    /// The columns `*` expands to, in order.
+    fn visible_columns(&self) -> impl Iterator<Item = (&String, ColumnId)> {
+        self.relations
+            .iter()
+            .flat_map(|relation| &relation.columns)
+            .filter(|(_, id)| !self.merged.contains(id))
+            .map(|(name, id)| (name, *id))
+    }
+
+    fn bind_expr(&self, expr: &Expr) -> Result<BoundExpr, BindError> {
+        Ok(match expr {
+            Expr::Literal(literal) => BoundExpr::Literal(literal.clone()),
+            Expr::Column(column_name) => BoundExpr::Column(self.bind_column(column_name)?),
+            Expr::Unary { operator, operand } => BoundExpr::Unary {
+                operator: *operator,
+                operand: Box::new(self.bind_expr(operand)?),
+            },
+            Expr::Binary {
+                left,
+                operator,
+                right,
+            } => BoundExpr::Binary {
+                left: Box::new(self.bind_expr(left)?),
+                operator: *operator,
+                right: Box::new(self.bind_expr(right)?),
+            },
+            Expr::IsNull { operand, negated } => BoundExpr::IsNull {
+                operand: Box::new(self.bind_expr(operand)?),
+                negated: *negated,
+            },
+            Expr::Function {
+                name,
+                args,
+                distinct,
+                order_by,
+                filter,
+            } => BoundExpr::Function {
+                name: name.clone(),
+                args: match args {
+                    FunctionArgs::Wildcard => None,
+                    FunctionArgs::List(args) => Some(
+                        args.iter()
+                            .map(|arg| self.bind_expr(arg))
+                            .collect::<Result<_, _>>()?,
+                    ),
+                },
+                distinct: *distinct,
+                order_by: order_by
+                    .iter()
+                    .map(|item| {
+                        Ok(BoundOrderByItem {
+                            expr: self.bind_expr(&item.expr)?,
+                            ordering: item.ordering.clone(),
+                        })
+                    })
+                    .collect::<Result<_, BindError>>()?,
+                filter: match filter {
+                    Some(filter) => Some(Box::new(self.bind_expr(filter)?)),
+                    None => None,
+                },
+            },
+            Expr::Cast { operand, data_type } => BoundExpr::Cast {
+                operand: Box::new(self.bind_expr(operand)?),
+                data_type: data_type.clone(),
+            },
+            Expr::Collate { operand, collation } => BoundExpr::Collate {
+                operand: Box::new(self.bind_expr(operand)?),
+                collation: collation.clone(),
+            },
+            Expr::Nested(expr) => self.bind_expr(expr)?,
+        })
+    }
+
+    fn bind_column(&self, column_name: &ColumnName) -> Result<ColumnId, BindError> {
+        let result = self.bind_local_column(column_name);
+
+        let Some(outer) = &self.outer else {
+            return result;
+        };
+
+        match result {
+            // Qualified names refer to the relation of this scope their qualifier matches
+            Err(BindError::ColumnNotFound { .. }) if column_name.qualifier().is_some() => result,
+            Err(err @ (BindError::ColumnNotFound { .. } | BindError::TableNotInFrom { .. })) => {
+                outer
+                    .bind_column(column_name)
+                    .map_err(|outer_err| match outer_err {
+                        BindError::ColumnNotFound { .. } | BindError::TableNotInFrom { .. } => err,
+                        outer_err => outer_err,
+                    })
+            }
+            result => result,
+        }
+    }
+
+    fn bind_local_column(&self, column_name: &ColumnName) -> Result<ColumnId, BindError> {
+        let name = column_name.name();
+
+        let Some(qualifier) = column_name.qualifier() else {
+            let mut matches = self.visible_columns().filter(|(column, _)| *column == name);
+
+            return match (matches.next(), matches.next()) {
+                (Some((_, id)), None) => Ok(id),
+                (Some(_), Some(_)) => Err(BindError::AmbiguousColumn {
+                    name: name.to_string(),
+                    span: column_name.span(),
+                }),
+                (None, _) => Err(BindError::ColumnNotFound {
+                    name: name.to_string(),
+                    span: column_name.span(),
+                    suggestion: closest_name(name, self.visible_columns().map(|(name, _)| name)),
+                }),
+            };
+        };
+
+        let mut relations = self
+            .relations
+            .iter()
+            .filter(|relation| relation.qualifier.matches(qualifier));
+
+        let relation = match (relations.next(), relations.next()) {
+            (Some(relation), None) => relation,
+            (Some(_), Some(_)) => {
+                return Err(BindError::AmbiguousTable {
+                    name: qualifier.to_string(),
+                    span: qualifier.span,
+                })
+            }
+            (None, _) => {
+                let names = self
+                    .relations
+                    .iter()
+                    .filter_map(|relation| relation.qualifier.name());
+
+                return Err(BindError::TableNotInFrom {
+                    name: qualifier.to_string(),
+                    span: qualifier.span,
+                    suggestion: closest_name(qualifier.name(), names),
+                });
+            }
+        };
+
+        let mut matches = relation.columns.iter().filter(|(column, _)| column == name);
+
+        match (matches.next(), matches.next()) {
+            (Some((_, id)), None) => Ok(*id),
+            (Some(_), Some(_)) => Err(BindError::AmbiguousColumn {
+                name: column_name.to_string(),
+                span: column_name.span(),
+            }),
+            (None, _) => Err(BindError::ColumnNotFound {
+                name: column_name.to_string(),
+                span: column_name.span(),
+                suggestion: closest_name(name, relation.columns.iter().map(|(name, _)| name)),
+            }),
+        }
+    }
+}
+
+impl Scope {
+    /// The column named in `USING` among the first `count` relations, which are the left side
+    /// of the join, along with the index of its relation.
+    fn left_using_column(
+        &self,
+        count: usize,
+        name: &str,
+        left: &FromClause,
+    ) -> Result<(usize, ColumnId), BindError> {
+        let mut matches = self.relations[..count]
+            .iter()
+            .enumerate()
+            .flat_map(|(i, relation)| relation.columns.iter().map(move |column| (i, column)))
+            .filter(|(_, (column, id))| column == name && !self.merged.contains(id));
+
+        let span = left
+            .tables()
+            .into_iter()
+            .map(|table| table.span)
+            .reduce(|first, last| Span::new(first.start(), last.end()))
+            .unwrap();
+
+        match (matches.next(), matches.next()) {
+            (Some((i, (_, id))), None) => Ok((i, *id)),
+            (Some(_), Some(_)) => Err(BindError::AmbiguousColumn {
+                name: name.to_string(),
+                span,
+            }),
+            (None, _) => Err(BindError::UsingColumnNotFound {
+                name: name.to_string(),
+                side: "left",
+                span,
+            }),
+        }
+    }
+}
+
+impl ScopeRelation {
+    fn using_column(
+        &self,
+        name: &str,
+        side: &'static str,
+        table: &AliasedFromTable,
+    ) -> Result<ColumnId, BindError> {
+        self.columns
+            .iter()
+            .find(|(column, _)| column == name)
+            .map(|(_, id)| *id)
+            .ok_or_else(|| BindError::UsingColumnNotFound {
+                name: name.to_string(),
+                side,
+                span: table.span,
+            })
+    }
+}
+
+impl BoundQuery {
+    /// Turns a bound expression back into SQL, with every column qualified by the relation it
+    /// belongs to where that has a name.
+    pub fn unbind(&self, expr: &BoundExpr) -> Expr {
+        match expr {
+            BoundExpr::Literal(literal) => Expr::Literal(literal.clone()),
+            BoundExpr::Constant(value) => Expr::Literal(match value {
+                Value::Null => Literal::Null,
+                Value::Boolean(value) => Literal::Boolean(*value),
+                Value::SmallInt(_) | Value::Int(_) | Value::BigInt(_) | Value::Decimal(_) => {
+                    Literal::Number(value.to_string())
+                }
+                Value::Real(number) if number.is_finite() => Literal::Number(value.to_string()),
+                Value::Double(number) if number.is_finite() => Literal::Number(value.to_string()),
+                value => Literal::String(value.to_string()),
+            }),
+            BoundExpr::Column(id) => Expr::Column(self.column_name(*id)),
+            BoundExpr::Unary { operator, operand } => Expr::Unary {
+                operator: *operator,
+                operand: Box::new(self.unbind(operand)),
+            },
+            BoundExpr::Binary {
+                left,
+                operator,
+                right,
+            } => Expr::Binary {
+                left: Box::new(self.unbind(left)),
+                operator: *operator,
+                right: Box::new(self.unbind(right)),
+            },
+            BoundExpr::IsNull { operand, negated } => Expr::IsNull {
+                operand: Box::new(self.unbind(operand)),
+                negated: *negated,
+            },
+            BoundExpr::Function {
+                name,
+                args,
+                distinct,
+                order_by,
+                filter,
+            } => Expr::Function {
+                name: name.clone(),
+                args: match args {
+                    Some(args) => {
+                        FunctionArgs::List(args.iter().map(|arg| self.unbind(arg)).collect())
+                    }
+                    None => FunctionArgs::Wildcard,
+                },
+                distinct: *distinct,
+                order_by: order_by
+                    .iter()
+                    .map(|item| OrderByExpr {
+                        expr: self.unbind(&item.expr),
+                        ordering: item.ordering.clone(),
+                    })
+                    .collect(),
+                filter: filter.as_ref().map(|filter| Box::new(self.unbind(filter))),
+            },
+            BoundExpr::Cast { operand, data_type } => Expr::Cast {
+                operand: Box::new(self.unbind(operand)),
+                data_type: data_type.clone(),
+            },
+            BoundExpr::Collate { operand, collation } => Expr::Collate {
+                operand: Box::new(self.unbind(operand)),
+                collation: collation.clone(),
+            },
+        }
+    }
+
+    pub fn column_name(&self, id: ColumnId) -> ColumnName {
+        qualified_column_name(&self.columns[id.0])
+    }
+}
+
+/// The name of a column, qualified by its relation if it has a name.
+fn qualified_column_name(column: &BoundColumn) -> ColumnName {
+    match &column.relation {
+        Some(relation) => ColumnName::Qualified {
+            table_name: ObjectName::unqualified(relation),
+            column_name: column.name.clone(),
+            span: Span::empty(),
+        },
+        None => ColumnName::Direct {
+            name: column.name.clone(),
+            span: Span::empty(),
+        },
+    }
+}
+
+impl BoundSelect {
+    /// Whether the query, or any query within it, reads one of `columns`.
+    fn references(&self, columns: &[ColumnId]) -> bool {
+        let exprs = self.columns.iter().map(|column| &column.expr);
+        let exprs = exprs
+            .chain(self.from.conditions())
+            .chain(&self.where_clause);
+        let exprs = exprs.chain(&self.group_by).chain(&self.having);
+        let mut exprs = exprs.chain(self.order_by.iter().map(|item| &item.expr));
+
+        self.from
+            .relations()
+            .into_iter()
+            .any(|relation| relation.references(columns))
+            || exprs.any(|expr| expr.references(columns))
+            || self
+                .set_operations
+                .iter()
+                .any(|set_operation| set_operation.query.references(columns))
+    }
+
+    pub fn column_names(&self) -> Vec<String> {
+        self.columns
+            .iter()
+            .map(|column| column.name.clone())
+            .collect()
+    }
+}
+
+impl BoundFrom {
+    /// Every relation joined, in the order they are written.
+    pub fn relations(&self) -> Vec<&BoundRelation> {
+        match self {
+            BoundFrom::Relation(relation) => vec![relation],
+            BoundFrom::Join { left, right, .. } => {
+                let mut relations = left.relations();
+                relations.push(right);
+                relations
+            }
+        }
+    }
+
+    /// The `ON` conditions of every join.
+    pub fn conditions(&self) -> Vec<&BoundExpr> {
+        match self {
+            BoundFrom::Relation(_) => Vec::new(),
+            BoundFrom::Join {
+                left, condition, ..
+            } => {
+                let mut conditions = left.conditions();
+
+                if let Some(BoundJoinCondition::On(condition)) = condition {
+                    conditions.push(condition);
+                }
+
+                conditions
+            }
+        }
+    }
+
+    /// The columns of every relation joined, in order.
+    pub fn columns(&self) -> Vec<ColumnId> {
+        self.relations()
+            .into_iter()
+            .flat_map(|relation| relation.columns.iter().copied())
+            .collect()
+    }
+}
+
+impl BoundRelation {
+    /// Whether the relation reads one of `columns` of the relations before it.
+    pub fn references(&self, columns: &[ColumnId]) -> bool {
+        match &self.source {
+            RelationSource::Table(_) | RelationSource::MaterializedView(_) => false,
+            RelationSource::View { query, .. } | RelationSource::Derived(query) => {
+                query.references(columns)
+            }
+            RelationSource::Values(rows) => {
+                rows.iter().flatten().any(|expr| expr.references(columns))
+            }
+            RelationSource::Function { args, .. } => args.iter().any(|arg| arg.references(columns)),
+        }
+    }
+}
+
+impl BoundExpr {
+    /// Whether `predicate` holds for the expression or any part of it.
+    pub fn any(&self, predicate: &mut impl FnMut(&BoundExpr) -> bool) -> bool {
+        if predicate(self) {
+            return true;
+        }
+
+        match self {
+            BoundExpr::Literal(_) | BoundExpr::Constant(_) | BoundExpr::Column(_) => false,
+            BoundExpr::Unary { operand, .. }
+            | BoundExpr::IsNull { operand, .. }
+            | BoundExpr::Cast { operand, .. }
+            | BoundExpr::Collate { operand, .. } => operand.any(predicate),
+            BoundExpr::Binary { left, right, .. } => left.any(predicate) || right.any(predicate),
+            BoundExpr::Function {
+                args,
+                order_by,
+                filter,
+                ..
+            } => {
+                args.iter().flatten().any(|arg| arg.any(predicate))
+                    || order_by.iter().any(|item| item.expr.any(predicate))
+                    || filter.as_ref().is_some_and(|filter| filter.any(predicate))
+            }
+        }
+    }
+
+    /// Copies the expression, replacing each part of it for which `replace` returns an
+    /// expression, without looking into the replacements.
+    pub fn rewrite(&self, replace: &mut impl FnMut(&BoundExpr) -> Option<BoundExpr>) -> BoundExpr {
+        if let Some(replacement) = replace(self) {
+            return replacement;
+        }
+
+        match self {
+            BoundExpr::Literal(_) | BoundExpr::Constant(_) | BoundExpr::Column(_) => self.clone(),
+            BoundExpr::Unary { operator, operand } => BoundExpr::Unary {
+                operator: *operator,
+                operand: Box::new(operand.rewrite(replace)),
+            },
+            BoundExpr::Binary {
+                left,
+                operator,
+                right,
+            } => BoundExpr::Binary {
+                left: Box::new(left.rewrite(replace)),
+                operator: *operator,
+                right: Box::new(right.rewrite(replace)),
+            },
+            BoundExpr::IsNull { operand, negated } => BoundExpr::IsNull {
+                operand: Box::new(operand.rewrite(replace)),
+                negated: *negated,
+            },
+            BoundExpr::Function {
+                name,
+                args,
+                distinct,
+                order_by,
+                filter,
+            } => BoundExpr::Function {
+                name: name.clone(),
+                args: args
+                    .as_ref()
+                    .map(|args| args.iter().map(|arg| arg.rewrite(replace)).collect()),
+                distinct: *distinct,
+                order_by: order_by
+                    .iter()
+                    .map(|item| BoundOrderByItem {
+                        expr: item.expr.rewrite(replace),
+                        ordering: item.ordering.clone(),
+                    })
+                    .collect(),
+                filter: filter
+                    .as_ref()
+                    .map(|filter| Box::new(filter.rewrite(replace))),
+            },
+            BoundExpr::Cast { operand, data_type } => BoundExpr::Cast {
+                operand: Box::new(operand.rewrite(replace)),
+                data_type: data_type.clone(),
+            },
+            BoundExpr::Collate { operand, collation } => BoundExpr::Collate {
+                operand: Box::new(operand.rewrite(replace)),
+                collation: collation.clone(),
+            },
+        }
+    }
+
+    fn references(&self, columns: &[ColumnId]) -> bool {
+        self.any(&mut |expr| matches!(expr, BoundExpr::Column(id) if columns.contains(id)))
+    }
+}
+
+/// Finds the result column an unqualified name in `ORDER BY` or `GROUP BY` refers to, if any.
+fn result_column(
+    columns: &[BoundResultColumn],
+    column_name: &ColumnName,
+) -> Result<Option<BoundExpr>, BindError> {
+    if column_name.qualifier().is_some() {
+        return Ok(None);
+    }
+
+    let mut matches = columns
+        .iter()
+        .filter(|column| column.name == column_name.name());
+
+    match (matches.next(), matches.next()) {
+        (Some(column), None) => Ok(Some(column.expr.clone())),
+        (Some(_), Some(_)) => Err(BindError::AmbiguousColumn {
+            name: column_name.to_string(),
+            span: column_name.span(),
+        }),
+        (None, _) => Ok(None),
+    }
+}
+
+/// Whether a function is an aggregate, so that unsupported aggregates are reported as such
+/// rather than being mistaken for scalar functions.
+pub fn is_aggregate(registry: &FunctionRegistry, name: &str) -> bool {
+    registry
+        .overloads(&name.to_ascii_lowercase())
+        .iter()
+        .any(|function| matches!(function.kind, FunctionKind::Aggregate(_)))
+}
+
+pub fn is_aggregate_call(registry: &FunctionRegistry, expr: &BoundExpr) -> bool {
+    matches!(expr, BoundExpr::Function { name, .. } if is_aggregate(registry, name))
+}
+
+/// The name of an aggregate called within the arguments, ordering or filter of another
+/// aggregate call in an expression.
+fn nested_aggregate(registry: &FunctionRegistry, expr: &BoundExpr) -> Option<String> {
+    let mut nested = None;
+
+    expr.any(&mut |part| match part {
+        BoundExpr::Function {
+            name,
+            args,
+            order_by,
+            filter,
+            ..
+        } if is_aggregate(registry, name) => {
+            let mut inner = args
+                .iter()
+                .flatten()
+                .chain(order_by.iter().map(|item| &item.expr))
+                .chain(filter.as_deref());
+
+            if inner.any(|inner| inner.any(&mut |part| is_aggregate_call(registry, part))) {
+                nested = Some(name.clone());
+            }
+
+            nested.is_some()
+        }
+        _ => false,
+    });
+
+    nested
+}
+
+/// The name of a result column, which is its alias or else derived from its expression.
+fn output_name(column: &AliasedExpr) -> String {
+    match &column.alias {
+        Some(alias) => alias.clone(),
+        None => derived_name(&column.expr),
+    }
+}
+
+/// The name a result column without an alias is given.
+pub fn derived_name(expr: &Expr) -> String {
+    match expr {
+        Expr::Column(column_name) => column_name.name().to_string(),
+        Expr::Function { name, .. } => name.clone(),
+        // Casts are named after what they cast, or after their type if that has no name
+        Expr::Cast { operand, data_type } => match operand.as_ref() {
+            Expr::Column(_) | Expr::Function { .. } | Expr::Cast { .. } => derived_name(operand),
+            _ => data_type.name.to_ascii_lowercase(),
+        },
+        _ => "?column?".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use drown_common::sql::{
+        parser::StatementParser,
+        statement::{SelectStatement, Statement},
+    };
+
+    use super::*;
+    use crate::{execute::execute_statement, session::Session};
+
+    fn catalog() -> (Catalog, Session) {
+        let mut catalog = Catalog::default();
+        let mut session = Session::default();
+
+        let statements = StatementParser::parse(
+            "CREATE TABLE users (id INT, code TEXT, team INT); \
+             CREATE TABLE teams (id INT, code TEXT); \
+             CREATE VIEW named AS SELECT id, code AS tag FROM users",
+        )
+        .unwrap();
+
+        for statement in &statements {
+            execute_statement(&mut catalog, &mut session, statement).unwrap();
+        }
+
+        (catalog, session)
+    }
+
+    fn select(sql: &str) -> SelectStatement {
+        match StatementParser::parse(sql).unwrap().remove(0) {
+            Statement::Select(select) => select,
+            statement => panic!("{statement:?} is not a SELECT"),
+        }
+    }
+
+    fn bind(sql: &str) -> Result<BoundQuery, BindError> {
+        let (catalog, session) = catalog();
+        bind_select(&catalog, &session.search_path, &select(sql))
+    }
+
+    fn error(sql: &str) -> BindError {
+        bind(sql).expect_err(sql)
+    }
+
+    /// The span of the first occurrence of `text` in `sql`.
+    fn span_of(sql: &str, text: &str) -> Span {
+        let start = sql.find(text).unwrap();
+        Span::new(start, start + text.len())
+    }
+
+    #[test]
+    fn resolves_columns() {
+        let query =
+            bind("SELECT u.code, t.code AS team FROM users AS u JOIN teams AS t ON u.team = t.id")
+                .unwrap();
+        assert_eq!(query.select.column_names(), ["code", "team"]);
+
+        let names = query
+            .select
+            .columns
+            .iter()
+            .map(|column| match &column.expr {
+                BoundExpr::Column(id) => query.column_name(*id).to_string(),
+                expr => panic!("{expr:?} is not a column"),
+            });
+        assert_eq!(names.collect::<Vec<_>>(), ["u.code", "t.code"]);
+
+        // `*` expands to the columns of every relation, and views are expanded
+        let query = bind("SELECT * FROM named JOIN teams USING (id)").unwrap();
+        assert_eq!(query.select.column_names(), ["id", "tag", "code"]);
+        assert!(matches!(
+            query.select.from.relations()[0].source,
+            RelationSource::View { .. }
+        ));
+    }
+
+    #[test]
+    fn unknown_columns() {
+        let sql = "SELECT cod FROM users";
+        match error(sql) {
+            BindError::ColumnNotFound {
+                name,
+                span,
+                suggestion,
+            } => {
+                assert_eq!(name, "cod");
+                assert_eq!(span, span_of(sql, "cod"));
+                assert_eq!(suggestion.as_deref(), Some("code"));
+            }
+            err => panic!("{err:?}"),
+        }
+
+        let sql = "SELECT teem.id FROM teams AS team";
+        match error(sql) {
+            BindError::TableNotInFrom {
+                name,
+                span,
+                suggestion,
+            } => {
+                assert_eq!(name, "teem");
+                assert_eq!(span, span_of(sql, "teem"));
+                assert_eq!(suggestion.as_deref(), Some("team"));
+            }
+            err => panic!("{err:?}"),
+        }
+
+        let sql = "SELECT id FROM usres";
+        match error(sql) {
+            BindError::RelationNotFound {
+                span, suggestion, ..
+            } => {
+                assert_eq!(span, span_of(sql, "usres"));
+                assert_eq!(suggestion.as_deref(), Some("users"));
+            }
+            err => panic!("{err:?}"),
+        }
+
+        let sql = "SELECT * FROM users JOIN teams USING (team)";
+        assert!(matches!(
+            error(sql),
+            BindError::UsingColumnNotFound { side: "right", span, .. } if span == span_of(sql, "teams")
+        ));
+    }
+
+    #[test]
+    fn ambiguous_names() {
+        let sql = "SELECT code FROM users, teams";
+        match error(sql) {
+            BindError::AmbiguousColumn { name, span } => {
+                assert_eq!(name, "code");
+                assert_eq!(span, span_of(sql, "code"));
+            }
+            err => panic!("{err:?}"),
+        }
+
+        let sql = "SELECT 1 FROM users AS t, teams AS t";
+        assert!(matches!(
+            error(sql),
+            BindError::DuplicateTableName { name, .. } if name == "t"
+        ));
+
+        // Qualified and USING columns are not ambiguous
+        assert!(bind("SELECT users.code FROM users, teams").is_ok());
+        assert!(bind("SELECT id FROM users JOIN teams USING (id)").is_ok());
+    }
+
+    #[test]
+    fn aggregates() {
+        assert!(bind("SELECT team, count(*), max(code) FROM users GROUP BY team").is_ok());
+        assert!(bind("SELECT count(*) FROM users HAVING sum(id) > 1").is_ok());
+        assert!(bind("SELECT team FROM users GROUP BY team ORDER BY team").is_ok());
+
+        assert!(matches!(
+            error("SELECT team, code FROM users GROUP BY team"),
+            BindError::UngroupedColumn(name) if name == "users.code"
+        ));
+        assert!(matches!(
+            error("SELECT id, count(*) FROM users"),
+            BindError::UngroupedColumn(name) if name == "users.id"
+        ));
+        assert!(matches!(
+            error("SELECT sum(count(*)) FROM users"),
+            BindError::NestedAggregate(name) if name == "sum"
+        ));
+        assert!(matches!(
+            error("SELECT id FROM users WHERE count(*) > 1"),
+            BindError::MisplacedAggregate("WHERE")
+        ));
+
+        // Expressions are grouped by, and the select list can use them like columns
+        assert!(bind("SELECT lower(code), count(*) FROM users GROUP BY lower(code)").is_ok());
+        assert!(bind("SELECT lower(code) = 'x' FROM users GROUP BY lower(code)").is_ok());
+        assert!(bind("SELECT id + 1 FROM users GROUP BY id + 1 HAVING id + 1 > 2").is_ok());
+        assert!(matches!(
+            error("SELECT upper(code) FROM users GROUP BY lower(code)"),
+            BindError::UngroupedColumn(name) if name == "users.code"
+        ));
+        assert!(matches!(
+            error("SELECT id FROM users GROUP BY id + 1"),
+            BindError::UngroupedColumn(name) if name == "users.id"
+        ));
+        assert!(matches!(
+            error("SELECT count(*) FROM users GROUP BY count(*) + 1"),
+            BindError::MisplacedAggregate("GROUP BY")
+        ));
+    }
+
+    #[test]
+    fn error_messages() {
+        let err = error("SELECT cod FROM users");
+        assert_eq!(err.to_string(), "Column `cod` does not exist at 7..10");
+        assert_eq!(err.suggestion(), Some("code"));
+
+        let err = error("SELECT id FROM users UNION SELECT id, code FROM users");
+        assert_eq!(
+            err.to_string(),
+            "Each UNION query must have the same number of columns, but 1 and 2 were given"
+        );
+    }
+}
@@ -0,0 +1,1229 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use drown_common::sql::{
+    collation::{Collation, CollationError},
+    data_type::{DataType, TypeNameError},
+    function::{
+        Function, FunctionContext, FunctionError, FunctionRegistry, Param, RegistryError, Returns,
+        ScalarOptions, Signature,
+    },
+    statement::{
+        AliasedFromTable, CheckOption, ColumnDefinition, ColumnName, CreateDatabaseStatement,
+        CreateMaterializedViewStatement, CreateSchemaStatement, CreateTableStatement,
+        CreateViewStatement, DropFunctionStatement, DropSchemaStatement, DropViewStatement, Expr,
+        FromClause, FromTable, ObjectName, ResultColumns, SelectStatement, Statement, TypeName,
+    },
+    suggest,
+    value::Value,
+    visitor::{walk_from_table, Visitor},
+};
+use thiserror::Error;
+
+use crate::incremental::{IncrementalError, Maintenance};
+
+/// The database every session starts in, which always exists.
+pub const DEFAULT_DATABASE: &str = "main";
+
+/// The schema every database is created with, which is the only one searched by default.
+pub const DEFAULT_SCHEMA: &str = "public";
+
+/// The values of the columns of a row, in order.
+pub type Row = Vec<Value>;
+
+/// Definitions of every object known to the server.
+#[derive(Debug)]
+pub struct Catalog {
+    databases: HashMap<String, Database>,
+    functions: FunctionRegistry,
+    /// The name and parameter types of every function created with CREATE FUNCTION, which
+    /// unlike the others can be replaced and dropped
+    created_functions: HashSet<(String, Vec<DataType>)>,
+}
+
+#[derive(Debug)]
+pub struct Database {
+    pub name: String,
+    schemas: HashMap<String, Schema>,
+}
+
+/// A namespace of tables and views within a database, which share one set of names.
+#[derive(Debug, Default)]
+pub struct Schema {
+    pub name: String,
+    tables: HashMap<String, Table>,
+    views: HashMap<String, View>,
+    materialized_views: HashMap<String, MaterializedView>,
+}
+
+/// Where unqualified names are looked up: the schemas of one database, searched in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchPath {
+    pub database: String,
+    /// Schemas which do not exist are skipped
+    pub schemas: Vec<String>,
+}
+
+/// The fully qualified name of a table or view.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RelationName {
+    pub database: String,
+    pub schema: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub name: String,
+    pub columns: Vec<ColumnDefinition>,
+    pub rows: Vec<Row>,
+}
+
+/// A named query which is expanded in place wherever it is referenced.
+#[derive(Debug, Clone)]
+pub struct View {
+    pub name: String,
+    /// Column aliases given in the view definition, or empty if the query's own names are used
+    pub columns: Vec<String>,
+    /// Always a `Statement::Select`
+    pub query: Statement,
+    /// The SQL text of the query exactly as it was written by the user
+    pub sql: String,
+    /// Only set on simple views, where rows written through the view must remain visible in it
+    pub check_option: Option<CheckOption>,
+    /// The search path in effect when the view was created, which unqualified names in the query
+    /// are resolved with, in the database the view belongs to
+    pub search_path: SearchPath,
+    /// The name and parameter types of the functions created with CREATE FUNCTION which the
+    /// query calls, which cannot be dropped while the view exists
+    pub functions: Vec<(String, Vec<DataType>)>,
+}
+
+/// A view whose query results are stored, and only recomputed when it is refreshed.
+#[derive(Debug, Clone)]
+pub struct MaterializedView {
+    /// Never has a check option, as materialized views cannot be written to
+    pub definition: View,
+    /// Whether the stored results are available to be read
+    pub populated: bool,
+    /// How changes to the base tables are applied to the stored results as they happen, if
+    /// they are
+    pub maintenance: Option<Maintenance>,
+    /// The stored results, which are empty while the view is not populated and are replaced as
+    /// a whole when it is refreshed or its base tables change
+    pub rows: Arc<Vec<Row>>,
+    /// The hidden columns kept for each of `rows` to maintain its aggregates, which are empty
+    /// unless the view is maintained incrementally and aggregates
+    pub state: Vec<Row>,
+}
+
+#[derive(Debug, Error)]
+pub enum CatalogError {
+    #[error("Database `{0}` already exists")]
+    DatabaseAlreadyExists(String),
+    #[error("Database `{name}` does not exist")]
+    DatabaseNotFound {
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[error("Schema `{0}` already exists")]
+    SchemaAlreadyExists(String),
+    #[error("Schema `{name}` does not exist")]
+    SchemaNotFound {
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[error("Schema `{0}` cannot be dropped while it contains tables or views (use CASCADE to drop them too)")]
+    SchemaNotEmpty(String),
+    #[error(
+        "None of the schemas in the search path exist, so there is no schema to create `{0}` in"
+    )]
+    NoSchemaSelected(String),
+    #[error("Table `{name}` does not exist")]
+    TableNotFound {
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[error("Type `{name}` does not exist")]
+    TypeNotFound {
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[error("{0}")]
+    InvalidType(TypeNameError),
+    #[error("Collation `{name}` does not exist")]
+    CollationNotFound {
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[error("Column `{column}` of type {data_type} cannot have a collation, as it is not text")]
+    CollationNotText { column: String, data_type: DataType },
+    #[error("Column `{column}` is defined more than once in table `{table}`")]
+    DuplicateColumn { table: String, column: String },
+    #[error("A table or view named `{0}` already exists")]
+    RelationAlreadyExists(String),
+    #[error("View `{name}` does not exist")]
+    ViewNotFound {
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[error("Materialized view `{name}` does not exist")]
+    MaterializedViewNotFound {
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[error("View `{0}` cannot reference itself")]
+    RecursiveView(String),
+    #[error("View `{name}` selects {expected} columns but {found} column names were given")]
+    ViewColumnCountMismatch {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+    #[error("View `{0}` cannot use WITH CHECK OPTION because it is not a simple view")]
+    ViewNotUpdatable(String),
+    #[error("View `{name}` cannot be dropped while view `{dependent}` depends on it (use CASCADE to drop it too)")]
+    DependentView { name: String, dependent: String },
+    #[error("Function `{name}` cannot be dropped while view `{dependent}` depends on it (use CASCADE to drop it too)")]
+    DependentFunction { name: String, dependent: String },
+    #[error("Function `{0}` already exists")]
+    FunctionAlreadyExists(String),
+    #[error("Function `{name}` does not exist")]
+    FunctionNotFound {
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[error(
+        "Function `{0}` was not created with CREATE FUNCTION, so it cannot be replaced or dropped"
+    )]
+    FunctionNotCreated(String),
+    #[error("{0}")]
+    InvalidFunction(#[from] RegistryError),
+    #[error("Materialized view `{name}` cannot be maintained incrementally: {reason}")]
+    NotIncrementallyMaintainable {
+        name: String,
+        reason: IncrementalError,
+    },
+}
+
+impl CatalogError {
+    /// The existing object a missing one was most likely meant to be.
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            CatalogError::DatabaseNotFound { suggestion, .. }
+            | CatalogError::SchemaNotFound { suggestion, .. }
+            | CatalogError::TableNotFound { suggestion, .. }
+            | CatalogError::TypeNotFound { suggestion, .. }
+            | CatalogError::CollationNotFound { suggestion, .. }
+            | CatalogError::ViewNotFound { suggestion, .. }
+            | CatalogError::MaterializedViewNotFound { suggestion, .. }
+            | CatalogError::FunctionNotFound { suggestion, .. } => suggestion.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl SearchPath {
+    /// The search path names within the definition of the object called `name` are resolved
+    /// with, which searches the same schemas in the database the object belongs to.
+    pub fn for_object(&self, name: &ObjectName) -> SearchPath {
+        SearchPath {
+            database: name.database().unwrap_or(&self.database).to_string(),
+            schemas: self.schemas.clone(),
+        }
+    }
+}
+
+impl RelationName {
+    /// Whether the qualifier of a column name, such as `b.a` in `b.a.x`, refers to this
+    /// relation, which is the case when it matches the trailing parts of the fully qualified
+    /// name.
+    pub fn is_referred_to_by(&self, qualifier: &ObjectName) -> bool {
+        let parts = [&self.database, &self.schema, &self.name];
+
+        qualifier.parts.len() <= parts.len()
+            && parts
+                .iter()
+                .rev()
+                .zip(qualifier.parts.iter().rev())
+                .all(|(part, qualifier_part)| *part == qualifier_part)
+    }
+}
+
+impl View {
+    pub fn select(&self) -> &SelectStatement {
+        match &self.query {
+            Statement::Select(select) => select,
+            _ => unreachable!("View `{}` is not defined by a SELECT statement", self.name),
+        }
+    }
+
+    /// Whether rows can be written through the view, which requires each row of the view to
+    /// correspond to exactly one row of a single underlying table.
+    pub fn is_updatable(&self) -> bool {
+        let select = self.select();
+
+        let single_table = matches!(
+            &select.from_clause,
+            FromClause::Direct {
+                table: AliasedFromTable {
+                    table: FromTable::TableName { .. },
+                    ..
+                },
+            }
+        );
+
+        let direct_columns = match &select.columns {
+            ResultColumns::All => true,
+            ResultColumns::Specific(columns) => columns
+                .iter()
+                .all(|column| matches!(column.expr, Expr::Column(ColumnName::Direct { .. }))),
+        };
+
+        single_table
+            && direct_columns
+            && !select.distinct
+            && select.set_operations.is_empty()
+            && select.group_by_clause.is_none()
+            && select.having_clause.is_none()
+            && select.offset.is_none()
+            && select.limit.is_none()
+    }
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self {
+            databases: HashMap::from([(
+                DEFAULT_DATABASE.to_string(),
+                Database::new(DEFAULT_DATABASE),
+            )]),
+            functions: FunctionRegistry::default(),
+            created_functions: HashSet::new(),
+        }
+    }
+}
+
+impl Default for SearchPath {
+    fn default() -> Self {
+        Self {
+            database: DEFAULT_DATABASE.to_string(),
+            schemas: vec![DEFAULT_SCHEMA.to_string()],
+        }
+    }
+}
+
+impl Catalog {
+    /// The functions that can be called in queries, both built-in and registered.
+    pub fn functions(&self) -> &FunctionRegistry {
+        &self.functions
+    }
+
+    pub fn functions_mut(&mut self) -> &mut FunctionRegistry {
+        &mut self.functions
+    }
+
+    pub fn database(&self, name: &str) -> Result<&Database, CatalogError> {
+        self.databases
+            .get(name)
+            .ok_or_else(|| self.database_not_found(name))
+    }
+
+    pub fn database_mut(&mut self, name: &str) -> Result<&mut Database, CatalogError> {
+        if !self.databases.contains_key(name) {
+            return Err(self.database_not_found(name));
+        }
+
+        Ok(self.databases.get_mut(name).unwrap())
+    }
+
+    fn database_not_found(&self, name: &str) -> CatalogError {
+        let names = self.databases().into_iter().map(|database| &database.name);
+
+        CatalogError::DatabaseNotFound {
+            name: name.to_string(),
+            suggestion: closest_name(name, names),
+        }
+    }
+
+    /// Every database, ordered by name.
+    pub fn databases(&self) -> Vec<&Database> {
+        let mut databases = self.databases.values().collect::<Vec<_>>();
+        databases.sort_by(|a, b| a.name.cmp(&b.name));
+        databases
+    }
+
+    pub fn create_database(
+        &mut self,
+        statement: &CreateDatabaseStatement,
+    ) -> Result<(), CatalogError> {
+        let name = &statement.name;
+
+        if self.databases.contains_key(name) {
+            return match statement.if_not_exists {
+                true => Ok(()),
+                false => Err(CatalogError::DatabaseAlreadyExists(name.clone())),
+            };
+        }
+
+        self.databases.insert(name.clone(), Database::new(name));
+
+        Ok(())
+    }
+
+    /// Creates a schema in the database it names, or in `database` if it does not name one.
+    pub fn create_schema(
+        &mut self,
+        database: &str,
+        statement: &CreateSchemaStatement,
+    ) -> Result<(), CatalogError> {
+        let database = self.database_mut(statement.name.database.as_deref().unwrap_or(database))?;
+        let name = &statement.name.name;
+
+        if database.schemas.contains_key(name) {
+            return match statement.if_not_exists {
+                true => Ok(()),
+                false => Err(CatalogError::SchemaAlreadyExists(name.clone())),
+            };
+        }
+
+        let schema = Schema {
+            name: name.clone(),
+            ..Default::default()
+        };
+
+        database.schemas.insert(name.clone(), schema);
+
+        Ok(())
+    }
+
+    /// Drops every named schema, or none of them if any does not exist or, without `CASCADE`,
+    /// still contains tables or views.
+    pub fn drop_schemas(
+        &mut self,
+        database: &str,
+        statement: &DropSchemaStatement,
+    ) -> Result<(), CatalogError> {
+        let names = statement
+            .names
+            .iter()
+            .map(|name| (name.database.as_deref().unwrap_or(database), &name.name))
+            .collect::<Vec<_>>();
+
+        for (database, name) in &names {
+            let schema = match self.database(database)?.schema(name) {
+                Ok(schema) => schema,
+                Err(_) if statement.if_exists => continue,
+                Err(err) => return Err(err),
+            };
+
+            if !statement.cascade && !schema.is_empty() {
+                return Err(CatalogError::SchemaNotEmpty(schema.name.clone()));
+            }
+        }
+
+        for (database, name) in names {
+            self.database_mut(database)?.schemas.remove(name);
+        }
+
+        Ok(())
+    }
+
+    /// Resolves the name of an existing relation.
+    ///
+    /// An unqualified name refers to the first schema in the search path that has a relation of
+    /// that name, or, if none does, to the schema it would be created in.
+    pub fn resolve(&self, name: &ObjectName, search_path: &SearchPath) -> RelationName {
+        if name.schema().is_some() {
+            return qualify(name, search_path, "");
+        }
+
+        let schema = search_path
+            .schemas
+            .iter()
+            .find(|schema| {
+                self.schema(&search_path.database, schema)
+                    .is_ok_and(|schema| schema.relation_exists(name.name()))
+            })
+            .or_else(|| self.creation_schema(search_path))
+            .or(search_path.schemas.first())
+            .map_or(DEFAULT_SCHEMA, String::as_str);
+
+        qualify(name, search_path, schema)
+    }
+
+    /// Resolves the name of a relation about to be created, which is created in the first schema
+    /// of the search path that exists unless the name says otherwise.
+    fn resolve_new(
+        &self,
+        name: &ObjectName,
+        search_path: &SearchPath,
+    ) -> Result<RelationName, CatalogError> {
+        if name.schema().is_some() {
+            return Ok(qualify(name, search_path, ""));
+        }
+
+        match self.creation_schema(search_path) {
+            Some(schema) => Ok(qualify(name, search_path, schema)),
+            None => Err(CatalogError::NoSchemaSelected(name.name().to_string())),
+        }
+    }
+
+    fn creation_schema<'a>(&self, search_path: &'a SearchPath) -> Option<&'a String> {
+        search_path
+            .schemas
+            .iter()
+            .find(|schema| self.schema(&search_path.database, schema).is_ok())
+    }
+
+    fn schema(&self, database: &str, name: &str) -> Result<&Schema, CatalogError> {
+        self.database(database)?.schema(name)
+    }
+
+    fn schema_mut(&mut self, database: &str, name: &str) -> Result<&mut Schema, CatalogError> {
+        self.database_mut(database)?.schema_mut(name)
+    }
+
+    /// Looks up a table, resolving its name with `search_path` if it is not fully qualified.
+    pub fn table(
+        &self,
+        search_path: &SearchPath,
+        name: &ObjectName,
+    ) -> Result<&Table, CatalogError> {
+        let name = self.resolve(name, search_path);
+        self.schema(&name.database, &name.schema)?.table(&name.name)
+    }
+
+    /// Looks up a table by its resolved name, unlike `Catalog::table`.
+    pub fn base_table(&self, name: &RelationName) -> Option<&Table> {
+        self.schema(&name.database, &name.schema)
+            .ok()?
+            .table(&name.name)
+            .ok()
+    }
+
+    pub fn base_table_mut(&mut self, name: &RelationName) -> Option<&mut Table> {
+        self.schema_mut(&name.database, &name.schema)
+            .ok()?
+            .tables
+            .get_mut(&name.name)
+    }
+
+    pub fn view(&self, name: &RelationName) -> Option<&View> {
+        self.schema(&name.database, &name.schema)
+            .ok()?
+            .view(&name.name)
+    }
+
+    pub fn materialized_view(&self, name: &RelationName) -> Option<&MaterializedView> {
+        self.schema(&name.database, &name.schema)
+            .ok()?
+            .materialized_view(&name.name)
+    }
+
+    pub fn materialized_view_mut(
+        &mut self,
+        name: &RelationName,
+    ) -> Result<&mut MaterializedView, CatalogError> {
+        let schema = self.schema_mut(&name.database, &name.schema)?;
+
+        if !schema.materialized_views.contains_key(&name.name) {
+            return Err(schema.view_not_found(&name.name, true));
+        }
+
+        Ok(schema.materialized_views.get_mut(&name.name).unwrap())
+    }
+
+    pub fn create_table(
+        &mut self,
+        search_path: &SearchPath,
+        statement: &CreateTableStatement,
+    ) -> Result<(), CatalogError> {
+        let name = self.resolve_new(&statement.name, search_path)?;
+        let schema = self.schema_mut(&name.database, &name.schema)?;
+        let name = name.name;
+
+        if schema.relation_exists(&name) {
+            return match statement.if_not_exists && schema.tables.contains_key(&name) {
+                true => Ok(()),
+                false => Err(CatalogError::RelationAlreadyExists(name)),
+            };
+        }
+
+        for (i, column) in statement.columns.iter().enumerate() {
+            let data_type = resolve_type(&column.data_type)?;
+
+            if let Some(collation) = &column.collation {
+                if let Err(CollationError::UnknownCollation(name)) = Collation::parse(collation) {
+                    return Err(CatalogError::CollationNotFound {
+                        suggestion: suggest::closest(&name, Collation::names())
+                            .map(ToString::to_string),
+                        name,
+                    });
+                }
+
+                if !data_type.is_text() {
+                    return Err(CatalogError::CollationNotText {
+                        column: column.name.clone(),
+                        data_type,
+                    });
+                }
+            }
+
+            if statement.columns[..i].iter().any(|c| c.name == column.name) {
+                return Err(CatalogError::DuplicateColumn {
+                    table: name,
+                    column: column.name.clone(),
+                });
+            }
+        }
+
+        let table = Table {
+            name: name.clone(),
+            columns: statement.columns.clone(),
+            rows: Vec::new(),
+        };
+
+        schema.tables.insert(name, table);
+
+        Ok(())
+    }
+
+    pub fn create_view(
+        &mut self,
+        search_path: &SearchPath,
+        statement: &CreateViewStatement,
+        functions: Vec<(String, Vec<DataType>)>,
+    ) -> Result<(), CatalogError> {
+        let name = self.resolve_new(&statement.name, search_path)?;
+        let schema = self.schema(&name.database, &name.schema)?;
+
+        let replaceable = statement.or_replace && schema.views.contains_key(&name.name);
+
+        if !replaceable && schema.relation_exists(&name.name) {
+            return Err(CatalogError::RelationAlreadyExists(name.name));
+        }
+
+        let view_search_path = search_path.for_object(&statement.name);
+
+        if self.references(&view_search_path, &statement.query, &name) {
+            return Err(CatalogError::RecursiveView(name.name));
+        }
+
+        validate_column_aliases(&name.name, &statement.columns, &statement.query)?;
+
+        let view = View {
+            name: name.name.clone(),
+            columns: statement.columns.clone(),
+            query: Statement::Select(*statement.query.clone()),
+            sql: statement.query_text.clone(),
+            check_option: statement.check_option,
+            search_path: view_search_path,
+            functions,
+        };
+
+        if view.check_option.is_some() && !view.is_updatable() {
+            return Err(CatalogError::ViewNotUpdatable(name.name));
+        }
+
+        self.schema_mut(&name.database, &name.schema)?
+            .views
+            .insert(name.name, view);
+
+        Ok(())
+    }
+
+    /// Registers a materialized view without populating it.
+    pub fn create_materialized_view(
+        &mut self,
+        search_path: &SearchPath,
+        statement: &CreateMaterializedViewStatement,
+        functions: Vec<(String, Vec<DataType>)>,
+    ) -> Result<(), CatalogError> {
+        let name = self.resolve_new(&statement.name, search_path)?;
+
+        if self
+            .schema(&name.database, &name.schema)?
+            .relation_exists(&name.name)
+        {
+            return Err(CatalogError::RelationAlreadyExists(name.name));
+        }
+
+        validate_column_aliases(&name.name, &statement.columns, &statement.query)?;
+
+        let view_search_path = search_path.for_object(&statement.name);
+
+        let maintenance = match statement.incremental {
+            true => Some(
+                Maintenance::new(self, &view_search_path, &statement.query).map_err(|reason| {
+                    CatalogError::NotIncrementallyMaintainable {
+                        name: name.name.clone(),
+                        reason,
+                    }
+                })?,
+            ),
+            false => None,
+        };
+
+        let materialized_view = MaterializedView {
+            definition: View {
+                name: name.name.clone(),
+                columns: statement.columns.clone(),
+                query: Statement::Select(*statement.query.clone()),
+                sql: statement.query_text.clone(),
+                check_option: None,
+                search_path: view_search_path,
+                functions,
+            },
+            populated: false,
+            maintenance,
+            rows: Arc::new(Vec::new()),
+            state: Vec::new(),
+        };
+
+        self.schema_mut(&name.database, &name.schema)?
+            .materialized_views
+            .insert(name.name, materialized_view);
+
+        Ok(())
+    }
+
+    /// Drops every named view, or none of them if any does not exist.
+    ///
+    /// Views reading from a dropped view prevent it from being dropped, unless the statement
+    /// cascades, in which case they are dropped along with it.
+    pub fn drop_views(
+        &mut self,
+        search_path: &SearchPath,
+        statement: &DropViewStatement,
+        materialized: bool,
+    ) -> Result<(), CatalogError> {
+        let mut names = Vec::new();
+
+        for name in &statement.names {
+            let name = self.resolve(name, search_path);
+            let schema = self.schema(&name.database, &name.schema);
+
+            let exists = schema.as_ref().is_ok_and(|schema| match materialized {
+                true => schema.materialized_views.contains_key(&name.name),
+                false => schema.views.contains_key(&name.name),
+            });
+
+            match (exists, schema) {
+                (true, _) => names.push(name),
+                (false, _) if statement.if_exists => {}
+                (false, Ok(schema)) => return Err(schema.view_not_found(&name.name, materialized)),
+                (false, Err(err)) => return Err(err),
+            }
+        }
+
+        self.remove_views(names, statement.cascade)
+    }
+
+    /// Removes the views and materialized views, along with the views depending on them if
+    /// `cascade` is set.
+    fn remove_views(
+        &mut self,
+        mut names: Vec<RelationName>,
+        cascade: bool,
+    ) -> Result<(), CatalogError> {
+        // Dependents of the views dropped with CASCADE are dropped too
+        loop {
+            let dependents = self.dependent_views(&names);
+
+            let Some((dependent, name)) = dependents.first() else {
+                break;
+            };
+
+            if !cascade {
+                return Err(CatalogError::DependentView {
+                    name: name.name.clone(),
+                    dependent: dependent.name.clone(),
+                });
+            }
+
+            names.extend(dependents.into_iter().map(|(dependent, _)| dependent));
+        }
+
+        for name in &names {
+            let Ok(schema) = self.schema_mut(&name.database, &name.schema) else {
+                continue;
+            };
+
+            schema.views.remove(&name.name);
+            schema.materialized_views.remove(&name.name);
+        }
+
+        Ok(())
+    }
+
+    /// Every populated materialized view which is maintained from the changes made to `table`.
+    pub fn maintained_views(&self, table: &RelationName) -> Vec<RelationName> {
+        let mut names = Vec::new();
+
+        for database in self.databases.values() {
+            for schema in database.schemas.values() {
+                for view in schema.materialized_views() {
+                    let maintained = view
+                        .maintenance
+                        .as_ref()
+                        .is_some_and(|maintenance| maintenance.tables.contains(table));
+
+                    if view.populated && maintained {
+                        names.push(RelationName {
+                            database: database.name.clone(),
+                            schema: schema.name.clone(),
+                            name: view.definition.name.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        names
+    }
+
+    /// Every view or materialized view outside of `names` which reads from one of them, along
+    /// with the first of them it reads from, ordered by name.
+    fn dependent_views(&self, names: &[RelationName]) -> Vec<(RelationName, RelationName)> {
+        let mut dependents = Vec::new();
+
+        for database in self.databases.values() {
+            for schema in database.schemas.values() {
+                let views = schema.views().into_iter().chain(
+                    schema
+                        .materialized_views()
+                        .into_iter()
+                        .map(|view| &view.definition),
+                );
+
+                for view in views {
+                    let name = RelationName {
+                        database: database.name.clone(),
+                        schema: schema.name.clone(),
+                        name: view.name.clone(),
+                    };
+
+                    if names.contains(&name) {
+                        continue;
+                    }
+
+                    let dropped = self
+                        .referenced_relations(&view.search_path, view.select())
+                        .into_iter()
+                        .find(|relation| names.contains(relation));
+
+                    if let Some(dropped) = dropped {
+                        dependents.push((name, dropped));
+                    }
+                }
+            }
+        }
+
+        dependents.sort_by(|(a, _), (b, _)| {
+            (&a.database, &a.schema, &a.name).cmp(&(&b.database, &b.schema, &b.name))
+        });
+        dependents
+    }
+
+    /// Registers a scalar function created with CREATE FUNCTION, which replaces the function
+    /// created with the same name and parameters before if `or_replace` is set.
+    ///
+    /// The name must be in lower case.
+    pub fn create_function(
+        &mut self,
+        or_replace: bool,
+        name: String,
+        params: Vec<DataType>,
+        returns: DataType,
+        options: ScalarOptions,
+        call: impl Fn(&FunctionContext, &[Value]) -> Result<Value, FunctionError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Result<(), CatalogError> {
+        let param_types = params.iter().copied().map(Param::Type).collect::<Vec<_>>();
+
+        if self.has_overload(&name, &param_types) {
+            let display_name = function_display_name(&name, &params);
+
+            if !self
+                .created_functions
+                .contains(&(name.clone(), params.clone()))
+            {
+                return Err(CatalogError::FunctionNotCreated(display_name));
+            }
+
+            if !or_replace {
+                return Err(CatalogError::FunctionAlreadyExists(display_name));
+            }
+
+            self.functions.unregister(&name, &param_types);
+        }
+
+        self.created_functions.insert((name.clone(), params));
+
+        let signature = Signature {
+            name: name.into(),
+            params: Some(param_types.into()),
+            variadic: 0,
+            returns: Returns::Type(returns),
+        };
+
+        Ok(self.functions.register_scalar(signature, options, call)?)
+    }
+
+    pub fn drop_function(&mut self, statement: &DropFunctionStatement) -> Result<(), CatalogError> {
+        let name = statement.name.to_ascii_lowercase();
+
+        let params = statement
+            .params
+            .iter()
+            .map(resolve_type)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let param_types = params.iter().copied().map(Param::Type).collect::<Vec<_>>();
+
+        let key = (name.clone(), params.clone());
+
+        if self.created_functions.contains(&key) {
+            let dependents = self.function_dependents(&key);
+
+            if let (Some(dependent), false) = (dependents.first(), statement.cascade) {
+                return Err(CatalogError::DependentFunction {
+                    name: function_display_name(&name, &params),
+                    dependent: dependent.name.clone(),
+                });
+            }
+
+            self.remove_views(dependents, true)?;
+            self.created_functions.remove(&key);
+            self.functions.unregister(&name, &param_types);
+            return Ok(());
+        }
+
+        if self.has_overload(&name, &param_types) {
+            return Err(CatalogError::FunctionNotCreated(function_display_name(
+                &name, &params,
+            )));
+        }
+
+        match statement.if_exists {
+            true => Ok(()),
+            false => Err(CatalogError::FunctionNotFound {
+                suggestion: suggest::closest(
+                    &name,
+                    self.created_functions.iter().map(|(name, _)| name.as_str()),
+                )
+                .map(ToString::to_string),
+                name: function_display_name(&name, &params),
+            }),
+        }
+    }
+
+    /// The name and parameter types of the functions created with CREATE FUNCTION among
+    /// `functions`, which a view calling them depends on.
+    pub fn function_dependencies(&self, functions: &[&Function]) -> Vec<(String, Vec<DataType>)> {
+        let mut dependencies = Vec::new();
+
+        for function in functions {
+            let params = function
+                .signature
+                .params
+                .iter()
+                .flat_map(|params| params.iter())
+                .filter_map(|param| match param {
+                    Param::Type(data_type) => Some(*data_type),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+            let key = (function.signature.name.to_string(), params);
+
+            if self.created_functions.contains(&key) && !dependencies.contains(&key) {
+                dependencies.push(key);
+            }
+        }
+
+        dependencies
+    }
+
+    /// Every view or materialized view calling the created function, ordered by name.
+    fn function_dependents(&self, function: &(String, Vec<DataType>)) -> Vec<RelationName> {
+        let mut dependents = Vec::new();
+
+        for database in self.databases.values() {
+            for schema in database.schemas.values() {
+                let views = schema.views().into_iter().chain(
+                    schema
+                        .materialized_views()
+                        .into_iter()
+                        .map(|view| &view.definition),
+                );
+
+                for view in views {
+                    if view.functions.contains(function) {
+                        dependents.push(RelationName {
+                            database: database.name.clone(),
+                            schema: schema.name.clone(),
+                            name: view.name.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        dependents.sort_by(|a, b| {
+            (&a.database, &a.schema, &a.name).cmp(&(&b.database, &b.schema, &b.name))
+        });
+        dependents
+    }
+
+    /// Whether the function has an overload taking exactly `params`.
+    fn has_overload(&self, name: &str, params: &[Param]) -> bool {
+        self.functions.overloads(name).iter().any(|overload| {
+            overload.signature.params.as_deref() == Some(params) && overload.signature.variadic == 0
+        })
+    }
+
+    /// Returns every view and table `select` reads from, expanding views but not materialized
+    /// views, whose stored results are read instead.
+    ///
+    /// Names are resolved with `search_path`, except within views, where they are resolved with
+    /// the search path the view was created with.
+    pub fn referenced_relations(
+        &self,
+        search_path: &SearchPath,
+        select: &SelectStatement,
+    ) -> Vec<RelationName> {
+        let mut collector = RelationCollector {
+            catalog: self,
+            search_path: search_path.clone(),
+            relations: Vec::new(),
+        };
+        collector.visit_select(select);
+        collector.relations
+    }
+
+    /// Whether `select` reads from the relation called `name`, either directly or through views.
+    fn references(
+        &self,
+        search_path: &SearchPath,
+        select: &SelectStatement,
+        name: &RelationName,
+    ) -> bool {
+        self.referenced_relations(search_path, select)
+            .iter()
+            .any(|relation| relation == name)
+    }
+}
+
+impl Database {
+    /// Creates an empty database with only the default schema.
+    fn new(name: &str) -> Self {
+        let schema = Schema {
+            name: DEFAULT_SCHEMA.to_string(),
+            ..Default::default()
+        };
+
+        Self {
+            name: name.to_string(),
+            schemas: HashMap::from([(DEFAULT_SCHEMA.to_string(), schema)]),
+        }
+    }
+
+    pub fn schema(&self, name: &str) -> Result<&Schema, CatalogError> {
+        self.schemas
+            .get(name)
+            .ok_or_else(|| self.schema_not_found(name))
+    }
+
+    fn schema_mut(&mut self, name: &str) -> Result<&mut Schema, CatalogError> {
+        if !self.schemas.contains_key(name) {
+            return Err(self.schema_not_found(name));
+        }
+
+        Ok(self.schemas.get_mut(name).unwrap())
+    }
+
+    fn schema_not_found(&self, name: &str) -> CatalogError {
+        let names = self.schemas().into_iter().map(|schema| &schema.name);
+
+        CatalogError::SchemaNotFound {
+            name: name.to_string(),
+            suggestion: closest_name(name, names),
+        }
+    }
+
+    /// Every schema, ordered by name.
+    pub fn schemas(&self) -> Vec<&Schema> {
+        let mut schemas = self.schemas.values().collect::<Vec<_>>();
+        schemas.sort_by(|a, b| a.name.cmp(&b.name));
+        schemas
+    }
+}
+
+impl Schema {
+    pub fn table(&self, name: &str) -> Result<&Table, CatalogError> {
+        self.tables.get(name).ok_or_else(|| {
+            let names = self.tables().into_iter().map(|table| &table.name);
+
+            CatalogError::TableNotFound {
+                name: name.to_string(),
+                suggestion: closest_name(name, names),
+            }
+        })
+    }
+
+    /// Every table, ordered by name.
+    pub fn tables(&self) -> Vec<&Table> {
+        let mut tables = self.tables.values().collect::<Vec<_>>();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        tables
+    }
+
+    /// Every view, ordered by name.
+    pub fn views(&self) -> Vec<&View> {
+        let mut views = self.views.values().collect::<Vec<_>>();
+        views.sort_by(|a, b| a.name.cmp(&b.name));
+        views
+    }
+
+    /// Every materialized view, ordered by name.
+    pub fn materialized_views(&self) -> Vec<&MaterializedView> {
+        let mut views = self.materialized_views.values().collect::<Vec<_>>();
+        views.sort_by(|a, b| a.definition.name.cmp(&b.definition.name));
+        views
+    }
+
+    pub fn view(&self, name: &str) -> Option<&View> {
+        self.views.get(name)
+    }
+
+    pub fn materialized_view(&self, name: &str) -> Option<&MaterializedView> {
+        self.materialized_views.get(name)
+    }
+
+    /// The error for a missing view, suggesting the closest view of the same kind.
+    fn view_not_found(&self, name: &str, materialized: bool) -> CatalogError {
+        let name = name.to_string();
+
+        match materialized {
+            true => {
+                let names = self
+                    .materialized_views()
+                    .into_iter()
+                    .map(|view| &view.definition.name);
+
+                CatalogError::MaterializedViewNotFound {
+                    suggestion: closest_name(&name, names),
+                    name,
+                }
+            }
+            false => {
+                let names = self.views().into_iter().map(|view| &view.name);
+
+                CatalogError::ViewNotFound {
+                    suggestion: closest_name(&name, names),
+                    name,
+                }
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tables.is_empty() && self.views.is_empty() && self.materialized_views.is_empty()
+    }
+
+    /// Whether a table, view or materialized view called `name` exists, as they all share one
+    /// namespace.
+    fn relation_exists(&self, name: &str) -> bool {
+        self.tables.contains_key(name)
+            || self.views.contains_key(name)
+            || self.materialized_views.contains_key(name)
+    }
+}
+
+/// Gathers the relations a query reads from, descending into the definitions of views.
+struct RelationCollector<'a> {
+    catalog: &'a Catalog,
+    /// The search path unqualified names are resolved with
+    search_path: SearchPath,
+    relations: Vec<RelationName>,
+}
+
+impl Visitor for RelationCollector<'_> {
+    fn visit_from_table(&mut self, table: &FromTable) {
+        if let FromTable::TableName { name } = table {
+            let relation = self.catalog.resolve(name, &self.search_path);
+
+            if let Some(view) = self.catalog.view(&relation) {
+                let outer = std::mem::replace(&mut self.search_path, view.search_path.clone());
+                self.visit_select(view.select());
+                self.search_path = outer;
+            }
+
+            self.relations.push(relation);
+        }
+
+        walk_from_table(self, table);
+    }
+}
+
+/// Fills in the parts of `name` it leaves out from `search_path`, using `schema` if it is
+/// unqualified.
+fn qualify(name: &ObjectName, search_path: &SearchPath, schema: &str) -> RelationName {
+    RelationName {
+        database: name.database().unwrap_or(&search_path.database).to_string(),
+        schema: name.schema().unwrap_or(schema).to_string(),
+        name: name.name().to_string(),
+    }
+}
+
+/// The data type a type name refers to.
+pub fn resolve_type(type_name: &TypeName) -> Result<DataType, CatalogError> {
+    DataType::from_type_name(type_name).map_err(|err| match err {
+        TypeNameError::UnknownType(name) => CatalogError::TypeNotFound {
+            suggestion: suggest::closest(&name, DataType::type_names()).map(ToString::to_string),
+            name,
+        },
+        err => CatalogError::InvalidType(err),
+    })
+}
+
+/// How a function is written in messages, like `f(INT, TEXT)`.
+fn function_display_name(name: &str, params: &[DataType]) -> String {
+    let params = params
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("{name}({params})")
+}
+
+/// Suggests the existing name closest to a missing one.
+pub fn closest_name<'a>(name: &str, names: impl IntoIterator<Item = &'a String>) -> Option<String> {
+    suggest::closest(name, names.into_iter().map(String::as_str)).map(ToString::to_string)
+}
+
+/// Checks that a view definition gives either no column aliases or one for every column.
+fn validate_column_aliases(
+    name: &str,
+    aliases: &[String],
+    query: &SelectStatement,
+) -> Result<(), CatalogError> {
+    if let ResultColumns::Specific(columns) = &query.columns {
+        if !aliases.is_empty() && aliases.len() != columns.len() {
+            return Err(CatalogError::ViewColumnCountMismatch {
+                name: name.to_string(),
+                expected: columns.len(),
+                found: aliases.len(),
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,929 @@
+use drown_common::sql::{
+    data_type::DataType,
+    function::FunctionContext,
+    statement::{
+        AliasedExpr, AliasedFromTable, CheckOption, ColumnName, DeleteStatement, Expr, FromClause,
+        FromTable, InsertSource, InsertStatement, Literal, ObjectName, ResultColumns,
+        SelectStatement, Statement, UpdateStatement, WhereClause,
+    },
+    value::{Value, ValueError},
+};
+use thiserror::Error;
+
+use crate::{
+    binder::{bind_select, is_aggregate_call, BindError, BoundExpr, BoundQuery},
+    catalog::{
+        closest_name, resolve_type, Catalog, CatalogError, RelationName, Row, SearchPath, Table,
+    },
+    executor::{compile_plan, ExecutorError},
+    fold::fold_select,
+    incremental::{maintain, Change},
+    plan::plan_query,
+    typecheck::{check_query_types, ExprType, QueryTypes, TypeError},
+};
+
+#[derive(Debug, Error)]
+pub enum DmlError {
+    #[error("Column `{name}` of table `{table}` does not exist")]
+    ColumnNotFound {
+        table: String,
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[error("Column `{0}` is given more than once")]
+    DuplicateColumn(String),
+    #[error("Rows must have a value for each of the {expected} columns, not {found} values")]
+    ValueCount { expected: usize, found: usize },
+    #[error("Column `{column}` is {data_type}, which {found} cannot be assigned to")]
+    AssignmentMismatch {
+        column: String,
+        data_type: DataType,
+        found: ExprType,
+    },
+    #[error("Column `{0}` cannot be NULL")]
+    NullValue(String),
+    #[error("Cannot write to view `{0}` because it is not a simple view")]
+    ViewNotUpdatable(String),
+    #[error("Cannot write to materialized view `{0}`")]
+    MaterializedView(String),
+    #[error("New row violates the check option of view `{0}`")]
+    CheckOptionViolation(String),
+    #[error("{0}")]
+    CatalogError(#[from] CatalogError),
+    #[error("{0}")]
+    BindError(#[from] BindError),
+    #[error("{0}")]
+    TypeError(#[from] TypeError),
+    #[error("{0}")]
+    ExecutorError(#[from] ExecutorError),
+    #[error("{0}")]
+    ValueError(#[from] ValueError),
+}
+
+impl DmlError {
+    /// The existing name a missing one was most likely meant to be.
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            DmlError::ColumnNotFound { suggestion, .. } => suggestion.as_deref(),
+            DmlError::CatalogError(err) => err.suggestion(),
+            DmlError::BindError(err) => err.suggestion(),
+            DmlError::TypeError(err) => err.suggestion(),
+            _ => None,
+        }
+    }
+}
+
+/// The table a statement writes to, either directly or through views.
+struct Target {
+    name: RelationName,
+    columns: Vec<TargetColumn>,
+    /// The views rows are written through, starting with the one named by the statement, each
+    /// reading from the next one or from the table
+    views: Vec<TargetView>,
+    /// The name the statement gives the relation it writes to
+    written: ObjectName,
+}
+
+struct TargetColumn {
+    name: String,
+    data_type: DataType,
+    nullable: bool,
+}
+
+/// A simple view rows are written through.
+struct TargetView {
+    name: String,
+    /// The name of each column of the view, with the column of the relation it reads from
+    columns: Vec<(String, String)>,
+    /// The alias the view gives the relation it reads from
+    alias: Option<String>,
+    condition: Option<Expr>,
+    /// Whether rows written through the view have to meet its condition, because it or a view
+    /// reading from it with CASCADED has a check option
+    checked: bool,
+}
+
+impl Target {
+    fn resolve(
+        catalog: &Catalog,
+        search_path: &SearchPath,
+        name: &ObjectName,
+    ) -> Result<Self, DmlError> {
+        let mut relation = catalog.resolve(name, search_path);
+        let mut table_name = (name.clone(), search_path.clone());
+        let mut views = Vec::new();
+        let mut cascaded = false;
+
+        while let Some(view) = catalog.view(&relation) {
+            if !view.is_updatable() {
+                return Err(DmlError::ViewNotUpdatable(view.name.clone()));
+            }
+
+            let select = view.select();
+            let FromClause::Direct {
+                table:
+                    AliasedFromTable {
+                        table: FromTable::TableName { name: from },
+                        alias,
+                        ..
+                    },
+            } = &select.from_clause
+            else {
+                unreachable!("simple views read from a single relation");
+            };
+
+            let query = bind_select(catalog, &view.search_path, select)?;
+            let columns = query
+                .select
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(index, column)| {
+                    let BoundExpr::Column(id) = &column.expr else {
+                        unreachable!("the columns of simple views are columns they read");
+                    };
+
+                    let name = view.columns.get(index).unwrap_or(&column.name);
+                    (name.clone(), query.columns[id.0].name.clone())
+                })
+                .collect();
+
+            cascaded |= view.check_option == Some(CheckOption::Cascaded);
+
+            views.push(TargetView {
+                name: view.name.clone(),
+                columns,
+                alias: alias.clone(),
+                condition: select
+                    .where_clause
+                    .as_ref()
+                    .map(|where_clause| where_clause.condition.clone()),
+                checked: cascaded || view.check_option.is_some(),
+            });
+
+            relation = catalog.resolve(from, &view.search_path);
+            table_name = (from.clone(), view.search_path.clone());
+        }
+
+        if catalog.materialized_view(&relation).is_some() {
+            return Err(DmlError::MaterializedView(relation.name));
+        }
+
+        let table = catalog.table(&table_name.1, &table_name.0)?;
+
+        let columns = table
+            .columns
+            .iter()
+            .map(|column| {
+                Ok(TargetColumn {
+                    name: column.name.clone(),
+                    data_type: resolve_type(&column.data_type)?,
+                    nullable: column.nullable,
+                })
+            })
+            .collect::<Result<Vec<_>, CatalogError>>()?;
+
+        Ok(Self {
+            name: relation,
+            columns,
+            views,
+            written: name.clone(),
+        })
+    }
+
+    /// The names of the columns of the relation the statement writes to.
+    fn written_columns(&self) -> Vec<&String> {
+        match self.views.first() {
+            Some(view) => view.columns.iter().map(|(name, _)| name).collect(),
+            None => self.columns.iter().map(|column| &column.name).collect(),
+        }
+    }
+
+    /// The index of the column of the table which the column called `name` of the relation the
+    /// statement writes to is stored in.
+    fn stored_column(&self, name: &str) -> Option<usize> {
+        let mut name = name;
+
+        for view in &self.views {
+            name = &view.columns.iter().find(|(column, _)| column == name)?.1;
+        }
+
+        self.columns.iter().position(|column| column.name == *name)
+    }
+
+    /// The relation the expressions of the view at `level`, or of the table past the last view,
+    /// are evaluated over, under `alias`.
+    ///
+    /// The conditions of the views are left out, so that its rows are the rows of the table in
+    /// the order they are stored in, which the results of queries over it line up with.
+    fn relation(&self, level: usize, alias: Option<String>) -> AliasedFromTable {
+        let span = self.written.span;
+
+        let Some(view) = self.views.get(level) else {
+            let name = ObjectName {
+                parts: vec![
+                    self.name.database.clone(),
+                    self.name.schema.clone(),
+                    self.name.name.clone(),
+                ],
+                span,
+            };
+
+            return AliasedFromTable {
+                table: FromTable::TableName { name },
+                alias,
+                span,
+            };
+        };
+
+        let columns = view
+            .columns
+            .iter()
+            .map(|(name, column)| AliasedExpr {
+                expr: Expr::Column(ColumnName::Direct {
+                    name: column.clone(),
+                    span,
+                }),
+                alias: Some(name.clone()),
+            })
+            .collect();
+
+        let query = SelectStatement {
+            columns: ResultColumns::Specific(columns),
+            ..select_from(self.relation(level + 1, view.alias.clone()))
+        };
+
+        AliasedFromTable {
+            table: FromTable::DerivedTable {
+                query: Box::new(query),
+                lateral: false,
+            },
+            alias,
+            span,
+        }
+    }
+
+    /// The relation the expressions of the statement are evaluated over.
+    fn written_relation(&self) -> AliasedFromTable {
+        match self.views.is_empty() {
+            true => self.relation(0, None),
+            false => self.relation(0, Some(self.written.name().to_string())),
+        }
+    }
+
+    /// The index of each named column, which must all be different.
+    fn column_indices<'a>(
+        &self,
+        names: impl IntoIterator<Item = &'a String>,
+    ) -> Result<Vec<usize>, DmlError> {
+        let mut indices = Vec::new();
+
+        for name in names {
+            let Some(index) = self.stored_column(name) else {
+                return Err(DmlError::ColumnNotFound {
+                    table: self.written.name().to_string(),
+                    name: name.clone(),
+                    suggestion: closest_name(name, self.written_columns()),
+                });
+            };
+
+            if indices.contains(&index) {
+                return Err(DmlError::DuplicateColumn(name.clone()));
+            }
+
+            indices.push(index);
+        }
+
+        Ok(indices)
+    }
+
+    /// Checks that values of `found` can be stored in the column at `index`.
+    fn check_assignment(&self, index: usize, found: ExprType) -> Result<(), DmlError> {
+        let column = &self.columns[index];
+
+        let assignable = match found {
+            ExprType::Known(data_type) => data_type.can_cast(column.data_type),
+            ExprType::Null | ExprType::StringLiteral => true,
+        };
+
+        match assignable {
+            true => Ok(()),
+            false => Err(DmlError::AssignmentMismatch {
+                column: column.name.clone(),
+                data_type: column.data_type,
+                found,
+            }),
+        }
+    }
+
+    /// Converts a value to the type of the column at `index`, which it is stored as.
+    fn assign(
+        &self,
+        index: usize,
+        value: &Value,
+        context: &FunctionContext,
+    ) -> Result<Value, DmlError> {
+        let column = &self.columns[index];
+
+        if value.is_null() && !column.nullable {
+            return Err(DmlError::NullValue(column.name.clone()));
+        }
+
+        // Unlike with CAST, text which is too long for a VARCHAR is not cut short
+        if let DataType::Varchar(Some(_)) = column.data_type {
+            if let Value::Text(text) = value.cast(DataType::Text, &context.time_zone)? {
+                return Ok(Value::parse(&text, column.data_type)?);
+            }
+        }
+
+        Ok(value.cast(column.data_type, &context.time_zone)?)
+    }
+}
+
+/// Inserts the rows given by `statement`, returning how many there were.
+pub fn insert(
+    catalog: &mut Catalog,
+    search_path: &SearchPath,
+    context: &FunctionContext,
+    statement: &InsertStatement,
+) -> Result<usize, DmlError> {
+    let target = Target::resolve(catalog, search_path, &statement.table)?;
+
+    let indices = match statement.columns.is_empty() {
+        true => target.column_indices(target.written_columns())?,
+        false => target.column_indices(&statement.columns)?,
+    };
+
+    let (query, mut query_types) = bind_source(catalog, search_path, &inserted_rows(statement))?;
+
+    if query.select.columns.len() != indices.len() {
+        return Err(DmlError::ValueCount {
+            expected: indices.len(),
+            found: query.select.columns.len(),
+        });
+    }
+
+    for (column, &index) in query.select.columns.iter().zip(&indices) {
+        target.check_assignment(index, query_types.expr_type(&column.expr)?)?;
+    }
+
+    let rows = execute_source(catalog, query, &mut query_types, context)?
+        .into_iter()
+        .map(|values| {
+            let mut row = vec![Value::Null; target.columns.len()];
+
+            for (value, &index) in values.iter().zip(&indices) {
+                row[index] = value.clone();
+            }
+
+            row.iter()
+                .enumerate()
+                .map(|(index, value)| target.assign(index, value, context))
+                .collect::<Result<Row, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    check_views(catalog, search_path, context, &target, rows.clone())?;
+
+    let count = rows.len();
+
+    let table = catalog.base_table_mut(&target.name).unwrap();
+    let previous = table.rows.len();
+    table.rows.extend(rows.iter().cloned());
+
+    let change = Change {
+        table: target.name.clone(),
+        deleted: Vec::new(),
+        inserted: rows,
+    };
+
+    maintain_or_undo(catalog, context, change, |table| {
+        table.rows.truncate(previous)
+    })?;
+
+    Ok(count)
+}
+
+/// Assigns new values to the rows meeting the condition of `statement`, returning how many
+/// there were.
+pub fn update(
+    catalog: &mut Catalog,
+    search_path: &SearchPath,
+    context: &FunctionContext,
+    statement: &UpdateStatement,
+) -> Result<usize, DmlError> {
+    let target = Target::resolve(catalog, search_path, &statement.table)?;
+
+    let indices = target.column_indices(
+        statement
+            .assignments
+            .iter()
+            .map(|assignment| &assignment.column),
+    )?;
+
+    let matched = matching_rows(
+        catalog,
+        search_path,
+        context,
+        &target,
+        &statement.where_clause,
+    )?;
+
+    // The new values are only computed for the rows being updated, so the table only holds
+    // those while they are
+    let table = catalog.base_table_mut(&target.name).unwrap();
+    let rows = std::mem::take(&mut table.rows);
+    table.rows = rows
+        .iter()
+        .zip(&matched)
+        .filter(|(_, matched)| **matched)
+        .map(|(row, _)| row.clone())
+        .collect();
+
+    let values = assigned_values(catalog, search_path, context, statement, &target, &indices);
+    let table = catalog.base_table_mut(&target.name).unwrap();
+    let old_rows = std::mem::replace(&mut table.rows, rows);
+
+    let mut updated = Vec::new();
+
+    for (mut row, values) in old_rows.iter().cloned().zip(values?) {
+        for (value, &index) in values.iter().zip(&indices) {
+            row[index] = target.assign(index, value, context)?;
+        }
+
+        updated.push(row);
+    }
+
+    check_views(catalog, search_path, context, &target, updated.clone())?;
+
+    let count = updated.len();
+    let table = catalog.base_table_mut(&target.name).unwrap();
+    let previous = table.rows.clone();
+    let mut updated_rows = updated.iter().cloned();
+
+    for (row, matched) in table.rows.iter_mut().zip(matched) {
+        if matched {
+            *row = updated_rows.next().unwrap();
+        }
+    }
+
+    let change = Change {
+        table: target.name.clone(),
+        deleted: old_rows,
+        inserted: updated,
+    };
+
+    maintain_or_undo(catalog, context, change, |table| table.rows = previous)?;
+
+    Ok(count)
+}
+
+/// Removes the rows meeting the condition of `statement`, returning how many there were.
+pub fn delete(
+    catalog: &mut Catalog,
+    search_path: &SearchPath,
+    context: &FunctionContext,
+    statement: &DeleteStatement,
+) -> Result<usize, DmlError> {
+    let target = Target::resolve(catalog, search_path, &statement.table)?;
+
+    let matched = matching_rows(
+        catalog,
+        search_path,
+        context,
+        &target,
+        &statement.where_clause,
+    )?;
+
+    let table = catalog.base_table_mut(&target.name).unwrap();
+    let previous = std::mem::take(&mut table.rows);
+    let (deleted, kept) = previous
+        .iter()
+        .cloned()
+        .zip(matched)
+        .partition::<Vec<_>, _>(|(_, matched)| *matched);
+
+    table.rows = kept.into_iter().map(|(row, _)| row).collect();
+
+    let count = deleted.len();
+    let change = Change {
+        table: target.name.clone(),
+        deleted: deleted.into_iter().map(|(row, _)| row).collect(),
+        inserted: Vec::new(),
+    };
+
+    maintain_or_undo(catalog, context, change, |table| table.rows = previous)?;
+
+    Ok(count)
+}
+
+/// The query computing what a data-modifying statement writes, which `EXPLAIN` shows below it,
+/// along with the table it writes to: the rows an INSERT adds, the new values of the rows an
+/// UPDATE changes, or the rows a DELETE removes.
+pub fn modified_rows(
+    catalog: &Catalog,
+    search_path: &SearchPath,
+    statement: &Statement,
+) -> Result<(RelationName, BoundQuery), DmlError> {
+    // Reading from the relation the statement names, views included, finds the rows it changes
+    let (target, clause, query) = match statement {
+        Statement::Insert(insert) => {
+            let target = Target::resolve(catalog, search_path, &insert.table)?;
+            let indices = match insert.columns.is_empty() {
+                true => target.column_indices(target.written_columns())?,
+                false => target.column_indices(&insert.columns)?,
+            };
+
+            let query = bind_select(catalog, search_path, &inserted_rows(insert))?;
+
+            if query.select.columns.len() != indices.len() {
+                return Err(DmlError::ValueCount {
+                    expected: indices.len(),
+                    found: query.select.columns.len(),
+                });
+            }
+
+            return Ok((target.name, query));
+        }
+        Statement::Update(update) => {
+            let values = update
+                .assignments
+                .iter()
+                .map(|assignment| assignment.value.clone())
+                .collect();
+
+            let target = Target::resolve(catalog, search_path, &update.table)?;
+            target.column_indices(
+                update
+                    .assignments
+                    .iter()
+                    .map(|assignment| &assignment.column),
+            )?;
+
+            let query = SelectStatement {
+                where_clause: update.where_clause.clone(),
+                ..select_exprs(written_table(&update.table), values)
+            };
+
+            (target, "UPDATE", query)
+        }
+        Statement::Delete(delete) => {
+            let query = SelectStatement {
+                where_clause: delete.where_clause.clone(),
+                ..select_from(written_table(&delete.table))
+            };
+
+            (
+                Target::resolve(catalog, search_path, &delete.table)?,
+                "DELETE",
+                query,
+            )
+        }
+        _ => unreachable!("Only INSERT, UPDATE and DELETE modify rows"),
+    };
+
+    let query = bind_select(catalog, search_path, &query)?;
+
+    if query.select.columns.iter().any(|column| {
+        column
+            .expr
+            .any(&mut |part| is_aggregate_call(catalog.functions(), part))
+    }) {
+        return Err(BindError::MisplacedAggregate(clause).into());
+    }
+
+    Ok((target.name, query))
+}
+
+/// Applies a change made to a table to the materialized views maintained from it, undoing the
+/// change with `undo` if it cannot be applied to them.
+fn maintain_or_undo(
+    catalog: &mut Catalog,
+    context: &FunctionContext,
+    change: Change,
+    undo: impl FnOnce(&mut Table),
+) -> Result<(), DmlError> {
+    let table = change.table.clone();
+
+    match maintain(catalog, context, change) {
+        Ok(()) => Ok(()),
+        Err(err) => {
+            undo(catalog.base_table_mut(&table).unwrap());
+            Err(err)
+        }
+    }
+}
+
+/// Whether each row of the table written to meets the condition of `where_clause`, and can be
+/// seen through the views it is written through, in the order the rows are stored in.
+fn matching_rows(
+    catalog: &Catalog,
+    search_path: &SearchPath,
+    context: &FunctionContext,
+    target: &Target,
+    where_clause: &Option<WhereClause>,
+) -> Result<Vec<bool>, DmlError> {
+    let condition = match where_clause {
+        Some(where_clause) => where_clause.condition.clone(),
+        None => Expr::Literal(Literal::Boolean(true)),
+    };
+
+    let mut matched = meets_condition(
+        catalog,
+        search_path,
+        context,
+        target.written_relation(),
+        condition,
+    )?;
+
+    for (level, view) in target.views.iter().enumerate() {
+        if let Some(condition) = &view.condition {
+            let relation = target.relation(level + 1, view.alias.clone());
+            let visible =
+                meets_condition(catalog, search_path, context, relation, condition.clone())?;
+
+            for (matched, visible) in matched.iter_mut().zip(visible) {
+                *matched &= visible;
+            }
+        }
+    }
+
+    Ok(matched)
+}
+
+/// Checks that `rows`, which are about to be stored in the table written to, meet the
+/// conditions of the views they are written through which have to be met.
+fn check_views(
+    catalog: &mut Catalog,
+    search_path: &SearchPath,
+    context: &FunctionContext,
+    target: &Target,
+    rows: Vec<Row>,
+) -> Result<(), DmlError> {
+    if !target.views.iter().any(|view| view.checked) {
+        return Ok(());
+    }
+
+    with_rows(catalog, &target.name, rows, |catalog| {
+        for (level, view) in target.views.iter().enumerate() {
+            let Some(condition) = view.condition.as_ref().filter(|_| view.checked) else {
+                continue;
+            };
+
+            let relation = target.relation(level + 1, view.alias.clone());
+
+            if meets_condition(catalog, search_path, context, relation, condition.clone())?
+                .contains(&false)
+            {
+                return Err(DmlError::CheckOptionViolation(view.name.clone()));
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// Whether each row of `relation` meets `condition`, in the order the rows are read in.
+fn meets_condition(
+    catalog: &Catalog,
+    search_path: &SearchPath,
+    context: &FunctionContext,
+    relation: AliasedFromTable,
+    condition: Expr,
+) -> Result<Vec<bool>, DmlError> {
+    let (query, mut query_types) = bind_source(
+        catalog,
+        search_path,
+        &select_exprs(relation, vec![condition]),
+    )?;
+    let expr = &query.select.columns[0].expr;
+
+    if expr.any(&mut |part| is_aggregate_call(catalog.functions(), part)) {
+        return Err(BindError::MisplacedAggregate("WHERE").into());
+    }
+
+    match query_types.expr_type(expr)? {
+        ExprType::Known(DataType::Boolean) | ExprType::Null => {}
+        found => {
+            return Err(TypeError::NotBoolean {
+                clause: "WHERE",
+                found,
+            }
+            .into())
+        }
+    }
+
+    let results = execute_source(catalog, query, &mut query_types, context)?;
+
+    Ok(results
+        .iter()
+        .map(|result| result[0] == Value::Boolean(true))
+        .collect())
+}
+
+/// Calls `f` while `rows` are the rows of `table`, putting its own rows back afterwards.
+pub fn with_rows<T>(
+    catalog: &mut Catalog,
+    table: &RelationName,
+    rows: Vec<Row>,
+    f: impl FnOnce(&Catalog) -> T,
+) -> T {
+    let table_rows = std::mem::replace(&mut catalog.base_table_mut(table).unwrap().rows, rows);
+    let result = f(catalog);
+    catalog.base_table_mut(table).unwrap().rows = table_rows;
+
+    result
+}
+
+/// The values assigned by `statement` to each row of its table, in the order the rows are
+/// stored in.
+fn assigned_values(
+    catalog: &Catalog,
+    search_path: &SearchPath,
+    context: &FunctionContext,
+    statement: &UpdateStatement,
+    target: &Target,
+    indices: &[usize],
+) -> Result<Vec<Row>, DmlError> {
+    let values = statement
+        .assignments
+        .iter()
+        .map(|assignment| assignment.value.clone())
+        .collect();
+
+    let (query, mut query_types) = bind_source(
+        catalog,
+        search_path,
+        &select_exprs(target.written_relation(), values),
+    )?;
+
+    for (column, &index) in query.select.columns.iter().zip(indices) {
+        if column
+            .expr
+            .any(&mut |part| is_aggregate_call(catalog.functions(), part))
+        {
+            return Err(BindError::MisplacedAggregate("UPDATE").into());
+        }
+
+        target.check_assignment(index, query_types.expr_type(&column.expr)?)?;
+    }
+
+    execute_source(catalog, query, &mut query_types, context)
+}
+
+/// The query computing the rows `statement` inserts.
+fn inserted_rows(statement: &InsertStatement) -> SelectStatement {
+    match &statement.source {
+        InsertSource::Values { rows } => select_from(AliasedFromTable {
+            table: FromTable::Values { rows: rows.clone() },
+            alias: None,
+            span: statement.table.span,
+        }),
+        InsertSource::Select(query) => *query.clone(),
+    }
+}
+
+/// The relation named by a statement writing to it, as read by a query.
+fn written_table(name: &ObjectName) -> AliasedFromTable {
+    AliasedFromTable {
+        table: FromTable::TableName { name: name.clone() },
+        alias: None,
+        span: name.span,
+    }
+}
+
+/// A query computing `exprs` for each row of `relation`.
+fn select_exprs(relation: AliasedFromTable, exprs: Vec<Expr>) -> SelectStatement {
+    SelectStatement {
+        columns: ResultColumns::Specific(
+            exprs
+                .into_iter()
+                .map(|expr| AliasedExpr { expr, alias: None })
+                .collect(),
+        ),
+        ..select_from(relation)
+    }
+}
+
+/// A query selecting every column of a single relation, with no other clauses.
+fn select_from(table: AliasedFromTable) -> SelectStatement {
+    SelectStatement {
+        distinct: false,
+        columns: ResultColumns::All,
+        from_clause: FromClause::Direct { table },
+        where_clause: None,
+        group_by_clause: None,
+        having_clause: None,
+        set_operations: Vec::new(),
+        order_by_clause: None,
+        offset: None,
+        limit: None,
+    }
+}
+
+fn bind_source<'a>(
+    catalog: &'a Catalog,
+    search_path: &SearchPath,
+    select: &SelectStatement,
+) -> Result<(BoundQuery, QueryTypes<'a>), DmlError> {
+    let query = bind_select(catalog, search_path, select)?;
+    let (_, query_types) = check_query_types(catalog, &query)?;
+
+    Ok((query, query_types))
+}
+
+/// Runs a bound query, returning its rows.
+pub fn execute_source<'a>(
+    catalog: &'a Catalog,
+    mut query: BoundQuery,
+    query_types: &mut QueryTypes<'a>,
+    context: &FunctionContext,
+) -> Result<Vec<Row>, DmlError> {
+    fold_select(catalog.functions(), &mut query.select);
+    let plan = plan_query(catalog.functions(), &mut query);
+    let mut operator = compile_plan(catalog, query_types, context, &plan)?;
+
+    Ok(operator.execute(context)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use drown_common::sql::value::Value;
+
+    use crate::Server;
+
+    fn execute(server: &mut Server, sql: &str) -> Result<Vec<Vec<Value>>, String> {
+        server
+            .execute(&format!("USE d; SET search_path TO s; {sql}"))
+            .map(|result| result.data)
+            .map_err(|err| err.error)
+    }
+
+    fn server() -> Server {
+        let mut server = Server::default();
+        server
+            .execute(
+                "CREATE DATABASE d; USE d; CREATE SCHEMA s; SET search_path TO s; \
+                 CREATE TABLE t (id INT NOT NULL, n INT); \
+                 INSERT INTO t VALUES (1, 5), (2, 50), (3, 500); \
+                 CREATE VIEW small AS SELECT id, n AS amount FROM t WHERE n < 100 \
+                 WITH CHECK OPTION; \
+                 CREATE VIEW tiny AS SELECT id, amount FROM small WHERE amount < 10; \
+                 CREATE VIEW tiny_local AS SELECT id, amount FROM small WHERE amount < 10 \
+                 WITH LOCAL CHECK OPTION",
+            )
+            .unwrap();
+        server
+    }
+
+    fn ids(server: &mut Server) -> Vec<Vec<Value>> {
+        execute(server, "SELECT id, n FROM t").unwrap()
+    }
+
+    #[test]
+    fn writes_through_views() {
+        let mut server = server();
+
+        // Only the rows seen through the view are updated and deleted
+        execute(&mut server, "UPDATE small SET amount = amount + 1").unwrap();
+        execute(&mut server, "DELETE FROM tiny").unwrap();
+        execute(&mut server, "INSERT INTO small (id, amount) VALUES (4, 40)").unwrap();
+
+        assert_eq!(
+            ids(&mut server),
+            [
+                [Value::Int(2), Value::Int(51)],
+                [Value::Int(3), Value::Int(500)],
+                [Value::Int(4), Value::Int(40)],
+            ]
+        );
+    }
+
+    #[test]
+    fn check_options() {
+        let mut server = server();
+        let before = ids(&mut server);
+
+        let statements = [
+            ("INSERT INTO small VALUES (4, 400)", "small"),
+            ("UPDATE small SET amount = 100 WHERE id = 1", "small"),
+            // The option of `small` applies to rows written through views reading from it
+            ("INSERT INTO tiny VALUES (4, 400)", "small"),
+            // A local option only checks the condition of its own view
+            ("INSERT INTO tiny_local VALUES (4, 40)", "tiny_local"),
+        ];
+
+        for (statement, view) in statements {
+            let err = execute(&mut server, statement).unwrap_err();
+            assert!(
+                err.contains(&format!("check option of view `{view}`")),
+                "{statement}: {err}"
+            );
+        }
+
+        assert_eq!(ids(&mut server), before);
+
+        // Views without an option only check the options of the views they read from
+        execute(&mut server, "INSERT INTO tiny VALUES (4, 40)").unwrap();
+    }
+}
@@ -0,0 +1,615 @@
+use std::sync::Arc;
+
+use drown_common::{
+    proto::packet::S2CQuerySuccessResponsePacket,
+    sql::{
+        data_type::DataType,
+        datetime::TimeZone,
+        format::{format_statement, FormatOptions},
+        function::{FunctionContext, ScalarOptions},
+        parser::Span,
+        statement::{
+            CreateFunctionStatement, CreateTableStatement, DropViewStatement, ObjectName,
+            SelectStatement, Statement, Volatility,
+        },
+        suggest,
+        value::Value,
+    },
+};
+use thiserror::Error;
+
+use crate::{
+    binder::{bind_select, BindError},
+    catalog::{resolve_type, Catalog, CatalogError, SearchPath},
+    dml::{self, DmlError},
+    executor::{compile_plan, ExecutorError},
+    explain::{explain, ExplainError},
+    fold::fold_select,
+    incremental::populate,
+    plan::plan_query,
+    session::Session,
+    typecheck::{check_query_types, TypeError},
+    wasm::{WasmError, WasmFunction},
+};
+
+#[derive(Debug, Error)]
+pub enum ExecuteError {
+    #[error("{0}")]
+    ExplainError(#[from] ExplainError),
+    #[error("{0}")]
+    CatalogError(#[from] CatalogError),
+    #[error("{0}")]
+    BindError(#[from] BindError),
+    #[error("{0}")]
+    TypeError(#[from] TypeError),
+    #[error("{0}")]
+    ExecutorError(#[from] ExecutorError),
+    #[error("{0}")]
+    DmlError(#[from] DmlError),
+    #[error("Materialized view `{0}` cannot be refreshed CONCURRENTLY WITH NO DATA")]
+    ConcurrentRefreshWithNoData(String),
+    #[error("Materialized view `{0}` cannot be refreshed CONCURRENTLY before it is populated")]
+    ConcurrentRefreshUnpopulated(String),
+    #[error("{0}")]
+    WasmError(#[from] WasmError),
+    #[error("Functions cannot be written in `{0}`, only in `wasm`")]
+    UnsupportedLanguage(String),
+    #[error("Time zone `{name}` does not exist")]
+    TimeZoneNotFound {
+        name: String,
+        suggestion: Option<String>,
+    },
+}
+
+impl ExecuteError {
+    /// The existing object a missing one was most likely meant to be.
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            ExecuteError::CatalogError(err) => err.suggestion(),
+            ExecuteError::ExplainError(err) => err.suggestion(),
+            ExecuteError::BindError(err) => err.suggestion(),
+            ExecuteError::TypeError(err) => err.suggestion(),
+            ExecuteError::DmlError(err) => err.suggestion(),
+            ExecuteError::TimeZoneNotFound { suggestion, .. } => suggestion.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Executes a single parsed statement, producing the result set sent back to the client.
+pub fn execute_statement(
+    catalog: &mut Catalog,
+    session: &mut Session,
+    statement: &Statement,
+) -> Result<S2CQuerySuccessResponsePacket, ExecuteError> {
+    match statement {
+        Statement::Select(select) => {
+            catalog.database(&session.search_path.database)?;
+            Ok(run_query(
+                catalog,
+                &session.search_path,
+                &session.function_context(),
+                select,
+            )?)
+        }
+        Statement::Insert(insert) => {
+            let count = dml::insert(
+                catalog,
+                &session.search_path,
+                &session.function_context(),
+                insert,
+            )?;
+
+            Ok(command_complete(&format!("INSERT 0 {}", count)))
+        }
+        Statement::Update(update) => {
+            let count = dml::update(
+                catalog,
+                &session.search_path,
+                &session.function_context(),
+                update,
+            )?;
+
+            Ok(command_complete(&format!("UPDATE {}", count)))
+        }
+        Statement::Delete(delete) => {
+            let count = dml::delete(
+                catalog,
+                &session.search_path,
+                &session.function_context(),
+                delete,
+            )?;
+
+            Ok(command_complete(&format!("DELETE {}", count)))
+        }
+        Statement::Explain(explain_statement) => {
+            catalog.database(&session.search_path.database)?;
+            let plan = explain(
+                catalog,
+                &session.search_path,
+                &session.function_context(),
+                explain_statement,
+            )?;
+
+            Ok(result_set(
+                &["QUERY PLAN"],
+                plan.into_iter().map(|line| vec![line]).collect(),
+            ))
+        }
+        Statement::CreateView(create_view) => {
+            let search_path = session.search_path.for_object(&create_view.name);
+            let query = bind_select(catalog, &search_path, &create_view.query)?;
+            let (_, types) = check_query_types(catalog, &query)?;
+            let functions = catalog.function_dependencies(types.functions());
+
+            catalog.create_view(&session.search_path, create_view, functions)?;
+
+            Ok(command_complete("CREATE VIEW"))
+        }
+        Statement::DropView(drop_view) => {
+            catalog.drop_views(&session.search_path, drop_view, false)?;
+
+            Ok(command_complete("DROP VIEW"))
+        }
+        Statement::CreateMaterializedView(create_view) => {
+            let search_path = session.search_path.for_object(&create_view.name);
+            let query = bind_select(catalog, &search_path, &create_view.query)?;
+            let (_, types) = check_query_types(catalog, &query)?;
+            let functions = catalog.function_dependencies(types.functions());
+
+            catalog.create_materialized_view(&session.search_path, create_view, functions)?;
+
+            if create_view.with_data {
+                let name = catalog.resolve(&create_view.name, &session.search_path);
+
+                // The view is only kept if it can be populated
+                if let Err(err) = populate(catalog, &name, &session.function_context()) {
+                    let drop_view = DropViewStatement {
+                        if_exists: false,
+                        names: vec![create_view.name.clone()],
+                        cascade: false,
+                    };
+                    catalog.drop_views(&session.search_path, &drop_view, true)?;
+
+                    return Err(err.into());
+                }
+            }
+
+            Ok(command_complete("CREATE MATERIALIZED VIEW"))
+        }
+        Statement::RefreshMaterializedView(refresh) => {
+            let name = refresh.name.to_string();
+            let relation = catalog.resolve(&refresh.name, &session.search_path);
+
+            let view = catalog.materialized_view_mut(&relation)?;
+
+            if refresh.concurrently && !refresh.with_data {
+                return Err(ExecuteError::ConcurrentRefreshWithNoData(name.clone()));
+            }
+
+            if refresh.concurrently && !view.populated {
+                return Err(ExecuteError::ConcurrentRefreshUnpopulated(name.clone()));
+            }
+
+            if !refresh.with_data {
+                view.rows = Arc::new(Vec::new());
+                view.state = Vec::new();
+                view.populated = false;
+
+                return Ok(command_complete("REFRESH MATERIALIZED VIEW"));
+            }
+
+            // The new results are computed in full before they replace the old ones, so a
+            // refresh which fails leaves the view as it was, and readers still holding the old
+            // results keep them. As statements run one at a time with the catalog locked, a
+            // CONCURRENTLY refresh is no different, and blocks other statements just the same.
+            populate(catalog, &relation, &session.function_context())?;
+
+            Ok(command_complete("REFRESH MATERIALIZED VIEW"))
+        }
+        Statement::DropMaterializedView(drop_view) => {
+            catalog.drop_views(&session.search_path, drop_view, true)?;
+
+            Ok(command_complete("DROP MATERIALIZED VIEW"))
+        }
+        Statement::CreateDatabase(create_database) => {
+            catalog.create_database(create_database)?;
+
+            Ok(command_complete("CREATE DATABASE"))
+        }
+        Statement::CreateTable(create_table) => {
+            catalog.create_table(&session.search_path, create_table)?;
+
+            Ok(command_complete("CREATE TABLE"))
+        }
+        Statement::CreateFunction(create_function) => {
+            define_function(catalog, create_function)?;
+
+            Ok(command_complete("CREATE FUNCTION"))
+        }
+        Statement::DropFunction(drop_function) => {
+            catalog.drop_function(drop_function)?;
+
+            Ok(command_complete("DROP FUNCTION"))
+        }
+        Statement::CreateSchema(create_schema) => {
+            catalog.create_schema(&session.search_path.database, create_schema)?;
+
+            Ok(command_complete("CREATE SCHEMA"))
+        }
+        Statement::DropSchema(drop_schema) => {
+            catalog.drop_schemas(&session.search_path.database, drop_schema)?;
+
+            Ok(command_complete("DROP SCHEMA"))
+        }
+        Statement::SetSearchPath(set_search_path) => {
+            // Schemas are not required to exist, as those which do not are skipped when resolving
+            session.search_path.schemas = match &set_search_path.schemas {
+                Some(schemas) => schemas.clone(),
+                None => SearchPath::default().schemas,
+            };
+
+            Ok(command_complete("SET"))
+        }
+        Statement::SetTimeZone(set_time_zone) => {
+            session.time_zone = match &set_time_zone.time_zone {
+                Some(name) => {
+                    TimeZone::parse(name).map_err(|_| ExecuteError::TimeZoneNotFound {
+                        name: name.clone(),
+                        suggestion: suggest::closest(name, TimeZone::names())
+                            .map(ToString::to_string),
+                    })?
+                }
+                None => TimeZone::default(),
+            };
+
+            Ok(command_complete("SET"))
+        }
+        Statement::Use(use_statement) => {
+            let database = catalog.database(&use_statement.database)?;
+            session.search_path.database = database.name.clone();
+
+            Ok(command_complete("USE"))
+        }
+        Statement::ShowDatabases => Ok(result_set(
+            &["database"],
+            catalog
+                .databases()
+                .into_iter()
+                .map(|database| vec![database.name.clone()])
+                .collect(),
+        )),
+        Statement::ShowSchemas => {
+            let database = catalog.database(&session.search_path.database)?;
+
+            Ok(result_set(
+                &["schema"],
+                database
+                    .schemas()
+                    .into_iter()
+                    .map(|schema| vec![schema.name.clone()])
+                    .collect(),
+            ))
+        }
+        Statement::ShowTables => {
+            let database = catalog.database(&session.search_path.database)?;
+
+            let mut relations = Vec::new();
+
+            for schema in database.schemas() {
+                let tables = schema
+                    .tables()
+                    .into_iter()
+                    .map(|table| (&table.name, "table"));
+                let views = schema.views().into_iter().map(|view| (&view.name, "view"));
+                let materialized_views = schema
+                    .materialized_views()
+                    .into_iter()
+                    .map(|view| (&view.definition.name, "materialized view"));
+
+                relations.extend(
+                    tables
+                        .chain(views)
+                        .chain(materialized_views)
+                        .map(|(name, kind)| (&schema.name, name, kind)),
+                );
+            }
+
+            relations.sort();
+
+            Ok(result_set(
+                &["schema", "name", "type"],
+                relations
+                    .into_iter()
+                    .map(|(schema, name, kind)| {
+                        vec![schema.clone(), name.clone(), kind.to_string()]
+                    })
+                    .collect(),
+            ))
+        }
+        Statement::ShowSearchPath => Ok(result_set(
+            &["search_path"],
+            vec![vec![session.search_path.schemas.join(", ")]],
+        )),
+        Statement::ShowTimeZone => Ok(result_set(
+            &["TimeZone"],
+            vec![vec![session.time_zone.to_string()]],
+        )),
+        Statement::ShowColumns(show_columns) => {
+            let table = catalog.table(&session.search_path, &show_columns.table)?;
+
+            Ok(result_set(
+                &["name", "type", "nullable"],
+                table
+                    .columns
+                    .iter()
+                    .map(|column| {
+                        vec![
+                            column.name.clone(),
+                            column.data_type.to_string(),
+                            match column.nullable {
+                                true => "YES".to_string(),
+                                false => "NO".to_string(),
+                            },
+                        ]
+                    })
+                    .collect(),
+            ))
+        }
+        Statement::ShowCreateTable(show_create_table) => {
+            let table = catalog.table(&session.search_path, &show_create_table.table)?;
+            let name = catalog.resolve(&show_create_table.table, &session.search_path);
+
+            // Qualified with the schema, so that it creates the same table wherever it is run
+            // in the database
+            let create_table = Statement::CreateTable(CreateTableStatement {
+                if_not_exists: false,
+                name: ObjectName {
+                    parts: vec![name.schema, table.name.clone()],
+                    span: Span::empty(),
+                },
+                columns: table.columns.clone(),
+            });
+
+            let sql = format_statement(&create_table, &FormatOptions::default());
+
+            Ok(result_set(
+                &["table", "create table"],
+                vec![vec![table.name.clone(), sql]],
+            ))
+        }
+    }
+}
+
+/// Binds, plans and executes a query, producing its result set.
+fn run_query(
+    catalog: &Catalog,
+    search_path: &SearchPath,
+    context: &FunctionContext,
+    select: &SelectStatement,
+) -> Result<S2CQuerySuccessResponsePacket, ExecuteError> {
+    let mut query = bind_select(catalog, search_path, select)?;
+    let (types, mut query_types) = check_query_types(catalog, &query)?;
+
+    let schema = query.select.column_names();
+
+    fold_select(catalog.functions(), &mut query.select);
+    let plan = plan_query(catalog.functions(), &mut query);
+    let mut operator = compile_plan(catalog, &mut query_types, context, &plan)?;
+
+    Ok(S2CQuerySuccessResponsePacket {
+        schema,
+        types,
+        data: operator.execute(context)?,
+        command: None,
+    })
+}
+
+/// Creates a function exported by a WebAssembly module, which is the only language functions can
+/// be written in.
+fn define_function(
+    catalog: &mut Catalog,
+    statement: &CreateFunctionStatement,
+) -> Result<(), ExecuteError> {
+    if statement.language != "wasm" {
+        return Err(ExecuteError::UnsupportedLanguage(
+            statement.language.clone(),
+        ));
+    }
+
+    let params = statement
+        .params
+        .iter()
+        .map(|param| resolve_type(&param.data_type))
+        .collect::<Result<Vec<_>, _>>()?;
+    let returns = resolve_type(&statement.returns)?;
+
+    let name = statement.name.to_ascii_lowercase();
+    let export = statement.symbol.as_deref().unwrap_or(&statement.name);
+    let function = WasmFunction::new(
+        name.clone(),
+        &statement.definition,
+        export,
+        &params,
+        returns,
+    )?;
+
+    // WebAssembly has no NULL, and calls are only evaluated while planning when the function is
+    // declared IMMUTABLE, like in PostgreSQL
+    let options = ScalarOptions {
+        strict: true,
+        deterministic: statement.volatility == Volatility::Immutable,
+    };
+
+    catalog.create_function(
+        statement.or_replace,
+        name,
+        params,
+        returns,
+        options,
+        move |_, args| function.call(args),
+    )?;
+
+    Ok(())
+}
+
+/// A result set of text columns.
+fn result_set(schema: &[&str], data: Vec<Vec<String>>) -> S2CQuerySuccessResponsePacket {
+    S2CQuerySuccessResponsePacket {
+        schema: schema.iter().map(ToString::to_string).collect(),
+        types: vec![DataType::Text; schema.len()],
+        data: data
+            .into_iter()
+            .map(|row| row.into_iter().map(Value::Text).collect())
+            .collect(),
+        command: None,
+    }
+}
+
+/// The result of a statement which does not return any rows.
+fn command_complete(tag: &str) -> S2CQuerySuccessResponsePacket {
+    S2CQuerySuccessResponsePacket {
+        schema: Vec::new(),
+        types: Vec::new(),
+        data: Vec::new(),
+        command: Some(tag.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use drown_common::sql::value::Value;
+
+    use crate::Server;
+
+    fn query(server: &mut Server, sql: &str) -> Result<Vec<Vec<Value>>, String> {
+        server
+            .execute(sql)
+            .map(|result| result.data)
+            .map_err(|err| err.error)
+    }
+
+    #[test]
+    fn refresh_concurrently() {
+        let mut server = Server::default();
+        let values = |values: &[i32]| -> Vec<Vec<Value>> {
+            values
+                .iter()
+                .map(|&value| vec![Value::Int(value)])
+                .collect()
+        };
+
+        query(
+            &mut server,
+            "CREATE TABLE t (n INT); INSERT INTO t VALUES (1), (2); \
+             CREATE MATERIALIZED VIEW m AS SELECT 10 / n AS q FROM t WITH NO DATA",
+        )
+        .unwrap();
+
+        let err = query(&mut server, "REFRESH MATERIALIZED VIEW CONCURRENTLY m").unwrap_err();
+        assert!(err.contains("before it is populated"), "{err}");
+
+        query(
+            &mut server,
+            "REFRESH MATERIALIZED VIEW m; INSERT INTO t VALUES (5)",
+        )
+        .unwrap();
+        query(&mut server, "REFRESH MATERIALIZED VIEW CONCURRENTLY m").unwrap();
+        assert_eq!(
+            query(&mut server, "SELECT q FROM m"),
+            Ok(values(&[10, 5, 2]))
+        );
+
+        // A refresh which fails leaves the rows the view had
+        let err = query(
+            &mut server,
+            "INSERT INTO t VALUES (0); REFRESH MATERIALIZED VIEW CONCURRENTLY m",
+        )
+        .unwrap_err();
+        assert!(err.contains("Division by zero"), "{err}");
+        assert_eq!(
+            query(&mut server, "SELECT q FROM m"),
+            Ok(values(&[10, 5, 2]))
+        );
+
+        let err = query(
+            &mut server,
+            "REFRESH MATERIALIZED VIEW CONCURRENTLY m WITH NO DATA",
+        )
+        .unwrap_err();
+        assert!(err.contains("WITH NO DATA"), "{err}");
+    }
+
+    #[test]
+    fn drop_function_with_dependent_views() {
+        let mut server = Server::default();
+
+        // `plus(a, b) = a + b`
+        query(
+            &mut server,
+            "CREATE TABLE t (n INT); INSERT INTO t VALUES (1); \
+             CREATE FUNCTION plus(a INT, b INT) RETURNS INT LANGUAGE wasm AS \
+             '\\x0061736d0100000001070160027f7f017f03020100070501016600000a09010700200020016a0b', 'f'; \
+             CREATE VIEW v AS SELECT plus(n, 1) AS m FROM t; \
+             CREATE VIEW w AS SELECT m FROM v; \
+             CREATE MATERIALIZED VIEW mv AS SELECT n FROM t WHERE plus(n, n) > 0; \
+             CREATE VIEW u AS SELECT n + 1 AS m FROM t",
+        )
+        .unwrap();
+
+        let err = query(&mut server, "DROP FUNCTION plus(INT, INT)").unwrap_err();
+        assert!(
+            err.ends_with(
+                "Function `plus(INT, INT)` cannot be dropped while view `mv` depends on it \
+                 (use CASCADE to drop it too)"
+            ),
+            "{err}"
+        );
+        query(&mut server, "DROP FUNCTION plus(INT, INT) RESTRICT").unwrap_err();
+        assert_eq!(
+            query(&mut server, "SELECT m FROM w"),
+            Ok(vec![vec![Value::Int(2)]])
+        );
+
+        // Views reading from the dropped views are dropped too
+        query(&mut server, "DROP FUNCTION plus(INT, INT) CASCADE").unwrap();
+        for view in ["v", "w", "mv"] {
+            let err = query(&mut server, &format!("SELECT * FROM {view}")).unwrap_err();
+            assert!(err.contains("does not exist"), "{err}");
+        }
+        assert_eq!(
+            query(&mut server, "SELECT m FROM u"),
+            Ok(vec![vec![Value::Int(2)]])
+        );
+        query(&mut server, "SELECT plus(1, 1) FROM t").unwrap_err();
+    }
+
+    #[test]
+    fn show_create_table() {
+        let mut server = Server::default();
+
+        query(
+            &mut server,
+            "CREATE SCHEMA s; CREATE TABLE s.t (id INT NOT NULL, code TEXT); \
+             CREATE TABLE u (id INT)",
+        )
+        .unwrap();
+
+        let show = |server: &mut Server, sql: &str| {
+            let rows = query(server, sql).unwrap();
+            assert_eq!(rows.len(), 1);
+            (rows[0][0].to_string(), rows[0][1].to_string())
+        };
+
+        let (table, sql) = show(&mut server, "SHOW CREATE TABLE s.t");
+        assert_eq!(table, "t");
+        assert!(sql.starts_with("CREATE TABLE s.t ("), "{sql}");
+
+        let (_, sql) = show(&mut server, "SHOW CREATE TABLE u");
+        assert!(sql.starts_with("CREATE TABLE public.u ("), "{sql}");
+
+        // Found through the search path, and still written with its schema
+        let (_, sql) = show(&mut server, "SET search_path TO s; SHOW CREATE TABLE t");
+        assert!(sql.starts_with("CREATE TABLE s.t ("), "{sql}");
+    }
+}
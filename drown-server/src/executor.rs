@@ -0,0 +1,1585 @@
+use std::{
+    borrow::Cow,
+    cmp::Ordering as CmpOrdering,
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+use drown_common::sql::{
+    aggregate::{AggregateCall, CallState, SortKey},
+    collation::{CollatedValue, Collation},
+    data_type::DataType,
+    datetime::{
+        add_interval, add_interval_in, add_interval_to_time, subtract_timestamps, DateTimeError,
+    },
+    function::{Function, FunctionContext, FunctionError, FunctionKind, Param, Returns},
+    jsonb::{parse_path, JsonbError},
+    statement::{BinaryOperator, JoinType, Literal, Ordering, SetOperator, UnaryOperator},
+    value::{Decimal, DecimalError, Interval, RoundingMode, Value, ValueError},
+};
+use thiserror::Error;
+
+use crate::{
+    binder::{BoundExpr, BoundOrderByItem, ColumnId},
+    catalog::{resolve_type, Catalog, CatalogError, Row},
+    plan::LogicalPlan,
+    typecheck::{
+        arithmetic_operand_types, arithmetic_type, json_operator_types, literal_type,
+        resolve_function, ExprType, QueryTypes, TypeError,
+    },
+};
+
+#[derive(Debug, Error)]
+pub enum ExecutorError {
+    #[error("Division by zero")]
+    DivisionByZero,
+    #[error("{0} value is out of range")]
+    OutOfRange(DataType),
+    #[error("Materialized view `{0}` has not been populated")]
+    MaterializedViewNotPopulated(String),
+    #[error("{0}")]
+    FunctionError(#[from] FunctionError),
+    #[error("{0}")]
+    ValueError(#[from] ValueError),
+    #[error("{0}")]
+    DecimalError(#[from] DecimalError),
+    #[error("{0}")]
+    DateTimeError(#[from] DateTimeError),
+    #[error("{0}")]
+    JsonbError(#[from] JsonbError),
+    #[error("{0}")]
+    TypeError(#[from] TypeError),
+    #[error("{0}")]
+    CatalogError(#[from] CatalogError),
+}
+
+/// The values of columns from outside of the operator being run, which are the columns of the
+/// current left row of the lateral joins it is the right side of.
+type Env = [(ColumnId, Value)];
+
+/// An operator of a compiled plan, which computes the rows of its plan operator all at once each
+/// time it is run and keeps statistics of its runs.
+pub struct Operator<'a> {
+    kind: OperatorKind<'a>,
+    inputs: Vec<Operator<'a>>,
+    /// The most rows a run has to produce, when a LIMIT above the operator reads no more, so
+    /// that scans, filters and projections below it can stop early
+    wanted: Option<usize>,
+    stats: Stats,
+}
+
+/// How often an operator ran and what it took, as shown by `EXPLAIN ANALYZE`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stats {
+    /// The number of rows produced by every run together
+    pub rows: u64,
+    pub loops: u64,
+    /// The time spent in every run together, including the time spent running its inputs
+    pub time: Duration,
+    /// The most bytes the rows produced by a single run took up
+    pub memory: usize,
+}
+
+enum OperatorKind<'a> {
+    Scan(&'a [Row]),
+    FunctionScan {
+        function: &'a Function,
+        args: Vec<Expr<'a>>,
+    },
+    Values(Vec<Vec<Expr<'a>>>),
+    Subquery,
+    Filter(Expr<'a>),
+    Project(Vec<Expr<'a>>),
+    Join {
+        join_type: JoinType,
+        condition: Option<Expr<'a>>,
+        /// The columns of the left side, when the right side is run again for every left row
+        lateral: Option<Vec<ColumnId>>,
+        left_width: usize,
+        right_width: usize,
+    },
+    Aggregate {
+        keys: Vec<(Expr<'a>, Collation)>,
+        calls: Vec<CompiledCall<'a>>,
+    },
+    Sort(Vec<(Expr<'a>, SortKey)>),
+    Limit {
+        offset: u64,
+        limit: Option<u64>,
+    },
+    Distinct(Vec<Collation>),
+    SetOp {
+        operator: SetOperator,
+        all: bool,
+        collations: Vec<Collation>,
+        /// The types the values of each column of the left and the right input are cast to, when
+        /// they are not of the type of the column already
+        casts: [Vec<Option<DataType>>; 2],
+    },
+}
+
+/// An aggregate call along with the expressions computing what it is updated with.
+struct CompiledCall<'a> {
+    call: AggregateCall,
+    args: Vec<Expr<'a>>,
+    order_by: Vec<Expr<'a>>,
+    filter: Option<Expr<'a>>,
+}
+
+/// An expression whose operands have been coerced to the types the operators and functions
+/// applied to them take, which reads the columns of a row by their position.
+enum Expr<'a> {
+    Constant(Value),
+    Column(usize),
+    /// A column of a lateral join's left row
+    Outer(ColumnId),
+    Not(Box<Expr<'a>>),
+    Negate(Box<Expr<'a>>),
+    And(Box<Expr<'a>>, Box<Expr<'a>>),
+    Or(Box<Expr<'a>>, Box<Expr<'a>>),
+    IsNull {
+        operand: Box<Expr<'a>>,
+        negated: bool,
+    },
+    Compare {
+        left: Box<Expr<'a>>,
+        operator: BinaryOperator,
+        right: Box<Expr<'a>>,
+        collation: Collation,
+    },
+    Arithmetic {
+        left: Box<Expr<'a>>,
+        operator: BinaryOperator,
+        right: Box<Expr<'a>>,
+    },
+    Json {
+        left: Box<Expr<'a>>,
+        operator: BinaryOperator,
+        right: Box<Expr<'a>>,
+    },
+    Call {
+        function: &'a Function,
+        args: Vec<Expr<'a>>,
+    },
+    Cast {
+        operand: Box<Expr<'a>>,
+        data_type: DataType,
+    },
+}
+
+/// Compiles a plan of a query checked with `types` into operators which can be run, defining
+/// the columns added by the plan in `types`.
+pub fn compile_plan<'a>(
+    catalog: &'a Catalog,
+    types: &mut QueryTypes<'a>,
+    context: &FunctionContext,
+    plan: &LogicalPlan,
+) -> Result<Operator<'a>, ExecutorError> {
+    let mut compiler = Compiler {
+        catalog,
+        types,
+        context,
+    };
+
+    let mut operator = compiler.operator(plan)?;
+    operator.limit_rows(None);
+
+    Ok(operator)
+}
+
+struct Compiler<'a, 't> {
+    catalog: &'a Catalog,
+    types: &'t mut QueryTypes<'a>,
+    context: &'t FunctionContext,
+}
+
+impl<'a> Compiler<'a, '_> {
+    fn operator(&mut self, plan: &LogicalPlan) -> Result<Operator<'a>, ExecutorError> {
+        let inputs = plan
+            .inputs()
+            .into_iter()
+            .map(|input| self.operator(input))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The columns of the rows the expressions of the operator are evaluated on
+        let layout = match plan.inputs()[..] {
+            [input] => input.output(),
+            [left, right] => {
+                let mut layout = left.output();
+                layout.extend(right.output());
+                layout
+            }
+            _ => Vec::new(),
+        };
+
+        let kind = match plan {
+            LogicalPlan::Scan {
+                relation,
+                materialized: false,
+                ..
+            } => match self.catalog.base_table(relation) {
+                Some(table) => OperatorKind::Scan(&table.rows),
+                None => unreachable!("Table `{}` of a plan does not exist", relation.name),
+            },
+            LogicalPlan::Scan {
+                relation,
+                materialized: true,
+                ..
+            } => match self.catalog.materialized_view(relation) {
+                Some(view) if view.populated => OperatorKind::Scan(&view.rows),
+                Some(_) => {
+                    return Err(ExecutorError::MaterializedViewNotPopulated(
+                        relation.name.clone(),
+                    ))
+                }
+                None => unreachable!("Materialized view `{}` does not exist", relation.name),
+            },
+            LogicalPlan::FunctionScan { name, args, .. } => {
+                let (function, args) = self.call(name, Some(args), &layout)?;
+
+                match function.kind {
+                    FunctionKind::Table(_) => OperatorKind::FunctionScan { function, args },
+                    _ => unreachable!("`{}` of a function scan is not a table function", name),
+                }
+            }
+            LogicalPlan::Values { rows, columns } => OperatorKind::Values(
+                rows.iter()
+                    .map(|row| {
+                        row.iter()
+                            .zip(columns)
+                            .map(|(expr, id)| {
+                                let target = self.types.column_type(*id);
+                                self.coerced(expr, &layout, target)
+                            })
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            LogicalPlan::Subquery { .. } => OperatorKind::Subquery,
+            LogicalPlan::Filter { predicate, .. } => {
+                OperatorKind::Filter(self.coerced(predicate, &layout, DataType::Boolean)?)
+            }
+            LogicalPlan::Project { exprs, .. } => {
+                let mut compiled = Vec::new();
+
+                for (expr, id) in exprs {
+                    compiled.push(self.expr(expr, &layout)?);
+                    self.types.define(*id, expr)?;
+                }
+
+                OperatorKind::Project(compiled)
+            }
+            LogicalPlan::Join {
+                left,
+                right,
+                join_type,
+                condition,
+                lateral,
+            } => {
+                let condition = match condition {
+                    Some(condition) => Some(self.coerced(condition, &layout, DataType::Boolean)?),
+                    None => None,
+                };
+
+                OperatorKind::Join {
+                    join_type: join_type.clone(),
+                    condition,
+                    lateral: lateral.then(|| left.output()),
+                    left_width: left.output().len(),
+                    right_width: right.output().len(),
+                }
+            }
+            LogicalPlan::Aggregate {
+                group_by,
+                aggregates,
+                ..
+            } => {
+                let mut keys = Vec::new();
+
+                for (expr, id) in group_by {
+                    keys.push((self.expr(expr, &layout)?, self.types.collation(expr)?));
+
+                    // Grouping by a column passes it through as it is
+                    if *expr != BoundExpr::Column(*id) {
+                        self.types.define(*id, expr)?;
+                    }
+                }
+
+                let mut calls = Vec::new();
+
+                for (expr, id) in aggregates {
+                    calls.push(self.aggregate_call(expr, &layout)?);
+                    self.types.define(*id, expr)?;
+                }
+
+                OperatorKind::Aggregate { keys, calls }
+            }
+            LogicalPlan::Sort { keys, .. } => OperatorKind::Sort(self.sort_keys(keys, &layout)?),
+            LogicalPlan::Limit { offset, limit, .. } => OperatorKind::Limit {
+                offset: *offset,
+                limit: *limit,
+            },
+            LogicalPlan::Distinct { .. } => OperatorKind::Distinct(
+                plan.output()
+                    .into_iter()
+                    .map(|id| self.types.collation(&BoundExpr::Column(id)))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            LogicalPlan::SetOp {
+                left,
+                right,
+                operator,
+                all,
+                columns,
+            } => {
+                let (left, right) = (left.output(), right.output());
+                let mut casts = [Vec::new(), Vec::new()];
+
+                for (i, id) in columns.iter().enumerate() {
+                    self.types.define_combined(*id, left[i], right[i]);
+                    let target = self.types.column_type(*id);
+
+                    for (casts, input) in casts.iter_mut().zip([left[i], right[i]]) {
+                        let data_type = self.types.column_type(input);
+                        casts.push((data_type.unlimited() != target.unlimited()).then_some(target));
+                    }
+                }
+
+                // Rows are only compared when some are removed, and only then must text have a
+                // determinate collation
+                let collations = match (operator, all) {
+                    (SetOperator::Union, true) => Vec::new(),
+                    _ => columns
+                        .iter()
+                        .map(|id| self.types.collation(&BoundExpr::Column(*id)))
+                        .collect::<Result<Vec<_>, _>>()?,
+                };
+
+                OperatorKind::SetOp {
+                    operator: *operator,
+                    all: *all,
+                    collations,
+                    casts,
+                }
+            }
+        };
+
+        Ok(Operator {
+            kind,
+            inputs,
+            wanted: None,
+            stats: Stats::default(),
+        })
+    }
+
+    fn sort_keys(
+        &mut self,
+        keys: &[BoundOrderByItem],
+        layout: &[ColumnId],
+    ) -> Result<Vec<(Expr<'a>, SortKey)>, ExecutorError> {
+        keys.iter()
+            .map(|key| {
+                let sort_key = SortKey {
+                    descending: key.ordering == Some(Ordering::Descending),
+                    collation: self.types.collation(&key.expr)?,
+                };
+
+                Ok((self.expr(&key.expr, layout)?, sort_key))
+            })
+            .collect()
+    }
+
+    fn aggregate_call(
+        &mut self,
+        expr: &BoundExpr,
+        layout: &[ColumnId],
+    ) -> Result<CompiledCall<'a>, ExecutorError> {
+        let BoundExpr::Function {
+            name,
+            args: expr_args,
+            distinct,
+            order_by,
+            filter,
+        } = expr
+        else {
+            unreachable!("Aggregates of a plan are function calls");
+        };
+
+        let (function, args) = self.call(name, expr_args.as_deref(), layout)?;
+
+        let FunctionKind::Aggregate(aggregate) = &function.kind else {
+            unreachable!("`{}` of an aggregate is not an aggregate function", name);
+        };
+
+        let collations = expr_args
+            .iter()
+            .flatten()
+            .map(|arg| self.types.collation(arg))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (order_by, sort_keys) = self.sort_keys(order_by, layout)?.into_iter().unzip();
+
+        let filter = match filter {
+            Some(filter) => Some(self.coerced(filter, layout, DataType::Boolean)?),
+            None => None,
+        };
+
+        Ok(CompiledCall {
+            call: AggregateCall::new(aggregate.clone(), *distinct, sort_keys, collations),
+            args,
+            order_by,
+            filter,
+        })
+    }
+
+    /// Resolves the overload of a function called with `args`, which are `None` for `f(*)`,
+    /// compiling them coerced to the types of its parameters.
+    fn call(
+        &mut self,
+        name: &str,
+        args: Option<&[BoundExpr]>,
+        layout: &[ColumnId],
+    ) -> Result<(&'a Function, Vec<Expr<'a>>), ExecutorError> {
+        let arg_types = match args {
+            Some(args) => Some(
+                args.iter()
+                    .map(|arg| self.types.expr_type(arg))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            None => None,
+        };
+        let (function, return_type) =
+            resolve_function(self.catalog.functions(), name, arg_types.as_deref())?;
+        let (args, arg_types) = (args.unwrap_or_default(), arg_types.unwrap_or_default());
+
+        let compiled = args
+            .iter()
+            .zip(&arg_types)
+            .enumerate()
+            .map(|(i, (arg, arg_type))| {
+                let target = match function.signature.param(i).unwrap() {
+                    Param::Type(data_type) => data_type,
+                    Param::Any if matches!(function.signature.returns, Returns::CommonType) => {
+                        return_type.resolve()
+                    }
+                    Param::Any => arg_type.resolve(),
+                    Param::Field | Param::Unit | Param::Zone | Param::Pattern => DataType::Text,
+                };
+
+                self.coerced(arg, layout, target)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((function, compiled))
+    }
+
+    /// Compiles an expression whose value is coerced to `target`.
+    fn coerced(
+        &mut self,
+        expr: &BoundExpr,
+        layout: &[ColumnId],
+        target: DataType,
+    ) -> Result<Expr<'a>, ExecutorError> {
+        let expr_type = self.types.expr_type(expr)?;
+        let compiled = self.expr(expr, layout)?;
+
+        // Implicit coercions never limit the length of text or the digits of decimals
+        let target = target.unlimited();
+
+        Ok(match expr_type {
+            ExprType::Known(data_type) if data_type.unlimited() == target => compiled,
+            ExprType::Null => compiled,
+            _ if target == DataType::Null => compiled,
+            // String literals are read as values of the type they are used as once
+            _ => match compiled {
+                Expr::Constant(value) => {
+                    Expr::Constant(value.cast(target, &self.context.time_zone)?)
+                }
+                compiled => Expr::Cast {
+                    operand: Box::new(compiled),
+                    data_type: target,
+                },
+            },
+        })
+    }
+
+    fn expr(&mut self, expr: &BoundExpr, layout: &[ColumnId]) -> Result<Expr<'a>, ExecutorError> {
+        Ok(match expr {
+            BoundExpr::Literal(literal) => Expr::Constant(match literal {
+                Literal::Null => Value::Null,
+                Literal::Boolean(value) => Value::Boolean(*value),
+                Literal::String(text) => Value::Text(text.clone()),
+                Literal::Number(number) => Value::parse(number, literal_type(literal)?.resolve())?,
+            }),
+            BoundExpr::Constant(value) => Expr::Constant(value.clone()),
+            BoundExpr::Column(id) => match layout.iter().position(|column| column == id) {
+                Some(index) => Expr::Column(index),
+                None => Expr::Outer(*id),
+            },
+            BoundExpr::Unary { operator, operand } => match operator {
+                UnaryOperator::Not => Expr::Not(Box::new(self.coerced(
+                    operand,
+                    layout,
+                    DataType::Boolean,
+                )?)),
+                UnaryOperator::Minus => Expr::Negate(Box::new(self.expr(operand, layout)?)),
+                UnaryOperator::Plus => self.expr(operand, layout)?,
+            },
+            BoundExpr::Binary {
+                left,
+                operator,
+                right,
+            } => self.binary(*operator, left, right, layout)?,
+            BoundExpr::IsNull { operand, negated } => Expr::IsNull {
+                operand: Box::new(self.expr(operand, layout)?),
+                negated: *negated,
+            },
+            BoundExpr::Function { name, args, .. } => {
+                let (function, args) = self.call(name, args.as_deref(), layout)?;
+
+                Expr::Call { function, args }
+            }
+            BoundExpr::Cast { operand, data_type } => Expr::Cast {
+                operand: Box::new(self.expr(operand, layout)?),
+                data_type: resolve_type(data_type)?,
+            },
+            BoundExpr::Collate { operand, .. } => self.expr(operand, layout)?,
+        })
+    }
+
+    fn binary(
+        &mut self,
+        operator: BinaryOperator,
+        left: &BoundExpr,
+        right: &BoundExpr,
+        layout: &[ColumnId],
+    ) -> Result<Expr<'a>, ExecutorError> {
+        let left_type = self.types.expr_type(left)?;
+        let right_type = self.types.expr_type(right)?;
+
+        let operands = |compiler: &mut Self, left_target, right_target| {
+            Ok::<_, ExecutorError>((
+                Box::new(compiler.coerced(left, layout, left_target)?),
+                Box::new(compiler.coerced(right, layout, right_target)?),
+            ))
+        };
+
+        Ok(match operator {
+            BinaryOperator::And | BinaryOperator::Or => {
+                let (left, right) = operands(self, DataType::Boolean, DataType::Boolean)?;
+
+                match operator {
+                    BinaryOperator::And => Expr::And(left, right),
+                    _ => Expr::Or(left, right),
+                }
+            }
+            BinaryOperator::Equals
+            | BinaryOperator::NotEquals
+            | BinaryOperator::LessThan
+            | BinaryOperator::LessThanOrEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterThanOrEqual => {
+                let common_type = ExprType::common_type(left_type, right_type)
+                    .expect("Operands of a checked comparison have a common type")
+                    .resolve();
+                let collation = match common_type.is_text() {
+                    true => self.types.comparison_collation(left, right)?,
+                    false => Collation::Binary,
+                };
+                let (left, right) = operands(self, common_type, common_type)?;
+
+                Expr::Compare {
+                    left,
+                    operator,
+                    right,
+                    collation,
+                }
+            }
+            BinaryOperator::JsonGet
+            | BinaryOperator::JsonGetText
+            | BinaryOperator::JsonGetPath
+            | BinaryOperator::JsonGetPathText
+            | BinaryOperator::Contains
+            | BinaryOperator::ContainedBy
+            | BinaryOperator::HasKey
+            | BinaryOperator::HasAnyKey
+            | BinaryOperator::HasAllKeys => {
+                let (param, _) = json_operator_types(operator, right_type)
+                    .expect("Operands of a checked JSON operator are accepted by it");
+                let (left, right) = operands(self, DataType::Jsonb, param)?;
+
+                Expr::Json {
+                    left,
+                    operator,
+                    right,
+                }
+            }
+            BinaryOperator::Plus
+            | BinaryOperator::Minus
+            | BinaryOperator::Multiply
+            | BinaryOperator::Divide
+            | BinaryOperator::Modulo => {
+                let (left_type, right_type) =
+                    arithmetic_operand_types(operator, left_type, right_type);
+                let result = arithmetic_type(operator, left_type, right_type)
+                    .expect("Operands of checked arithmetic are accepted by it")
+                    .resolve();
+
+                // Numbers are computed in the type of the result, anything else as it is
+                let numeric = |operand: ExprType| match operand {
+                    ExprType::Known(data_type) => data_type.is_numeric(),
+                    ExprType::Null => true,
+                    ExprType::StringLiteral => false,
+                };
+
+                let (left, right) = match numeric(left_type) && numeric(right_type) {
+                    true => operands(self, result, result)?,
+                    false => operands(self, left_type.resolve(), right_type.resolve())?,
+                };
+
+                Expr::Arithmetic {
+                    left,
+                    operator,
+                    right,
+                }
+            }
+        })
+    }
+}
+
+impl<'a> Operator<'a> {
+    /// Runs the operator and its inputs once, returning every row it produces.
+    pub fn execute(&mut self, context: &FunctionContext) -> Result<Vec<Row>, ExecutorError> {
+        self.run(context, &[])
+    }
+
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    /// The operators this one reads the rows of, in the order of the inputs of its plan
+    /// operator.
+    pub fn inputs(&self) -> &[Operator<'a>] {
+        &self.inputs
+    }
+
+    /// Limits the operator to producing `wanted` rows, passing on how many of the rows of its
+    /// inputs that takes where it is known.
+    fn limit_rows(&mut self, wanted: Option<usize>) {
+        self.wanted = wanted;
+
+        let input_wanted = match &self.kind {
+            OperatorKind::Limit { offset, limit } => {
+                let limit = match (limit, wanted) {
+                    (Some(limit), Some(wanted)) => Some((*limit as usize).min(wanted)),
+                    (limit, wanted) => limit.map(|limit| limit as usize).or(wanted),
+                };
+
+                limit.map(|limit| (*offset as usize).saturating_add(limit))
+            }
+            // Every row of their input is one of theirs
+            OperatorKind::Subquery | OperatorKind::Project(_) => wanted,
+            _ => None,
+        };
+
+        for input in &mut self.inputs {
+            input.limit_rows(input_wanted);
+        }
+    }
+
+    fn run(&mut self, context: &FunctionContext, env: &Env) -> Result<Vec<Row>, ExecutorError> {
+        let start = Instant::now();
+        let rows = self.compute(context, env)?;
+
+        self.stats.rows += rows.len() as u64;
+        self.stats.loops += 1;
+        self.stats.time += start.elapsed();
+        self.stats.memory = self
+            .stats
+            .memory
+            .max(rows.iter().flatten().map(Value::size).sum());
+
+        Ok(rows)
+    }
+
+    fn compute(&mut self, context: &FunctionContext, env: &Env) -> Result<Vec<Row>, ExecutorError> {
+        let inputs = &mut self.inputs;
+        let wanted = self.wanted.unwrap_or(usize::MAX);
+
+        let rows = match &self.kind {
+            OperatorKind::Scan(rows) => rows[..wanted.min(rows.len())].to_vec(),
+            OperatorKind::FunctionScan { function, args } => {
+                let FunctionKind::Table(table_function) = &function.kind else {
+                    unreachable!("Function scans call table functions");
+                };
+
+                let args = args
+                    .iter()
+                    .map(|arg| arg.eval(&[], env, context))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                table_function.call(context, &args)?
+            }
+            OperatorKind::Values(rows) => rows
+                .iter()
+                .take(wanted)
+                .map(|row| {
+                    row.iter()
+                        .map(|expr| expr.eval(&[], env, context))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            OperatorKind::Subquery => inputs[0].run(context, env)?,
+            OperatorKind::Filter(predicate) => {
+                let mut rows = Vec::new();
+
+                for row in inputs[0].run(context, env)? {
+                    if rows.len() == wanted {
+                        break;
+                    }
+
+                    if predicate.eval(&row, env, context)? == Value::Boolean(true) {
+                        rows.push(row);
+                    }
+                }
+
+                rows
+            }
+            OperatorKind::Project(exprs) => inputs[0]
+                .run(context, env)?
+                .into_iter()
+                .map(|row| {
+                    exprs
+                        .iter()
+                        .map(|expr| expr.eval(&row, env, context))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            OperatorKind::Join {
+                join_type,
+                condition,
+                lateral,
+                left_width,
+                right_width,
+            } => {
+                let [left, right] = &mut inputs[..] else {
+                    unreachable!("Joins have two inputs");
+                };
+
+                let left_rows = left.run(context, env)?;
+                let right_rows = match lateral {
+                    Some(_) => Vec::new(),
+                    None => right.run(context, env)?,
+                };
+
+                let mut rows = Vec::new();
+                let mut right_matched = vec![false; right_rows.len()];
+
+                for left_row in left_rows {
+                    // The right side of a lateral join is computed for every left row, reading
+                    // its columns
+                    let right_rows = match lateral {
+                        Some(columns) => {
+                            let mut env = env.to_vec();
+                            env.extend(columns.iter().copied().zip(left_row.iter().cloned()));
+                            Cow::Owned(right.run(context, &env)?)
+                        }
+                        None => Cow::Borrowed(&right_rows),
+                    };
+
+                    let mut left_matched = false;
+
+                    for (i, right_row) in right_rows.iter().enumerate() {
+                        let mut row = left_row.clone();
+                        row.extend(right_row.iter().cloned());
+
+                        let matched = match condition {
+                            Some(condition) => {
+                                condition.eval(&row, env, context)? == Value::Boolean(true)
+                            }
+                            None => true,
+                        };
+
+                        if matched {
+                            left_matched = true;
+
+                            if lateral.is_none() {
+                                right_matched[i] = true;
+                            }
+
+                            rows.push(row);
+                        }
+                    }
+
+                    if !left_matched
+                        && matches!(join_type, JoinType::LeftOuter | JoinType::FullOuter)
+                    {
+                        let mut row = left_row;
+                        row.resize(left_width + right_width, Value::Null);
+                        rows.push(row);
+                    }
+                }
+
+                if matches!(join_type, JoinType::RightOuter | JoinType::FullOuter) {
+                    for (right_row, matched) in right_rows.into_iter().zip(right_matched) {
+                        if !matched {
+                            let mut row = vec![Value::Null; *left_width];
+                            row.extend(right_row);
+                            rows.push(row);
+                        }
+                    }
+                }
+
+                rows
+            }
+            OperatorKind::Aggregate { keys, calls } => {
+                let mut indexes = HashMap::<Vec<CollatedValue>, usize>::new();
+                let mut groups = Vec::<(Row, Vec<CallState>)>::new();
+
+                for row in inputs[0].run(context, env)? {
+                    let values = keys
+                        .iter()
+                        .map(|(key, _)| key.eval(&row, env, context))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let collated = values
+                        .iter()
+                        .zip(keys)
+                        .map(|(value, (_, collation))| CollatedValue {
+                            value: value.clone(),
+                            collation: *collation,
+                        })
+                        .collect();
+
+                    // Groups are produced in the order their first rows came in
+                    let index = *indexes.entry(collated).or_insert_with(|| {
+                        let states = calls.iter().map(|call| call.call.init()).collect();
+                        groups.push((values, states));
+                        groups.len() - 1
+                    });
+
+                    for (call, state) in calls.iter().zip(&mut groups[index].1) {
+                        call.update(state, &row, env, context)?;
+                    }
+                }
+
+                // Without keys, all rows form a single group, even when there are none
+                if keys.is_empty() && groups.is_empty() {
+                    let states = calls.iter().map(|call| call.call.init()).collect();
+                    groups.push((Vec::new(), states));
+                }
+
+                groups
+                    .into_iter()
+                    .map(|(mut row, states)| {
+                        for (call, state) in calls.iter().zip(states) {
+                            row.push(call.call.finalize(state)?);
+                        }
+
+                        Ok(row)
+                    })
+                    .collect::<Result<Vec<_>, ExecutorError>>()?
+            }
+            OperatorKind::Sort(keys) => {
+                let mut keyed = inputs[0]
+                    .run(context, env)?
+                    .into_iter()
+                    .map(|row| {
+                        let values = keys
+                            .iter()
+                            .map(|(key, _)| key.eval(&row, env, context))
+                            .collect::<Result<Vec<_>, _>>()?;
+
+                        Ok((values, row))
+                    })
+                    .collect::<Result<Vec<_>, ExecutorError>>()?;
+
+                // Sorting is stable, so rows with equal keys keep the order they came in
+                keyed.sort_by(|(a, _), (b, _)| {
+                    a.iter()
+                        .zip(b)
+                        .zip(keys)
+                        .map(|((a, b), (_, key))| match key.descending {
+                            true => key.collation.compare_values(b, a),
+                            false => key.collation.compare_values(a, b),
+                        })
+                        .find(|ordering| ordering.is_ne())
+                        .unwrap_or(CmpOrdering::Equal)
+                });
+
+                keyed.into_iter().map(|(_, row)| row).collect()
+            }
+            OperatorKind::Limit { offset, limit } => inputs[0]
+                .run(context, env)?
+                .into_iter()
+                .skip(*offset as usize)
+                .take(limit.map_or(usize::MAX, |limit| limit as usize))
+                .collect(),
+            OperatorKind::Distinct(collations) => {
+                let mut seen = HashSet::new();
+
+                inputs[0]
+                    .run(context, env)?
+                    .into_iter()
+                    .filter(|row| seen.insert(collated(row, collations)))
+                    .collect()
+            }
+            OperatorKind::SetOp {
+                operator,
+                all,
+                collations,
+                casts,
+            } => {
+                let [left, right] = &mut inputs[..] else {
+                    unreachable!("Set operations have two inputs");
+                };
+
+                let left_rows = cast_rows(left.run(context, env)?, &casts[0], context)?;
+                let right_rows = cast_rows(right.run(context, env)?, &casts[1], context)?;
+
+                set_operation(*operator, *all, left_rows, right_rows, collations)
+            }
+        };
+
+        Ok(rows)
+    }
+}
+
+impl CompiledCall<'_> {
+    /// Updates the state of the call with a row of its group, unless its filter rejects it.
+    fn update(
+        &self,
+        state: &mut CallState,
+        row: &[Value],
+        env: &Env,
+        context: &FunctionContext,
+    ) -> Result<(), ExecutorError> {
+        if let Some(filter) = &self.filter {
+            if filter.eval(row, env, context)? != Value::Boolean(true) {
+                return Ok(());
+            }
+        }
+
+        let eval = |exprs: &[Expr]| {
+            exprs
+                .iter()
+                .map(|expr| expr.eval(row, env, context))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let args = eval(&self.args)?;
+        let keys = eval(&self.order_by)?;
+
+        Ok(self.call.update(state, &args, &keys)?)
+    }
+}
+
+/// The values of a row as compared with `collations`, which removing duplicates uses.
+fn collated(row: &[Value], collations: &[Collation]) -> Vec<CollatedValue> {
+    row.iter()
+        .zip(collations)
+        .map(|(value, collation)| CollatedValue {
+            value: value.clone(),
+            collation: *collation,
+        })
+        .collect()
+}
+
+fn cast_rows(
+    rows: Vec<Row>,
+    casts: &[Option<DataType>],
+    context: &FunctionContext,
+) -> Result<Vec<Row>, ExecutorError> {
+    if casts.iter().all(Option::is_none) {
+        return Ok(rows);
+    }
+
+    rows.into_iter()
+        .map(|row| {
+            row.into_iter()
+                .zip(casts)
+                .map(|(value, cast)| match cast {
+                    Some(data_type) => Ok(value.cast(*data_type, &context.time_zone)?),
+                    None => Ok(value),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Combines the rows of two queries, where rows are the same when all their values compare
+/// equal under `collations`, keeping the order of the left rows.
+fn set_operation(
+    operator: SetOperator,
+    all: bool,
+    left: Vec<Row>,
+    right: Vec<Row>,
+    collations: &[Collation],
+) -> Vec<Row> {
+    if let (SetOperator::Union, true) = (operator, all) {
+        let mut rows = left;
+        rows.extend(right);
+        return rows;
+    }
+
+    let mut seen = HashSet::new();
+
+    if operator == SetOperator::Union {
+        return left
+            .into_iter()
+            .chain(right)
+            .filter(|row| seen.insert(collated(row, collations)))
+            .collect();
+    }
+
+    let mut counts = HashMap::<_, usize>::new();
+
+    for row in &right {
+        *counts.entry(collated(row, collations)).or_default() += 1;
+    }
+
+    left.into_iter()
+        .filter(|row| {
+            let key = collated(row, collations);
+            let count = counts.get(&key).copied().unwrap_or(0);
+
+            match (operator, all) {
+                // Each right row cancels out a single left row
+                (SetOperator::Intersect, true) if count > 0 => {
+                    counts.insert(key, count - 1);
+                    true
+                }
+                (SetOperator::Except, true) if count > 0 => {
+                    counts.insert(key, count - 1);
+                    false
+                }
+                (SetOperator::Intersect, true) => false,
+                (SetOperator::Except, true) => true,
+                (SetOperator::Intersect, false) => count > 0 && seen.insert(key),
+                (_, false) => count == 0 && seen.insert(key),
+                (SetOperator::Union, true) => unreachable!(),
+            }
+        })
+        .collect()
+}
+
+impl Expr<'_> {
+    fn eval(
+        &self,
+        row: &[Value],
+        env: &Env,
+        context: &FunctionContext,
+    ) -> Result<Value, ExecutorError> {
+        let eval = |expr: &Expr| expr.eval(row, env, context);
+
+        Ok(match self {
+            Expr::Constant(value) => value.clone(),
+            Expr::Column(index) => row[*index].clone(),
+            Expr::Outer(id) => match env.iter().find(|(column, _)| column == id) {
+                Some((_, value)) => value.clone(),
+                None => unreachable!("Column {} is not in the row it is evaluated on", id.0),
+            },
+            Expr::Not(operand) => match eval(operand)? {
+                Value::Boolean(value) => Value::Boolean(!value),
+                _ => Value::Null,
+            },
+            Expr::Negate(operand) => negate(eval(operand)?)?,
+            // Logic has three values, where `NULL` is unknown and only decides the result when
+            // the other operand does not
+            Expr::And(left, right) => match eval(left)? {
+                Value::Boolean(false) => Value::Boolean(false),
+                left => match (left, eval(right)?) {
+                    (_, Value::Boolean(false)) => Value::Boolean(false),
+                    (Value::Boolean(true), right) => right,
+                    _ => Value::Null,
+                },
+            },
+            Expr::Or(left, right) => match eval(left)? {
+                Value::Boolean(true) => Value::Boolean(true),
+                left => match (left, eval(right)?) {
+                    (_, Value::Boolean(true)) => Value::Boolean(true),
+                    (Value::Boolean(false), right) => right,
+                    _ => Value::Null,
+                },
+            },
+            Expr::IsNull { operand, negated } => {
+                Value::Boolean(eval(operand)?.is_null() != *negated)
+            }
+            Expr::Compare {
+                left,
+                operator,
+                right,
+                collation,
+            } => match (eval(left)?, eval(right)?) {
+                (Value::Null, _) | (_, Value::Null) => Value::Null,
+                (left, right) => {
+                    let ordering = collation.compare_values(&left, &right);
+
+                    Value::Boolean(match operator {
+                        BinaryOperator::Equals => ordering.is_eq(),
+                        BinaryOperator::NotEquals => ordering.is_ne(),
+                        BinaryOperator::LessThan => ordering.is_lt(),
+                        BinaryOperator::LessThanOrEqual => ordering.is_le(),
+                        BinaryOperator::GreaterThan => ordering.is_gt(),
+                        BinaryOperator::GreaterThanOrEqual => ordering.is_ge(),
+                        _ => unreachable!("`{}` is not a comparison", operator),
+                    })
+                }
+            },
+            Expr::Arithmetic {
+                left,
+                operator,
+                right,
+            } => arithmetic(*operator, eval(left)?, eval(right)?, context)?,
+            Expr::Json {
+                left,
+                operator,
+                right,
+            } => json(*operator, eval(left)?, eval(right)?)?,
+            Expr::Call { function, args } => {
+                let args = args.iter().map(eval).collect::<Result<Vec<_>, _>>()?;
+                function.call(context, &args)?
+            }
+            Expr::Cast { operand, data_type } => {
+                eval(operand)?.cast(*data_type, &context.time_zone)?
+            }
+        })
+    }
+}
+
+fn negate(value: Value) -> Result<Value, ExecutorError> {
+    let data_type = value.data_type();
+    let out_of_range = || ExecutorError::OutOfRange(data_type);
+
+    Ok(match value {
+        Value::SmallInt(value) => Value::SmallInt(value.checked_neg().ok_or_else(out_of_range)?),
+        Value::Int(value) => Value::Int(value.checked_neg().ok_or_else(out_of_range)?),
+        Value::BigInt(value) => Value::BigInt(value.checked_neg().ok_or_else(out_of_range)?),
+        Value::Decimal(value) => Value::Decimal(Decimal::from(0).checked_sub(&value)?),
+        Value::Real(value) => Value::Real(-value),
+        Value::Double(value) => Value::Double(-value),
+        Value::Interval(value) => Value::Interval(value.checked_neg()?),
+        value => value,
+    })
+}
+
+/// Applies an arithmetic operator to operands of the types `arithmetic_type` takes them as,
+/// where both are of the type of the result when they are numbers.
+pub fn arithmetic(
+    operator: BinaryOperator,
+    left: Value,
+    right: Value,
+    context: &FunctionContext,
+) -> Result<Value, ExecutorError> {
+    use BinaryOperator::{Divide, Minus, Modulo, Multiply, Plus};
+
+    let integer = |value: &Value| match value {
+        Value::SmallInt(value) => Some(*value as i64),
+        Value::Int(value) => Some(*value as i64),
+        Value::BigInt(value) => Some(*value),
+        _ => None,
+    };
+
+    // Intervals are scaled by numbers of any type
+    let factor = |value: &Value| value.to_string().parse::<f64>().unwrap_or(f64::NAN);
+
+    let negated = |interval: Interval| match operator {
+        Minus => interval.checked_neg(),
+        _ => Ok(interval),
+    };
+
+    let value = match (left, right) {
+        (Value::Null, _) | (_, Value::Null) => Value::Null,
+        (left @ (Value::SmallInt(_) | Value::Int(_) | Value::BigInt(_)), right)
+            if right.data_type() == left.data_type() =>
+        {
+            let data_type = left.data_type();
+            let (a, b) = (integer(&left).unwrap(), integer(&right).unwrap());
+
+            if matches!(operator, Divide | Modulo) && b == 0 {
+                return Err(ExecutorError::DivisionByZero);
+            }
+
+            let result = match operator {
+                Plus => a.checked_add(b),
+                Minus => a.checked_sub(b),
+                Multiply => a.checked_mul(b),
+                Divide => a.checked_div(b),
+                Modulo => a.checked_rem(b),
+                _ => unreachable!("`{}` is not arithmetic", operator),
+            }
+            .ok_or(ExecutorError::OutOfRange(data_type))?;
+
+            Value::parse(&result.to_string(), data_type)
+                .map_err(|_| ExecutorError::OutOfRange(data_type))?
+        }
+        (Value::Decimal(a), Value::Decimal(b)) => Value::Decimal(match operator {
+            Plus => a.checked_add(&b)?,
+            Minus => a.checked_sub(&b)?,
+            Multiply => a.checked_mul(&b)?,
+            Divide => {
+                // As many digits after the decimal point as the operands have, and at least as
+                // many as a mean has, as fit next to those before it
+                let integer_digits = a.precision().saturating_sub(a.scale());
+                let scale = a
+                    .scale()
+                    .max(b.scale())
+                    .max(16)
+                    .min(Decimal::MAX_PRECISION.saturating_sub(integer_digits));
+
+                a.checked_div(&b, scale, RoundingMode::HalfUp)?
+            }
+            Modulo => a.checked_sub(&a.checked_div(&b, 0, RoundingMode::Down)?.checked_mul(&b)?)?,
+            _ => unreachable!("`{}` is not arithmetic", operator),
+        }),
+        (Value::Real(a), Value::Real(b)) => {
+            Value::Real(float_arithmetic(operator, a as f64, b as f64)? as f32)
+        }
+        (Value::Double(a), Value::Double(b)) => Value::Double(float_arithmetic(operator, a, b)?),
+        (Value::Date(date), days) if integer(&days).is_some() => {
+            let days = integer(&days).unwrap();
+            let days = match operator {
+                Minus => days.checked_neg(),
+                _ => Some(days),
+            };
+
+            days.and_then(|days| i32::try_from(date as i64 + days).ok())
+                .map(Value::Date)
+                .ok_or(ExecutorError::OutOfRange(DataType::Date))?
+        }
+        (days, Value::Date(date)) if integer(&days).is_some() => {
+            i32::try_from(date as i64 + integer(&days).unwrap())
+                .map(Value::Date)
+                .map_err(|_| ExecutorError::OutOfRange(DataType::Date))?
+        }
+        (Value::Date(a), Value::Date(b)) => Value::Int(a - b),
+        (date @ Value::Date(_), Value::Interval(interval))
+        | (Value::Interval(interval), date @ Value::Date(_)) => {
+            let Value::Timestamp(timestamp) = date.cast(DataType::Timestamp, &context.time_zone)?
+            else {
+                unreachable!("Dates are cast to timestamps");
+            };
+
+            Value::Timestamp(add_interval(timestamp, &negated(interval)?)?)
+        }
+        (date @ Value::Date(_), Value::Time(time)) | (Value::Time(time), date @ Value::Date(_)) => {
+            let Value::Timestamp(timestamp) = date.cast(DataType::Timestamp, &context.time_zone)?
+            else {
+                unreachable!("Dates are cast to timestamps");
+            };
+
+            Value::Timestamp(timestamp + time)
+        }
+        (Value::Timestamp(timestamp), Value::Interval(interval))
+        | (Value::Interval(interval), Value::Timestamp(timestamp)) => {
+            Value::Timestamp(add_interval(timestamp, &negated(interval)?)?)
+        }
+        (Value::TimestampTz(timestamp), Value::Interval(interval))
+        | (Value::Interval(interval), Value::TimestampTz(timestamp)) => Value::TimestampTz(
+            add_interval_in(timestamp, &negated(interval)?, &context.time_zone)?,
+        ),
+        (Value::Time(time), Value::Interval(interval))
+        | (Value::Interval(interval), Value::Time(time)) => {
+            Value::Time(add_interval_to_time(time, &negated(interval)?))
+        }
+        (Value::Timestamp(a), Value::Timestamp(b))
+        | (Value::TimestampTz(a), Value::TimestampTz(b)) => {
+            Value::Interval(subtract_timestamps(a, b)?)
+        }
+        (Value::Time(a), Value::Time(b)) => Value::Interval(Interval {
+            months: 0,
+            days: 0,
+            micros: a - b,
+        }),
+        (Value::Interval(a), Value::Interval(b)) => Value::Interval(match operator {
+            Plus => a.checked_add(&b)?,
+            _ => a.checked_sub(&b)?,
+        }),
+        (Value::Interval(interval), factor_value) => Value::Interval(match operator {
+            Multiply => interval.checked_mul(factor(&factor_value))?,
+            _ => interval.checked_div(factor(&factor_value))?,
+        }),
+        (factor_value, Value::Interval(interval)) => {
+            Value::Interval(interval.checked_mul(factor(&factor_value))?)
+        }
+        (left, right) => unreachable!(
+            "`{:?} {} {:?}` is not checked arithmetic",
+            left, operator, right
+        ),
+    };
+
+    Ok(value)
+}
+
+fn float_arithmetic(operator: BinaryOperator, a: f64, b: f64) -> Result<f64, ExecutorError> {
+    Ok(match operator {
+        BinaryOperator::Plus => a + b,
+        BinaryOperator::Minus => a - b,
+        BinaryOperator::Multiply => a * b,
+        BinaryOperator::Divide | BinaryOperator::Modulo if b == 0.0 => {
+            return Err(ExecutorError::DivisionByZero)
+        }
+        BinaryOperator::Divide => a / b,
+        BinaryOperator::Modulo => a % b,
+        _ => unreachable!("`{}` is not arithmetic", operator),
+    })
+}
+
+/// Applies a JSON operator to a `JSONB` value and an operand of the type `json_operator_types`
+/// passes it as.
+fn json(operator: BinaryOperator, left: Value, right: Value) -> Result<Value, ExecutorError> {
+    let (Value::Jsonb(document), false) = (left, right.is_null()) else {
+        return Ok(Value::Null);
+    };
+
+    let root = document.root();
+
+    let text = |value: &Value| match value {
+        Value::Text(text) => text.clone(),
+        value => value.to_string(),
+    };
+
+    let found = match (operator, &right) {
+        (BinaryOperator::JsonGet | BinaryOperator::JsonGetText, Value::BigInt(index)) => {
+            root.index(*index)
+        }
+        (BinaryOperator::JsonGet | BinaryOperator::JsonGetText, key) => root.get(&text(key)),
+        (BinaryOperator::JsonGetPath | BinaryOperator::JsonGetPathText, path) => {
+            root.path(&parse_path(&text(path))?)
+        }
+        (BinaryOperator::Contains | BinaryOperator::ContainedBy, Value::Jsonb(other)) => {
+            let contained = match operator {
+                BinaryOperator::Contains => root.contains(&other.root()),
+                _ => other.root().contains(&root),
+            };
+
+            return Ok(Value::Boolean(contained));
+        }
+        (BinaryOperator::HasKey, key) => return Ok(Value::Boolean(root.has_key(&text(key)))),
+        (BinaryOperator::HasAnyKey, keys) => {
+            return Ok(Value::Boolean(root.has_any_key(&parse_path(&text(keys))?)))
+        }
+        (BinaryOperator::HasAllKeys, keys) => {
+            return Ok(Value::Boolean(root.has_all_keys(&parse_path(&text(keys))?)))
+        }
+        _ => unreachable!("`{}` is not a JSON operator taking {:?}", operator, right),
+    };
+
+    Ok(match operator {
+        BinaryOperator::JsonGetText | BinaryOperator::JsonGetPathText => found
+            .and_then(|value| value.to_text())
+            .map_or(Value::Null, Value::Text),
+        _ => found.map_or(Value::Null, |value| Value::Jsonb(value.to_jsonb())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use drown_common::sql::value::Value;
+
+    use crate::Server;
+
+    fn server() -> Server {
+        let mut server = Server::default();
+        server
+            .execute(
+                "CREATE TABLE l (id INT, code TEXT); \
+                 CREATE TABLE r (id INT, n INT); \
+                 CREATE TABLE empty (id INT); \
+                 INSERT INTO l VALUES (1, 'a'), (2, 'B'), (3, 'b'), (NULL, 'A'); \
+                 INSERT INTO r VALUES (2, 20), (3, 30), (3, 31), (4, 40)",
+            )
+            .unwrap();
+        server
+    }
+
+    /// Runs a query, writing each row as its values separated by commas.
+    fn rows(server: &mut Server, sql: &str) -> Vec<String> {
+        let result = server
+            .execute(sql)
+            .unwrap_or_else(|err| panic!("{sql}: {}", err.error));
+
+        result
+            .data
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|value| match value {
+                        Value::Null => "NULL".to_string(),
+                        value => value.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect()
+    }
+
+    fn error(server: &mut Server, sql: &str) -> String {
+        server.execute(sql).unwrap_err().error
+    }
+
+    #[test]
+    fn joins() {
+        let mut server = server();
+
+        assert_eq!(
+            rows(&mut server, "SELECT l.id, n FROM l JOIN r ON l.id = r.id"),
+            ["2,20", "3,30", "3,31"]
+        );
+        assert_eq!(
+            rows(
+                &mut server,
+                "SELECT l.id, n FROM l LEFT JOIN r ON l.id = r.id"
+            ),
+            ["1,NULL", "2,20", "3,30", "3,31", "NULL,NULL"]
+        );
+        assert_eq!(
+            rows(
+                &mut server,
+                "SELECT l.id, n FROM l RIGHT JOIN r ON l.id = r.id"
+            ),
+            ["2,20", "3,30", "3,31", "NULL,40"]
+        );
+        assert_eq!(
+            rows(
+                &mut server,
+                "SELECT l.id, r.id FROM l FULL JOIN r ON l.id = r.id"
+            ),
+            ["1,NULL", "2,2", "3,3", "3,3", "NULL,NULL", "NULL,4"]
+        );
+        assert_eq!(rows(&mut server, "SELECT count(*) FROM l, r"), ["16"]);
+
+        // The right side of a lateral join runs again for each left row
+        assert_eq!(
+            rows(
+                &mut server,
+                "SELECT l.id, x FROM l LEFT JOIN LATERAL generate_series(2, l.id) AS x ON TRUE"
+            ),
+            ["1,NULL", "2,2", "3,2", "3,3", "NULL,NULL"]
+        );
+    }
+
+    #[test]
+    fn aggregates() {
+        let mut server = server();
+
+        assert_eq!(
+            rows(
+                &mut server,
+                "SELECT r.id, count(*), sum(n) FROM r GROUP BY r.id"
+            ),
+            ["2,1,20", "3,2,61", "4,1,40"]
+        );
+
+        // Without GROUP BY there is a single group even without rows, and with it there are none
+        assert_eq!(
+            rows(&mut server, "SELECT count(*), max(id) FROM empty"),
+            ["0,NULL"]
+        );
+        assert_eq!(
+            rows(&mut server, "SELECT id, count(*) FROM empty GROUP BY id"),
+            Vec::<String>::new()
+        );
+
+        // Rows are grouped by the values of expressions too
+        assert_eq!(
+            rows(
+                &mut server,
+                "SELECT n / 10 * 10 AS tens, count(*) FROM r GROUP BY n / 10 * 10 ORDER BY tens"
+            ),
+            ["20,1", "30,2", "40,1"]
+        );
+
+        // FILTER only updates a call with the rows meeting its condition
+        assert_eq!(
+            rows(
+                &mut server,
+                "SELECT r.id, count(*) FILTER (WHERE n > 20), sum(n) FILTER (WHERE n % 2 = 1), \
+                 count(DISTINCT r.id) FILTER (WHERE n < 40) FROM r GROUP BY r.id"
+            ),
+            ["2,0,NULL,1", "3,2,31,1", "4,1,NULL,0"]
+        );
+    }
+
+    #[test]
+    fn sorts_and_limits() {
+        let mut server = server();
+
+        // Sorting is stable, and NULL sorts after every other value
+        assert_eq!(
+            rows(
+                &mut server,
+                "SELECT id, code FROM l ORDER BY code COLLATE nocase"
+            ),
+            ["1,a", "NULL,A", "2,B", "3,b"]
+        );
+        assert_eq!(
+            rows(&mut server, "SELECT id FROM l ORDER BY id DESC"),
+            ["NULL", "3", "2", "1"]
+        );
+        assert_eq!(
+            rows(&mut server, "SELECT n FROM r ORDER BY n LIMIT 2 OFFSET 1"),
+            ["30", "31"]
+        );
+        assert_eq!(
+            rows(&mut server, "SELECT n FROM r ORDER BY n OFFSET 10"),
+            Vec::<String>::new()
+        );
+        // Rows past the limit are never computed
+        assert_eq!(
+            rows(&mut server, "SELECT 10 / (n - 30) FROM r LIMIT 1"),
+            ["-1"]
+        );
+        assert_eq!(
+            rows(
+                &mut server,
+                "SELECT n FROM r WHERE 10 / (n - 30) < 0 LIMIT 1"
+            ),
+            ["20"]
+        );
+        assert_eq!(
+            rows(&mut server, "SELECT n FROM r LIMIT 0"),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            rows(&mut server, "SELECT DISTINCT code COLLATE nocase FROM l"),
+            ["a", "B"]
+        );
+    }
+
+    #[test]
+    fn set_operations() {
+        let mut server = server();
+
+        assert_eq!(
+            rows(&mut server, "SELECT id FROM l UNION SELECT id FROM r"),
+            ["1", "2", "3", "NULL", "4"]
+        );
+        assert_eq!(
+            rows(
+                &mut server,
+                "SELECT id FROM r UNION ALL SELECT id FROM l WHERE id > 2"
+            ),
+            ["2", "3", "3", "4", "3"]
+        );
+        assert_eq!(
+            rows(
+                &mut server,
+                "SELECT id FROM r INTERSECT ALL SELECT id FROM l"
+            ),
+            ["2", "3"]
+        );
+        assert_eq!(
+            rows(&mut server, "SELECT id FROM r EXCEPT ALL SELECT id FROM l"),
+            ["3", "4"]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut server = server();
+
+        assert!(error(&mut server, "SELECT n / 0 FROM r").contains("Division by zero"));
+        assert!(error(&mut server, "SELECT 2147483647 + n FROM r")
+            .contains("INT value is out of range"));
+        assert!(error(&mut server, "SELECT -(-2147483647 - 1) FROM r").contains("out of range"));
+    }
+}
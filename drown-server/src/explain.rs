@@ -0,0 +1,762 @@
+use std::{collections::HashMap, time::Instant};
+
+use drown_common::sql::{
+    function::FunctionContext,
+    statement::{ExplainFormat, ExplainStatement, JoinType, Ordering, SetOperator, Statement},
+};
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::{
+    binder::{
+        bind_select, derived_name, BindError, BoundExpr, BoundOrderByItem, BoundQuery, ColumnId,
+    },
+    catalog::{Catalog, RelationName, SearchPath},
+    dml::{modified_rows, DmlError},
+    executor::{compile_plan, ExecutorError, Operator, Stats},
+    fold::fold_select,
+    plan::{plan_query, LogicalPlan},
+    typecheck::{check_query_types, TypeError},
+};
+
+/// A single operator in the tree produced by `EXPLAIN`.
+#[derive(Debug)]
+pub struct PlanNode {
+    name: &'static str,
+    detail: Option<String>,
+    output: Vec<String>,
+    /// Additional information which is only shown by `EXPLAIN VERBOSE`
+    properties: Vec<(&'static str, String)>,
+    /// How the operator ran, which is only known to `EXPLAIN ANALYZE`
+    stats: Option<Stats>,
+    children: Vec<PlanNode>,
+}
+
+#[derive(Debug, Error)]
+pub enum ExplainError {
+    #[error("{0}")]
+    BindError(#[from] BindError),
+    #[error("{0}")]
+    TypeError(#[from] TypeError),
+    #[error("{0}")]
+    Execution(#[from] ExecutorError),
+    #[error("{0}")]
+    DmlError(#[from] DmlError),
+    #[error("Only SELECT, INSERT, UPDATE and DELETE statements can be explained")]
+    NotExplainable,
+    #[error("EXPLAIN ANALYZE cannot be used with {0}, because it would change the data")]
+    AnalyzeModification(String),
+}
+
+impl ExplainError {
+    /// The existing name a missing one was most likely meant to be.
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            ExplainError::BindError(err) => err.suggestion(),
+            ExplainError::TypeError(err) => err.suggestion(),
+            ExplainError::DmlError(err) => err.suggestion(),
+            _ => None,
+        }
+    }
+}
+
+impl PlanNode {
+    fn new(name: &'static str, detail: Option<String>, output: Vec<String>) -> Self {
+        Self {
+            name,
+            detail,
+            output,
+            properties: Vec::new(),
+            stats: None,
+            children: Vec::new(),
+        }
+    }
+
+    fn with_property(mut self, key: &'static str, value: String) -> Self {
+        self.properties.push((key, value));
+        self
+    }
+
+    fn with_child(mut self, child: PlanNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Builds the operator tree for a plan of `query`, in which views have already been
+    /// expanded.
+    pub fn from_plan(catalog: &Catalog, query: &BoundQuery, plan: &LogicalPlan) -> Self {
+        let mut describer = Describer {
+            catalog,
+            query,
+            definitions: HashMap::new(),
+        };
+
+        describer.define(plan);
+        describer.node(plan)
+    }
+
+    /// Adds the statistics of the operators which ran the plan the tree was built for.
+    fn analyze(&mut self, operator: &Operator) {
+        self.stats = Some(*operator.stats());
+
+        for (child, input) in self.children.iter_mut().zip(operator.inputs()) {
+            child.analyze(input);
+        }
+    }
+
+    /// Renders the tree as indented text, one line per entry.
+    pub fn to_text(&self, verbose: bool) -> Vec<String> {
+        let mut lines = Vec::new();
+        self.write_text(0, verbose, &mut lines);
+        lines
+    }
+
+    fn write_text(&self, depth: usize, verbose: bool, lines: &mut Vec<String>) {
+        let (prefix, indent) = match depth {
+            0 => (String::new(), String::new()),
+            _ => {
+                let indent = " ".repeat((depth - 1) * 6 + 2);
+                (format!("{}->  ", indent), format!("{}    ", indent))
+            }
+        };
+
+        let mut line = match &self.detail {
+            Some(detail) => format!("{}{}: {}", prefix, self.name, detail),
+            None => format!("{}{}", prefix, self.name),
+        };
+
+        // Rows and times are averaged over the loops, as in PostgreSQL
+        match self.stats {
+            Some(Stats { loops: 0, .. }) => line.push_str("  (never executed)"),
+            Some(stats) => line.push_str(&format!(
+                "  (actual rows={} loops={} time={:.3} ms memory={} bytes)",
+                rows_per_loop(&stats),
+                stats.loops,
+                milliseconds_per_loop(&stats),
+                stats.memory
+            )),
+            None => {}
+        }
+
+        lines.push(line);
+
+        if verbose && !self.output.is_empty() {
+            lines.push(format!("{}  Output: {}", indent, self.output.join(", ")));
+        }
+
+        if verbose {
+            for (key, value) in &self.properties {
+                lines.push(format!("{}  {}: {}", indent, key, value));
+            }
+        }
+
+        for child in &self.children {
+            child.write_text(depth + 1, verbose, lines);
+        }
+    }
+
+    /// Renders the tree as a JSON object.
+    pub fn to_json(&self, verbose: bool) -> Value {
+        let mut node = json!({ "Node Type": self.name });
+
+        if let Some(detail) = &self.detail {
+            node["Detail"] = json!(detail);
+        }
+
+        if let Some(stats) = &self.stats {
+            node["Actual Loops"] = json!(stats.loops);
+
+            if stats.loops > 0 {
+                node["Actual Rows"] = json!(rows_per_loop(stats));
+                node["Actual Total Time"] = json!(round_milliseconds(milliseconds_per_loop(stats)));
+                node["Memory Usage"] = json!(stats.memory);
+            }
+        }
+
+        if verbose {
+            node["Output"] = json!(self.output);
+
+            for (key, value) in &self.properties {
+                node[*key] = json!(value);
+            }
+        }
+
+        if !self.children.is_empty() {
+            node["Plans"] = self
+                .children
+                .iter()
+                .map(|child| child.to_json(verbose))
+                .collect();
+        }
+
+        node
+    }
+}
+
+/// Describes the operators of a plan in terms of the SQL they were planned from.
+struct Describer<'a> {
+    catalog: &'a Catalog,
+    query: &'a BoundQuery,
+    /// The expressions computing the columns added by the plan, which are shown in their place
+    definitions: HashMap<ColumnId, &'a BoundExpr>,
+}
+
+impl<'a> Describer<'a> {
+    fn define(&mut self, plan: &'a LogicalPlan) {
+        match plan {
+            LogicalPlan::Project { exprs, .. } => {
+                self.definitions
+                    .extend(exprs.iter().map(|(expr, id)| (*id, expr)));
+            }
+            LogicalPlan::Aggregate {
+                group_by,
+                aggregates,
+                ..
+            } => {
+                for (expr, id) in group_by.iter().chain(aggregates) {
+                    // Grouping by a column passes it through as it is
+                    if *expr != BoundExpr::Column(*id) {
+                        self.definitions.insert(*id, expr);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        for input in plan.inputs() {
+            self.define(input);
+        }
+    }
+
+    fn node(&self, plan: &LogicalPlan) -> PlanNode {
+        let output = plan
+            .output()
+            .into_iter()
+            .map(|id| self.expr(&BoundExpr::Column(id)))
+            .collect::<Vec<_>>();
+
+        let node = match plan {
+            LogicalPlan::Scan {
+                relation,
+                materialized: false,
+                alias,
+                ..
+            } => PlanNode::new("Scan", Some(relation_detail(relation, alias)), output),
+            LogicalPlan::Scan {
+                relation,
+                materialized: true,
+                alias,
+                ..
+            } => {
+                // The stored results are scanned, so the query itself is not part of the plan
+                let detail = relation_detail(relation, alias);
+                let node = PlanNode::new("Materialized View Scan", Some(detail), output);
+
+                match self.catalog.materialized_view(relation) {
+                    Some(view) if view.maintenance.is_some() => {
+                        node.with_property("Maintenance", "incremental".to_string())
+                    }
+                    Some(_) => node.with_property("Maintenance", "on refresh".to_string()),
+                    None => node,
+                }
+            }
+            LogicalPlan::FunctionScan {
+                name, args, alias, ..
+            } => {
+                let args = args.iter().map(|arg| self.expr(arg)).collect::<Vec<_>>();
+                let call = format!("{}({})", name, args.join(", "));
+
+                let detail = match alias {
+                    Some(alias) => format!("{} AS {}", call, alias),
+                    None => call,
+                };
+
+                PlanNode::new("Function Scan", Some(detail), output)
+            }
+            LogicalPlan::Values { rows, .. } => {
+                PlanNode::new("Values", Some(format!("{} rows", rows.len())), output)
+            }
+            LogicalPlan::Subquery {
+                view: Some(name),
+                alias,
+                ..
+            } => {
+                let node = PlanNode::new("View Scan", Some(relation_detail(name, alias)), output);
+
+                match self.catalog.view(name) {
+                    Some(view) => node.with_property("Definition", view.sql.clone()),
+                    None => node,
+                }
+            }
+            LogicalPlan::Subquery {
+                view: None, alias, ..
+            } => PlanNode::new("Subquery Scan", alias.clone(), output),
+            LogicalPlan::Filter { predicate, .. } => {
+                PlanNode::new("Filter", Some(self.expr(predicate)), output)
+            }
+            LogicalPlan::Project { exprs, .. } => {
+                let output = exprs
+                    .iter()
+                    .map(|(expr, id)| {
+                        let expr = self.query.unbind(&self.expand(expr));
+                        let name = &self.query.columns[id.0].name;
+
+                        match derived_name(&expr) == *name {
+                            true => expr.to_string(),
+                            false => format!("{} AS {}", expr, name),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                PlanNode::new("Project", Some(output.join(", ")), output)
+            }
+            LogicalPlan::Join {
+                join_type,
+                condition,
+                lateral,
+                ..
+            } => {
+                let join_type = match join_type {
+                    JoinType::Inner => "Inner",
+                    JoinType::Cross => "Cross",
+                    JoinType::LeftOuter => "Left",
+                    JoinType::RightOuter => "Right",
+                    JoinType::FullOuter => "Full",
+                };
+
+                let mut detail = match lateral {
+                    true => format!("Lateral {}", join_type),
+                    false => join_type.to_string(),
+                };
+
+                if let Some(condition) = condition {
+                    detail = format!("{} on {}", detail, self.expr(condition));
+                }
+
+                PlanNode::new("Join", Some(detail), output)
+            }
+            LogicalPlan::Aggregate {
+                group_by,
+                aggregates,
+                ..
+            } => {
+                let mut detail = aggregates
+                    .iter()
+                    .map(|(call, _)| self.expr(call))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                if !group_by.is_empty() {
+                    let keys = group_by
+                        .iter()
+                        .map(|(key, _)| self.expr(key))
+                        .collect::<Vec<_>>();
+
+                    if !detail.is_empty() {
+                        detail.push(' ');
+                    }
+
+                    detail.push_str(&format!("group by {}", keys.join(", ")));
+                }
+
+                PlanNode::new("Aggregate", (!detail.is_empty()).then_some(detail), output)
+            }
+            LogicalPlan::Sort { keys, .. } => {
+                PlanNode::new("Sort", Some(self.order_by(keys)), output)
+            }
+            LogicalPlan::Limit { offset, limit, .. } => {
+                let mut detail = Vec::new();
+
+                if let Some(limit) = limit {
+                    detail.push(format!("limit={}", limit));
+                }
+
+                if *offset > 0 {
+                    detail.push(format!("offset={}", offset));
+                }
+
+                PlanNode::new("Limit", Some(detail.join(" ")), output)
+            }
+            LogicalPlan::Distinct { .. } => PlanNode::new("Distinct", None, output),
+            LogicalPlan::SetOp { operator, all, .. } => {
+                let name = match operator {
+                    SetOperator::Union => "Union",
+                    SetOperator::Intersect => "Intersect",
+                    SetOperator::Except => "Except",
+                };
+
+                PlanNode::new(name, all.then(|| "all".to_string()), output)
+            }
+        };
+
+        plan.inputs()
+            .into_iter()
+            .fold(node, |node, input| node.with_child(self.node(input)))
+    }
+
+    fn order_by(&self, keys: &[BoundOrderByItem]) -> String {
+        keys.iter()
+            .map(|key| match key.ordering {
+                Some(Ordering::Descending) => format!("{} DESC", self.expr(&key.expr)),
+                Some(Ordering::Ascending) | None => self.expr(&key.expr),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn expr(&self, expr: &BoundExpr) -> String {
+        self.query.unbind(&self.expand(expr)).to_string()
+    }
+
+    /// Replaces the columns added by the plan with the expressions computing them.
+    fn expand(&self, expr: &BoundExpr) -> BoundExpr {
+        expr.rewrite(&mut |expr| match expr {
+            BoundExpr::Column(id) => self.definitions.get(id).map(|expr| self.expand(expr)),
+            _ => None,
+        })
+    }
+}
+
+fn rows_per_loop(stats: &Stats) -> u64 {
+    (stats.rows as f64 / stats.loops as f64).round() as u64
+}
+
+fn milliseconds_per_loop(stats: &Stats) -> f64 {
+    stats.time.as_secs_f64() * 1000.0 / stats.loops as f64
+}
+
+/// Rounds a time to whole microseconds, which is as precise as the text format shows it.
+fn round_milliseconds(milliseconds: f64) -> f64 {
+    (milliseconds * 1000.0).round() / 1000.0
+}
+
+fn relation_detail(name: &RelationName, alias: &Option<String>) -> String {
+    match alias {
+        Some(alias) => format!("{} AS {}", name.name, alias),
+        None => name.name.clone(),
+    }
+}
+
+/// Produces the rows of the `QUERY PLAN` result set for an `EXPLAIN` statement, resolving names
+/// with `search_path`, and running the query with `context` for `EXPLAIN ANALYZE`, which only
+/// explains queries, since the catalog is not changed.
+pub fn explain(
+    catalog: &Catalog,
+    search_path: &SearchPath,
+    context: &FunctionContext,
+    statement: &ExplainStatement,
+) -> Result<Vec<String>, ExplainError> {
+    // A statement changing rows is shown as the query computing what it writes, below an
+    // operator for the change itself
+    let (modification, mut query) = match &*statement.statement {
+        Statement::Select(select) => (None, bind_select(catalog, search_path, select)?),
+        modifying => {
+            let name = match modifying {
+                Statement::Insert(_) => "Insert",
+                Statement::Update(_) => "Update",
+                Statement::Delete(_) => "Delete",
+                _ => return Err(ExplainError::NotExplainable),
+            };
+
+            if statement.analyze {
+                return Err(ExplainError::AnalyzeModification(name.to_uppercase()));
+            }
+
+            let (table, query) = modified_rows(catalog, search_path, modifying)?;
+            (Some((name, table)), query)
+        }
+    };
+
+    let (_, mut types) = check_query_types(catalog, &query)?;
+    fold_select(catalog.functions(), &mut query.select);
+    let plan = plan_query(catalog.functions(), &mut query);
+    let mut node = PlanNode::from_plan(catalog, &query, &plan);
+
+    if let Some((name, table)) = modification {
+        node =
+            PlanNode::new(name, Some(relation_detail(&table, &None)), Vec::new()).with_child(node);
+    }
+
+    // The rows are computed and thrown away, only how they were computed is shown
+    let execution_time = match statement.analyze {
+        true => {
+            let start = Instant::now();
+            let mut operator = compile_plan(catalog, &mut types, context, &plan)?;
+            operator.execute(context)?;
+            node.analyze(&operator);
+
+            Some(start.elapsed().as_secs_f64() * 1000.0)
+        }
+        false => None,
+    };
+
+    Ok(match statement.format {
+        ExplainFormat::Text => {
+            let mut lines = node.to_text(statement.verbose);
+
+            if let Some(time) = execution_time {
+                lines.push(format!("Execution Time: {:.3} ms", time));
+            }
+
+            lines
+        }
+        ExplainFormat::Json => {
+            let mut explained = json!({ "Plan": node.to_json(statement.verbose) });
+
+            if let Some(time) = execution_time {
+                explained["Execution Time"] = json!(round_milliseconds(time));
+            }
+
+            vec![serde_json::to_string_pretty(&json!([explained])).unwrap()]
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use drown_common::sql::value::Value as SqlValue;
+
+    use super::*;
+    use crate::Server;
+
+    fn server() -> Server {
+        let mut server = Server::default();
+        server
+            .execute(
+                "CREATE TABLE users (id INT, team INT, score INT); \
+                 CREATE TABLE teams (id INT, title TEXT); \
+                 CREATE VIEW good AS SELECT id, score FROM users WHERE score > 10; \
+                 INSERT INTO users VALUES (1, 1, 5), (2, 1, 20), (3, 2, 30); \
+                 INSERT INTO teams VALUES (1, 'a'), (2, 'b')",
+            )
+            .unwrap();
+        server
+    }
+
+    fn explain(server: &mut Server, sql: &str) -> Result<Vec<String>, String> {
+        let result = server.execute(sql).map_err(|err| {
+            err.error
+                .trim_start_matches("Failed to execute query: ")
+                .to_string()
+        })?;
+        assert_eq!(result.schema, ["QUERY PLAN"]);
+
+        Ok(result
+            .data
+            .into_iter()
+            .map(|row| match &row[..] {
+                [SqlValue::Text(line)] => line.clone(),
+                row => panic!("{row:?} is not a line of a plan"),
+            })
+            .collect())
+    }
+
+    #[test]
+    fn text() {
+        let mut server = server();
+
+        assert_eq!(
+            explain(
+                &mut server,
+                "EXPLAIN SELECT title, count(*) FROM users JOIN teams ON users.team = teams.id \
+                 WHERE score > 1 GROUP BY title ORDER BY title LIMIT 5"
+            )
+            .unwrap(),
+            [
+                "Limit: limit=5",
+                "  ->  Sort: teams.title",
+                "        ->  Project: teams.title, count(*)",
+                "              ->  Aggregate: count(*) group by teams.title",
+                "                    ->  Filter: users.score > 1",
+                "                          ->  Join: Inner on users.team = teams.id",
+                "                                ->  Scan: users",
+                "                                ->  Scan: teams",
+            ]
+        );
+
+        assert_eq!(
+            explain(&mut server, "EXPLAIN VERBOSE SELECT id FROM good").unwrap(),
+            [
+                "Project: good.id",
+                "  Output: good.id",
+                "  ->  View Scan: good",
+                "        Output: good.id, good.score",
+                "        Definition: SELECT id, score FROM users WHERE score > 10",
+                "        ->  Project: users.id, users.score",
+                "              Output: users.id, users.score",
+                "              ->  Filter: users.score > 10",
+                "                    Output: users.id, users.team, users.score",
+                "                    ->  Scan: users",
+                "                          Output: users.id, users.team, users.score",
+            ]
+        );
+    }
+
+    #[test]
+    fn json() {
+        let mut server = server();
+
+        let plan = explain(
+            &mut server,
+            "EXPLAIN (FORMAT JSON) SELECT id FROM users WHERE id = 2",
+        )
+        .unwrap();
+        let plan: Value = serde_json::from_str(&plan[0]).unwrap();
+
+        assert_eq!(
+            plan,
+            json!([{
+                "Plan": {
+                    "Node Type": "Project",
+                    "Detail": "users.id",
+                    "Plans": [{
+                        "Node Type": "Filter",
+                        "Detail": "users.id = 2",
+                        "Plans": [{ "Node Type": "Scan", "Detail": "users" }],
+                    }],
+                },
+            }])
+        );
+
+        // Options in parentheses can come in any order
+        let plan = explain(
+            &mut server,
+            "EXPLAIN (FORMAT JSON, ANALYZE, VERBOSE FALSE) SELECT id FROM users",
+        )
+        .unwrap();
+        let plan: Value = serde_json::from_str(&plan[0]).unwrap();
+
+        assert!(plan[0]["Execution Time"].is_number());
+        assert_eq!(plan[0]["Plan"]["Actual Rows"], 3);
+        assert_eq!(plan[0]["Plan"]["Actual Loops"], 1);
+        assert_eq!(plan[0]["Plan"].get("Output"), None);
+    }
+
+    #[test]
+    fn analyze() {
+        let mut server = server();
+
+        let plan = explain(
+            &mut server,
+            "EXPLAIN ANALYZE SELECT users.id, x FROM users, \
+             LATERAL generate_series(1, users.team) AS x WHERE score > 10",
+        )
+        .unwrap();
+
+        let counts = plan
+            .iter()
+            .map(|line| {
+                let (operator, stats) = line.split_once("  (actual ").unwrap_or((line, ""));
+                let rows = stats.split(' ').take(2).collect::<Vec<_>>().join(" ");
+                format!("{} {}", operator.trim_start_matches([' ', '-', '>']), rows)
+            })
+            .collect::<Vec<_>>();
+        let (execution_time, counts) = counts.split_last().unwrap();
+
+        assert!(execution_time.starts_with("Execution Time: "));
+
+        assert_eq!(
+            counts,
+            [
+                "Project: users.id, x.x rows=3 loops=1",
+                "Filter: users.score > 10 rows=3 loops=1",
+                "Join: Lateral Cross rows=4 loops=1",
+                "Scan: users rows=3 loops=1",
+                // Rows are averaged over the loops, which ran for each row of users
+                "Function Scan: generate_series(1, users.team) AS x rows=1 loops=3",
+            ]
+        );
+    }
+
+    #[test]
+    fn limits() {
+        let mut server = server();
+
+        // Operators below a LIMIT stop once they produced the rows it reads
+        let plan = explain(
+            &mut server,
+            "EXPLAIN (ANALYZE, FORMAT JSON) SELECT id FROM users LIMIT 1 OFFSET 1",
+        )
+        .unwrap();
+        let plan: Value = serde_json::from_str(&plan[0]).unwrap();
+        let project = &plan[0]["Plan"]["Plans"][0];
+
+        assert_eq!(plan[0]["Plan"]["Actual Rows"], 1);
+        assert_eq!(project["Actual Rows"], 2);
+        assert_eq!(project["Plans"][0]["Actual Rows"], 2);
+
+        // Sorting needs every row
+        let plan = explain(
+            &mut server,
+            "EXPLAIN (ANALYZE, FORMAT JSON) SELECT id FROM users ORDER BY id LIMIT 1",
+        )
+        .unwrap();
+        let plan: Value = serde_json::from_str(&plan[0]).unwrap();
+
+        assert_eq!(plan[0]["Plan"]["Actual Rows"], 1);
+        assert_eq!(plan[0]["Plan"]["Plans"][0]["Actual Rows"], 3);
+    }
+
+    #[test]
+    fn modifications() {
+        let mut server = server();
+
+        assert_eq!(
+            explain(
+                &mut server,
+                "EXPLAIN INSERT INTO users (id) VALUES (4), (5)"
+            )
+            .unwrap(),
+            [
+                "Insert: users",
+                "  ->  Project: column1",
+                "        ->  Values: 2 rows",
+            ]
+        );
+        assert_eq!(
+            explain(
+                &mut server,
+                "EXPLAIN UPDATE good SET score = score + 1 WHERE id > 1"
+            )
+            .unwrap(),
+            [
+                "Update: users",
+                "  ->  Project: good.score + 1",
+                "        ->  Filter: good.id > 1",
+                "              ->  View Scan: good",
+                "                    ->  Project: users.id, users.score",
+                "                          ->  Filter: users.score > 10",
+                "                                ->  Scan: users",
+            ]
+        );
+        assert_eq!(
+            explain(&mut server, "EXPLAIN DELETE FROM teams WHERE id = 1").unwrap(),
+            [
+                "Delete: teams",
+                "  ->  Project: teams.id, teams.title",
+                "        ->  Filter: teams.id = 1",
+                "              ->  Scan: teams",
+            ]
+        );
+
+        // Explaining a statement leaves the data as it was
+        let result = server.execute("SELECT count(*) FROM teams").unwrap();
+        assert_eq!(result.data, [[SqlValue::BigInt(2)]]);
+
+        assert_eq!(
+            explain(&mut server, "EXPLAIN ANALYZE DELETE FROM teams"),
+            Err(
+                "EXPLAIN ANALYZE cannot be used with DELETE, because it would change the data"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            explain(&mut server, "EXPLAIN INSERT INTO users VALUES (1)"),
+            Err("Rows must have a value for each of the 3 columns, not 1 values".to_string())
+        );
+        assert_eq!(
+            explain(&mut server, "EXPLAIN UPDATE users SET points = 1"),
+            Err("Column `points` of table `users` does not exist".to_string())
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use drown_common::sql::{
+    data_type::DataType,
+    datetime::TimeZone,
+    function::{FunctionContext, FunctionKind, FunctionRegistry, Param, Returns},
+    statement::{Literal, TypeName, UnaryOperator},
+    value::Value,
+};
+
+use crate::{
+    binder::{
+        BoundExpr, BoundFrom, BoundJoinCondition, BoundRelation, BoundSelect, RelationSource,
+    },
+    catalog::resolve_type,
+    typecheck::{constant_type, literal_type, resolve_function, ExprType},
+};
+
+/// Replaces calls of deterministic scalar functions whose arguments are all constants with their
+/// results, so that they are computed once while planning rather than for every row.
+///
+/// The query must have been type checked. Calls which fail are left as they are, so that they
+/// only fail if they are ever evaluated, and so are those returning `NULL`, which as a constant
+/// would lose the type the executor coerces the call's value by.
+pub fn fold_select(registry: &FunctionRegistry, select: &mut BoundSelect) {
+    fold_from(registry, &mut select.from);
+
+    for column in &mut select.columns {
+        fold_expr(registry, &mut column.expr);
+    }
+
+    let clauses = select.where_clause.iter_mut().chain(&mut select.group_by);
+    let clauses = clauses.chain(&mut select.having);
+
+    for expr in clauses.chain(select.order_by.iter_mut().map(|item| &mut item.expr)) {
+        fold_expr(registry, expr);
+    }
+
+    for set_operation in &mut select.set_operations {
+        fold_select(registry, &mut set_operation.query);
+    }
+}
+
+fn fold_from(registry: &FunctionRegistry, from: &mut BoundFrom) {
+    match from {
+        BoundFrom::Relation(relation) => fold_relation(registry, relation),
+        BoundFrom::Join {
+            left,
+            right,
+            condition,
+            ..
+        } => {
+            fold_from(registry, left);
+            fold_relation(registry, right);
+
+            if let Some(BoundJoinCondition::On(condition)) = condition {
+                fold_expr(registry, condition);
+            }
+        }
+    }
+}
+
+fn fold_relation(registry: &FunctionRegistry, relation: &mut BoundRelation) {
+    match &mut relation.source {
+        RelationSource::View { query, .. } | RelationSource::Derived(query) => {
+            fold_select(registry, query)
+        }
+        RelationSource::Values(rows) => {
+            for expr in rows.iter_mut().flatten() {
+                fold_expr(registry, expr);
+            }
+        }
+        RelationSource::Function { args, .. } => {
+            for arg in args {
+                fold_expr(registry, arg);
+            }
+        }
+        RelationSource::Table(_) | RelationSource::MaterializedView(_) => {}
+    }
+}
+
+fn fold_expr(registry: &FunctionRegistry, expr: &mut BoundExpr) {
+    match expr {
+        BoundExpr::Literal(_) | BoundExpr::Constant(_) | BoundExpr::Column(_) => {}
+        BoundExpr::Unary { operand, .. }
+        | BoundExpr::IsNull { operand, .. }
+        | BoundExpr::Collate { operand, .. } => fold_expr(registry, operand),
+        BoundExpr::Binary { left, right, .. } => {
+            fold_expr(registry, left);
+            fold_expr(registry, right);
+        }
+        BoundExpr::Cast { operand, data_type } => {
+            fold_expr(registry, operand);
+
+            if let Some(value) = cast(operand, data_type).filter(|value| !value.is_null()) {
+                *expr = BoundExpr::Constant(value);
+            }
+        }
+        BoundExpr::Function {
+            name,
+            args,
+            order_by,
+            filter,
+            ..
+        } => {
+            for arg in args.iter_mut().flatten() {
+                fold_expr(registry, arg);
+            }
+
+            for item in order_by {
+                fold_expr(registry, &mut item.expr);
+            }
+
+            if let Some(filter) = filter {
+                fold_expr(registry, filter);
+            }
+
+            if let Some(value) = args
+                .as_deref()
+                .and_then(|args| evaluate(registry, name, args))
+                .filter(|value| !value.is_null())
+            {
+                *expr = BoundExpr::Constant(value);
+            }
+        }
+    }
+}
+
+/// The result of a call, if the function is a deterministic scalar function and every argument
+/// is a constant.
+fn evaluate(registry: &FunctionRegistry, name: &str, args: &[BoundExpr]) -> Option<Value> {
+    let constants = args.iter().map(constant).collect::<Option<Vec<_>>>()?;
+    let arg_types = constants
+        .iter()
+        .map(|(_, arg_type)| *arg_type)
+        .collect::<Vec<_>>();
+    let (function, return_type) = resolve_function(registry, name, Some(&arg_types)).ok()?;
+
+    let FunctionKind::Scalar {
+        deterministic: true,
+        ..
+    } = function.kind
+    else {
+        return None;
+    };
+
+    let values = constants
+        .into_iter()
+        .enumerate()
+        .map(|(i, (value, arg_type))| {
+            let target = match function.signature.param(i)? {
+                Param::Type(data_type) => data_type,
+                Param::Any if matches!(function.signature.returns, Returns::CommonType) => {
+                    return_type.resolve()
+                }
+                Param::Any => arg_type.resolve(),
+                Param::Field | Param::Unit | Param::Zone | Param::Pattern => DataType::Text,
+            };
+
+            coerce(value, target)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    // Deterministic functions depend on neither the session nor the time
+    let context = FunctionContext {
+        time_zone: TimeZone::UTC,
+        now: 0,
+    };
+
+    function.call(&context, &values).ok()
+}
+
+/// The result of a cast of a constant, unless it is read or written in the time zone of the
+/// session.
+fn cast(operand: &BoundExpr, data_type: &TypeName) -> Option<Value> {
+    let (value, _) = constant(operand)?;
+    let target = resolve_type(data_type).ok()?;
+
+    match (value.data_type(), target) {
+        (DataType::TimestampTz, _) | (_, DataType::TimestampTz) => None,
+        _ => value.cast(target, &TimeZone::UTC).ok(),
+    }
+}
+
+/// The value of an expression which is known while planning, along with its type.
+fn constant(expr: &BoundExpr) -> Option<(Value, ExprType)> {
+    match expr {
+        BoundExpr::Constant(value) => Some((value.clone(), constant_type(value))),
+        BoundExpr::Literal(literal) => {
+            let literal_type = literal_type(literal).ok()?;
+
+            let value = match literal {
+                Literal::Null => Value::Null,
+                Literal::Boolean(value) => Value::Boolean(*value),
+                Literal::String(text) => Value::Text(text.clone()),
+                Literal::Number(number) => Value::parse(number, literal_type.resolve()).ok()?,
+            };
+
+            Some((value, literal_type))
+        }
+        // Negative numbers are parsed as negated literals
+        BoundExpr::Unary {
+            operator: UnaryOperator::Minus,
+            operand,
+        } => match &**operand {
+            BoundExpr::Literal(Literal::Number(number)) => {
+                constant(&BoundExpr::Literal(Literal::Number(format!("-{number}"))))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Coerces a constant to the type of the parameter it is passed to through its text form, or
+/// returns `None` if the result would depend on the session.
+fn coerce(value: Value, target: DataType) -> Option<Value> {
+    match value {
+        Value::Null => Some(Value::Null),
+        value if value.data_type() == target => Some(value),
+        // Times without an offset are read in the time zone of the session
+        _ if target == DataType::TimestampTz => None,
+        Value::Text(text) => Value::parse(&text, target).ok(),
+        value => Value::parse(&value.to_string(), target).ok(),
+    }
+}
@@ -0,0 +1,850 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use drown_common::sql::{
+    collation::{CollatedValue, Collation},
+    function::{FunctionContext, FunctionRegistry},
+    statement::{
+        AliasedFromTable, BinaryOperator, Expr, FromClause, FromTable, JoinType, ResultColumns,
+        SelectStatement,
+    },
+    value::Value,
+};
+use thiserror::Error;
+
+use crate::{
+    binder::{
+        bind_select, is_aggregate, is_aggregate_call, BindError, BoundExpr, BoundQuery,
+        BoundResultColumn,
+    },
+    catalog::{Catalog, MaterializedView, RelationName, Row, SearchPath, View},
+    dml::{execute_source, with_rows, DmlError},
+    executor::arithmetic,
+    typecheck::{check_query_types, TypeError},
+};
+
+/// Aggregates whose state can be updated from the rows inserted into and deleted from a group.
+///
+/// `MIN` and `MAX` are only partially incremental: deleting the current extreme of a group
+/// requires the group to be recomputed from the base tables, which `Aggregation::apply` does.
+const MAINTAINABLE_AGGREGATES: &[&str] = &["count", "sum", "min", "max"];
+
+/// The reason a query cannot be used for an incrementally maintained materialized view.
+#[derive(Debug, Error)]
+pub enum IncrementalError {
+    #[error("`{0}` is not a base table")]
+    NotBaseTable(String),
+    #[error("subqueries in FROM are not supported")]
+    DerivedTable,
+    #[error("functions in FROM are not supported")]
+    TableFunction,
+    #[error("only inner joins are supported")]
+    OuterJoin,
+    #[error("{0} is not supported")]
+    UnsupportedClause(&'static str),
+    #[error("aggregate `{0}` is not supported, only COUNT, SUM, MIN and MAX are")]
+    UnsupportedAggregate(String),
+    #[error("`{0}` must either be a GROUP BY expression or a single aggregate call")]
+    UngroupedExpression(String),
+    #[error("table `{0}` is read more than once")]
+    RepeatedTable(String),
+    #[error("{0}")]
+    BindError(#[from] Box<BindError>),
+    #[error("{0}")]
+    TypeError(#[from] Box<TypeError>),
+}
+
+/// How the changes made to its base tables are applied to an incrementally maintained
+/// materialized view.
+#[derive(Debug, Clone)]
+pub struct Maintenance {
+    /// The base tables the view reads from, each of which its query reads only once
+    pub tables: Vec<RelationName>,
+    /// How the groups of an aggregating view are combined, or `None` if the view does not
+    /// aggregate, in which case its rows are added and removed as they are
+    aggregation: Option<Aggregation>,
+}
+
+/// The groups of an aggregating view, which each keep hidden columns after the visible ones:
+/// the GROUP BY expressions, the number of rows in the group, and the number of values aggregated by
+/// each `SUM`, `MIN` and `MAX`.
+#[derive(Debug, Clone)]
+struct Aggregation {
+    /// How each visible column, followed by each hidden column, is combined
+    columns: Vec<Combine>,
+    /// The number of visible columns, which the hidden columns follow
+    visible: usize,
+    /// The hidden column counting the rows of each group
+    rows: usize,
+    /// Whether there is a GROUP BY clause, without which there is a single group even when no
+    /// rows are left in it
+    grouped: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Combine {
+    /// A GROUP BY expression, which identifies the group under its collation
+    Key(Collation),
+    /// A column which has the same value for every row of the group
+    Grouped,
+    /// `COUNT`, which is added to and subtracted from
+    Count,
+    /// `SUM`, which is NULL while none of the values counted by the column `counted` are left
+    Sum { counted: usize },
+    /// `MIN` or `MAX`, which is replaced by values ordered before it in `ordering`, and has to
+    /// be recomputed when it is deleted while other values are left
+    Extreme {
+        counted: usize,
+        collation: Collation,
+        ordering: Ordering,
+    },
+}
+
+/// The rows a statement removed from a table and added to it.
+#[derive(Debug)]
+pub struct Change {
+    pub table: RelationName,
+    pub deleted: Vec<Row>,
+    pub inserted: Vec<Row>,
+}
+
+impl Maintenance {
+    /// Checks that a materialized view query only uses filters, projections, inner joins and
+    /// grouped `COUNT`/`SUM`/`MIN`/`MAX` aggregates, which can all be maintained from the
+    /// changes made to the base tables.
+    pub fn new(
+        catalog: &Catalog,
+        search_path: &SearchPath,
+        select: &SelectStatement,
+    ) -> Result<Self, IncrementalError> {
+        let registry = catalog.functions();
+
+        check_joins(&select.from_clause)?;
+
+        let mut tables = Vec::new();
+
+        // Changes to a table read twice would have to be joined with themselves
+        for table in select.from_clause.tables() {
+            let relation = check_base_table(catalog, search_path, table)?;
+
+            if tables.contains(&relation) {
+                return Err(IncrementalError::RepeatedTable(relation.name));
+            }
+
+            tables.push(relation);
+        }
+
+        // Whether a row is still there after a deletion would depend on the rows like it
+        if select.distinct {
+            return Err(IncrementalError::UnsupportedClause("SELECT DISTINCT"));
+        }
+
+        if !select.set_operations.is_empty() {
+            return Err(IncrementalError::UnsupportedClause(
+                "UNION, INTERSECT or EXCEPT",
+            ));
+        }
+
+        if select.having_clause.is_some() {
+            return Err(IncrementalError::UnsupportedClause("HAVING"));
+        }
+
+        if select.order_by_clause.is_some() {
+            return Err(IncrementalError::UnsupportedClause("ORDER BY"));
+        }
+
+        if select.offset.is_some() {
+            return Err(IncrementalError::UnsupportedClause("OFFSET"));
+        }
+
+        if select.limit.is_some() {
+            return Err(IncrementalError::UnsupportedClause("LIMIT"));
+        }
+
+        if let ResultColumns::Specific(columns) = &select.columns {
+            let aggregate_call = |expr: &Expr| matches!(expr, Expr::Function { name, .. } if is_aggregate(registry, name));
+
+            let aggregating = select.group_by_clause.is_some()
+                || columns.iter().any(|column| aggregate_call(&column.expr));
+            let grouped = |expr: &Expr| {
+                select
+                    .group_by_clause
+                    .as_ref()
+                    .is_some_and(|clause| clause.groupings.contains(expr))
+            };
+
+            // The binder has checked that columns are grouped by and that aggregates are not
+            // nested
+            for column in columns {
+                match &column.expr {
+                    Expr::Function {
+                        name,
+                        distinct,
+                        order_by,
+                        ..
+                    } if is_aggregate(registry, name) => {
+                        if !MAINTAINABLE_AGGREGATES.contains(&name.to_ascii_lowercase().as_str()) {
+                            return Err(IncrementalError::UnsupportedAggregate(name.clone()));
+                        }
+
+                        // The rows of a group would have to be kept to know whether a value
+                        // deleted from it is still there
+                        if *distinct {
+                            return Err(IncrementalError::UnsupportedClause(
+                                "DISTINCT in aggregates",
+                            ));
+                        }
+
+                        if !order_by.is_empty() {
+                            return Err(IncrementalError::UnsupportedClause(
+                                "ORDER BY in aggregates",
+                            ));
+                        }
+                    }
+                    Expr::Column(_) => {}
+                    expr if aggregating && !grouped(expr) => {
+                        return Err(IncrementalError::UngroupedExpression(expr.to_string()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(Self {
+            tables,
+            aggregation: Aggregation::new(catalog, search_path, select)?,
+        })
+    }
+}
+
+impl Aggregation {
+    /// Describes the groups of `select`, or returns `None` if it does not aggregate.
+    fn new(
+        catalog: &Catalog,
+        search_path: &SearchPath,
+        select: &SelectStatement,
+    ) -> Result<Option<Self>, IncrementalError> {
+        let registry = catalog.functions();
+
+        let mut query = bind_select(catalog, search_path, select).map_err(Box::new)?;
+        let visible = query.select.columns.len();
+        let keys = query.select.group_by.len();
+
+        if !aggregates(registry, &query) {
+            return Ok(None);
+        }
+
+        add_hidden_columns(registry, &mut query);
+        let (_, query_types) = check_query_types(catalog, &query).map_err(Box::new)?;
+
+        let mut columns = Vec::new();
+        let mut counted = visible + keys + 1;
+
+        for column in &query.select.columns[..visible] {
+            let combine = match &column.expr {
+                BoundExpr::Function { name, args, .. } if is_aggregate(registry, name) => {
+                    let combine = match (name.to_ascii_lowercase().as_str(), args.as_deref()) {
+                        ("sum", _) => Combine::Sum { counted },
+                        ("min", Some([arg])) => Combine::Extreme {
+                            counted,
+                            collation: query_types.collation(arg).map_err(Box::new)?,
+                            ordering: Ordering::Less,
+                        },
+                        ("max", Some([arg])) => Combine::Extreme {
+                            counted,
+                            collation: query_types.collation(arg).map_err(Box::new)?,
+                            ordering: Ordering::Greater,
+                        },
+                        _ => Combine::Count,
+                    };
+
+                    if !matches!(combine, Combine::Count) {
+                        counted += 1;
+                    }
+
+                    combine
+                }
+                _ => Combine::Grouped,
+            };
+
+            columns.push(combine);
+        }
+
+        for key in &query.select.group_by {
+            columns.push(Combine::Key(query_types.collation(key).map_err(Box::new)?));
+        }
+
+        // The rows of each group, and the values aggregated by each `SUM`, `MIN` and `MAX`
+        columns.resize(query.select.columns.len(), Combine::Count);
+
+        Ok(Some(Self {
+            columns,
+            visible,
+            rows: visible + keys,
+            grouped: keys > 0,
+        }))
+    }
+
+    /// The values identifying the group of a row.
+    fn key(&self, row: &Row) -> Vec<CollatedValue> {
+        self.columns
+            .iter()
+            .zip(row)
+            .filter_map(|(combine, value)| match combine {
+                Combine::Key(collation) => Some(CollatedValue {
+                    value: value.clone(),
+                    collation: *collation,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Combines the rows of `changes` with those of `group`, which is a stored group with the
+    /// same key.
+    fn add(
+        &self,
+        group: &mut Row,
+        changes: &Row,
+        context: &FunctionContext,
+    ) -> Result<(), DmlError> {
+        for (index, combine) in self.columns.iter().enumerate() {
+            let change = &changes[index];
+
+            match combine {
+                Combine::Key(_) | Combine::Grouped => {}
+                _ if change.is_null() => {}
+                _ if group[index].is_null() => group[index] = change.clone(),
+                Combine::Count | Combine::Sum { .. } => {
+                    group[index] = arithmetic(
+                        BinaryOperator::Plus,
+                        group[index].clone(),
+                        change.clone(),
+                        context,
+                    )?;
+                }
+                Combine::Extreme {
+                    collation,
+                    ordering,
+                    ..
+                } => {
+                    if collation.compare_values(change, &group[index]) == *ordering {
+                        group[index] = change.clone();
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes the rows of `changes` from those of `group`, returning whether the group has to
+    /// be recomputed because its `MIN` or `MAX` was deleted.
+    fn subtract(
+        &self,
+        group: &mut Row,
+        changes: &Row,
+        context: &FunctionContext,
+    ) -> Result<bool, DmlError> {
+        // The counts are subtracted first, as they tell whether any values are left
+        for (index, combine) in self.columns.iter().enumerate() {
+            if let Combine::Count = combine {
+                group[index] = arithmetic(
+                    BinaryOperator::Minus,
+                    group[index].clone(),
+                    changes[index].clone(),
+                    context,
+                )?;
+            }
+        }
+
+        let mut recompute = false;
+
+        for (index, combine) in self.columns.iter().enumerate() {
+            let change = &changes[index];
+
+            match combine {
+                Combine::Sum { counted } | Combine::Extreme { counted, .. }
+                    if group[*counted] == Value::BigInt(0) =>
+                {
+                    group[index] = Value::Null;
+                }
+                _ if change.is_null() => {}
+                Combine::Sum { .. } => {
+                    group[index] = arithmetic(
+                        BinaryOperator::Minus,
+                        group[index].clone(),
+                        change.clone(),
+                        context,
+                    )?;
+                }
+                Combine::Extreme { collation, .. } => {
+                    recompute |= collation.compare_values(change, &group[index]) == Ordering::Equal;
+                }
+                Combine::Key(_) | Combine::Grouped | Combine::Count => {}
+            }
+        }
+
+        Ok(recompute)
+    }
+
+    /// Applies the changes to the groups of a view, returning its new rows and state.
+    fn apply(
+        &self,
+        catalog: &Catalog,
+        view: &MaterializedView,
+        deleted: Vec<Row>,
+        inserted: Vec<Row>,
+        context: &FunctionContext,
+    ) -> Result<(Vec<Row>, Vec<Row>), DmlError> {
+        let mut groups = view
+            .rows
+            .iter()
+            .zip(&view.state)
+            .map(|(row, state)| Some(row.iter().chain(state).cloned().collect::<Row>()))
+            .collect::<Vec<_>>();
+
+        let mut indices = groups
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(index, group)| (self.key(group), index))
+            .collect::<HashMap<_, _>>();
+
+        let mut recomputed = HashSet::new();
+
+        for changes in deleted {
+            let key = self.key(&changes);
+
+            let Some(&index) = indices.get(&key) else {
+                recomputed.insert(key);
+                continue;
+            };
+
+            let Some(group) = &mut groups[index] else {
+                recomputed.insert(key);
+                continue;
+            };
+
+            if self.subtract(group, &changes, context)? {
+                recomputed.insert(key);
+            } else if self.grouped && group[self.rows] == Value::BigInt(0) {
+                groups[index] = None;
+            }
+        }
+
+        for changes in inserted {
+            let key = self.key(&changes);
+
+            match indices.get(&key) {
+                Some(&index) => match &mut groups[index] {
+                    Some(group) => self.add(group, &changes, context)?,
+                    None => groups[index] = Some(changes),
+                },
+                None => {
+                    indices.insert(key, groups.len());
+                    groups.push(Some(changes));
+                }
+            }
+        }
+
+        if !recomputed.is_empty() {
+            for key in &recomputed {
+                if let Some(&index) = indices.get(key) {
+                    groups[index] = None;
+                }
+            }
+
+            // The table has already been changed, so the groups are read from it as they are
+            for group in run(catalog, &view.definition, true, context)? {
+                let key = self.key(&group);
+
+                if !recomputed.contains(&key) {
+                    continue;
+                }
+
+                match indices.get(&key) {
+                    Some(&index) => groups[index] = Some(group),
+                    None => {
+                        indices.insert(key, groups.len());
+                        groups.push(Some(group));
+                    }
+                }
+            }
+        }
+
+        Ok(groups
+            .into_iter()
+            .flatten()
+            .map(|mut row| {
+                let state = row.split_off(self.visible);
+                (row, state)
+            })
+            .unzip())
+    }
+}
+
+/// Runs the query of a materialized view over its base tables as they are, replacing its stored
+/// rows, or leaving them as they were if the query fails.
+pub fn populate(
+    catalog: &mut Catalog,
+    name: &RelationName,
+    context: &FunctionContext,
+) -> Result<(), DmlError> {
+    let view = catalog.materialized_view(name).unwrap();
+    let aggregation = view
+        .maintenance
+        .as_ref()
+        .and_then(|maintenance| maintenance.aggregation.as_ref());
+
+    let mut rows = run(catalog, &view.definition, aggregation.is_some(), context)?;
+    let state = match aggregation {
+        Some(aggregation) => rows
+            .iter_mut()
+            .map(|row| row.split_off(aggregation.visible))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let view = catalog.materialized_view_mut(name)?;
+    view.rows = Arc::new(rows);
+    view.state = state;
+    view.populated = true;
+
+    Ok(())
+}
+
+/// Applies a change made to a table to the populated materialized views maintained from it,
+/// leaving them all as they were if it cannot be applied to any of them.
+pub fn maintain(
+    catalog: &mut Catalog,
+    context: &FunctionContext,
+    change: Change,
+) -> Result<(), DmlError> {
+    let names = catalog.maintained_views(&change.table);
+
+    if names.is_empty() {
+        return Ok(());
+    }
+
+    // The changes to each view are the results of its query while the changed rows stand in for
+    // the rows of the table, as it reads no other table which changed
+    let deleted = with_rows(catalog, &change.table, change.deleted, |catalog| {
+        run_views(catalog, &names, context)
+    })?;
+    let inserted = with_rows(catalog, &change.table, change.inserted, |catalog| {
+        run_views(catalog, &names, context)
+    })?;
+
+    let mut results = Vec::new();
+
+    for ((name, deleted), inserted) in names.iter().zip(deleted).zip(inserted) {
+        let view = catalog.materialized_view(name).unwrap();
+
+        let result = match view
+            .maintenance
+            .as_ref()
+            .and_then(|maintenance| maintenance.aggregation.as_ref())
+        {
+            Some(aggregation) => aggregation.apply(catalog, view, deleted, inserted, context)?,
+            None => {
+                let mut rows = view.rows.to_vec();
+
+                for row in deleted {
+                    if let Some(index) = rows.iter().position(|stored| *stored == row) {
+                        rows.remove(index);
+                    }
+                }
+
+                rows.extend(inserted);
+                (rows, Vec::new())
+            }
+        };
+
+        results.push(result);
+    }
+
+    // Readers still holding the old rows keep them
+    for (name, (rows, state)) in names.iter().zip(results) {
+        let view = catalog.materialized_view_mut(name)?;
+        view.rows = Arc::new(rows);
+        view.state = state;
+    }
+
+    Ok(())
+}
+
+/// Runs the query of each named materialized view, with its hidden columns if it aggregates.
+fn run_views(
+    catalog: &Catalog,
+    names: &[RelationName],
+    context: &FunctionContext,
+) -> Result<Vec<Vec<Row>>, DmlError> {
+    names
+        .iter()
+        .map(|name| {
+            let view = catalog.materialized_view(name).unwrap();
+            let aggregating = view
+                .maintenance
+                .as_ref()
+                .is_some_and(|maintenance| maintenance.aggregation.is_some());
+
+            run(catalog, &view.definition, aggregating, context)
+        })
+        .collect()
+}
+
+/// Runs the query of a view, adding the hidden columns of its groups if `aggregating` is set.
+fn run(
+    catalog: &Catalog,
+    definition: &View,
+    aggregating: bool,
+    context: &FunctionContext,
+) -> Result<Vec<Row>, DmlError> {
+    let mut query = bind_select(catalog, &definition.search_path, definition.select())?;
+
+    if aggregating {
+        add_hidden_columns(catalog.functions(), &mut query);
+    }
+
+    let (_, mut query_types) = check_query_types(catalog, &query)?;
+
+    execute_source(catalog, query, &mut query_types, context)
+}
+
+/// Whether a query has groups, either because it has a GROUP BY clause or because it calls
+/// aggregates.
+fn aggregates(registry: &FunctionRegistry, query: &BoundQuery) -> bool {
+    !query.select.group_by.is_empty()
+        || query
+            .select
+            .columns
+            .iter()
+            .any(|column| is_aggregate_call(registry, &column.expr))
+}
+
+/// Adds the hidden columns kept for each group after the visible columns of `query`.
+fn add_hidden_columns(registry: &FunctionRegistry, query: &mut BoundQuery) {
+    let count =
+        |args: Option<Vec<BoundExpr>>, filter: Option<Box<BoundExpr>>| BoundExpr::Function {
+            name: "count".to_string(),
+            args,
+            distinct: false,
+            order_by: Vec::new(),
+            filter,
+        };
+
+    let counted = query
+        .select
+        .columns
+        .iter()
+        .filter_map(|column| match &column.expr {
+            BoundExpr::Function {
+                name, args, filter, ..
+            } if is_aggregate(registry, name)
+                && matches!(name.to_ascii_lowercase().as_str(), "sum" | "min" | "max") =>
+            {
+                Some(count(args.clone(), filter.clone()))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let hidden = query
+        .select
+        .group_by
+        .iter()
+        .cloned()
+        .chain([count(None, None)])
+        .chain(counted)
+        .map(|expr| BoundResultColumn {
+            expr,
+            name: String::new(),
+        })
+        .collect::<Vec<_>>();
+
+    query.select.columns.extend(hidden);
+}
+
+/// Checks that every join is an inner join.
+fn check_joins(from_clause: &FromClause) -> Result<(), IncrementalError> {
+    let FromClause::Join {
+        left, join_type, ..
+    } = from_clause
+    else {
+        return Ok(());
+    };
+
+    if !matches!(join_type, JoinType::Inner | JoinType::Cross) {
+        return Err(IncrementalError::OuterJoin);
+    }
+
+    check_joins(left)
+}
+
+/// Checks that a relation in FROM is a base table, returning its resolved name.
+fn check_base_table(
+    catalog: &Catalog,
+    search_path: &SearchPath,
+    table: &AliasedFromTable,
+) -> Result<RelationName, IncrementalError> {
+    let name = match &table.table {
+        FromTable::TableName { name } => name,
+        FromTable::DerivedTable { .. } => return Err(IncrementalError::DerivedTable),
+        FromTable::Function { .. } => return Err(IncrementalError::TableFunction),
+        FromTable::Values { .. } => return Err(IncrementalError::UnsupportedClause("VALUES")),
+    };
+
+    let relation = catalog.resolve(name, search_path);
+
+    if catalog.view(&relation).is_some() || catalog.materialized_view(&relation).is_some() {
+        return Err(IncrementalError::NotBaseTable(name.to_string()));
+    }
+
+    Ok(relation)
+}
+
+#[cfg(test)]
+mod tests {
+    use drown_common::sql::value::Value;
+
+    use crate::Server;
+
+    const GROUPS: &str = "SELECT g, count(*) AS c, count(n) AS counted, sum(n) AS total, \
+                          min(n) AS low, max(n) AS high FROM t GROUP BY g";
+
+    fn query(server: &mut Server, sql: &str) -> Vec<Vec<Value>> {
+        server
+            .execute(&format!("USE d; SET search_path TO s; {sql}"))
+            .unwrap()
+            .data
+    }
+
+    /// Checks that the stored rows of `view` are the results of `select` as the tables are now.
+    fn assert_maintained(server: &mut Server, view: &str, select: &str) {
+        let mut stored = query(server, &format!("SELECT * FROM {view}"));
+        let mut computed = query(server, select);
+        stored.sort();
+        computed.sort();
+        assert_eq!(stored, computed);
+    }
+
+    fn server() -> Server {
+        let mut server = Server::default();
+        server
+            .execute(
+                "CREATE DATABASE d; USE d; CREATE SCHEMA s; SET search_path TO s; \
+                 CREATE TABLE t (g TEXT, n INT); CREATE TABLE u (g TEXT, label TEXT); \
+                 INSERT INTO t VALUES ('a', 1), ('a', 5), ('b', 3), ('b', NULL); \
+                 INSERT INTO u VALUES ('a', 'Alpha'), ('b', 'Beta')",
+            )
+            .unwrap();
+        server
+    }
+
+    #[test]
+    fn groups() {
+        let mut server = server();
+        query(
+            &mut server,
+            &format!("CREATE MATERIALIZED VIEW v WITH (incremental) AS {GROUPS}"),
+        );
+
+        let statements = [
+            "INSERT INTO t VALUES ('a', 0), ('c', 7), ('c', NULL)",
+            // Deletes the minimum of `a`, which is recomputed from the remaining rows
+            "DELETE FROM t WHERE n = 0",
+            "UPDATE t SET n = n * 10 WHERE g = 'a'",
+            "DELETE FROM t WHERE n = 7",
+            "DELETE FROM t WHERE g = 'b'",
+            "UPDATE t SET g = 'b' WHERE n = 50",
+        ];
+
+        for statement in statements {
+            query(&mut server, statement);
+            assert_maintained(&mut server, "v", GROUPS);
+        }
+    }
+
+    #[test]
+    fn single_group() {
+        let mut server = server();
+        let select = "SELECT count(*) AS c, sum(n) AS total, max(n) AS high FROM t";
+        query(
+            &mut server,
+            &format!("CREATE MATERIALIZED VIEW v WITH (incremental) AS {select}"),
+        );
+
+        query(&mut server, "DELETE FROM t WHERE n = 5");
+        assert_maintained(&mut server, "v", select);
+
+        // The group stays when its rows are all deleted, with no values to aggregate
+        query(&mut server, "DELETE FROM t");
+        assert_eq!(
+            query(&mut server, "SELECT * FROM v"),
+            [[Value::BigInt(0), Value::Null, Value::Null]]
+        );
+    }
+
+    #[test]
+    fn grouping_expressions() {
+        let mut server = server();
+        let select =
+            "SELECT upper(g) AS k, n % 2 AS parity, count(*) AS c, sum(n) AS total FROM t \
+             GROUP BY upper(g), n % 2";
+        query(
+            &mut server,
+            &format!("CREATE MATERIALIZED VIEW v WITH (incremental) AS {select}"),
+        );
+
+        query(&mut server, "INSERT INTO t VALUES ('A', 2), ('c', 4)");
+        assert_maintained(&mut server, "v", select);
+
+        query(&mut server, "DELETE FROM t WHERE g = 'a'");
+        assert_maintained(&mut server, "v", select);
+    }
+
+    #[test]
+    fn joins() {
+        let mut server = server();
+        let select = "SELECT t.g, u.label, t.n FROM t JOIN u ON t.g = u.g WHERE t.n > 1";
+        query(
+            &mut server,
+            &format!("CREATE MATERIALIZED VIEW v WITH (incremental) AS {select}"),
+        );
+
+        let statements = [
+            "INSERT INTO t VALUES ('a', 5), ('b', 4), ('c', 9)",
+            "INSERT INTO u VALUES ('c', 'Gamma')",
+            "UPDATE u SET label = 'Bravo' WHERE g = 'b'",
+            "DELETE FROM t WHERE n = 5",
+        ];
+
+        for statement in statements {
+            query(&mut server, statement);
+            assert_maintained(&mut server, "v", select);
+        }
+    }
+
+    #[test]
+    fn failed_maintenance_undoes_the_change() {
+        let mut server = server();
+        query(
+            &mut server,
+            "CREATE MATERIALIZED VIEW v WITH (incremental) AS SELECT n FROM t WHERE 10 / n > 1",
+        );
+
+        let err = server
+            .execute("USE d; SET search_path TO s; INSERT INTO t VALUES ('c', 0)")
+            .unwrap_err();
+        assert!(err.error.contains("Division by zero"), "{}", err.error);
+
+        assert_eq!(
+            query(&mut server, "SELECT count(*) FROM t"),
+            [[Value::BigInt(4)]]
+        );
+    }
+}
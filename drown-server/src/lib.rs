@@ -0,0 +1,403 @@
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
+
+use drown_common::{
+    proto::{
+        decode::DecodedPacket,
+        encode::EncodedPacket,
+        packet::{C2SPacket, S2CPacket, S2CQuerySuccessResponsePacket, S2CQueryErrorResponsePacket},
+    },
+    sql::{function::FunctionRegistry, parser::StatementParser, statement::Statement},
+};
+use catalog::Catalog;
+use execute::execute_statement;
+use futures::{SinkExt, TryStreamExt};
+use once_cell::sync::Lazy;
+use session::Session;
+use tokio::{
+    net::{tcp::OwnedWriteHalf, TcpListener, TcpStream, ToSocketAddrs},
+    sync::Mutex,
+};
+use tokio_util::codec::{FramedRead, FramedWrite, LengthDelimitedCodec};
+
+mod binder;
+mod catalog;
+mod dml;
+mod execute;
+mod executor;
+mod explain;
+mod fold;
+mod incremental;
+mod plan;
+mod session;
+mod typecheck;
+mod wasm;
+
+#[derive(Debug)]
+struct ClientConnection {
+    write_stream: FramedWrite<OwnedWriteHalf, LengthDelimitedCodec>,
+    keep_alive_state: KeepAliveState,
+    last_message_id: u32,
+    session: Session,
+    tasks: Vec<tokio::task::JoinHandle<()>>,
+}
+
+impl Drop for ClientConnection {
+    fn drop(&mut self) {
+        for task in self.tasks.drain(..) {
+            task.abort();
+        }
+    }
+}
+
+impl ClientConnection {
+    fn inc_message_id(&mut self) -> u32 {
+        self.last_message_id += 1;
+        self.last_message_id
+    }
+
+    async fn send_packet(&mut self, packet: EncodedPacket<S2CPacket>) {
+        self.write_stream
+            .send(packet.to_bytes())
+            .await
+            .expect("Failed to send keep-alive packet")
+    }
+}
+
+#[derive(Debug)]
+enum KeepAliveState {
+    WaitingForRequest {
+        response_received_at: std::time::Instant,
+    },
+    WaitingForResponse {
+        request_message_id: u32,
+        request_sent_at: std::time::Instant,
+    },
+}
+
+impl Default for KeepAliveState {
+    fn default() -> Self {
+        Self::WaitingForRequest {
+            response_received_at: std::time::Instant::now(),
+        }
+    }
+}
+
+/// Executes each statement in order, stopping at the first failure.
+///
+/// The result of the last statement is the one returned to the client.
+fn execute_statements(
+    catalog: &mut Catalog,
+    session: &mut Session,
+    statements: &[Statement],
+) -> Result<S2CQuerySuccessResponsePacket, S2CQueryErrorResponsePacket> {
+    let mut result = S2CQuerySuccessResponsePacket {
+        schema: Vec::new(),
+        types: Vec::new(),
+        data: Vec::new(),
+        command: None,
+    };
+
+    for statement in statements {
+        result = execute_statement(catalog, session, statement).map_err(|err| S2CQueryErrorResponsePacket {
+            error: format!("Failed to execute query: {}", err),
+            suggestion: err.suggestion().map(ToString::to_string),
+        })?;
+    }
+
+    Ok(result)
+}
+
+static CLIENT_CONNECTIONS: Lazy<Arc<Mutex<HashMap<SocketAddr, ClientConnection>>>> =
+    Lazy::new(Default::default);
+
+async fn handle_socket_connection(
+    socket: TcpStream,
+    catalog: Arc<Mutex<Catalog>>,
+) -> std::io::Result<()> {
+    let addr = socket.peer_addr()?;
+    let (read, write) = socket.into_split();
+
+    // Create a length-delimited reader and writer
+    let mut delimited_reader = FramedRead::new(read, LengthDelimitedCodec::new());
+    let delimited_writer = FramedWrite::new(write, LengthDelimitedCodec::new());
+
+    // Obtain a lock on the client connections map
+    let mut client_connections = CLIENT_CONNECTIONS.lock().await;
+
+    // Create a new client connection
+    let mut client_connection = ClientConnection {
+        write_stream: delimited_writer,
+        keep_alive_state: KeepAliveState::default(),
+        last_message_id: 0,
+        session: Session::default(),
+        tasks: Vec::new(),
+    };
+
+    // Spawn a task for periodically sending keep-alive packets
+    client_connection.tasks.push(tokio::spawn(async move {
+        const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(60);
+        const KEEP_ALIVE_RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+
+        loop {
+            // Sleep first to allow dropping the mutex lock immediately after the loop iteration
+            tokio::time::sleep(Duration::from_secs(1)).await;
+
+            let mut client_connections = CLIENT_CONNECTIONS.lock().await;
+
+            let client_connection = client_connections.get_mut(&addr).unwrap();
+
+            match client_connection.keep_alive_state {
+                KeepAliveState::WaitingForRequest {
+                    response_received_at,
+                } => {
+                    // Do nothing if the last response was received less than 60 seconds ago
+                    if response_received_at.elapsed() < KEEP_ALIVE_INTERVAL {
+                        continue;
+                    }
+
+                    // Send a keep-alive request
+                    let req = EncodedPacket::from_payload(S2CPacket::KeepAliveRequest)
+                        .with_id(client_connection.inc_message_id());
+                    client_connection.send_packet(req).await;
+
+                    // Update the keep-alive state
+                    client_connection.keep_alive_state = KeepAliveState::WaitingForResponse {
+                        request_message_id: client_connection.last_message_id,
+                        request_sent_at: std::time::Instant::now(),
+                    };
+                }
+                KeepAliveState::WaitingForResponse {
+                    request_sent_at, ..
+                } => {
+                    // If the last request was sent less than 30 seconds ago, wait for a response
+                    if request_sent_at.elapsed() < KEEP_ALIVE_RESPONSE_TIMEOUT {
+                        continue;
+                    }
+
+                    // If the last request was sent more than 30 seconds ago, disconnect the client
+                    println!("Client {:?} timed out", addr);
+                    client_connections.remove(&addr);
+                }
+            }
+        }
+    }));
+
+    // Spawn a task for handling incoming packets
+    client_connection.tasks.push(tokio::spawn(async move {
+        while let Some(msg) = delimited_reader.try_next().await.unwrap() {
+            let packet = DecodedPacket::<C2SPacket>::from_bytes(msg).unwrap();
+
+            let mut client_connections = CLIENT_CONNECTIONS.lock().await;
+            let client_connection = client_connections.get_mut(&addr).unwrap();
+
+            match packet.payload() {
+                C2SPacket::KeepAliveResponse => match client_connection.keep_alive_state {
+                    KeepAliveState::WaitingForResponse {
+                        request_message_id, ..
+                    } => {
+                        if packet.response_to() == Some(request_message_id) {
+                            client_connection.keep_alive_state = KeepAliveState::WaitingForRequest {
+                                response_received_at: std::time::Instant::now(),
+                            };
+
+                            println!("Got KeepAliveResponse from {:?}", addr);
+                        } else {
+                            eprintln!(
+                                "Got KeepAliveResponse with unexpected response_to: {:?}",
+                                packet
+                            )
+                        }
+                    }
+                    KeepAliveState::WaitingForRequest { .. } => {
+                        eprintln!("Got unexpected KeepAliveResponse packet: {:?}", packet)
+                    }
+                },
+                C2SPacket::QueryRequest(query_request) => {
+                    let result = match StatementParser::parse(&query_request.query) {
+                        Ok(statements) => {
+                            println!("Got query request: {:?}", statements);
+
+                            execute_statements(
+                                &mut *catalog.lock().await,
+                                &mut client_connection.session,
+                                &statements,
+                            )
+                        }
+                        Err(err) => Err(S2CQueryErrorResponsePacket {
+                            error: format!("Failed to parse query: {}", err),
+                            suggestion: err.suggestion().map(ToString::to_string),
+                        }),
+                    };
+
+                    let res = EncodedPacket::from_payload(S2CPacket::QueryResponse(result))
+                        .with_id(client_connection.inc_message_id())
+                        .with_response_to(packet.message_id());
+
+                    client_connection.send_packet(res).await;
+                }
+            }
+        }
+    }));
+
+    // Insert the client connection into the map
+    client_connections.insert(addr, client_connection);
+
+    Ok(())
+}
+
+/// A database server, which can be embedded in an application to call functions of the
+/// application in queries.
+///
+/// Functions are registered with [`FunctionRegistry::register_scalar`],
+/// [`FunctionRegistry::register_aggregate`] and [`FunctionRegistry::register_table`] before the
+/// server starts listening. They are type checked like the built-in ones, and scalar functions
+/// registered as deterministic are evaluated while planning when their arguments are constants.
+#[derive(Debug, Default)]
+pub struct Server {
+    catalog: Catalog,
+}
+
+impl Server {
+    /// The functions queries can call, which the application registers its own with.
+    pub fn functions_mut(&mut self) -> &mut FunctionRegistry {
+        self.catalog.functions_mut()
+    }
+
+    /// Executes SQL in a session of its own, as a client sending it would, returning the result
+    /// of its last statement.
+    pub fn execute(
+        &mut self,
+        sql: &str,
+    ) -> Result<S2CQuerySuccessResponsePacket, S2CQueryErrorResponsePacket> {
+        let statements = StatementParser::parse(sql).map_err(|err| S2CQueryErrorResponsePacket {
+            error: format!("Failed to parse query: {}", err),
+            suggestion: err.suggestion().map(ToString::to_string),
+        })?;
+
+        execute_statements(&mut self.catalog, &mut Session::default(), &statements)
+    }
+
+    /// Accepts client connections on `address` until accepting one fails.
+    pub async fn listen(self, address: impl ToSocketAddrs) -> std::io::Result<()> {
+        let listener = TcpListener::bind(address).await?;
+        let catalog = Arc::new(Mutex::new(self.catalog));
+
+        println!("listening on drown://{:?}", listener.local_addr()?);
+
+        loop {
+            let (socket, _) = listener.accept().await?;
+            handle_socket_connection(socket, catalog.clone()).await?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use drown_common::sql::{
+        data_type::DataType,
+        function::{Param, Returns, ScalarOptions, Signature},
+        value::Value,
+    };
+
+    use super::Server;
+
+    fn server() -> Server {
+        let mut server = Server::default();
+
+        server
+            .functions_mut()
+            .register_scalar(
+                Signature {
+                    name: Cow::Borrowed("double"),
+                    params: Some(Cow::Borrowed(&[Param::Type(DataType::Int)])),
+                    variadic: 0,
+                    returns: Returns::Type(DataType::Int),
+                },
+                ScalarOptions {
+                    strict: true,
+                    deterministic: true,
+                },
+                |_, args| match args[0] {
+                    Value::Int(value) => Ok(Value::Int(value * 2)),
+                    _ => unreachable!(),
+                },
+            )
+            .unwrap();
+
+        server
+    }
+
+    #[test]
+    fn registered_function() {
+        let mut server = server();
+
+        let result = server
+            .execute("SELECT double(value) FROM generate_series(1, 3) AS value")
+            .unwrap();
+        assert_eq!(result.types, [DataType::Int]);
+        assert_eq!(
+            result.data,
+            [[Value::Int(2)], [Value::Int(4)], [Value::Int(6)]]
+        );
+
+        let err = server
+            .execute("SELECT double('a') FROM generate_series(1, 1) AS one")
+            .unwrap_err();
+        assert!(err.error.contains("double"), "{}", err.error);
+
+        let result = server
+            .execute("EXPLAIN VERBOSE SELECT double(21) FROM generate_series(1, 1) AS one")
+            .unwrap();
+        assert_eq!(result.data[0], [Value::Text("Project: 42 AS double".to_string())]);
+    }
+
+    #[test]
+    fn non_ascii_text() {
+        let mut server = Server::default();
+
+        server
+            .execute("CREATE TABLE words (s TEXT); INSERT INTO words VALUES ('f'), ('É'), ('e'), ('é'), ('E'), ('Å')")
+            .unwrap();
+
+        let text = |text: &str| [Value::Text(text.to_string())];
+
+        let result = server
+            .execute("SELECT s FROM words ORDER BY s COLLATE unicode")
+            .unwrap();
+        assert_eq!(
+            result.data,
+            [text("Å"), text("e"), text("E"), text("é"), text("É"), text("f")]
+        );
+
+        let result = server
+            .execute("SELECT upper(s) FROM words WHERE s COLLATE nocase = 'é'")
+            .unwrap();
+        assert_eq!(result.data, [text("É"), text("É")]);
+
+        let result = server
+            .execute("SELECT '{\"名前\": \"Åsa\"}'::JSONB ->> '名前' FROM words WHERE s = 'f'")
+            .unwrap();
+        assert_eq!(result.data, [text("Åsa")]);
+    }
+
+    #[test]
+    fn command_tags() {
+        let mut server = Server::default();
+
+        let result = server
+            .execute("CREATE TABLE t (n INT); INSERT INTO t VALUES (1), (2)")
+            .unwrap();
+        assert_eq!(result.command.as_deref(), Some("INSERT 0 2"));
+        assert!(result.schema.is_empty() && result.types.is_empty() && result.data.is_empty());
+
+        let result = server.execute("DELETE FROM t WHERE n = 1").unwrap();
+        assert_eq!(result.command.as_deref(), Some("DELETE 1"));
+
+        // Rows returned by a statement always have one value per column
+        let result = server.execute("SELECT n FROM t").unwrap();
+        assert_eq!(result.command, None);
+        assert_eq!(result.data, [[Value::Int(2)]]);
+    }
+}
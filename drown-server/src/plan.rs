@@ -0,0 +1,685 @@
+use drown_common::sql::{
+    function::FunctionRegistry,
+    statement::{BinaryOperator, JoinType, SetOperator},
+};
+
+use crate::{
+    binder::{
+        is_aggregate, BoundColumn, BoundExpr, BoundFrom, BoundJoinCondition, BoundOrderByItem,
+        BoundQuery, BoundRelation, BoundSelect, ColumnId, RelationSource,
+    },
+    catalog::RelationName,
+};
+
+/// A tree of relational operators computing the rows of a query, which is what the optimizer
+/// rewrites and the executor runs.
+///
+/// Every operator produces rows of the columns given by `LogicalPlan::output`, which are
+/// identified by the `ColumnId`s of the bound query the plan was made from, so that the
+/// expressions of the operators above refer to them.
+#[derive(Debug)]
+pub enum LogicalPlan {
+    /// Reads every row of a table, or the stored rows of a materialized view
+    Scan {
+        relation: RelationName,
+        materialized: bool,
+        alias: Option<String>,
+        columns: Vec<ColumnId>,
+    },
+    /// Calls a table function, once for every row of the left side of the join it is the right
+    /// side of when its arguments refer to that row
+    FunctionScan {
+        name: String,
+        args: Vec<BoundExpr>,
+        alias: Option<String>,
+        columns: Vec<ColumnId>,
+    },
+    /// Rows given as expressions, as written in `VALUES`
+    Values {
+        rows: Vec<Vec<BoundExpr>>,
+        columns: Vec<ColumnId>,
+    },
+    /// Reads the rows of a view or a subquery in FROM as the columns of the relation it is
+    /// named as, which are its input columns in order
+    Subquery {
+        input: Box<LogicalPlan>,
+        view: Option<RelationName>,
+        alias: Option<String>,
+        columns: Vec<ColumnId>,
+    },
+    /// Keeps the rows for which the predicate is true
+    Filter {
+        input: Box<LogicalPlan>,
+        predicate: BoundExpr,
+    },
+    /// Computes a column from each expression
+    Project {
+        input: Box<LogicalPlan>,
+        exprs: Vec<(BoundExpr, ColumnId)>,
+    },
+    Join {
+        left: Box<LogicalPlan>,
+        right: Box<LogicalPlan>,
+        join_type: JoinType,
+        /// `None` for cross joins, and `USING` written as equalities of its columns
+        condition: Option<BoundExpr>,
+        /// Whether the right side refers to the columns of the left side, so that it has to be
+        /// computed again for every left row
+        lateral: bool,
+    },
+    /// Groups rows by the values of the keys and computes each aggregate call over the rows of
+    /// every group, producing the keys followed by the aggregates
+    ///
+    /// Without keys, all rows form a single group, even when there are none.
+    Aggregate {
+        input: Box<LogicalPlan>,
+        group_by: Vec<(BoundExpr, ColumnId)>,
+        aggregates: Vec<(BoundExpr, ColumnId)>,
+    },
+    Sort {
+        input: Box<LogicalPlan>,
+        keys: Vec<BoundOrderByItem>,
+    },
+    Limit {
+        input: Box<LogicalPlan>,
+        offset: u64,
+        limit: Option<u64>,
+    },
+    /// Removes duplicate rows
+    Distinct { input: Box<LogicalPlan> },
+    /// Combines the rows of two inputs with as many columns, producing them as `columns`
+    SetOp {
+        left: Box<LogicalPlan>,
+        right: Box<LogicalPlan>,
+        operator: SetOperator,
+        /// Whether duplicate rows are kept
+        all: bool,
+        columns: Vec<ColumnId>,
+    },
+}
+
+impl LogicalPlan {
+    /// The columns of the rows the operator produces, in order.
+    pub fn output(&self) -> Vec<ColumnId> {
+        match self {
+            LogicalPlan::Scan { columns, .. }
+            | LogicalPlan::FunctionScan { columns, .. }
+            | LogicalPlan::Values { columns, .. }
+            | LogicalPlan::Subquery { columns, .. }
+            | LogicalPlan::SetOp { columns, .. } => columns.clone(),
+            LogicalPlan::Filter { input, .. }
+            | LogicalPlan::Sort { input, .. }
+            | LogicalPlan::Limit { input, .. }
+            | LogicalPlan::Distinct { input } => input.output(),
+            LogicalPlan::Project { exprs, .. } => exprs.iter().map(|(_, id)| *id).collect(),
+            LogicalPlan::Join { left, right, .. } => {
+                let mut output = left.output();
+                output.extend(right.output());
+                output
+            }
+            LogicalPlan::Aggregate {
+                group_by,
+                aggregates,
+                ..
+            } => group_by
+                .iter()
+                .chain(aggregates)
+                .map(|(_, id)| *id)
+                .collect(),
+        }
+    }
+
+    /// The operators this one reads the rows of.
+    pub fn inputs(&self) -> Vec<&LogicalPlan> {
+        match self {
+            LogicalPlan::Scan { .. }
+            | LogicalPlan::FunctionScan { .. }
+            | LogicalPlan::Values { .. } => Vec::new(),
+            LogicalPlan::Subquery { input, .. }
+            | LogicalPlan::Filter { input, .. }
+            | LogicalPlan::Project { input, .. }
+            | LogicalPlan::Aggregate { input, .. }
+            | LogicalPlan::Sort { input, .. }
+            | LogicalPlan::Limit { input, .. }
+            | LogicalPlan::Distinct { input } => vec![input],
+            LogicalPlan::Join { left, right, .. } | LogicalPlan::SetOp { left, right, .. } => {
+                vec![left, right]
+            }
+        }
+    }
+}
+
+/// Plans a bound and type checked query, adding the columns computed by the plan to the columns
+/// of the query.
+pub fn plan_query(registry: &FunctionRegistry, query: &mut BoundQuery) -> LogicalPlan {
+    let mut planner = Planner {
+        registry,
+        columns: &mut query.columns,
+    };
+
+    planner.plan_select(&query.select)
+}
+
+struct Planner<'a> {
+    registry: &'a FunctionRegistry,
+    columns: &'a mut Vec<BoundColumn>,
+}
+
+impl Planner<'_> {
+    fn plan_select(&mut self, select: &BoundSelect) -> LogicalPlan {
+        let mut plan = match select.set_operations.is_empty() {
+            true => self.plan_select_core(select, select.order_by.clone()),
+            false => {
+                let first = self.plan_select_core(select, Vec::new());
+                sort(
+                    self.plan_set_operations(first, select),
+                    select.order_by.clone(),
+                )
+            }
+        };
+
+        if select.offset.is_some() || select.limit.is_some() {
+            plan = LogicalPlan::Limit {
+                input: Box::new(plan),
+                offset: select.offset.as_ref().map_or(0, |offset| offset.offset),
+                limit: select.limit.as_ref().map(|limit| limit.limit),
+            };
+        }
+
+        plan
+    }
+
+    /// Plans a query up to the rows it combines with others, sorting them by `keys`.
+    fn plan_select_core(
+        &mut self,
+        select: &BoundSelect,
+        mut keys: Vec<BoundOrderByItem>,
+    ) -> LogicalPlan {
+        let mut plan = self.plan_from(&select.from);
+
+        if let Some(predicate) = &select.where_clause {
+            plan = LogicalPlan::Filter {
+                input: Box::new(plan),
+                predicate: predicate.clone(),
+            };
+        }
+
+        let mut exprs = select
+            .columns
+            .iter()
+            .map(|column| (column.expr.clone(), column.name.clone()))
+            .collect::<Vec<_>>();
+        let mut having = select.having.clone();
+
+        let aggregating = !select.group_by.is_empty()
+            || exprs.iter().any(|(expr, _)| self.contains_aggregate(expr))
+            || having
+                .iter()
+                .chain(keys.iter().map(|key| &key.expr))
+                .any(|expr| self.contains_aggregate(expr));
+
+        if aggregating {
+            let computed = exprs.iter_mut().map(|(expr, _)| expr);
+            let computed = computed.chain(&mut having);
+            let computed = computed.chain(keys.iter_mut().map(|key| &mut key.expr));
+
+            plan = self.plan_aggregate(plan, &select.group_by, computed);
+        }
+
+        if let Some(predicate) = having {
+            plan = LogicalPlan::Filter {
+                input: Box::new(plan),
+                predicate,
+            };
+        }
+
+        let exprs = exprs
+            .into_iter()
+            .map(|(expr, name)| (expr, self.add_column(&name)))
+            .collect::<Vec<_>>();
+
+        // Rows are sorted after the projection when it computes every key, which the binder
+        // ensures for SELECT DISTINCT, and before it when some key is not selected
+        let projected = keys
+            .iter()
+            .all(|key| exprs.iter().any(|(expr, _)| *expr == key.expr));
+
+        if !projected {
+            return LogicalPlan::Project {
+                input: Box::new(sort(plan, keys)),
+                exprs,
+            };
+        }
+
+        for key in &mut keys {
+            let (_, id) = exprs.iter().find(|(expr, _)| *expr == key.expr).unwrap();
+            key.expr = BoundExpr::Column(*id);
+        }
+
+        plan = LogicalPlan::Project {
+            input: Box::new(plan),
+            exprs,
+        };
+
+        if select.distinct {
+            plan = LogicalPlan::Distinct {
+                input: Box::new(plan),
+            };
+        }
+
+        sort(plan, keys)
+    }
+
+    /// Combines the rows of `first` with those of the queries of the set operations of
+    /// `select`, producing them as its `set_columns`.
+    fn plan_set_operations(&mut self, first: LogicalPlan, select: &BoundSelect) -> LogicalPlan {
+        // The last combination made is the one producing the rows of the whole query, before it
+        // others produce columns of their own
+        let mut remaining = select.set_operations.len();
+
+        let mut combine = |planner: &mut Self, left, right, operator, all| {
+            remaining -= 1;
+
+            let columns = match remaining {
+                0 => select.set_columns.clone(),
+                _ => select
+                    .set_columns
+                    .iter()
+                    .map(|id| {
+                        let name = planner.columns[id.0].name.clone();
+                        planner.add_column(&name)
+                    })
+                    .collect(),
+            };
+
+            LogicalPlan::SetOp {
+                left: Box::new(left),
+                right: Box::new(right),
+                operator,
+                all,
+                columns,
+            }
+        };
+
+        // INTERSECT binds more tightly than UNION and EXCEPT, so the queries it combines form a
+        // single operand of those
+        let mut operands = vec![first];
+        let mut operators = Vec::new();
+
+        for set_operation in &select.set_operations {
+            let right = self.plan_select(&set_operation.query);
+
+            match set_operation.operator {
+                SetOperator::Intersect => {
+                    let left = operands.pop().unwrap();
+                    let plan =
+                        combine(self, left, right, SetOperator::Intersect, set_operation.all);
+                    operands.push(plan);
+                }
+                operator => {
+                    operands.push(right);
+                    operators.push((operator, set_operation.all));
+                }
+            }
+        }
+
+        let mut operands = operands.into_iter();
+        let mut plan = operands.next().unwrap();
+
+        for (right, (operator, all)) in operands.zip(operators) {
+            plan = combine(self, plan, right, operator, all);
+        }
+
+        plan
+    }
+
+    /// Groups the rows of `input` by `group_by`, rewriting the expressions computed from the
+    /// groups to read the keys and the results of their aggregate calls from its output.
+    fn plan_aggregate<'e>(
+        &mut self,
+        input: LogicalPlan,
+        group_by: &[BoundExpr],
+        computed: impl Iterator<Item = &'e mut BoundExpr>,
+    ) -> LogicalPlan {
+        // Plain columns are passed through as they are, other keys become new columns
+        let group_by = group_by
+            .iter()
+            .map(|key| match key {
+                BoundExpr::Column(id) => (key.clone(), *id),
+                _ => (key.clone(), self.add_column("?column?")),
+            })
+            .collect::<Vec<_>>();
+
+        let mut aggregates = Vec::<(BoundExpr, ColumnId)>::new();
+
+        for expr in computed {
+            *expr = expr.rewrite(&mut |part| {
+                if let Some((_, id)) = group_by.iter().find(|(key, _)| key == part) {
+                    return Some(BoundExpr::Column(*id));
+                }
+
+                match part {
+                    BoundExpr::Function { name, .. } if is_aggregate(self.registry, name) => {
+                        let id = match aggregates.iter().find(|(call, _)| call == part) {
+                            Some((_, id)) => *id,
+                            None => {
+                                let id = self.add_column(name);
+                                aggregates.push((part.clone(), id));
+                                id
+                            }
+                        };
+
+                        Some(BoundExpr::Column(id))
+                    }
+                    _ => None,
+                }
+            });
+        }
+
+        LogicalPlan::Aggregate {
+            input: Box::new(input),
+            group_by,
+            aggregates,
+        }
+    }
+
+    fn plan_from(&mut self, from: &BoundFrom) -> LogicalPlan {
+        match from {
+            BoundFrom::Relation(relation) => self.plan_relation(relation),
+            BoundFrom::Join {
+                left,
+                right,
+                join_type,
+                condition,
+            } => {
+                let condition = match condition {
+                    None => None,
+                    Some(BoundJoinCondition::On(expr)) => Some(expr.clone()),
+                    Some(BoundJoinCondition::Using(pairs)) => pairs
+                        .iter()
+                        .map(|(left, right)| BoundExpr::Binary {
+                            left: Box::new(BoundExpr::Column(*left)),
+                            operator: BinaryOperator::Equals,
+                            right: Box::new(BoundExpr::Column(*right)),
+                        })
+                        .reduce(|conjunction, equality| BoundExpr::Binary {
+                            left: Box::new(conjunction),
+                            operator: BinaryOperator::And,
+                            right: Box::new(equality),
+                        }),
+                };
+
+                LogicalPlan::Join {
+                    lateral: right.references(&left.columns()),
+                    left: Box::new(self.plan_from(left)),
+                    right: Box::new(self.plan_relation(right)),
+                    join_type: join_type.clone(),
+                    condition,
+                }
+            }
+        }
+    }
+
+    fn plan_relation(&mut self, relation: &BoundRelation) -> LogicalPlan {
+        let alias = relation.alias.clone();
+        let columns = relation.columns.clone();
+
+        match &relation.source {
+            RelationSource::Table(name) | RelationSource::MaterializedView(name) => {
+                LogicalPlan::Scan {
+                    relation: name.clone(),
+                    materialized: matches!(relation.source, RelationSource::MaterializedView(_)),
+                    alias,
+                    columns,
+                }
+            }
+            RelationSource::View { name, query } => LogicalPlan::Subquery {
+                input: Box::new(self.plan_select(query)),
+                view: Some(name.clone()),
+                alias,
+                columns,
+            },
+            RelationSource::Derived(query) => LogicalPlan::Subquery {
+                input: Box::new(self.plan_select(query)),
+                view: None,
+                alias,
+                columns,
+            },
+            RelationSource::Values(rows) => LogicalPlan::Values {
+                rows: rows.clone(),
+                columns,
+            },
+            RelationSource::Function { name, args } => LogicalPlan::FunctionScan {
+                name: name.clone(),
+                args: args.clone(),
+                alias,
+                columns,
+            },
+        }
+    }
+
+    fn contains_aggregate(&self, expr: &BoundExpr) -> bool {
+        expr.any(&mut |expr| match expr {
+            BoundExpr::Function { name, .. } => is_aggregate(self.registry, name),
+            _ => false,
+        })
+    }
+
+    /// Adds a column computed by the plan, which belongs to no relation.
+    fn add_column(&mut self, name: &str) -> ColumnId {
+        self.columns.push(BoundColumn {
+            name: name.to_string(),
+            relation: None,
+        });
+
+        ColumnId(self.columns.len() - 1)
+    }
+}
+
+fn sort(input: LogicalPlan, keys: Vec<BoundOrderByItem>) -> LogicalPlan {
+    match keys.is_empty() {
+        true => input,
+        false => LogicalPlan::Sort {
+            input: Box::new(input),
+            keys,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use drown_common::sql::{parser::StatementParser, statement::Statement};
+
+    use super::*;
+    use crate::{
+        binder::bind_select, catalog::Catalog, execute::execute_statement, session::Session,
+    };
+
+    /// Plans a query over a few tables and views and writes its operators as an indented tree.
+    fn plan(sql: &str) -> Vec<String> {
+        let mut catalog = Catalog::default();
+        let mut session = Session::default();
+
+        let setup = StatementParser::parse(
+            "CREATE TABLE users (id INT, team INT, score INT); \
+             CREATE TABLE teams (id INT, label TEXT); \
+             CREATE VIEW good AS SELECT id, score FROM users WHERE score > 10; \
+             CREATE MATERIALIZED VIEW totals AS SELECT team, sum(score) AS total FROM users GROUP BY team WITH NO DATA",
+        )
+        .unwrap();
+
+        for statement in &setup {
+            execute_statement(&mut catalog, &mut session, statement).unwrap();
+        }
+
+        let Statement::Select(select) = &StatementParser::parse(sql).unwrap()[0] else {
+            panic!("{sql} is not a SELECT");
+        };
+
+        let mut query = bind_select(&catalog, &session.search_path, select).unwrap();
+        let plan = plan_query(catalog.functions(), &mut query);
+
+        let mut lines = Vec::new();
+        shape(&plan, 0, &mut lines);
+        lines
+    }
+
+    fn shape(plan: &LogicalPlan, depth: usize, lines: &mut Vec<String>) {
+        let operator = match plan {
+            LogicalPlan::Scan {
+                relation,
+                materialized,
+                ..
+            } => match materialized {
+                true => format!("Scan materialized {}", relation.name),
+                false => format!("Scan {}", relation.name),
+            },
+            LogicalPlan::FunctionScan { name, .. } => format!("FunctionScan {name}"),
+            LogicalPlan::Values { rows, .. } => format!("Values {}", rows.len()),
+            LogicalPlan::Subquery { view, .. } => match view {
+                Some(view) => format!("Subquery {}", view.name),
+                None => "Subquery".to_string(),
+            },
+            LogicalPlan::Filter { .. } => "Filter".to_string(),
+            LogicalPlan::Project { exprs, .. } => format!("Project {}", exprs.len()),
+            LogicalPlan::Join {
+                join_type,
+                condition,
+                lateral,
+                ..
+            } => format!(
+                "Join {join_type:?}{}{}",
+                if condition.is_some() { " on" } else { "" },
+                if *lateral { " lateral" } else { "" }
+            ),
+            LogicalPlan::Aggregate {
+                group_by,
+                aggregates,
+                ..
+            } => {
+                format!("Aggregate {} {}", group_by.len(), aggregates.len())
+            }
+            LogicalPlan::Sort { keys, .. } => format!("Sort {}", keys.len()),
+            LogicalPlan::Limit { offset, limit, .. } => format!("Limit {offset} {limit:?}"),
+            LogicalPlan::Distinct { .. } => "Distinct".to_string(),
+            LogicalPlan::SetOp { operator, all, .. } => format!("SetOp {operator:?} {all}"),
+        };
+
+        lines.push(format!("{}{operator}", "  ".repeat(depth)));
+
+        for input in plan.inputs() {
+            shape(input, depth + 1, lines);
+        }
+    }
+
+    #[test]
+    fn filters_and_projections() {
+        assert_eq!(
+            plan("SELECT id FROM users WHERE score > 1"),
+            ["Project 1", "  Filter", "    Scan users"]
+        );
+    }
+
+    #[test]
+    fn joins() {
+        assert_eq!(
+            plan("SELECT teams.label FROM users JOIN teams ON users.team = teams.id LEFT JOIN good USING (score), teams AS t"),
+            [
+                "Project 1",
+                "  Join Cross",
+                "    Join LeftOuter on",
+                "      Join Inner on",
+                "        Scan users",
+                "        Scan teams",
+                "      Subquery good",
+                "        Project 2",
+                "          Filter",
+                "            Scan users",
+                "    Scan teams",
+            ]
+        );
+
+        assert_eq!(
+            plan("SELECT * FROM users, LATERAL generate_series(1, users.score) AS n"),
+            [
+                "Project 4",
+                "  Join Cross lateral",
+                "    Scan users",
+                "    FunctionScan generate_series",
+            ]
+        );
+    }
+
+    #[test]
+    fn aggregates() {
+        assert_eq!(
+            plan("SELECT team, count(*), sum(score) + max(score) FROM users GROUP BY team HAVING count(*) > 1"),
+            ["Project 3", "  Filter", "    Aggregate 1 3", "      Scan users"]
+        );
+
+        assert_eq!(
+            plan("SELECT count(DISTINCT team) FROM users"),
+            ["Project 1", "  Aggregate 0 1", "    Scan users"]
+        );
+    }
+
+    #[test]
+    fn sorts_and_limits() {
+        // Keys which are selected are sorted by after the projection, others before it
+        assert_eq!(
+            plan("SELECT id FROM users ORDER BY id LIMIT 5 OFFSET 10"),
+            [
+                "Limit 10 Some(5)",
+                "  Sort 1",
+                "    Project 1",
+                "      Scan users"
+            ]
+        );
+        assert_eq!(
+            plan("SELECT id FROM users ORDER BY score DESC"),
+            ["Project 1", "  Sort 1", "    Scan users"]
+        );
+        assert_eq!(
+            plan("SELECT DISTINCT team FROM users ORDER BY team"),
+            ["Sort 1", "  Distinct", "    Project 1", "      Scan users"]
+        );
+        assert_eq!(
+            plan("SELECT id FROM users UNION SELECT id FROM teams INTERSECT SELECT id FROM good ORDER BY id LIMIT 1"),
+            [
+                "Limit 0 Some(1)",
+                "  Sort 1",
+                "    SetOp Union false",
+                "      Project 1",
+                "        Scan users",
+                "      SetOp Intersect false",
+                "        Project 1",
+                "          Scan teams",
+                "        Project 1",
+                "          Subquery good",
+                "            Project 2",
+                "              Filter",
+                "                Scan users",
+            ]
+        );
+    }
+
+    #[test]
+    fn views() {
+        // Views are expanded into their query, while materialized views are read like tables
+        assert_eq!(
+            plan("SELECT id FROM good WHERE id > 1"),
+            [
+                "Project 1",
+                "  Filter",
+                "    Subquery good",
+                "      Project 2",
+                "        Filter",
+                "          Scan users",
+            ]
+        );
+        assert_eq!(
+            plan("SELECT total FROM totals"),
+            ["Project 1", "  Scan materialized totals"]
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use drown_common::sql::{datetime::TimeZone, function::FunctionContext};
+
+use crate::catalog::SearchPath;
+
+/// State belonging to a single client connection which affects how its statements execute.
+#[derive(Debug, Default)]
+pub struct Session {
+    /// The database and schemas that unqualified names are resolved in, changed by `USE` and
+    /// `SET search_path`
+    pub search_path: SearchPath,
+    /// The time zone `TIMESTAMPTZ` values are shown in, changed by `SET TIME ZONE`
+    pub time_zone: TimeZone,
+}
+
+impl Session {
+    /// The context of the functions called by a statement starting now.
+    pub fn function_context(&self) -> FunctionContext {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_micros() as i64);
+
+        FunctionContext {
+            time_zone: self.time_zone,
+            now,
+        }
+    }
+}
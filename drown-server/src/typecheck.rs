@@ -0,0 +1,1362 @@
+use std::fmt::{self, Display};
+
+use drown_common::sql::{
+    collation::Collation,
+    data_type::DataType,
+    datetime::{DateField, DateTimeError, TimeZone},
+    function::{
+        check_pattern, Function, FunctionError, FunctionKind, FunctionRegistry, Param, Returns,
+        Signature,
+    },
+    jsonb::{parse_path, JsonbError},
+    statement::{BinaryOperator, Literal, SetOperator, UnaryOperator},
+    suggest,
+    value::{Value, ValueError},
+};
+use thiserror::Error;
+
+use crate::{
+    binder::{
+        bind_select, BindError, BoundExpr, BoundFrom, BoundJoinCondition, BoundQuery,
+        BoundRelation, BoundSelect, ColumnId, RelationSource,
+    },
+    catalog::{resolve_type, Catalog, CatalogError},
+};
+
+#[derive(Debug, Error)]
+pub enum TypeError {
+    #[error("Operator `{operator}` cannot be applied to {left} and {right}")]
+    BinaryOperatorMismatch {
+        operator: BinaryOperator,
+        left: ExprType,
+        right: ExprType,
+    },
+    #[error("Operator `{operator}` cannot be applied to {operand}")]
+    UnaryOperatorMismatch {
+        operator: UnaryOperator,
+        operand: ExprType,
+    },
+    #[error("The condition of {clause} must be BOOLEAN, not {found}")]
+    NotBoolean {
+        clause: &'static str,
+        found: ExprType,
+    },
+    #[error("Function `{name}({args})` does not exist")]
+    FunctionNotFound {
+        name: String,
+        args: String,
+        suggestion: Option<String>,
+    },
+    #[error("Function call `{name}({args})` is ambiguous, as it matches more than one overload")]
+    AmbiguousFunction { name: String, args: String },
+    #[error("{modifier} is only allowed in calls of aggregates, which `{name}` is not")]
+    NotAggregate {
+        name: String,
+        modifier: &'static str,
+    },
+    #[error("ORDER BY expressions of `{0}(DISTINCT ...)` must be among its arguments")]
+    DistinctOrderBy(String),
+    #[error("Arguments of `{name}({args})` have no common type")]
+    NoCommonType { name: String, args: String },
+    #[error("Collation `{name}` does not exist")]
+    CollationNotFound {
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[error("Values of column `{name}` cannot be both {left} and {right}")]
+    ColumnTypeMismatch {
+        name: String,
+        left: ExprType,
+        right: ExprType,
+    },
+    #[error("{from} cannot be cast to {to}")]
+    InvalidCast { from: ExprType, to: DataType },
+    #[error("COLLATE cannot be applied to {found}, as it is not text")]
+    CollationNotText { found: ExprType },
+    #[error("Collations `{left}` and `{right}` given with COLLATE conflict")]
+    CollationMismatch { left: Collation, right: Collation },
+    #[error(
+        "Cannot decide between collations `{left}` and `{right}`, choose one of them with COLLATE"
+    )]
+    IndeterminateCollation { left: Collation, right: Collation },
+    #[error("{0}")]
+    InvalidLiteral(#[from] ValueError),
+    #[error("{0}")]
+    DateTimeError(#[from] DateTimeError),
+    #[error("{0}")]
+    JsonbError(#[from] JsonbError),
+    #[error("{0}")]
+    FunctionError(#[from] FunctionError),
+    #[error("{0}")]
+    BindError(#[from] BindError),
+    #[error("{0}")]
+    Catalog(#[from] CatalogError),
+}
+
+impl TypeError {
+    /// The existing function a missing one was most likely meant to be.
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            TypeError::FunctionNotFound { suggestion, .. }
+            | TypeError::CollationNotFound { suggestion, .. } => suggestion.as_deref(),
+            TypeError::BindError(err) => err.suggestion(),
+            TypeError::Catalog(err) => err.suggestion(),
+            _ => None,
+        }
+    }
+}
+
+/// The type of an expression, which for `NULL` and string literals is only decided by how they
+/// are used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExprType {
+    Known(DataType),
+    /// `NULL`, which can become a value of any type
+    Null,
+    /// A string literal, which is `TEXT` unless it is used as a value of another type, such as a
+    /// `DATE` or a `UUID`
+    StringLiteral,
+}
+
+impl ExprType {
+    /// The type the expression has when nothing decides it otherwise.
+    pub fn resolve(self) -> DataType {
+        match self {
+            ExprType::Known(data_type) => data_type,
+            ExprType::Null => DataType::Null,
+            ExprType::StringLiteral => DataType::Text,
+        }
+    }
+
+    /// How many steps up the coercion lattice a value of this type has to be moved to become
+    /// `target`, or `None` if it cannot be implicitly coerced to it at all.
+    fn coercion_cost(self, target: DataType) -> Option<usize> {
+        match self {
+            ExprType::Known(data_type) => data_type.coercion_cost(target),
+            ExprType::Null => Some(0),
+            ExprType::StringLiteral => match target {
+                DataType::Text | DataType::Varchar(_) => Some(0),
+                DataType::Boolean
+                | DataType::Bytea
+                | DataType::Date
+                | DataType::Time
+                | DataType::Timestamp
+                | DataType::TimestampTz
+                | DataType::Interval
+                | DataType::Uuid
+                | DataType::Jsonb => Some(1),
+                _ => None,
+            },
+        }
+    }
+
+    /// The type both operands of a comparison are coerced to.
+    pub fn common_type(a: ExprType, b: ExprType) -> Option<ExprType> {
+        match (a, b) {
+            (ExprType::Known(a), ExprType::Known(b)) => {
+                DataType::common_type(a, b).map(ExprType::Known)
+            }
+            (ExprType::Known(data_type), other) | (other, ExprType::Known(data_type)) => other
+                .coercion_cost(data_type)
+                .map(|_| ExprType::Known(data_type)),
+            (ExprType::Null, other) | (other, ExprType::Null) => Some(other),
+            (ExprType::StringLiteral, ExprType::StringLiteral) => Some(ExprType::StringLiteral),
+        }
+    }
+}
+
+impl Display for ExprType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExprType::Known(data_type) => data_type.fmt(f),
+            ExprType::Null => f.write_str("NULL"),
+            ExprType::StringLiteral => f.write_str("TEXT"),
+        }
+    }
+}
+
+/// Where the collation of an expression comes from, which decides the collation a comparison
+/// of two expressions uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Derivation {
+    /// Literals, values computed by operators and columns without a collation, which are
+    /// compared with the collation of whatever they are compared with, or `binary`
+    Default,
+    /// The collation of a column
+    Implicit(Collation),
+    /// A collation given with `COLLATE`, which takes precedence over implicit ones
+    Explicit(Collation),
+    /// Two different implicit collations, of which neither takes precedence
+    Conflict(Collation, Collation),
+}
+
+impl Derivation {
+    fn combine(self, other: Derivation) -> Result<Derivation, TypeError> {
+        use Derivation::*;
+
+        Ok(match (self, other) {
+            (Explicit(left), Explicit(right)) if left != right => {
+                return Err(TypeError::CollationMismatch { left, right })
+            }
+            (Explicit(collation), _) | (_, Explicit(collation)) => Explicit(collation),
+            (conflict @ Conflict(..), _) | (_, conflict @ Conflict(..)) => conflict,
+            (Implicit(left), Implicit(right)) if left != right => Conflict(left, right),
+            (Implicit(collation), _) | (_, Implicit(collation)) => Implicit(collation),
+            (Default, Default) => Default,
+        })
+    }
+
+    /// The collation text with this derivation is compared, sorted and grouped with.
+    fn collation(self) -> Result<Collation, TypeError> {
+        match self {
+            Derivation::Default => Ok(Collation::Binary),
+            Derivation::Implicit(collation) | Derivation::Explicit(collation) => Ok(collation),
+            Derivation::Conflict(left, right) => {
+                Err(TypeError::IndeterminateCollation { left, right })
+            }
+        }
+    }
+}
+
+/// Infers the type of every expression in a bound query, checking that operators and functions
+/// are applied to values they accept, and returns the types of its result columns along with
+/// the types of all its columns, which the plan made from it is executed with.
+pub fn check_query_types<'a>(
+    catalog: &'a Catalog,
+    query: &BoundQuery,
+) -> Result<(Vec<DataType>, QueryTypes<'a>), TypeError> {
+    let mut checker = TypeChecker {
+        catalog,
+        column_types: vec![None; query.columns.len()],
+        column_collations: vec![Derivation::Default; query.columns.len()],
+        functions: Vec::new(),
+    };
+
+    let types = checker.check_select(&query.select)?;
+
+    Ok((types, QueryTypes { checker }))
+}
+
+/// The types and collations of the columns of a checked query, and of the columns added by the
+/// plan made from it once they are defined.
+pub struct QueryTypes<'a> {
+    checker: TypeChecker<'a>,
+}
+
+impl QueryTypes<'_> {
+    pub fn column_type(&self, id: ColumnId) -> DataType {
+        match self.checker.column_types.get(id.0) {
+            Some(Some(data_type)) => *data_type,
+            _ => unreachable!("Column {} is used before it is defined", id.0),
+        }
+    }
+
+    /// The functions the query calls, once for every call.
+    pub fn functions(&self) -> &[&Function] {
+        &self.checker.functions
+    }
+
+    /// The type of an expression over columns whose types are known.
+    pub fn expr_type(&mut self, expr: &BoundExpr) -> Result<ExprType, TypeError> {
+        self.checker.check_expr(expr)
+    }
+
+    /// The collation an expression is sorted and grouped with.
+    pub fn collation(&self, expr: &BoundExpr) -> Result<Collation, TypeError> {
+        self.checker.derivation(expr)?.collation()
+    }
+
+    /// The collation two expressions are compared with.
+    pub fn comparison_collation(
+        &self,
+        left: &BoundExpr,
+        right: &BoundExpr,
+    ) -> Result<Collation, TypeError> {
+        let left = self.checker.derivation(left)?;
+        left.combine(self.checker.derivation(right)?)?.collation()
+    }
+
+    /// Gives a column added by the plan the type and collation of the expression computing it.
+    pub fn define(&mut self, id: ColumnId, expr: &BoundExpr) -> Result<(), TypeError> {
+        let data_type = self.checker.check_expr(expr)?.resolve();
+        let collation = match self.checker.derivation(expr)? {
+            Derivation::Explicit(collation) => Derivation::Implicit(collation),
+            derivation => derivation,
+        };
+
+        self.set(id, data_type, collation);
+
+        Ok(())
+    }
+
+    /// Gives a column added by the plan the common type and collation of two columns whose
+    /// values it combines, unless it already has one.
+    pub fn define_combined(&mut self, id: ColumnId, left: ColumnId, right: ColumnId) {
+        if let Some(Some(_)) = self.checker.column_types.get(id.0) {
+            return;
+        }
+
+        let (left_type, right_type) = (self.column_type(left), self.column_type(right));
+        let data_type = DataType::common_type(left_type, right_type).unwrap_or(left_type);
+        let collation = self.checker.column_collations[left.0]
+            .combine(self.checker.column_collations[right.0])
+            .unwrap_or(Derivation::Default);
+
+        self.set(id, data_type, collation);
+    }
+
+    fn set(&mut self, id: ColumnId, data_type: DataType, collation: Derivation) {
+        let checker = &mut self.checker;
+
+        if checker.column_types.len() <= id.0 {
+            checker.column_types.resize(id.0 + 1, None);
+            checker
+                .column_collations
+                .resize(id.0 + 1, Derivation::Default);
+        }
+
+        checker.column_types[id.0] = Some(data_type);
+        checker.column_collations[id.0] = collation;
+    }
+}
+
+struct TypeChecker<'a> {
+    catalog: &'a Catalog,
+    /// The type of every column of the query, indexed by `ColumnId`, which is known once the
+    /// relation producing the column has been checked
+    column_types: Vec<Option<DataType>>,
+    /// The collation of every column of the query, indexed by `ColumnId`, which is known along
+    /// with its type
+    column_collations: Vec<Derivation>,
+    /// The functions the query calls, once for every call
+    functions: Vec<&'a Function>,
+}
+
+impl<'a> TypeChecker<'a> {
+    fn check_select(&mut self, select: &BoundSelect) -> Result<Vec<DataType>, TypeError> {
+        let mut types = self.check_select_core(select)?;
+
+        if !select.set_operations.is_empty() {
+            let mut collations = self.output_collations(select)?;
+
+            for set_operation in &select.set_operations {
+                let query = &set_operation.query;
+                let query_types = self.check_select(query)?;
+                let query_collations = self.output_collations(query)?;
+
+                for (i, column) in select.columns.iter().enumerate() {
+                    types[i] =
+                        DataType::common_type(types[i], query_types[i]).ok_or_else(|| {
+                            TypeError::ColumnTypeMismatch {
+                                name: column.name.clone(),
+                                left: ExprType::Known(types[i]),
+                                right: ExprType::Known(query_types[i]),
+                            }
+                        })?;
+
+                    collations[i] = collations[i].combine(query_collations[i])?;
+                }
+            }
+
+            // Combined rows are told apart by every column unless all of them are kept
+            let compared = select.set_operations.iter().any(|set_operation| {
+                !set_operation.all || set_operation.operator != SetOperator::Union
+            });
+
+            let columns = select.set_columns.iter().zip(&types).zip(collations);
+
+            for ((id, data_type), collation) in columns {
+                self.column_types[id.0] = Some(*data_type);
+                self.column_collations[id.0] = collation;
+
+                if compared && data_type.is_text() {
+                    collation.collation()?;
+                }
+            }
+        }
+
+        for item in &select.order_by {
+            self.check_sort_key(&item.expr)?;
+        }
+
+        Ok(types)
+    }
+
+    /// Checks a query up to the rows it combines with others.
+    fn check_select_core(&mut self, select: &BoundSelect) -> Result<Vec<DataType>, TypeError> {
+        self.check_from(&select.from)?;
+
+        if let Some(condition) = &select.where_clause {
+            self.check_condition("WHERE", condition)?;
+        }
+
+        for key in &select.group_by {
+            self.check_sort_key(key)?;
+        }
+
+        if let Some(condition) = &select.having {
+            self.check_condition("HAVING", condition)?;
+        }
+
+        // Rows of SELECT DISTINCT are told apart by every column
+        if select.distinct {
+            for column in &select.columns {
+                self.check_sort_key(&column.expr)?;
+            }
+        }
+
+        select
+            .columns
+            .iter()
+            .map(|column| Ok(self.check_expr(&column.expr)?.resolve()))
+            .collect()
+    }
+
+    fn check_from(&mut self, from: &BoundFrom) -> Result<(), TypeError> {
+        match from {
+            BoundFrom::Relation(relation) => self.check_relation(relation),
+            BoundFrom::Join {
+                left,
+                right,
+                condition,
+                ..
+            } => {
+                self.check_from(left)?;
+                self.check_relation(right)?;
+
+                match condition {
+                    None => {}
+                    Some(BoundJoinCondition::On(expr)) => self.check_condition("JOIN", expr)?,
+                    Some(BoundJoinCondition::Using(columns)) => {
+                        for (left, right) in columns {
+                            self.check_binary(
+                                BinaryOperator::Equals,
+                                &BoundExpr::Column(*left),
+                                &BoundExpr::Column(*right),
+                            )?;
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Checks the query behind a relation, which decides the types and collations of its
+    /// columns.
+    fn check_relation(&mut self, relation: &BoundRelation) -> Result<(), TypeError> {
+        let (types, collations) = match &relation.source {
+            RelationSource::Table(name) => match self.catalog.base_table(name) {
+                Some(table) => table
+                    .columns
+                    .iter()
+                    .map(|column| {
+                        let collation = match &column.collation {
+                            Some(collation) => {
+                                Derivation::Implicit(Collation::parse(collation).unwrap())
+                            }
+                            None => Derivation::Default,
+                        };
+
+                        (
+                            DataType::from_type_name(&column.data_type).unwrap(),
+                            collation,
+                        )
+                    })
+                    .unzip(),
+                None => unreachable!("Table `{}` was dropped while being bound", name.name),
+            },
+            RelationSource::View { query, .. } | RelationSource::Derived(query) => {
+                (self.check_select(query)?, self.output_collations(query)?)
+            }
+            RelationSource::Values(rows) => {
+                let mut types = Vec::new();
+                let mut collations = Vec::new();
+
+                for row in rows {
+                    for (i, expr) in row.iter().enumerate() {
+                        let data_type = self.check_expr(expr)?;
+                        let collation = match self.derivation(expr)? {
+                            Derivation::Explicit(collation) => Derivation::Implicit(collation),
+                            derivation => derivation,
+                        };
+
+                        if i == types.len() {
+                            types.push(data_type);
+                            collations.push(collation);
+                            continue;
+                        }
+
+                        types[i] = ExprType::common_type(types[i], data_type).ok_or_else(|| {
+                            TypeError::ColumnTypeMismatch {
+                                name: format!("column{}", i + 1),
+                                left: types[i],
+                                right: data_type,
+                            }
+                        })?;
+
+                        collations[i] = collations[i].combine(collation)?;
+                    }
+                }
+
+                (
+                    types.into_iter().map(ExprType::resolve).collect(),
+                    collations,
+                )
+            }
+            RelationSource::Function { name, args } => {
+                let arg_types = args
+                    .iter()
+                    .map(|arg| self.check_expr(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let (function, _) =
+                    resolve_function(self.catalog.functions(), name, Some(&arg_types))?;
+                let signature = &function.signature;
+                self.functions.push(function);
+
+                for (i, arg) in args.iter().enumerate() {
+                    check_argument(&signature.name, signature.param(i).unwrap(), arg)?;
+                }
+
+                let Returns::Table(columns) = signature.returns else {
+                    unreachable!("Function `{name}` has both table and other overloads")
+                };
+
+                columns
+                    .iter()
+                    .map(|column| (column.data_type, Derivation::Default))
+                    .unzip()
+            }
+            RelationSource::MaterializedView(name) => {
+                let Some(materialized_view) = self.catalog.materialized_view(name) else {
+                    unreachable!(
+                        "Materialized view `{}` was dropped while being bound",
+                        name.name
+                    )
+                };
+
+                // The stored columns have the types of the query they were computed by
+                let view = &materialized_view.definition;
+                let query = bind_select(self.catalog, &view.search_path, view.select())?;
+
+                let mut checker = TypeChecker {
+                    catalog: self.catalog,
+                    column_types: vec![None; query.columns.len()],
+                    column_collations: vec![Derivation::Default; query.columns.len()],
+                    functions: Vec::new(),
+                };
+
+                let types = checker.check_select(&query.select)?;
+
+                (types, checker.output_collations(&query.select)?)
+            }
+        };
+
+        for ((id, data_type), collation) in relation.columns.iter().zip(types).zip(collations) {
+            self.column_types[id.0] = Some(data_type);
+            self.column_collations[id.0] = collation;
+        }
+
+        Ok(())
+    }
+
+    /// The collations of the result columns of a checked query, which are implicit to the
+    /// queries reading from it even when given with `COLLATE`.
+    fn output_collations(&self, select: &BoundSelect) -> Result<Vec<Derivation>, TypeError> {
+        if !select.set_columns.is_empty() {
+            return Ok(select
+                .set_columns
+                .iter()
+                .map(|id| self.column_collations[id.0])
+                .collect());
+        }
+
+        select
+            .columns
+            .iter()
+            .map(|column| {
+                Ok(match self.derivation(&column.expr)? {
+                    Derivation::Explicit(collation) => Derivation::Implicit(collation),
+                    derivation => derivation,
+                })
+            })
+            .collect()
+    }
+
+    /// Checks a key rows are sorted or grouped by, which must have a single collation if it is
+    /// text.
+    fn check_sort_key(&mut self, expr: &BoundExpr) -> Result<(), TypeError> {
+        if self.check_expr(expr)?.resolve().is_text() {
+            self.derivation(expr)?.collation()?;
+        }
+
+        Ok(())
+    }
+
+    /// Derives the collation of a checked expression.
+    fn derivation(&self, expr: &BoundExpr) -> Result<Derivation, TypeError> {
+        match expr {
+            BoundExpr::Column(id) => Ok(self.column_collations[id.0]),
+            BoundExpr::Collate { collation, .. } => {
+                Ok(Derivation::Explicit(resolve_collation(collation)?))
+            }
+            // Functions of text return text in the collation of their arguments
+            BoundExpr::Function {
+                args: Some(args), ..
+            } => args
+                .iter()
+                .try_fold(Derivation::Default, |derivation, arg| {
+                    derivation.combine(self.derivation(arg)?)
+                }),
+            _ => Ok(Derivation::Default),
+        }
+    }
+
+    fn check_condition(&mut self, clause: &'static str, expr: &BoundExpr) -> Result<(), TypeError> {
+        let found = self.check_expr(expr)?;
+
+        match found.coercion_cost(DataType::Boolean) {
+            Some(_) => check_string_literals(ExprType::Known(DataType::Boolean), [expr]),
+            None => Err(TypeError::NotBoolean { clause, found }),
+        }
+    }
+
+    fn check_expr(&mut self, expr: &BoundExpr) -> Result<ExprType, TypeError> {
+        match expr {
+            BoundExpr::Literal(literal) => literal_type(literal),
+            BoundExpr::Constant(value) => Ok(constant_type(value)),
+            BoundExpr::Column(id) => match self.column_types[id.0] {
+                Some(data_type) => Ok(ExprType::Known(data_type)),
+                None => unreachable!("Column {} is used before its relation is checked", id.0),
+            },
+            BoundExpr::Unary { operator, operand } => {
+                let operand_type = self.check_expr(operand)?;
+
+                let accepted = match operator {
+                    UnaryOperator::Not => operand_type.coercion_cost(DataType::Boolean).is_some(),
+                    UnaryOperator::Plus | UnaryOperator::Minus => match operand_type {
+                        ExprType::Known(DataType::Interval) => true,
+                        ExprType::Known(data_type) => data_type.is_numeric(),
+                        ExprType::Null => true,
+                        ExprType::StringLiteral => false,
+                    },
+                };
+
+                if !accepted {
+                    return Err(TypeError::UnaryOperatorMismatch {
+                        operator: *operator,
+                        operand: operand_type,
+                    });
+                }
+
+                if *operator == UnaryOperator::Not {
+                    check_string_literals(ExprType::Known(DataType::Boolean), [&**operand])?;
+                }
+
+                Ok(match operator {
+                    UnaryOperator::Not => ExprType::Known(DataType::Boolean),
+                    UnaryOperator::Plus | UnaryOperator::Minus => operand_type,
+                })
+            }
+            BoundExpr::Binary {
+                left,
+                operator,
+                right,
+            } => self.check_binary(*operator, left, right),
+            BoundExpr::IsNull { operand, .. } => {
+                self.check_expr(operand)?;
+                Ok(ExprType::Known(DataType::Boolean))
+            }
+            BoundExpr::Function {
+                name,
+                args,
+                distinct,
+                order_by,
+                filter,
+            } => {
+                let arg_types = match args {
+                    Some(args) => Some(
+                        args.iter()
+                            .map(|arg| self.check_expr(arg))
+                            .collect::<Result<Vec<_>, _>>()?,
+                    ),
+                    None => None,
+                };
+
+                let (function, return_type) =
+                    resolve_function(self.catalog.functions(), name, arg_types.as_deref())?;
+                let signature = &function.signature;
+                self.functions.push(function);
+
+                if let FunctionKind::Table(_) = function.kind {
+                    return Err(FunctionError::NotExpression(signature.name.to_string()).into());
+                }
+
+                for (i, arg) in args.iter().flatten().enumerate() {
+                    check_argument(&signature.name, signature.param(i).unwrap(), arg)?;
+                }
+
+                if let (Returns::CommonType, Some(args)) = (signature.returns, args) {
+                    check_string_literals(return_type, args)?;
+                }
+
+                let modifier = if *distinct {
+                    Some("DISTINCT")
+                } else if !order_by.is_empty() {
+                    Some("ORDER BY")
+                } else if filter.is_some() {
+                    Some("FILTER")
+                } else {
+                    None
+                };
+
+                if let (Some(modifier), FunctionKind::Scalar { .. }) = (modifier, &function.kind) {
+                    return Err(TypeError::NotAggregate {
+                        name: signature.name.to_string(),
+                        modifier,
+                    });
+                }
+
+                // Rows are deduplicated by their arguments alone, which only keeps their order
+                // when they are sorted by nothing else
+                if *distinct
+                    && !order_by
+                        .iter()
+                        .all(|item| args.iter().flatten().any(|arg| *arg == item.expr))
+                {
+                    return Err(TypeError::DistinctOrderBy(signature.name.to_string()));
+                }
+
+                // Aggregates compare text arguments under their collation, as `DISTINCT`,
+                // `min` and `max` do
+                if let FunctionKind::Aggregate(_) = function.kind {
+                    for arg in args.iter().flatten() {
+                        self.check_sort_key(arg)?;
+                    }
+                }
+
+                for item in order_by {
+                    self.check_sort_key(&item.expr)?;
+                }
+
+                if let Some(filter) = filter {
+                    self.check_condition("FILTER", filter)?;
+                }
+
+                Ok(return_type)
+            }
+            BoundExpr::Cast { operand, data_type } => {
+                let operand_type = self.check_expr(operand)?;
+                let target = resolve_type(data_type)?;
+
+                match operand_type {
+                    ExprType::Known(data_type) if !data_type.can_cast(target) => {
+                        return Err(TypeError::InvalidCast {
+                            from: operand_type,
+                            to: target,
+                        })
+                    }
+                    // Literals are checked to be valid values of the type up front, which
+                    // does not depend on the time zone they are read in
+                    ExprType::StringLiteral => {
+                        if let BoundExpr::Literal(Literal::String(text)) = operand.as_ref() {
+                            Value::Text(text.clone()).cast(target, &TimeZone::UTC)?;
+                        }
+                    }
+                    _ => {}
+                }
+
+                Ok(ExprType::Known(target))
+            }
+            BoundExpr::Collate { operand, collation } => {
+                let operand_type = self.check_expr(operand)?;
+
+                match operand_type {
+                    ExprType::Known(data_type) if !data_type.is_text() => {
+                        return Err(TypeError::CollationNotText {
+                            found: operand_type,
+                        })
+                    }
+                    _ => {}
+                }
+
+                resolve_collation(collation)?;
+
+                Ok(operand_type)
+            }
+        }
+    }
+
+    fn check_binary(
+        &mut self,
+        operator: BinaryOperator,
+        left: &BoundExpr,
+        right: &BoundExpr,
+    ) -> Result<ExprType, TypeError> {
+        let left_type = self.check_expr(left)?;
+        let right_type = self.check_expr(right)?;
+
+        let mismatch = || TypeError::BinaryOperatorMismatch {
+            operator,
+            left: left_type,
+            right: right_type,
+        };
+
+        let result = match operator {
+            BinaryOperator::And | BinaryOperator::Or => {
+                let boolean =
+                    |operand: ExprType| operand.coercion_cost(DataType::Boolean).is_some();
+
+                match boolean(left_type) && boolean(right_type) {
+                    true => {
+                        check_string_literals(ExprType::Known(DataType::Boolean), [left, right])?;
+                        ExprType::Known(DataType::Boolean)
+                    }
+                    false => return Err(mismatch()),
+                }
+            }
+            BinaryOperator::Equals
+            | BinaryOperator::NotEquals
+            | BinaryOperator::LessThan
+            | BinaryOperator::LessThanOrEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterThanOrEqual => {
+                let common_type =
+                    ExprType::common_type(left_type, right_type).ok_or_else(mismatch)?;
+
+                check_string_literals(common_type, [left, right])?;
+
+                if common_type.resolve().is_text() {
+                    self.derivation(left)?
+                        .combine(self.derivation(right)?)?
+                        .collation()?;
+                }
+                ExprType::Known(DataType::Boolean)
+            }
+            BinaryOperator::JsonGet
+            | BinaryOperator::JsonGetText
+            | BinaryOperator::JsonGetPath
+            | BinaryOperator::JsonGetPathText
+            | BinaryOperator::Contains
+            | BinaryOperator::ContainedBy
+            | BinaryOperator::HasKey
+            | BinaryOperator::HasAnyKey
+            | BinaryOperator::HasAllKeys => {
+                let (param, returns) = json_operator_types(operator, right_type)
+                    .filter(|_| left_type.coercion_cost(DataType::Jsonb).is_some())
+                    .ok_or_else(mismatch)?;
+
+                check_string_literals(ExprType::Known(DataType::Jsonb), [left])?;
+
+                match operator {
+                    // Paths and lists of keys are written as text arrays, like `'{a,b}'`
+                    BinaryOperator::JsonGetPath
+                    | BinaryOperator::JsonGetPathText
+                    | BinaryOperator::HasAnyKey
+                    | BinaryOperator::HasAllKeys => {
+                        if let BoundExpr::Literal(Literal::String(text)) = right {
+                            parse_path(text)?;
+                        }
+                    }
+                    _ => check_string_literals(ExprType::Known(param), [right])?,
+                }
+
+                ExprType::Known(returns)
+            }
+            BinaryOperator::Plus
+            | BinaryOperator::Minus
+            | BinaryOperator::Multiply
+            | BinaryOperator::Divide
+            | BinaryOperator::Modulo => {
+                let (left_type, right_type) =
+                    arithmetic_operand_types(operator, left_type, right_type);
+
+                check_string_literals(left_type, [left])?;
+                check_string_literals(right_type, [right])?;
+
+                arithmetic_type(operator, left_type, right_type).ok_or_else(mismatch)?
+            }
+        };
+
+        Ok(result)
+    }
+}
+
+/// The type of a literal, where numbers which are not integers or do not fit into a `BIGINT` are
+/// exact decimals, and are checked to fit into one.
+pub fn literal_type(literal: &Literal) -> Result<ExprType, TypeError> {
+    let literal_type = match literal {
+        Literal::Number(number) => {
+            let data_type = if number.contains(['.', 'e', 'E']) {
+                DataType::Decimal(None)
+            } else if number.parse::<i32>().is_ok() {
+                DataType::Int
+            } else if number.parse::<i64>().is_ok() {
+                DataType::BigInt
+            } else {
+                DataType::Decimal(None)
+            };
+
+            if let DataType::Decimal(_) = data_type {
+                Value::parse(number, data_type)?;
+            }
+
+            ExprType::Known(data_type)
+        }
+        Literal::String(_) => ExprType::StringLiteral,
+        Literal::Boolean(_) => ExprType::Known(DataType::Boolean),
+        Literal::Null => ExprType::Null,
+    };
+
+    Ok(literal_type)
+}
+
+/// The type of a value computed while planning.
+pub fn constant_type(value: &Value) -> ExprType {
+    match value {
+        Value::Null => ExprType::Null,
+        value => ExprType::Known(value.data_type()),
+    }
+}
+
+/// The types the operands of an arithmetic operator are taken as. String literals can only take
+/// part in date and time arithmetic, as values of the type of the other operand or, failing
+/// that, as intervals.
+pub fn arithmetic_operand_types(
+    operator: BinaryOperator,
+    left: ExprType,
+    right: ExprType,
+) -> (ExprType, ExprType) {
+    let literal_type = |other: ExprType, literal_left: bool| {
+        [other, ExprType::Known(DataType::Interval)]
+            .into_iter()
+            .find(|literal| match literal_left {
+                true => arithmetic_type(operator, *literal, other).is_some(),
+                false => arithmetic_type(operator, other, *literal).is_some(),
+            })
+            .unwrap_or(other)
+    };
+
+    match (left, right) {
+        (ExprType::StringLiteral, other) if is_datetime(other) => {
+            (literal_type(other, true), other)
+        }
+        (other, ExprType::StringLiteral) if is_datetime(other) => {
+            (other, literal_type(other, false))
+        }
+        types => types,
+    }
+}
+
+/// The type of an arithmetic expression, which is the common numeric type of its operands without
+/// limits on its precision, except for dates, which can be moved by a number of days and
+/// subtracted from one another, and other dates and times, which are moved by intervals.
+pub fn arithmetic_type(
+    operator: BinaryOperator,
+    left: ExprType,
+    right: ExprType,
+) -> Option<ExprType> {
+    use DataType::*;
+
+    let integer = |data_type: DataType| matches!(data_type, SmallInt | Int | BigInt);
+
+    if let (ExprType::Known(left), ExprType::Known(right)) = (left, right) {
+        if let Some(data_type) = datetime_arithmetic_type(operator, left, right) {
+            return Some(ExprType::Known(data_type));
+        }
+    }
+
+    match (left, right) {
+        (ExprType::Known(Date), ExprType::Known(days)) if integer(days) => match operator {
+            BinaryOperator::Plus | BinaryOperator::Minus => Some(ExprType::Known(Date)),
+            _ => None,
+        },
+        (ExprType::Known(days), ExprType::Known(Date)) if integer(days) => match operator {
+            BinaryOperator::Plus => Some(ExprType::Known(Date)),
+            _ => None,
+        },
+        (ExprType::Known(Date), ExprType::Known(Date)) => match operator {
+            BinaryOperator::Minus => Some(ExprType::Known(Int)),
+            _ => None,
+        },
+        (ExprType::Known(left), ExprType::Known(right)) => DataType::common_type(left, right)
+            .filter(DataType::is_numeric)
+            .map(|data_type| ExprType::Known(data_type.unlimited())),
+        (ExprType::Known(data_type), ExprType::Null)
+        | (ExprType::Null, ExprType::Known(data_type))
+            if data_type.is_numeric() =>
+        {
+            Some(ExprType::Known(data_type.unlimited()))
+        }
+        (ExprType::Null, ExprType::Null) => Some(ExprType::Null),
+        _ => None,
+    }
+}
+
+/// The type the right operand of a JSON operator is passed as and the type the operator returns,
+/// where the left operand is always `JSONB`. `->` and `->>` take an array index when given an
+/// integer and a key otherwise.
+pub fn json_operator_types(
+    operator: BinaryOperator,
+    right: ExprType,
+) -> Option<(DataType, DataType)> {
+    use DataType::*;
+
+    let index = matches!(right, ExprType::Known(SmallInt | Int | BigInt));
+
+    let (param, returns) = match operator {
+        BinaryOperator::JsonGet if index => (BigInt, Jsonb),
+        BinaryOperator::JsonGetText if index => (BigInt, Text),
+        BinaryOperator::JsonGet | BinaryOperator::JsonGetPath => (Text, Jsonb),
+        BinaryOperator::JsonGetText | BinaryOperator::JsonGetPathText => (Text, Text),
+        BinaryOperator::Contains | BinaryOperator::ContainedBy => (Jsonb, Boolean),
+        BinaryOperator::HasKey | BinaryOperator::HasAnyKey | BinaryOperator::HasAllKeys => {
+            (Text, Boolean)
+        }
+        _ => unreachable!("`{}` is not a JSON operator", operator),
+    };
+
+    right.coercion_cost(param).map(|_| (param, returns))
+}
+
+/// The type of arithmetic on dates, times and intervals other than moving dates by a number of days.
+fn datetime_arithmetic_type(
+    operator: BinaryOperator,
+    left: DataType,
+    right: DataType,
+) -> Option<DataType> {
+    use BinaryOperator::{Divide, Minus, Multiply, Plus};
+    use DataType::*;
+
+    match (left, operator, right) {
+        (Date, Plus | Minus, Interval) | (Interval, Plus, Date) => Some(Timestamp),
+        (Date, Plus, Time) | (Time, Plus, Date) => Some(Timestamp),
+        (Timestamp | TimestampTz | Time, Plus | Minus, Interval) => Some(left),
+        (Interval, Plus, Timestamp | TimestampTz | Time) => Some(right),
+        (Timestamp, Minus, Timestamp)
+        | (TimestampTz, Minus, TimestampTz)
+        | (Time, Minus, Time)
+        | (Interval, Plus | Minus, Interval) => Some(Interval),
+        (Interval, Multiply | Divide, factor) if factor.is_numeric() => Some(Interval),
+        (factor, Multiply, Interval) if factor.is_numeric() => Some(Interval),
+        _ => None,
+    }
+}
+
+/// Whether values of the type are dates, times or intervals, which string literals can be added
+/// to or subtracted from.
+fn is_datetime(expr_type: ExprType) -> bool {
+    use DataType::*;
+
+    matches!(
+        expr_type,
+        ExprType::Known(Date | Time | Timestamp | TimestampTz | Interval)
+    )
+}
+
+/// Checks that a constant argument passed to a parameter is valid for it, such as the names of
+/// date fields and time zones, which would otherwise only be noticed once rows are processed.
+fn check_argument(function: &str, param: Param, arg: &BoundExpr) -> Result<(), TypeError> {
+    let BoundExpr::Literal(Literal::String(text)) = arg else {
+        return Ok(());
+    };
+
+    match param {
+        Param::Type(data_type) => check_string_literals(ExprType::Known(data_type), [arg])?,
+        Param::Any => {}
+        Param::Field => {
+            DateField::parse(text)?;
+        }
+        Param::Unit => {
+            let field = DateField::parse(text)?;
+
+            if !field.is_unit() {
+                return Err(DateTimeError::UnsupportedField {
+                    field,
+                    function: function.to_string(),
+                }
+                .into());
+            }
+        }
+        Param::Zone => {
+            TimeZone::parse(text)?;
+        }
+        Param::Pattern => check_pattern(text)?,
+    }
+
+    Ok(())
+}
+
+fn resolve_collation(name: &str) -> Result<Collation, TypeError> {
+    Collation::parse(name).map_err(|_| TypeError::CollationNotFound {
+        name: name.to_string(),
+        suggestion: suggest::closest(name, Collation::names()).map(ToString::to_string),
+    })
+}
+
+/// Checks that string literals coerced to another type are valid values of it, which can be
+/// done before anything is executed as literals are constant.
+fn check_string_literals<'a>(
+    target: ExprType,
+    exprs: impl IntoIterator<Item = &'a BoundExpr>,
+) -> Result<(), TypeError> {
+    // Text of any length can be compared with `VARCHAR` values, so only other types are checked
+    let data_type = match target {
+        ExprType::Known(DataType::Varchar(_)) | ExprType::Null | ExprType::StringLiteral => {
+            return Ok(())
+        }
+        ExprType::Known(data_type) => data_type,
+    };
+
+    for expr in exprs {
+        if let BoundExpr::Literal(Literal::String(text)) = expr {
+            Value::parse(text, data_type)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Picks the overload of a function which its arguments can be coerced to with the fewest steps
+/// up the coercion lattice, and returns it along with the type it returns.
+pub fn resolve_function<'a>(
+    registry: &'a FunctionRegistry,
+    name: &str,
+    args: Option<&[ExprType]>,
+) -> Result<(&'a Function, ExprType), TypeError> {
+    let name = name.to_ascii_lowercase();
+
+    let written_args = match args {
+        Some(args) => args
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+        None => "*".to_string(),
+    };
+
+    let overloads = registry.overloads(&name);
+
+    let mut candidates = overloads
+        .iter()
+        .filter_map(|function| Some((function, coercion_cost(&function.signature, args)?)))
+        .collect::<Vec<_>>();
+
+    let Some(min_cost) = candidates.iter().map(|(_, cost)| *cost).min() else {
+        return Err(TypeError::FunctionNotFound {
+            suggestion: match overloads.is_empty() {
+                true => suggest::closest(&name, registry.names()).map(ToString::to_string),
+                false => None,
+            },
+            name,
+            args: written_args,
+        });
+    };
+
+    candidates.retain(|(_, cost)| *cost == min_cost);
+
+    let [(function, _)] = candidates[..] else {
+        return Err(TypeError::AmbiguousFunction {
+            name,
+            args: written_args,
+        });
+    };
+
+    let return_type = match function.signature.returns {
+        Returns::Type(data_type) => ExprType::Known(data_type),
+        // Rows are not values, the columns of a table function are read from its signature
+        Returns::Table(_) => ExprType::Null,
+        Returns::FirstArg => args.unwrap()[0],
+        Returns::CommonType => args
+            .unwrap()
+            .iter()
+            .try_fold(ExprType::Null, |common, arg| {
+                ExprType::common_type(common, *arg)
+            })
+            .ok_or(TypeError::NoCommonType {
+                name,
+                args: written_args,
+            })?,
+    };
+
+    Ok((function, return_type))
+}
+
+/// The total number of steps up the coercion lattice needed to pass `args` to an overload, or
+/// `None` if they cannot be passed to it, which for variadic overloads is when they are not a
+/// whole number of repetitions of its last parameters.
+fn coercion_cost(signature: &Signature, args: Option<&[ExprType]>) -> Option<usize> {
+    match args {
+        None => signature.params.is_none().then_some(0),
+        Some(args) if signature.accepts(args.len()) => args
+            .iter()
+            .enumerate()
+            .map(|(i, arg)| param_cost(signature.param(i)?, *arg))
+            .sum(),
+        Some(_) => None,
+    }
+}
+
+fn param_cost(param: Param, arg: ExprType) -> Option<usize> {
+    match param {
+        Param::Any => Some(0),
+        Param::Type(data_type) => arg.coercion_cost(data_type),
+        Param::Field | Param::Unit | Param::Zone | Param::Pattern => {
+            arg.coercion_cost(DataType::Text)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use drown_common::sql::{parser::StatementParser, statement::Statement};
+
+    use super::*;
+    use crate::{execute::execute_statement, session::Session};
+
+    use DataType::*;
+
+    /// The types of the result columns of a query over a table with a column of each type.
+    fn check(sql: &str) -> Result<Vec<DataType>, TypeError> {
+        let mut catalog = Catalog::default();
+        let mut session = Session::default();
+
+        let statements = StatementParser::parse(&format!(
+            "CREATE TABLE t (s SMALLINT, i INT, b BIGINT, d DECIMAL(10, 2), r REAL, f DOUBLE, \
+             x TEXT, v VARCHAR(5), day DATE, ts TIMESTAMP, tz TIMESTAMPTZ, j JSONB); {sql}"
+        ))
+        .unwrap();
+
+        execute_statement(&mut catalog, &mut session, &statements[0]).unwrap();
+
+        let Statement::Select(select) = &statements[1] else {
+            panic!("{sql} is not a SELECT");
+        };
+
+        let query = bind_select(&catalog, &session.search_path, select).unwrap();
+        check_query_types(&catalog, &query).map(|(types, _)| types)
+    }
+
+    fn error(sql: &str) -> String {
+        check(sql).expect_err(sql).to_string()
+    }
+
+    #[test]
+    fn coercion_lattice() {
+        let chain = [SmallInt, Int, BigInt, Decimal(None), Real, Double];
+
+        for (from, a) in chain.iter().enumerate() {
+            for (to, b) in chain.iter().enumerate() {
+                let expected = (from <= to).then(|| to - from);
+                assert_eq!(a.coercion_cost(*b), expected, "{a} to {b}");
+                assert_eq!(DataType::common_type(*a, *b), Some(chain[from.max(to)]));
+            }
+        }
+
+        assert_eq!(Decimal(Some((10, 2))).coercion_cost(Decimal(None)), Some(0));
+        assert_eq!(Int.coercion_cost(Decimal(Some((10, 2)))), None);
+        assert_eq!(Varchar(Some(5)).coercion_cost(Text), Some(1));
+        assert_eq!(Date.coercion_cost(TimestampTz), Some(2));
+        assert_eq!(Text.coercion_cost(Int), None);
+        assert_eq!(Int.coercion_cost(Text), None);
+        assert_eq!(Boolean.coercion_cost(Int), None);
+
+        assert_eq!(
+            DataType::common_type(Varchar(Some(2)), Varchar(Some(5))),
+            Some(Text)
+        );
+        assert_eq!(
+            DataType::common_type(Decimal(Some((4, 2))), Int),
+            Some(Decimal(None))
+        );
+        assert_eq!(DataType::common_type(Int, Text), None);
+    }
+
+    #[test]
+    fn expression_types() {
+        assert_eq!(
+            ExprType::common_type(ExprType::StringLiteral, ExprType::Known(Date)),
+            Some(ExprType::Known(Date))
+        );
+        assert_eq!(
+            ExprType::common_type(ExprType::StringLiteral, ExprType::Known(Int)),
+            None
+        );
+        assert_eq!(
+            ExprType::common_type(ExprType::Null, ExprType::StringLiteral),
+            Some(ExprType::StringLiteral)
+        );
+
+        assert_eq!(
+            literal_type(&Literal::Number("2147483648".to_string())).unwrap(),
+            ExprType::Known(BigInt)
+        );
+        assert_eq!(
+            literal_type(&Literal::Number("1.5".to_string())).unwrap(),
+            ExprType::Known(Decimal(None))
+        );
+        assert!(literal_type(&Literal::Number("9".repeat(40))).is_err());
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(
+            check("SELECT s + s, s + i, i + b, b + d, d * 2, i / r, d - f FROM t").unwrap(),
+            [
+                SmallInt,
+                Int,
+                BigInt,
+                Decimal(None),
+                Decimal(None),
+                Real,
+                Double
+            ]
+        );
+        assert_eq!(
+            check("SELECT day + 1, day - day, ts + '1 day', tz - tz, NULL + i FROM t").unwrap(),
+            [Date, Int, Timestamp, Interval, Int]
+        );
+        assert_eq!(
+            check("SELECT v = 'a', i < d, day = ts, j ->> 'k', j -> 0 FROM t").unwrap(),
+            [Boolean, Boolean, Boolean, Text, Jsonb]
+        );
+    }
+
+    #[test]
+    fn rejected() {
+        assert_eq!(
+            error("SELECT 'abc' + 1 FROM t"),
+            "Operator `+` cannot be applied to TEXT and INT"
+        );
+        assert_eq!(
+            error("SELECT x + i FROM t"),
+            "Operator `+` cannot be applied to TEXT and INT"
+        );
+        assert_eq!(
+            error("SELECT i FROM t WHERE i"),
+            "The condition of WHERE must be BOOLEAN, not INT"
+        );
+        assert_eq!(
+            error("SELECT -x FROM t"),
+            "Operator `-` cannot be applied to TEXT"
+        );
+        assert_eq!(
+            error("SELECT lower(i) FROM t"),
+            "Function `lower(INT)` does not exist"
+        );
+        assert_eq!(
+            error("SELECT CAST(j AS DATE) FROM t"),
+            "JSONB cannot be cast to DATE"
+        );
+        assert_eq!(
+            error("SELECT i FROM t UNION SELECT x FROM t"),
+            "Values of column `i` cannot be both INT and TEXT"
+        );
+        assert_eq!(
+            error("SELECT x COLLATE nocase = x COLLATE unicode FROM t"),
+            "Collations `nocase` and `unicode` given with COLLATE conflict"
+        );
+    }
+}
@@ -0,0 +1,378 @@
+use drown_common::sql::{
+    data_type::DataType,
+    function::FunctionError,
+    value::{Value, ValueError},
+};
+use once_cell::sync::Lazy;
+use thiserror::Error;
+use wasmi::{
+    core::{ValueType, F32, F64},
+    Config, Engine, ExternType, Linker, Module, Store, StoreLimits, StoreLimitsBuilder,
+};
+
+/// The fuel a single call can use, which is about one unit per instruction executed, so that a
+/// function which never returns fails rather than holding up the server.
+const FUEL_PER_CALL: u64 = 10_000_000;
+
+/// The most linear memory a single call can use, in bytes.
+const MEMORY_PER_CALL: usize = 16 * 1024 * 1024;
+
+static ENGINE: Lazy<Engine> = Lazy::new(|| {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    Engine::new(&config)
+});
+
+#[derive(Debug, Error)]
+pub enum WasmError {
+    #[error("WebAssembly module must be written as a BYTEA value, like '\\x0061736d...': {0}")]
+    NotBytea(ValueError),
+    #[error("Invalid WebAssembly module: {0}")]
+    InvalidModule(wasmi::Error),
+    #[error("WebAssembly module cannot import anything, but imports `{module}.{name}`")]
+    Import { module: String, name: String },
+    #[error("WebAssembly module does not export a function named `{0}`")]
+    ExportNotFound(String),
+    #[error("WebAssembly functions cannot take or return {0}")]
+    UnsupportedType(DataType),
+    #[error("Export `{export}` has type `{found}`, but the function is declared as `{expected}`")]
+    SignatureMismatch {
+        export: String,
+        expected: String,
+        found: String,
+    },
+}
+
+/// A function exported by a WebAssembly module.
+///
+/// Every call runs in a new instance of the module, so that calls cannot see each other's
+/// memory and each has the whole fuel and memory allowance to itself. Modules cannot import
+/// anything, so they have no way to reach outside the instance.
+#[derive(Debug)]
+pub struct WasmFunction {
+    name: String,
+    module: Module,
+    export: String,
+    returns: DataType,
+}
+
+impl WasmFunction {
+    /// Compiles a module written as a `BYTEA` value, and checks that it exports a function
+    /// named `export` taking and returning the WebAssembly types of `params` and `returns`.
+    pub fn new(
+        name: String,
+        module: &str,
+        export: &str,
+        params: &[DataType],
+        returns: DataType,
+    ) -> Result<Self, WasmError> {
+        let bytes = match Value::parse(module, DataType::Bytea).map_err(WasmError::NotBytea)? {
+            Value::Bytea(bytes) => bytes,
+            _ => unreachable!("BYTEA text is parsed into a BYTEA value"),
+        };
+
+        let module = Module::new(&ENGINE, &bytes[..]).map_err(WasmError::InvalidModule)?;
+
+        if let Some(import) = module.imports().next() {
+            return Err(WasmError::Import {
+                module: import.module().to_string(),
+                name: import.name().to_string(),
+            });
+        }
+
+        let Some(ExternType::Func(found)) = module.get_export(export) else {
+            return Err(WasmError::ExportNotFound(export.to_string()));
+        };
+
+        let expected_params = params
+            .iter()
+            .map(|param| wasm_type(*param))
+            .collect::<Result<Vec<_>, _>>()?;
+        let expected_result = wasm_type(returns)?;
+
+        if found.params() != expected_params || found.results() != [expected_result] {
+            return Err(WasmError::SignatureMismatch {
+                export: export.to_string(),
+                expected: write_func_type(&expected_params, &[expected_result]),
+                found: write_func_type(found.params(), found.results()),
+            });
+        }
+
+        Ok(WasmFunction {
+            name,
+            module,
+            export: export.to_string(),
+            returns,
+        })
+    }
+
+    /// Calls the function on arguments of the types it was declared with, none of which are
+    /// `NULL`.
+    pub fn call(&self, args: &[Value]) -> Result<Value, FunctionError> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MEMORY_PER_CALL)
+            .instances(1)
+            .memories(1)
+            .tables(1)
+            .build();
+
+        let mut store = Store::new(&ENGINE, limits);
+        store.limiter(|limits| limits);
+        store
+            .add_fuel(FUEL_PER_CALL)
+            .expect("fuel metering is enabled for the engine");
+
+        let instance = Linker::<StoreLimits>::new(&ENGINE)
+            .instantiate(&mut store, &self.module)
+            .and_then(|instance| instance.start(&mut store))
+            .map_err(|err| self.failed(err))?;
+
+        let func = instance
+            .get_func(&store, &self.export)
+            .expect("the export is checked when the function is created");
+
+        let args = args.iter().map(to_wasm).collect::<Vec<_>>();
+        let mut results = [wasmi::Value::default(wasm_type(self.returns).unwrap())];
+
+        func.call(&mut store, &args, &mut results)
+            .map_err(|err| self.failed(err))?;
+
+        self.result(&results[0])
+    }
+
+    fn failed(&self, err: wasmi::Error) -> FunctionError {
+        FunctionError::Failed {
+            function: self.name.clone(),
+            reason: err.to_string(),
+        }
+    }
+
+    /// Converts the WebAssembly value returned by the function to its declared SQL type.
+    fn result(&self, value: &wasmi::Value) -> Result<Value, FunctionError> {
+        let out_of_range = || FunctionError::OutOfRange {
+            function: self.name.clone(),
+            data_type: self.returns,
+        };
+
+        Ok(match (self.returns, value) {
+            (DataType::Boolean, wasmi::Value::I32(value)) => Value::Boolean(*value != 0),
+            (DataType::SmallInt, wasmi::Value::I32(value)) => {
+                Value::SmallInt(i16::try_from(*value).map_err(|_| out_of_range())?)
+            }
+            (DataType::Int, wasmi::Value::I32(value)) => Value::Int(*value),
+            (DataType::BigInt, wasmi::Value::I64(value)) => Value::BigInt(*value),
+            (DataType::Real, wasmi::Value::F32(value)) => Value::Real(value.to_float()),
+            (DataType::Double, wasmi::Value::F64(value)) => Value::Double(value.to_float()),
+            _ => unreachable!("the result type is checked when the function is created"),
+        })
+    }
+}
+
+/// The WebAssembly type values of a SQL type are passed as, where booleans are 0 or 1.
+fn wasm_type(data_type: DataType) -> Result<ValueType, WasmError> {
+    match data_type {
+        DataType::Boolean | DataType::SmallInt | DataType::Int => Ok(ValueType::I32),
+        DataType::BigInt => Ok(ValueType::I64),
+        DataType::Real => Ok(ValueType::F32),
+        DataType::Double => Ok(ValueType::F64),
+        data_type => Err(WasmError::UnsupportedType(data_type)),
+    }
+}
+
+fn to_wasm(value: &Value) -> wasmi::Value {
+    match value {
+        Value::Boolean(value) => wasmi::Value::I32(*value as i32),
+        Value::SmallInt(value) => wasmi::Value::I32(*value as i32),
+        Value::Int(value) => wasmi::Value::I32(*value),
+        Value::BigInt(value) => wasmi::Value::I64(*value),
+        Value::Real(value) => wasmi::Value::F32(F32::from_float(*value)),
+        Value::Double(value) => wasmi::Value::F64(F64::from_float(*value)),
+        value => unreachable!("{} values are not passed to WebAssembly", value.data_type()),
+    }
+}
+
+/// Writes a function type like `(i32, i64) -> f64`.
+fn write_func_type(params: &[ValueType], results: &[ValueType]) -> String {
+    let names = |types: &[ValueType]| {
+        types
+            .iter()
+            .map(|value_type| format!("{value_type:?}").to_ascii_lowercase())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match results {
+        [result] => format!("({}) -> {}", names(params), names(&[*result])),
+        results => format!("({}) -> ({})", names(params), names(results)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const I32: u8 = 0x7f;
+    const I64: u8 = 0x7e;
+
+    /// Writes the sections as a module in `BYTEA` text, each as its id and contents.
+    fn assemble(sections: &[(u8, &[u8])]) -> String {
+        let mut bytes = b"\0asm\x01\0\0\0".to_vec();
+
+        for (id, contents) in sections {
+            bytes.push(*id);
+            bytes.push(contents.len() as u8);
+            bytes.extend(*contents);
+        }
+
+        let hex = bytes
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        format!("\\x{hex}")
+    }
+
+    /// A module with a memory of one page, exporting a function named `f` which runs `code`.
+    fn module(params: &[u8], results: &[u8], code: &[u8]) -> String {
+        let mut types = vec![1, 0x60, params.len() as u8];
+        types.extend(params);
+        types.push(results.len() as u8);
+        types.extend(results);
+
+        // No locals besides the parameters
+        let mut body = vec![0];
+        body.extend(code);
+        body.push(0x0b);
+
+        let mut codes = vec![1, body.len() as u8];
+        codes.extend(body);
+
+        assemble(&[
+            (1, &types),
+            (3, &[1, 0]),
+            (5, &[1, 0, 1]),
+            (7, &[1, 1, b'f', 0, 0]),
+            (10, &codes),
+        ])
+    }
+
+    /// `f(a, b) = a + b`
+    fn add(data_type: u8) -> String {
+        let add = if data_type == I64 { 0x7c } else { 0x6a };
+        module(
+            &[data_type, data_type],
+            &[data_type],
+            &[0x20, 0, 0x20, 1, add],
+        )
+    }
+
+    fn function(module: &str, params: &[DataType], returns: DataType) -> WasmFunction {
+        WasmFunction::new("f".to_string(), module, "f", params, returns).unwrap()
+    }
+
+    fn error(module: &str, export: &str, params: &[DataType], returns: DataType) -> String {
+        WasmFunction::new("f".to_string(), module, export, params, returns)
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn calls() {
+        let f = function(&add(I32), &[DataType::Int, DataType::Int], DataType::Int);
+        assert_eq!(
+            f.call(&[Value::Int(2), Value::Int(3)]).unwrap(),
+            Value::Int(5)
+        );
+
+        let f = function(
+            &add(I64),
+            &[DataType::BigInt, DataType::BigInt],
+            DataType::BigInt,
+        );
+        let args = [Value::BigInt(1 << 40), Value::BigInt(1)];
+        assert_eq!(f.call(&args).unwrap(), Value::BigInt((1 << 40) + 1));
+
+        // Values which do not fit the declared type are out of range rather than truncated
+        let f = function(
+            &add(I32),
+            &[DataType::SmallInt, DataType::Int],
+            DataType::SmallInt,
+        );
+        let args = [Value::SmallInt(i16::MAX), Value::Int(1)];
+        assert!(matches!(
+            f.call(&args),
+            Err(FunctionError::OutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn fuel_limit() {
+        // loop (br 0) end; i32.const 0
+        let spin = module(&[I32], &[I32], &[0x03, 0x40, 0x0c, 0, 0x0b, 0x41, 0]);
+        let f = function(&spin, &[DataType::Int], DataType::Int);
+
+        match f.call(&[Value::Int(0)]) {
+            Err(FunctionError::Failed { function, reason }) => {
+                assert_eq!(function, "f");
+                assert!(reason.contains("fuel"), "{reason}");
+            }
+            result => panic!("expected running out of fuel, got {result:?}"),
+        }
+
+        // Each call has the whole allowance to itself
+        let f = function(&add(I32), &[DataType::Int, DataType::Int], DataType::Int);
+        for _ in 0..3 {
+            assert!(f.call(&[Value::Int(1), Value::Int(1)]).is_ok());
+        }
+    }
+
+    #[test]
+    fn memory_limit() {
+        // memory.grow by the argument, returning the previous size in pages or -1
+        let grow = module(&[I32], &[I32], &[0x20, 0, 0x40, 0]);
+        let f = function(&grow, &[DataType::Int], DataType::Int);
+
+        let pages = (MEMORY_PER_CALL / 65536) as i32;
+        assert_eq!(f.call(&[Value::Int(pages - 1)]).unwrap(), Value::Int(1));
+        assert_eq!(f.call(&[Value::Int(pages)]).unwrap(), Value::Int(-1));
+
+        // Memory grown by one call is not kept for the next
+        assert_eq!(f.call(&[Value::Int(pages - 1)]).unwrap(), Value::Int(1));
+    }
+
+    #[test]
+    fn rejected_modules() {
+        // (import "env" "f" (func))
+        let import = assemble(&[
+            (1, &[1, 0x60, 0, 0]),
+            (2, &[1, 3, b'e', b'n', b'v', 1, b'f', 0, 0]),
+        ]);
+        assert_eq!(
+            error(&import, "f", &[], DataType::Int),
+            "WebAssembly module cannot import anything, but imports `env.f`"
+        );
+
+        let add = add(I32);
+        let params = [DataType::Int, DataType::Int];
+
+        assert_eq!(
+            error(&add, "f", &params, DataType::BigInt),
+            "Export `f` has type `(i32, i32) -> i32`, but the function is declared as `(i32, i32) -> i64`"
+        );
+        assert_eq!(
+            error(&add, "f", &params[..1], DataType::Int),
+            "Export `f` has type `(i32, i32) -> i32`, but the function is declared as `(i32) -> i32`"
+        );
+        assert_eq!(
+            error(&add, "g", &params, DataType::Int),
+            "WebAssembly module does not export a function named `g`"
+        );
+        assert_eq!(
+            error(&add, "f", &params, DataType::Text),
+            "WebAssembly functions cannot take or return TEXT"
+        );
+        assert!(error("\\x0061736d", "f", &params, DataType::Int)
+            .starts_with("Invalid WebAssembly module: "));
+        assert!(error("module.wasm", "f", &params, DataType::Int)
+            .starts_with("WebAssembly module must be written as a BYTEA value"));
+    }
+}